@@ -0,0 +1,15 @@
+-- Baseline schema, a no-op for databases created before migrations existed
+CREATE TABLE IF NOT EXISTS Author (
+    Id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    Name TEXT
+);
+
+CREATE TABLE IF NOT EXISTS Books (
+    title TEXT NOT NULL,
+    price REAL,
+    bought TIMESTAMP,
+    finished TIMESTAMP,
+    added TIMESTAMP,
+    AuthorFK INTEGER REFERENCES Author (Id),
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL
+);
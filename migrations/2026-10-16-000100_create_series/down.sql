@@ -0,0 +1,2 @@
+ALTER TABLE Books DROP COLUMN SeriesFK;
+DROP TABLE Series;
@@ -0,0 +1,7 @@
+CREATE TABLE Series (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    total_volumes INTEGER
+);
+
+ALTER TABLE Books ADD COLUMN SeriesFK INTEGER REFERENCES Series (id);
@@ -0,0 +1,2 @@
+ALTER TABLE Author DROP COLUMN wikipedia;
+ALTER TABLE Author DROP COLUMN website;
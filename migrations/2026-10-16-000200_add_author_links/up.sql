@@ -0,0 +1,2 @@
+ALTER TABLE Author ADD COLUMN website TEXT;
+ALTER TABLE Author ADD COLUMN wikipedia TEXT;
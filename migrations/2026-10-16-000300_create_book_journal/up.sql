@@ -0,0 +1,6 @@
+CREATE TABLE BookJournal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    book_fk INTEGER NOT NULL REFERENCES Books (id),
+    written_at TIMESTAMP NOT NULL,
+    body TEXT NOT NULL
+);
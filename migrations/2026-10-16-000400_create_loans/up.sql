@@ -0,0 +1,8 @@
+CREATE TABLE Loans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    book_fk INTEGER NOT NULL REFERENCES Books (id),
+    borrower TEXT NOT NULL,
+    loaned_on TIMESTAMP NOT NULL,
+    due_on TIMESTAMP,
+    returned_on TIMESTAMP
+);
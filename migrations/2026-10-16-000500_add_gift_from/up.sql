@@ -0,0 +1 @@
+ALTER TABLE Books ADD COLUMN gift_from TEXT;
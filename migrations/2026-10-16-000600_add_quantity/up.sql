@@ -0,0 +1 @@
+ALTER TABLE Books ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1;
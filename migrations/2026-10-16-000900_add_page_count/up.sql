@@ -0,0 +1 @@
+ALTER TABLE Books ADD COLUMN page_count INTEGER;
@@ -0,0 +1 @@
+ALTER TABLE Books ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT 0;
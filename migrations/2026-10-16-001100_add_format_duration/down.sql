@@ -0,0 +1,2 @@
+ALTER TABLE Books DROP COLUMN duration_minutes;
+ALTER TABLE Books DROP COLUMN format;
@@ -0,0 +1,2 @@
+ALTER TABLE Books ADD COLUMN format TEXT;
+ALTER TABLE Books ADD COLUMN duration_minutes INTEGER;
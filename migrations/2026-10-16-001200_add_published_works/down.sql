@@ -0,0 +1 @@
+ALTER TABLE Author DROP COLUMN published_works;
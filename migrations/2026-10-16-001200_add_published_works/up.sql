@@ -0,0 +1 @@
+ALTER TABLE Author ADD COLUMN published_works INTEGER;
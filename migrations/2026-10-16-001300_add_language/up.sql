@@ -0,0 +1 @@
+ALTER TABLE Books ADD COLUMN language TEXT;
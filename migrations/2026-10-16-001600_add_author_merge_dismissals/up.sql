@@ -0,0 +1,6 @@
+-- Author pairs the merge suggestions should not offer again, author_a is the lower id
+CREATE TABLE AuthorMergeDismissals (
+    author_a INTEGER NOT NULL REFERENCES Author (Id),
+    author_b INTEGER NOT NULL REFERENCES Author (Id),
+    PRIMARY KEY (author_a, author_b)
+);
@@ -0,0 +1,2 @@
+DROP TABLE BookListItems;
+DROP TABLE BookLists;
@@ -0,0 +1,12 @@
+-- Named reading orders, e.g. a book club's schedule. Deleting a list keeps its books.
+CREATE TABLE BookLists (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE BookListItems (
+    list_fk INTEGER NOT NULL REFERENCES BookLists (id),
+    book_fk INTEGER NOT NULL REFERENCES Books (id),
+    position INTEGER NOT NULL, -- Lowest first, gaps are allowed
+    PRIMARY KEY (list_fk, book_fk)
+);
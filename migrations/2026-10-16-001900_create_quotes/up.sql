@@ -0,0 +1,8 @@
+-- Favorite passages of a book, the page is optional
+CREATE TABLE Quotes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    book_fk INTEGER NOT NULL REFERENCES Books (id),
+    page INTEGER,
+    text TEXT NOT NULL,
+    noted_at TIMESTAMP NOT NULL
+);
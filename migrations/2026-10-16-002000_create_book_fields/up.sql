@@ -0,0 +1,8 @@
+-- Free key/value metadata of a book, e.g. "Shelf" = "A3". A key appears once per book.
+CREATE TABLE BookFields (
+    Id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    BookFK INTEGER NOT NULL REFERENCES Books (id),
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    UNIQUE (BookFK, key)
+);
@@ -0,0 +1,26 @@
+CREATE TABLE Books_old (
+    title TEXT NOT NULL,
+    price REAL,
+    bought TIMESTAMP,
+    finished TIMESTAMP,
+    added TIMESTAMP,
+    AuthorFK INTEGER REFERENCES Author (Id),
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    SeriesFK INTEGER REFERENCES Series (id),
+    gift_from TEXT,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    cover_path TEXT,
+    genre TEXT,
+    page_count INTEGER,
+    pinned BOOLEAN NOT NULL DEFAULT 0,
+    format TEXT,
+    duration_minutes INTEGER,
+    language TEXT,
+    acquired_from TEXT,
+    rating INTEGER
+);
+
+INSERT INTO Books_old SELECT * FROM Books;
+
+DROP TABLE Books;
+ALTER TABLE Books_old RENAME TO Books;
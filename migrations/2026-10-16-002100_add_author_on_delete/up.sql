@@ -0,0 +1,44 @@
+-- Deleting an author leaves its books without one. SQLite can't change a constraint in
+-- place, so Books is rebuilt. References to rows that are already gone are dropped on
+-- the way, they would fail as soon as foreign keys are enforced.
+CREATE TABLE Books_new (
+    title TEXT NOT NULL,
+    price REAL,
+    bought TIMESTAMP,
+    finished TIMESTAMP,
+    added TIMESTAMP,
+    AuthorFK INTEGER REFERENCES Author (Id) ON DELETE SET NULL,
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    SeriesFK INTEGER REFERENCES Series (id),
+    gift_from TEXT,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    cover_path TEXT,
+    genre TEXT,
+    page_count INTEGER,
+    pinned BOOLEAN NOT NULL DEFAULT 0,
+    format TEXT,
+    duration_minutes INTEGER,
+    language TEXT,
+    acquired_from TEXT,
+    rating INTEGER
+);
+
+INSERT INTO Books_new
+SELECT title, price, bought, finished, added,
+       CASE WHEN AuthorFK IN (SELECT Id FROM Author) THEN AuthorFK END,
+       id,
+       CASE WHEN SeriesFK IN (SELECT id FROM Series) THEN SeriesFK END,
+       gift_from, quantity, cover_path, genre, page_count, pinned, format,
+       duration_minutes, language, acquired_from, rating
+FROM Books;
+
+-- Ids of deleted books are not handed out again
+UPDATE sqlite_sequence
+SET seq = MAX(seq, (SELECT seq FROM sqlite_sequence WHERE name = 'Books'))
+WHERE name = 'Books_new';
+
+DROP TABLE Books;
+ALTER TABLE Books_new RENAME TO Books;
+
+DELETE FROM AuthorMergeDismissals
+WHERE author_a NOT IN (SELECT Id FROM Author) OR author_b NOT IN (SELECT Id FROM Author);
@@ -0,0 +1,9 @@
+-- Timed reading of a book. ended_at stays empty while the timer runs, the timer doesn't
+-- survive a restart so such rows are discarded the next time the app starts.
+CREATE TABLE ReadingSessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    book_fk INTEGER NOT NULL REFERENCES Books (id),
+    started_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP,
+    pages INTEGER
+);
@@ -0,0 +1,575 @@
+// src/backup.rs
+use crate::db::{self, DbError};
+use crate::models::{
+    AuthorModel, BookFieldModel, BookListItemModel, BookListModel, BookModel, JournalEntryModel,
+    LoanModel, NewBook, QuoteModel, ReadingSessionModel, SeriesModel, ID,
+};
+use crate::ui::{diff_book, BookField};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Written into every backup, documents without one are read as version 1
+pub const BACKUP_VERSION: u32 = 2;
+
+/// First version holding every table of the library, older backups can only be compared
+const RESTORABLE_VERSION: u32 = 2;
+
+/// Start of the file names of nightly backups, only these are rotated
+const NIGHTLY_PREFIX: &str = "nightly-";
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("Backup file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Backup format error: {0}")]
+    Format(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Database(#[from] DbError),
+
+    #[error("This backup is version {0} and lacks tables added since, it can only be compared")]
+    NotRestorable(u32),
+}
+
+fn default_version() -> u32 {
+    BACKUP_VERSION
+}
+
+/// The library as written to a JSON backup. Books without an id (0) can still be
+/// compared, they are matched by title and author.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupDocument {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub created: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub authors: Vec<AuthorModel>,
+    #[serde(default)]
+    pub series: Vec<SeriesModel>,
+    #[serde(default)]
+    pub books: Vec<BookModel>,
+    #[serde(default)]
+    pub quotes: Vec<QuoteModel>,
+    #[serde(default)]
+    pub fields: Vec<BookFieldModel>, // Custom key/value fields of the books
+    #[serde(default)]
+    pub journal: Vec<JournalEntryModel>,
+    #[serde(default)]
+    pub loans: Vec<LoanModel>,
+    #[serde(default)]
+    pub reading_sessions: Vec<ReadingSessionModel>,
+    #[serde(default)]
+    pub lists: Vec<BookListModel>,
+    #[serde(default)]
+    pub list_items: Vec<BookListItemModel>, // Places of the books on the lists
+    #[serde(default)]
+    pub merge_dismissals: Vec<(ID, ID)>, // Author pairs marked as different people
+}
+
+impl BackupDocument {
+    /// Snapshot of every table of the open database
+    pub fn from_database(now: NaiveDateTime) -> Result<Self, BackupError> {
+        Ok(db::backup_library(now)?)
+    }
+
+    /// Replaces the library with this backup. Backups from before every table was
+    /// written are refused, restoring one would drop the rows it lacks.
+    pub fn restore(&self) -> Result<(), BackupError> {
+        if self.version < RESTORABLE_VERSION {
+            return Err(BackupError::NotRestorable(self.version));
+        }
+        Ok(db::restore_library(self)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BackupError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BackupError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn author_name(&self, id: Option<ID>) -> Option<&str> {
+        let id = id?;
+        self.authors
+            .iter()
+            .find(|author| author.Id == id)
+            .and_then(|author| author.Name.as_deref())
+    }
+
+    fn series_name(&self, id: Option<ID>) -> Option<&str> {
+        let id = id?;
+        self.series
+            .iter()
+            .find(|series| series.id == id)
+            .map(|series| series.name.as_str())
+    }
+
+    // "Title — Author", or the title alone for a book without an author
+    fn book_label(&self, book: &BookModel) -> String {
+        match self.author_name(book.AuthorFK) {
+            Some(author) => format!("{} — {}", book.title, author),
+            None => book.title.clone(),
+        }
+    }
+
+    // Title and author ignoring case, how books without a matching id are paired
+    fn book_key(&self, book: &BookModel) -> String {
+        let author = self.author_name(book.AuthorFK).unwrap_or_default();
+        format!(
+            "{}\u{1f}{}",
+            book.title.trim().to_lowercase(),
+            author.trim().to_lowercase()
+        )
+    }
+}
+
+/// Writes a nightly backup into `dir` and deletes the oldest nightly backups beyond
+/// `keep`. Backups written by hand are left alone.
+pub fn write_nightly(dir: &Path, now: NaiveDateTime, keep: usize) -> Result<PathBuf, BackupError> {
+    let path = dir.join(format!(
+        "{}{}.json",
+        NIGHTLY_PREFIX,
+        now.format("%Y-%m-%d-%H%M%S")
+    ));
+    BackupDocument::from_database(now)?.save(&path)?;
+
+    // The names sort by date
+    let mut nightly: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(NIGHTLY_PREFIX) && name.ends_with(".json"))
+        })
+        .collect();
+    nightly.sort();
+    let excess = nightly.len().saturating_sub(keep.max(1));
+    for old in &nightly[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(path)
+}
+
+/// One field that differs, None stands for an empty value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A book or author found in both documents with different values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDiff {
+    pub label: String, // As named in the newer document
+    pub fields: Vec<FieldDiff>,
+}
+
+/// What changed between two backups, every list is sorted by label
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LibraryDiff {
+    pub books_added: Vec<String>,
+    pub books_removed: Vec<String>,
+    pub books_changed: Vec<ItemDiff>,
+    pub authors_added: Vec<String>,
+    pub authors_removed: Vec<String>,
+    pub authors_changed: Vec<ItemDiff>,
+}
+
+impl LibraryDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == LibraryDiff::default()
+    }
+
+    /// Counts like "Books: 2 added, 1 removed, 3 changed"
+    pub fn summary(&self) -> String {
+        format!(
+            "Books: {} added, {} removed, {} changed. Authors: {} added, {} removed, {} changed",
+            self.books_added.len(),
+            self.books_removed.len(),
+            self.books_changed.len(),
+            self.authors_added.len(),
+            self.authors_removed.len(),
+            self.authors_changed.len()
+        )
+    }
+
+    /// Short git-like listing: `+` added, `-` removed, `~` changed followed by its fields
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (heading, added, removed, changed) in [
+            (
+                "Books",
+                &self.books_added,
+                &self.books_removed,
+                &self.books_changed,
+            ),
+            (
+                "Authors",
+                &self.authors_added,
+                &self.authors_removed,
+                &self.authors_changed,
+            ),
+        ] {
+            let _ = writeln!(
+                out,
+                "{}: {} added, {} removed, {} changed",
+                heading,
+                added.len(),
+                removed.len(),
+                changed.len()
+            );
+            for label in added {
+                let _ = writeln!(out, "+ {}", label);
+            }
+            for label in removed {
+                let _ = writeln!(out, "- {}", label);
+            }
+            for item in changed {
+                let _ = writeln!(out, "~ {}", item.label);
+                for field in &item.fields {
+                    let _ = writeln!(
+                        out,
+                        "    {}: {} → {}",
+                        field.field,
+                        field.old.as_deref().unwrap_or("(empty)"),
+                        field.new.as_deref().unwrap_or("(empty)")
+                    );
+                }
+            }
+            out.push('\n');
+        }
+        out.trim_end().to_string()
+    }
+}
+
+// Items of two documents paired up, with the ones found in only one of them
+struct Pairing<'a, T> {
+    pairs: Vec<(&'a T, &'a T)>,
+    removed: Vec<&'a T>,
+    added: Vec<&'a T>,
+}
+
+// Pairs items with the same id (0 means none) when `same_item` agrees, so an id reused
+// for another item shows up as a removal and an addition. What is left is paired by key.
+fn pair_items<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    id: impl Fn(&T) -> ID,
+    same_item: impl Fn(&T, &T) -> bool,
+    old_key: impl Fn(&T) -> String,
+    new_key: impl Fn(&T) -> String,
+) -> Pairing<'a, T> {
+    let new_by_id: HashMap<ID, usize> = new
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| id(item) != 0)
+        .map(|(index, item)| (id(item), index))
+        .collect();
+
+    let mut pairs = Vec::new();
+    let mut paired_new = HashSet::new();
+    let mut unpaired_old = Vec::new();
+    for item in old {
+        let found = new_by_id
+            .get(&id(item))
+            .filter(|_| id(item) != 0)
+            .filter(|index| same_item(item, &new[**index]));
+        match found {
+            Some(index) => {
+                paired_new.insert(*index);
+                pairs.push((item, &new[*index]));
+            }
+            None => unpaired_old.push(item),
+        }
+    }
+
+    let mut new_by_key: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, item) in new.iter().enumerate().rev() {
+        if !paired_new.contains(&index) {
+            new_by_key.entry(new_key(item)).or_default().push(index);
+        }
+    }
+    let mut removed = Vec::new();
+    for item in unpaired_old {
+        match new_by_key.get_mut(&old_key(item)).and_then(Vec::pop) {
+            Some(index) => {
+                paired_new.insert(index);
+                pairs.push((item, &new[index]));
+            }
+            None => removed.push(item),
+        }
+    }
+
+    let added = new
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !paired_new.contains(index))
+        .map(|(_, item)| item)
+        .collect();
+    Pairing {
+        pairs,
+        removed,
+        added,
+    }
+}
+
+fn sorted(mut labels: Vec<String>) -> Vec<String> {
+    labels.sort_by_key(|label| label.to_lowercase());
+    labels
+}
+
+/// Compares two backups (or a backup and the live database) book by book and author by
+/// author. Ids are trusted while the title or the author still matches, anything else
+/// is matched by title and author ignoring case.
+pub fn diff(old: &BackupDocument, new: &BackupDocument) -> LibraryDiff {
+    let books = pair_items(
+        &old.books,
+        &new.books,
+        |book| book.id,
+        |a, b| {
+            a.title.trim().eq_ignore_ascii_case(b.title.trim())
+                || old.author_name(a.AuthorFK) == new.author_name(b.AuthorFK)
+        },
+        |book| old.book_key(book),
+        |book| new.book_key(book),
+    );
+
+    let mut books_changed: Vec<ItemDiff> = books
+        .pairs
+        .iter()
+        .map(|(before, after)| ItemDiff {
+            label: new.book_label(after),
+            fields: book_fields(old, new, before, after),
+        })
+        .filter(|item| !item.fields.is_empty())
+        .collect();
+    books_changed.sort_by_key(|item| item.label.to_lowercase());
+
+    // Authors keep their id through a rename, which is the usual change
+    let author_key = |author: &AuthorModel| {
+        author
+            .Name
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+    };
+    let authors = pair_items(
+        &old.authors,
+        &new.authors,
+        |author| author.Id,
+        |_, _| true,
+        author_key,
+        author_key,
+    );
+    let mut authors_changed: Vec<ItemDiff> = authors
+        .pairs
+        .iter()
+        .map(|(before, after)| ItemDiff {
+            label: after.to_string(),
+            fields: author_fields(before, after),
+        })
+        .filter(|item| !item.fields.is_empty())
+        .collect();
+    authors_changed.sort_by_key(|item| item.label.to_lowercase());
+
+    LibraryDiff {
+        books_added: sorted(books.added.iter().map(|b| new.book_label(b)).collect()),
+        books_removed: sorted(books.removed.iter().map(|b| old.book_label(b)).collect()),
+        books_changed,
+        authors_added: sorted(authors.added.iter().map(|a| a.to_string()).collect()),
+        authors_removed: sorted(authors.removed.iter().map(|a| a.to_string()).collect()),
+        authors_changed,
+    }
+}
+
+// The book form's diff, with author and series ids turned into names of each document.
+// Ids that differ between two databases but name the same author are no change.
+fn book_fields(
+    old: &BackupDocument,
+    new: &BackupDocument,
+    before: &BookModel,
+    after: &BookModel,
+) -> Vec<FieldDiff> {
+    diff_book(before, &NewBook::from(after))
+        .into_iter()
+        .map(|change| {
+            let (old_value, new_value) = match change.field {
+                BookField::Author => (
+                    old.author_name(before.AuthorFK).map(String::from),
+                    new.author_name(after.AuthorFK).map(String::from),
+                ),
+                BookField::Series => (
+                    old.series_name(before.SeriesFK).map(String::from),
+                    new.series_name(after.SeriesFK).map(String::from),
+                ),
+                _ => (change.old, change.new),
+            };
+            FieldDiff {
+                field: change.field.to_string(),
+                old: old_value,
+                new: new_value,
+            }
+        })
+        .filter(|field| field.old != field.new)
+        .collect()
+}
+
+fn author_fields(before: &AuthorModel, after: &AuthorModel) -> Vec<FieldDiff> {
+    let count = |count: Option<i32>| count.map(|count| count.to_string());
+    [
+        ("Name", before.Name.clone(), after.Name.clone()),
+        ("Website", before.website.clone(), after.website.clone()),
+        (
+            "Wikipedia",
+            before.wikipedia.clone(),
+            after.wikipedia.clone(),
+        ),
+        (
+            "Published works",
+            count(before.published_works),
+            count(after.published_works),
+        ),
+        (
+            "Also known as",
+            before.Aliases.clone(),
+            after.Aliases.clone(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(field, old, new)| FieldDiff {
+        field: field.to_string(),
+        old,
+        new,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn author(id: ID, name: &str) -> AuthorModel {
+        AuthorModel {
+            Id: id,
+            Name: Some(name.to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        }
+    }
+
+    fn book(id: ID, title: &str, author: Option<ID>) -> BookModel {
+        BookModel {
+            AuthorFK: author,
+            ..BookModel::titled(id, title)
+        }
+    }
+
+    fn document(authors: Vec<AuthorModel>, books: Vec<BookModel>) -> BackupDocument {
+        BackupDocument {
+            version: BACKUP_VERSION,
+            created: None,
+            authors,
+            series: Vec::new(),
+            books,
+            quotes: Vec::new(),
+            fields: Vec::new(),
+            journal: Vec::new(),
+            loans: Vec::new(),
+            reading_sessions: Vec::new(),
+            lists: Vec::new(),
+            list_items: Vec::new(),
+            merge_dismissals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn books_and_authors_are_added_removed_and_changed() {
+        let old = document(
+            vec![author(1, "Frank Herbert"), author(2, "Jane Austen")],
+            vec![book(1, "Dune", Some(1)), book(2, "Emma", Some(2))],
+        );
+        let mut dune = book(1, "Dune", Some(1));
+        dune.genre = Some("Sci-fi".to_string());
+        let new = document(
+            vec![author(1, "Frank P. Herbert"), author(3, "Stanisław Lem")],
+            vec![dune, book(3, "Solaris", Some(3))],
+        );
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.books_added, vec!["Solaris — Stanisław Lem"]);
+        assert_eq!(diff.books_removed, vec!["Emma — Jane Austen"]);
+        assert_eq!(
+            diff.books_changed,
+            vec![ItemDiff {
+                label: "Dune — Frank P. Herbert".to_string(),
+                fields: vec![FieldDiff {
+                    field: "Genre".to_string(),
+                    old: None,
+                    new: Some("Sci-fi".to_string()),
+                }],
+            }]
+        );
+        assert_eq!(diff.authors_added, vec!["Stanisław Lem"]);
+        assert_eq!(diff.authors_removed, vec!["Jane Austen"]);
+        assert_eq!(diff.authors_changed.len(), 1);
+        assert_eq!(diff.authors_changed[0].fields[0].field, "Name");
+        assert_eq!(
+            diff.summary(),
+            "Books: 1 added, 1 removed, 1 changed. Authors: 1 added, 1 removed, 1 changed"
+        );
+
+        let text = diff.to_text();
+        assert!(text.contains("+ Solaris — Stanisław Lem"));
+        assert!(text.contains("- Emma — Jane Austen"));
+        assert!(text.contains("~ Dune — Frank P. Herbert\n    Genre: (empty) → Sci-fi"));
+        assert!(super::diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn an_id_reused_for_another_book_is_a_removal_and_an_addition() {
+        let authors = vec![author(1, "Frank Herbert"), author(2, "Jane Austen")];
+        let old = document(authors.clone(), vec![book(1, "Dune", Some(1))]);
+        let new = document(authors, vec![book(1, "Emma", Some(2))]);
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.books_removed, vec!["Dune — Frank Herbert"]);
+        assert_eq!(diff.books_added, vec!["Emma — Jane Austen"]);
+        assert!(diff.books_changed.is_empty());
+    }
+
+    #[test]
+    fn books_without_ids_are_matched_by_title_and_author() {
+        let old = document(
+            vec![author(1, "Frank Herbert")],
+            vec![book(1, "Dune", Some(1)), book(2, "Dune Messiah", Some(1))],
+        );
+        // An import keeps neither the book ids nor the author ids
+        let mut dune = book(0, "Dune", Some(7));
+        dune.quantity = 2;
+        let new = document(vec![author(7, "Frank Herbert")], vec![dune]);
+
+        let diff = diff(&old, &new);
+        assert!(diff.books_added.is_empty());
+        assert_eq!(diff.books_removed, vec!["Dune Messiah — Frank Herbert"]);
+        assert_eq!(diff.books_changed.len(), 1);
+        assert_eq!(diff.books_changed[0].fields.len(), 1);
+        assert_eq!(diff.books_changed[0].fields[0].field, "Quantity");
+    }
+}
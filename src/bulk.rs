@@ -0,0 +1,39 @@
+// src/bulk.rs
+
+/// Number of concrete examples listed per category in a preview
+pub const PREVIEW_EXAMPLES: usize = 10;
+
+/// What a bulk operation is going to do, produced before anything is written
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkPreview {
+    pub to_create: Vec<String>,
+    pub to_update: Vec<String>,
+    pub to_skip: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl BulkPreview {
+    /// True when applying the plan would not change anything
+    pub fn is_noop(&self) -> bool {
+        self.to_create.is_empty() && self.to_update.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} to create, {} to update, {} to skip",
+            self.to_create.len(),
+            self.to_update.len(),
+            self.to_skip.len()
+        )
+    }
+}
+
+/// The first few items of a category, with a note about how many were left out
+pub fn examples<T: AsRef<str>>(items: &[T]) -> (Vec<&str>, usize) {
+    let shown = items
+        .iter()
+        .take(PREVIEW_EXAMPLES)
+        .map(AsRef::as_ref)
+        .collect();
+    (shown, items.len().saturating_sub(PREVIEW_EXAMPLES))
+}
@@ -0,0 +1,80 @@
+// src/cli.rs
+use std::env;
+use std::path::Path;
+use thiserror::Error;
+
+pub const USAGE: &str = "\
+Usage: bookshelf [OPTIONS]
+
+Options:
+  --db <PATH>    Open this database file instead of DATABASE_URL
+  -h, --help     Print this help
+  -V, --version  Print the version";
+
+/// Options given on the command line, everything else is set up in the app
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    pub database_url: Option<String>, // Takes precedence over DATABASE_URL
+}
+
+/// What the command line asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Run(CliArgs),
+    Help,
+    Version,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CliError {
+    #[error("{0} needs a value")]
+    MissingValue(String),
+
+    #[error("unknown argument '{0}'")]
+    Unknown(String),
+}
+
+/// Parses the arguments after the program name. `--db` takes its value either as the
+/// next argument or after `=`, the last one given wins.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<CliCommand, CliError> {
+    let mut cli = CliArgs::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-V" | "--version" => return Ok(CliCommand::Version),
+            "--db" => {
+                let value = args.next().filter(|value| !value.trim().is_empty());
+                let value = value.ok_or_else(|| CliError::MissingValue(arg.clone()))?;
+                cli.database_url = Some(from_working_dir(&value));
+            }
+            _ => match arg.strip_prefix("--db=") {
+                Some(value) if !value.trim().is_empty() => {
+                    cli.database_url = Some(from_working_dir(value));
+                }
+                Some(_) => return Err(CliError::MissingValue("--db".to_string())),
+                None => return Err(CliError::Unknown(arg)),
+            },
+        }
+    }
+
+    Ok(CliCommand::Run(cli))
+}
+
+// A relative path typed in a shell means the shell's folder, unlike DATABASE_URL which is
+// taken from the executable's folder. `~`, `:memory:` and `file:` URIs are left as they are.
+fn from_working_dir(value: &str) -> String {
+    let value = value.trim();
+    let bare = value.strip_prefix("sqlite://").unwrap_or(value);
+    if bare.starts_with('~') || bare == ":memory:" || bare.starts_with("file:") {
+        return value.to_string();
+    }
+    if Path::new(bare).is_absolute() {
+        return bare.to_string();
+    }
+    match env::current_dir() {
+        Ok(dir) => dir.join(bare).to_string_lossy().into_owned(),
+        Err(_) => bare.to_string(),
+    }
+}
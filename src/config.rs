@@ -0,0 +1,164 @@
+// src/config.rs
+use crate::import::ImportField;
+use crate::paths;
+use crate::report::ExportProfile;
+use crate::ui::{
+    table_column_count, table_column_widths, EmptyValues, ListMode, SearchFields, SortField,
+    StickyFields, SPLIT_RATIO_DEFAULT,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Settings file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Settings format error: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Database connection pool limits, only read at startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoolConfig {
+    pub max_size: u32,
+    pub min_idle: Option<u32>, // None keeps max_size connections open
+    pub connection_timeout_secs: u64, // How long a query waits for a free connection
+    pub foreign_keys: bool, // SQLite rejects references to rows that don't exist
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        // SQLite allows one writer at a time, more connections only add lock contention
+        Self {
+            max_size: 4,
+            min_idle: Some(1),
+            connection_timeout_secs: 10,
+            foreign_keys: true,
+        }
+    }
+}
+
+/// User settings persisted between runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub font_scale: f32,
+    pub dark_mode: Option<bool>, // None follows the system theme
+    pub list_mode: ListMode,
+    pub group_by_author: bool, // Books tab lists the books under collapsible author headers
+    pub group_by_series: bool, // Books tab lists the books under series progress headers
+    pub books_per_row: usize, // Cards per row in the grid layout
+    pub sort_in_database: bool, // Let SQLite order the book list instead of sorting in memory
+    pub confirm_bulk_actions: bool, // Show a dry-run preview before bulk operations
+    pub show_stats_strip: bool, // Counts above the book list
+    pub pin_books_to_top: bool, // Pinned books lead the list whatever the sort
+    pub max_price: f32, // Prices above this are rejected as typos, f32 gets imprecise past ~16M
+    pub allow_scientific_prices: bool, // Accept prices like "1.5e3"
+    pub show_query_console: bool, // Read-only SQL console on the settings tab
+    pub bought_when_finished: bool, // Finishing a book without a bought date sets it
+    pub review_changes_before_save: bool, // List the changed fields before an edit is saved
+    pub confirm_exit_with_unsaved_work: bool, // Ask before closing over an unsaved form
+    pub search_fields: SearchFields, // Fields the book search looks at
+    pub column_widths: Vec<f32>, // Shares of the visible table columns, summing to 1
+    pub show_reading_duration: bool, // "Read in" column in the table layout
+    pub hide_prices: bool, // Start in privacy mode, with every price masked
+    pub remember_search: bool, // Restore the last book search on startup
+    pub last_search: String, // Only kept while remember_search is on
+    pub stale_unread_days: i64, // Unread books bought longer ago show in the stats
+    pub database_pool: PoolConfig,
+    pub genre_colors: BTreeMap<String, String>, // Genre name to a hex color like "#3366cc"
+    pub tax_rate: f32, // Percent, 0 shows totals as they are stored
+    pub prices_include_tax: bool, // Stored prices are gross, otherwise net
+    pub idle_refresh: bool, // Reload the lists now and then while nothing is being edited
+    pub idle_refresh_minutes: u64,
+    pub import_presets: BTreeMap<String, BTreeMap<String, ImportField>>, // Name to header mapping
+    pub show_details_pane: bool, // Details of the focused book next to the list
+    pub split_ratio: f32, // Share of the width the list takes when the details pane is shown
+    pub open_details_on_click: bool, // A click on a book row opens its details, not just focus
+    pub sticky_fields: StickyFields, // Form values kept by "Save and add another"
+    pub sort_empty_values: EmptyValues, // Where books without a price or added date sort
+    pub secondary_sort: Option<SortField>, // "Then by" field for ties, always ascending
+    pub average_priced_only: bool, // Unpriced books are left out of the average, not counted as 0
+    pub merge_fold_diacritics: bool, // Merge suggestions pair "Lem" with "Łem"
+    pub import_author_limit: usize, // A CSV import creating more new authors asks first
+    pub desktop_notifications: bool, // Also tell the OS when a long task finishes
+    pub export_profile: ExportProfile, // Dates, decimals and quoting of CSV exports
+    pub nightly_maintenance: bool, // Backup and data-issue audit while the app is left open
+    pub maintenance_hour: u32, // Local hour after which the maintenance runs, 0-23
+    pub maintenance_backups_kept: usize, // Older nightly backups are deleted
+    pub last_maintenance: Option<NaiveDateTime>, // Start of the last maintenance run
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            font_scale: 1.0,
+            dark_mode: None,
+            list_mode: ListMode::default(),
+            group_by_author: false,
+            group_by_series: false,
+            books_per_row: 3,
+            sort_in_database: false,
+            confirm_bulk_actions: true,
+            show_stats_strip: true,
+            pin_books_to_top: true,
+            max_price: 100_000.0,
+            allow_scientific_prices: false,
+            show_query_console: false,
+            bought_when_finished: true,
+            review_changes_before_save: false,
+            confirm_exit_with_unsaved_work: true,
+            search_fields: SearchFields::default(),
+            column_widths: table_column_widths(&[], table_column_count(false)),
+            show_reading_duration: false,
+            hide_prices: false,
+            remember_search: false,
+            last_search: String::new(),
+            stale_unread_days: 365,
+            database_pool: PoolConfig::default(),
+            genre_colors: BTreeMap::new(),
+            tax_rate: 0.0,
+            prices_include_tax: true,
+            idle_refresh: false,
+            idle_refresh_minutes: 5,
+            import_presets: BTreeMap::new(),
+            show_details_pane: false,
+            split_ratio: SPLIT_RATIO_DEFAULT,
+            open_details_on_click: false,
+            sticky_fields: StickyFields::default(),
+            sort_empty_values: EmptyValues::default(),
+            secondary_sort: None,
+            average_priced_only: false,
+            merge_fold_diacritics: false,
+            import_author_limit: 50,
+            desktop_notifications: false,
+            export_profile: ExportProfile::default(),
+            nightly_maintenance: false,
+            maintenance_hour: 3,
+            maintenance_backups_kept: 7,
+            last_maintenance: None,
+        }
+    }
+}
+
+/// Loads the settings file, falling back to defaults when it is missing or unreadable
+pub fn load() -> AppConfig {
+    fs::read_to_string(paths::settings_file())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(config: &AppConfig) -> Result<(), ConfigError> {
+    let path = paths::settings_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
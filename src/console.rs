@@ -0,0 +1,231 @@
+// src/console.rs
+use thiserror::Error;
+
+/// Most rows the query console shows for one query
+pub const CONSOLE_ROW_LIMIT: usize = 500;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConsoleError {
+    #[error("Enter a query to run")]
+    Empty,
+
+    #[error("Only a single statement can be run at a time")]
+    MultipleStatements,
+
+    #[error("Only SELECT queries are allowed in the console")]
+    NotReadOnly,
+}
+
+/// Result of a console query, every value already rendered as text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryOutput {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub truncated: bool, // More than CONSOLE_ROW_LIMIT rows came back
+}
+
+// Statements that can follow a WITH clause, only SELECT and VALUES leave the data alone
+const MAIN_KEYWORDS: [&str; 6] = ["SELECT", "VALUES", "INSERT", "UPDATE", "DELETE", "REPLACE"];
+
+// Copy of the query where string literals and quoted identifiers become `x` and comments
+// become spaces, byte offsets stay the same so positions map back onto the original text
+fn mask_literals(sql: &str) -> String {
+    let mut masked = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+
+    let fill = |masked: &mut String, c: char, with: char| {
+        masked.extend(std::iter::repeat_n(with, c.len_utf8()))
+    };
+    let blank = |masked: &mut String, c: char| fill(masked, c, ' ');
+
+    while let Some(c) = chars.next() {
+        let closing = match c {
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            '`' => Some('`'),
+            '[' => Some(']'),
+            _ => None,
+        };
+
+        if let Some(closing) = closing {
+            fill(&mut masked, c, 'x');
+            for inner in chars.by_ref() {
+                fill(&mut masked, inner, 'x');
+                if inner == closing {
+                    break;
+                }
+            }
+        } else if c == '-' && chars.peek() == Some(&'-') {
+            blank(&mut masked, c);
+            for inner in chars.by_ref() {
+                if inner == '\n' {
+                    masked.push('\n');
+                    break;
+                }
+                blank(&mut masked, inner);
+            }
+        } else if c == '/' && chars.peek() == Some(&'*') {
+            blank(&mut masked, c);
+            let mut previous = ' ';
+            for inner in chars.by_ref() {
+                blank(&mut masked, inner);
+                if previous == '*' && inner == '/' {
+                    break;
+                }
+                previous = inner;
+            }
+        } else {
+            masked.push(c);
+        }
+    }
+
+    masked
+}
+
+// Uppercased words outside any parentheses, which tells CTE bodies from the main statement
+fn top_level_words(masked: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut word = String::new();
+
+    for c in masked.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            if depth == 0 {
+                words.push(word.to_uppercase());
+            }
+            word.clear();
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    words
+}
+
+/// Checks that the console input is a single read-only query.
+/// Returns the statement without trailing semicolons, ready to be wrapped in a subquery.
+pub fn check_read_only(sql: &str) -> Result<&str, ConsoleError> {
+    let masked = mask_literals(sql);
+
+    // Trailing semicolons are harmless, anything after a semicolon is a second statement
+    let end = masked
+        .trim_end_matches(|c: char| c.is_whitespace() || c == ';')
+        .len();
+    if masked[..end].trim().is_empty() {
+        return Err(ConsoleError::Empty);
+    }
+    if masked[..end].contains(';') {
+        return Err(ConsoleError::MultipleStatements);
+    }
+
+    let words = top_level_words(&masked[..end]);
+    let main = match words.first().map(String::as_str) {
+        Some("SELECT") | Some("VALUES") => true,
+        Some("WITH") => words
+            .iter()
+            .find(|word| MAIN_KEYWORDS.contains(&word.as_str()))
+            .is_some_and(|word| word == "SELECT" || word == "VALUES"),
+        _ => false,
+    };
+
+    if main {
+        Ok(&sql[..end])
+    } else {
+        Err(ConsoleError::NotReadOnly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_selects_pass_without_their_trailing_semicolons() {
+        for sql in [
+            "SELECT * FROM Books",
+            "select title from Books where price > 10;",
+            "  SELECT 1;;  \n",
+            "VALUES (1), (2)",
+            "SELECT 'a; DELETE FROM Books' AS text",
+            "SELECT 1 -- ; DELETE FROM Books",
+            "SELECT 1 /* ; DROP TABLE Books */",
+            "SELECT \"delete\", [update] FROM Books",
+            "SELECT 'it''s; fine'",
+            "WITH recent AS (SELECT * FROM Books) SELECT title FROM recent",
+            "WITH RECURSIVE n(x) AS (VALUES (1) UNION ALL SELECT x + 1 FROM n LIMIT 3) \
+             SELECT x FROM n",
+        ] {
+            let checked = check_read_only(sql);
+            assert!(checked.is_ok(), "{:?} was rejected: {:?}", sql, checked);
+        }
+        assert_eq!(check_read_only("  SELECT 1;;  \n"), Ok("  SELECT 1"));
+    }
+
+    #[test]
+    fn writes_are_rejected_however_they_are_hidden() {
+        for sql in [
+            "DELETE FROM Books",
+            "update Books set price = 0",
+            "INSERT INTO Author (Name) VALUES ('x')",
+            "REPLACE INTO Author (Name) VALUES ('x')",
+            "DROP TABLE Books",
+            "PRAGMA foreign_keys = OFF",
+            "pragma table_info(Books)",
+            "ATTACH DATABASE 'other.db' AS other",
+            "-- SELECT\nDELETE FROM Books",
+            "/* SELECT */ DELETE FROM Books",
+            "'SELECT' DELETE FROM Books",
+            "WITH doomed AS (SELECT id FROM Books) DELETE FROM Books WHERE id IN doomed",
+            "WITH x AS (SELECT 1) UPDATE Books SET price = 0",
+            "WITH x AS (SELECT 1), y AS (SELECT 2) INSERT INTO Author (Name) SELECT 'x'",
+        ] {
+            assert_eq!(check_read_only(sql), Err(ConsoleError::NotReadOnly), "{:?}", sql);
+        }
+    }
+
+    #[test]
+    fn a_second_statement_is_rejected() {
+        for sql in [
+            "SELECT 1; DELETE FROM Books",
+            "SELECT 1;SELECT 2",
+            "SELECT 'a'; PRAGMA foreign_keys = OFF;",
+            "SELECT 1 -- comment\n; DELETE FROM Books",
+            "SELECT 1 /* comment */; DROP TABLE Books",
+        ] {
+            assert_eq!(
+                check_read_only(sql),
+                Err(ConsoleError::MultipleStatements),
+                "{:?}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        for sql in ["", "   ", ";", " ; ;\n", "-- just a comment", "/* nothing */"] {
+            assert_eq!(check_read_only(sql), Err(ConsoleError::Empty), "{:?}", sql);
+        }
+    }
+
+    #[test]
+    fn masking_keeps_byte_offsets() {
+        let sql = "SELECT 'zażółć', \"ą\" -- ę\nFROM [Książki] /* ó */";
+        let masked = mask_literals(sql);
+        assert_eq!(masked.len(), sql.len());
+        assert_eq!(masked.find("FROM"), sql.find("FROM"));
+        assert!(!masked.contains(['ę', 'ó']));
+        let words: Vec<String> = top_level_words(&masked)
+            .into_iter()
+            .filter(|word| word.chars().any(|c| c != 'X'))
+            .collect();
+        assert_eq!(words, vec!["SELECT", "FROM"]);
+    }
+}
@@ -0,0 +1,22 @@
+// src/covers.rs
+pub mod fetch;
+
+use crate::paths;
+use chrono::Local;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Stores downloaded cover bytes in the covers directory and returns the file path
+pub fn save_cover(bytes: &[u8]) -> io::Result<PathBuf> {
+    let dir = paths::covers_dir();
+    fs::create_dir_all(&dir)?;
+
+    // Timestamped names keep covers of books that are not saved yet apart
+    let path = dir.join(format!(
+        "cover-{}.jpg",
+        Local::now().format("%Y%m%d-%H%M%S-%3f")
+    ));
+    fs::write(&path, bytes)?;
+    Ok(path)
+}
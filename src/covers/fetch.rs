@@ -0,0 +1,282 @@
+// src/covers/fetch.rs
+use serde::Deserialize;
+use std::io::Read;
+use std::time::Duration;
+use thiserror::Error;
+
+const SEARCH_URL: &str = "https://openlibrary.org/search.json";
+const COVERS_URL: &str = "https://covers.openlibrary.org/b/id";
+/// Requests slower than this give up instead of leaving the user waiting
+pub const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+// Covers are small, anything bigger is not an image we want to store
+const MAX_COVER_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CoverError {
+    #[error("No cover found on Open Library")]
+    NotFound,
+
+    #[error("Open Library did not answer in time")]
+    Timeout,
+
+    #[error("Cover request failed: {0}")]
+    Request(String),
+
+    #[error("Unexpected response from Open Library: {0}")]
+    Response(String),
+}
+
+/// Minimal HTTP access, so the fetch logic can run against a fake in place of the network
+pub trait HttpClient {
+    fn get(&self, url: &str) -> Result<HttpResponse, CoverError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// Real client with the request timeout applied
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(FETCH_TIMEOUT).build(),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str) -> Result<HttpResponse, CoverError> {
+        let response = match self.agent.get(url).call() {
+            Ok(response) => response,
+            // Error statuses are regular responses for the caller to interpret
+            Err(ureq::Error::Status(status, _)) => {
+                return Ok(HttpResponse {
+                    status,
+                    body: Vec::new(),
+                })
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                let message = transport.to_string();
+                return Err(if message.to_lowercase().contains("timed out") {
+                    CoverError::Timeout
+                } else {
+                    CoverError::Request(message)
+                });
+            }
+        };
+
+        let status = response.status();
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_COVER_BYTES)
+            .read_to_end(&mut body)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::TimedOut => CoverError::Timeout,
+                _ => CoverError::Request(e.to_string()),
+            })?;
+
+        Ok(HttpResponse { status, body })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    docs: Vec<SearchDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchDoc {
+    cover_i: Option<i64>,
+}
+
+// Percent-encodes a query parameter value
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Search for the best match by title and, when known, author
+pub fn search_url(title: &str, author: Option<&str>) -> String {
+    let mut url = format!(
+        "{}?title={}&limit=1&fields=cover_i",
+        SEARCH_URL,
+        encode(title.trim())
+    );
+    if let Some(author) = author.map(str::trim).filter(|author| !author.is_empty()) {
+        url.push_str(&format!("&author={}", encode(author)));
+    }
+    url
+}
+
+/// Medium size image, `default=false` turns a missing cover into a 404
+pub fn cover_url(cover_id: i64) -> String {
+    format!("{}/{}-M.jpg?default=false", COVERS_URL, cover_id)
+}
+
+fn check_status(response: &HttpResponse) -> Result<(), CoverError> {
+    match response.status {
+        200..=299 => Ok(()),
+        404 => Err(CoverError::NotFound),
+        status => Err(CoverError::Response(format!("status {}", status))),
+    }
+}
+
+/// Looks the book up on Open Library and downloads its medium cover
+pub fn fetch_cover(
+    client: &impl HttpClient,
+    title: &str,
+    author: Option<&str>,
+) -> Result<Vec<u8>, CoverError> {
+    let search = client.get(&search_url(title, author))?;
+    check_status(&search)?;
+
+    let results: SearchResponse =
+        serde_json::from_slice(&search.body).map_err(|e| CoverError::Response(e.to_string()))?;
+    let cover_id = results
+        .docs
+        .into_iter()
+        .find_map(|doc| doc.cover_i)
+        .ok_or(CoverError::NotFound)?;
+
+    let image = client.get(&cover_url(cover_id))?;
+    check_status(&image)?;
+    if image.body.is_empty() {
+        return Err(CoverError::NotFound);
+    }
+    Ok(image.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    // Answers requests in order from a script and remembers the urls asked for
+    struct FakeClient {
+        responses: RefCell<VecDeque<Result<HttpResponse, CoverError>>>,
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl FakeClient {
+        fn new(responses: Vec<Result<HttpResponse, CoverError>>) -> Self {
+            Self {
+                responses: RefCell::new(responses.into()),
+                requested: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, url: &str) -> Result<HttpResponse, CoverError> {
+            self.requested.borrow_mut().push(url.to_string());
+            self.responses
+                .borrow_mut()
+                .pop_front()
+                .expect("no response left for the request")
+        }
+    }
+
+    fn ok(body: &[u8]) -> Result<HttpResponse, CoverError> {
+        Ok(HttpResponse {
+            status: 200,
+            body: body.to_vec(),
+        })
+    }
+
+    fn status(status: u16) -> Result<HttpResponse, CoverError> {
+        Ok(HttpResponse {
+            status,
+            body: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn the_search_url_encodes_title_and_author() {
+        assert_eq!(
+            search_url(" Solaris ", Some("Stanisław Lem")),
+            "https://openlibrary.org/search.json?title=Solaris&limit=1&fields=cover_i\
+             &author=Stanis%C5%82aw%20Lem"
+        );
+        assert_eq!(
+            search_url("Q&A: 100%?", Some("  ")),
+            "https://openlibrary.org/search.json?title=Q%26A%3A%20100%25%3F&limit=1&fields=cover_i"
+        );
+        assert_eq!(
+            cover_url(12345),
+            "https://covers.openlibrary.org/b/id/12345-M.jpg?default=false"
+        );
+    }
+
+    #[test]
+    fn the_first_cover_found_is_downloaded() {
+        let client = FakeClient::new(vec![
+            ok(br#"{"docs": [{"title": "no cover"}, {"cover_i": 42}, {"cover_i": 7}]}"#),
+            ok(b"jpeg bytes"),
+        ]);
+
+        assert_eq!(fetch_cover(&client, "Dune", None), Ok(b"jpeg bytes".to_vec()));
+        assert_eq!(
+            client.requested.borrow().as_slice(),
+            [search_url("Dune", None), cover_url(42)]
+        );
+    }
+
+    #[test]
+    fn a_book_without_a_cover_is_not_found() {
+        for responses in [
+            vec![ok(br#"{"docs": []}"#)],
+            vec![ok(br#"{"numFound": 0}"#)],
+            vec![ok(br#"{"docs": [{"cover_i": null}]}"#)],
+            vec![status(404)],
+            vec![ok(br#"{"docs": [{"cover_i": 42}]}"#), status(404)],
+            vec![ok(br#"{"docs": [{"cover_i": 42}]}"#), ok(b"")],
+        ] {
+            let client = FakeClient::new(responses);
+            assert_eq!(fetch_cover(&client, "Dune", None), Err(CoverError::NotFound));
+        }
+    }
+
+    #[test]
+    fn a_timeout_stops_the_fetch() {
+        let client = FakeClient::new(vec![Err(CoverError::Timeout)]);
+        assert_eq!(fetch_cover(&client, "Dune", None), Err(CoverError::Timeout));
+        assert_eq!(client.requested.borrow().len(), 1);
+
+        let client = FakeClient::new(vec![
+            ok(br#"{"docs": [{"cover_i": 42}]}"#),
+            Err(CoverError::Timeout),
+        ]);
+        assert_eq!(fetch_cover(&client, "Dune", None), Err(CoverError::Timeout));
+    }
+
+    #[test]
+    fn unexpected_answers_are_reported() {
+        let client = FakeClient::new(vec![status(503)]);
+        assert_eq!(
+            fetch_cover(&client, "Dune", None),
+            Err(CoverError::Response("status 503".to_string()))
+        );
+
+        let client = FakeClient::new(vec![ok(b"<html>not json</html>")]);
+        assert!(matches!(
+            fetch_cover(&client, "Dune", None),
+            Err(CoverError::Response(_))
+        ));
+    }
+}
@@ -1,8 +1,18 @@
 // src/db.rs
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use diesel::prelude::*;
-use diesel::sqlite::SqliteConnection;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::result::UnexpectedEndOfRow;
+use diesel::row::{Field, Row};
+use diesel::sql_types::{BigInt, Binary, Double, Nullable, Text, Untyped};
+use diesel::sqlite::{Sqlite, SqliteConnection, SqliteType};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use std::collections::HashMap;
 use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
+use std::time::Duration;
 use once_cell::sync::Lazy;
 use anyhow::Result;
 use thiserror::Error;
@@ -11,11 +21,35 @@ use thiserror::Error;
 use r2d2;
 use diesel::r2d2::ConnectionManager;
 
-use crate::models::{AuthorModel, BookModel, BookWithAuthor, NewAuthor, NewBook, ID};
-use crate::schema::{Author, Books};
+use crate::models::{
+    join_aliases, AuthorExportRow, AuthorModel, BookFieldModel, BookListItemModel, BookListModel,
+    BookModel, BookWithAuthor, JournalEntryModel, LoanModel, NewAuthor, NewBook, NewBookField,
+    NewBookListItem, NewJournalEntry, NewLoan, NewQuote, NewReadingSession, NewSeries,
+    QuoteModel, ReadingSessionModel, SeriesModel, YearRow, ID,
+};
+use crate::backup::{BackupDocument, BACKUP_VERSION};
+use crate::bulk::BulkPreview;
+use crate::config::PoolConfig;
+use crate::paths;
+use crate::trash::{DeletedBook, DeletedItem, RestoreOutcome};
+use crate::console::{self, QueryOutput, CONSOLE_ROW_LIMIT};
+use crate::fuzzy;
+use crate::import::{
+    self, ImportCounts, ImportDecision, ImportPlan, ImportedBook, PlannedBook,
+};
+use crate::schema::{
+    Author, AuthorMergeDismissals, BookFields, BookJournal, BookListItems, BookLists, Books,
+    Loans, Quotes, ReadingSessions, Series,
+};
+use crate::ui::{EmptyValues, SortDirection, SortField};
+
+pub mod cached;
+use cached::QueryKind;
 
 pub type DbPool = r2d2::Pool<ConnectionManager<SqliteConnection>>;
 
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
 static DB_POOL: Lazy<Mutex<Option<DbPool>>> = Lazy::new(|| Mutex::new(None));
 
 #[derive(Debug, Error)]
@@ -24,10 +58,94 @@ pub enum DbError {
     Connection(String),
 
     #[error("Database query error: {0}")]
-    Query(#[from] diesel::result::Error),
+    Query(diesel::result::Error),
+
+    #[error("{0}")]
+    Duplicate(String), // A UNIQUE constraint rejected the row, holds a message for the user
 
     #[error("Database pool not initialized")]
     PoolNotInitialized,
+
+    #[error("Database busy, another operation is taking long. Try again in a moment.")]
+    Busy,
+
+    #[error("Database migration error: {0}")]
+    Migration(String),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("Invalid database path: {0}")]
+    Path(#[from] PathError),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PathError {
+    #[error("DATABASE_URL is not set")]
+    NotSet,
+
+    #[error("the path is empty")]
+    Empty,
+
+    #[error("the path contains a NUL character")]
+    NulCharacter,
+
+    #[error("~ cannot be expanded, the home folder is unknown")]
+    NoHomeDir,
+
+    #[error("{0} refers to another user's home folder, which is not supported")]
+    OtherUserHome(String),
+
+    #[error("folder {0} does not exist")]
+    MissingParent(PathBuf),
+
+    #[error("{0} is a folder, not a database file")]
+    IsDirectory(PathBuf),
+}
+
+// Unique violations become Duplicate so the forms can say what clashed
+impl From<diesel::result::Error> for DbError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                ref info,
+            ) => DbError::Duplicate(duplicate_message(info.constraint_name(), info.message())),
+            // SQLite doesn't say which reference failed
+            diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                _,
+            ) => DbError::InvalidInput(
+                "The author, series or book this refers to no longer exists".to_string(),
+            ),
+            err => DbError::Query(err),
+        }
+    }
+}
+
+/// Message for a UNIQUE constraint failure. SQLite doesn't report the constraint name,
+/// only "UNIQUE constraint failed: Books.isbn", so the table and columns come from there
+fn duplicate_message(constraint: Option<&str>, message: &str) -> String {
+    let columns = constraint
+        .or_else(|| message.split_once("constraint failed:").map(|(_, cols)| cols))
+        .unwrap_or_default();
+    let Some((table, column)) = columns.split(',').next().and_then(|c| c.trim().split_once('.'))
+    else {
+        return "This record already exists".to_string();
+    };
+
+    let record = match table {
+        "Books" => "book",
+        "Author" => "author",
+        "Series" => "series",
+        _ => "record",
+    };
+    let field = match column {
+        "isbn" => "ISBN".to_string(),
+        "Name" | "name" => "name".to_string(),
+        column => column.replace('_', " "),
+    };
+    format!("A {} with this {} already exists", record, field)
 }
 
 // Implementation for the standalone r2d2::Error
@@ -37,26 +155,287 @@ impl From<r2d2::Error> for DbError {
     }
 }
 
-pub fn initialize_pool() -> Result<(), DbError> {
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let manager = ConnectionManager::<SqliteConnection>::new(database_url);
+// Times a query gave up waiting for a free connection since the pool was created
+static POOL_TIMEOUTS: AtomicU64 = AtomicU64::new(0);
+
+// Set when another instance holds the database, writes are refused until restart
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+// Absolute path of the open database, shown in the settings
+static DATABASE_PATH: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+// What happened to a database found only in the working directory, shown once at startup
+static LEGACY_NOTICE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Turns a DATABASE_URL into an absolute path. `~` is expanded and relative paths are
+/// taken from the executable's folder, so the database doesn't depend on where the app
+/// was launched from. SQLite's `:memory:` and `file:` URIs are passed through as they are.
+///
+/// Relative paths used to be taken from the working directory. A database found only
+/// there is copied next to the executable, so an existing shelf doesn't turn up empty
+/// after an upgrade and the working directory stops mattering from then on. The copy is
+/// reported once through `take_legacy_database_notice`.
+pub fn resolve_database_path(input: &str) -> Result<PathBuf, PathError> {
+    let legacy_dir = env::current_dir().ok();
+    resolve_database_path_in(input, &database_base_dir(), legacy_dir.as_deref())
+}
+
+fn resolve_database_path_in(
+    input: &str,
+    base_dir: &Path,
+    legacy_dir: Option<&Path>,
+) -> Result<PathBuf, PathError> {
+    let input = input.trim();
+    let input = input.strip_prefix("sqlite://").unwrap_or(input);
+    if input.is_empty() {
+        return Err(PathError::Empty);
+    }
+    if input.contains('\0') {
+        return Err(PathError::NulCharacter);
+    }
+    if input == ":memory:" || input.starts_with("file:") {
+        return Ok(PathBuf::from(input));
+    }
+
+    // A .env written on Windows may be used elsewhere and the other way around
+    let input = input.replace(['\\', '/'], std::path::MAIN_SEPARATOR_STR);
+    let path = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(std::path::MAIN_SEPARATOR) => {
+            let home = dirs::home_dir().ok_or(PathError::NoHomeDir)?;
+            home.join(rest.trim_start_matches(std::path::MAIN_SEPARATOR))
+        }
+        Some(_) => return Err(PathError::OtherUserHome(input)),
+        None => PathBuf::from(input),
+    };
+    let path = if path.is_absolute() {
+        normalize_path(&path)
+    } else {
+        let resolved = normalize_path(&base_dir.join(&path));
+        match legacy_dir.map(|dir| normalize_path(&dir.join(&path))) {
+            Some(legacy) if legacy != resolved && !resolved.exists() && legacy.is_file() => {
+                adopt_legacy_database(&legacy, &resolved)
+            }
+            _ => resolved,
+        }
+    };
+
+    if path.is_dir() {
+        return Err(PathError::IsDirectory(path));
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => {
+            Err(PathError::MissingParent(parent.to_path_buf()))
+        }
+        _ => Ok(path),
+    }
+}
+
+// Copies a database found only in the working directory to where it now belongs, along
+// with SQLite's journal files. Should the copy fail the old one is kept, with a warning.
+fn adopt_legacy_database(legacy: &Path, resolved: &Path) -> PathBuf {
+    let copied = ["", "-wal", "-shm"].iter().try_for_each(|suffix| {
+        let from = PathBuf::from(format!("{}{}", legacy.display(), suffix));
+        let to = PathBuf::from(format!("{}{}", resolved.display(), suffix));
+        match from.is_file() {
+            true => std::fs::copy(&from, &to).map(|_| ()),
+            false => Ok(()),
+        }
+    });
+    let (notice, path) = match copied {
+        Ok(()) => (
+            format!(
+                "Moved the database from {} to {}, the old copy is no longer used",
+                legacy.display(),
+                resolved.display()
+            ),
+            resolved.to_path_buf(),
+        ),
+        Err(e) => {
+            let _ = std::fs::remove_file(resolved);
+            (
+                format!(
+                    "Could not move the database from {} to {} ({}), it is still opened from \
+                     the working directory",
+                    legacy.display(),
+                    resolved.display(),
+                    e
+                ),
+                legacy.to_path_buf(),
+            )
+        }
+    };
+    *LEGACY_NOTICE.lock().unwrap() = Some(notice);
+    path
+}
+
+/// Says once what happened to a database found only in the working directory, if any
+pub fn take_legacy_database_notice() -> Option<String> {
+    LEGACY_NOTICE.lock().unwrap().take()
+}
+
+// Next to the executable, or the config folder if its location is unknown
+fn database_base_dir() -> PathBuf {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(paths::config_dir)
+}
+
+// Drops `.` and folds `..` without touching the file system
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// The database opened by `initialize_pool`
+pub fn database_path() -> Option<PathBuf> {
+    DATABASE_PATH.lock().unwrap().clone()
+}
+
+/// The database opened at startup: the `--db` value, else DATABASE_URL
+pub fn startup_database_path(database_url: Option<&str>) -> Result<PathBuf, DbError> {
+    let database_url = match database_url {
+        Some(url) => url.to_string(),
+        None => env::var("DATABASE_URL").map_err(|_| PathError::NotSet)?,
+    };
+    Ok(resolve_database_path(&database_url)?)
+}
+
+/// Makes the pools opened from now on refuse writes and skip migrations
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+// Pragmas set on every new connection. With query_only SQLite rejects any write, with
+// foreign_keys a write that references a missing row.
+#[derive(Debug)]
+struct ConnectionPragmas {
+    query_only: bool,
+    foreign_keys: bool,
+}
+
+impl r2d2::CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ConnectionPragmas {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        let pragmas = [
+            ("query_only", self.query_only),
+            ("foreign_keys", self.foreign_keys),
+        ];
+        for (pragma, on) in pragmas {
+            diesel::sql_query(format!("PRAGMA {} = {}", pragma, if on { "ON" } else { "OFF" }))
+                .execute(conn)
+                .map_err(diesel::r2d2::Error::QueryError)?;
+        }
+        Ok(())
+    }
+}
+
+/// Opens the database named by DATABASE_URL
+pub fn initialize_pool(config: &PoolConfig) -> Result<(), DbError> {
+    let database_url = env::var("DATABASE_URL").map_err(|_| PathError::NotSet)?;
+    initialize_pool_with_url(&database_url, config)
+}
+
+/// Opens the given database, e.g. one passed with `--db`, and brings its schema up to date
+pub fn initialize_pool_with_url(database_url: &str, config: &PoolConfig) -> Result<(), DbError> {
+    let database_path = resolve_database_path(database_url)?;
+    let manager =
+        ConnectionManager::<SqliteConnection>::new(database_path.to_string_lossy().into_owned());
+    let max_size = config.max_size.max(1);
     let pool = r2d2::Pool::builder()
-        .max_size(15)
+        .max_size(max_size)
+        .min_idle(config.min_idle.map(|min_idle| min_idle.min(max_size)))
+        .connection_timeout(Duration::from_secs(config.connection_timeout_secs.max(1)))
+        .connection_customizer(Box::new(ConnectionPragmas {
+            query_only: is_read_only(),
+            foreign_keys: config.foreign_keys,
+        }))
         .build(manager)?;
+    POOL_TIMEOUTS.store(0, Ordering::Relaxed);
+
+    // Bring the schema up to date before anything else touches the database, the
+    // instance holding it has already done so when it is opened read-only
+    if !is_read_only() {
+        let mut conn = checkout(&pool)?;
+        migrate(&mut conn, config.foreign_keys)?;
+    }
 
     let mut db_pool = DB_POOL.lock().unwrap();
     *db_pool = Some(pool);
+    *DATABASE_PATH.lock().unwrap() = Some(database_path);
+    cached::invalidate_all();
     Ok(())
 }
 
+// A migration that rebuilds a table drops it first, which enforced foreign keys would
+// refuse while other tables still reference it. The pragma is ignored inside the
+// transaction each migration runs in, so it is switched around all of them.
+fn migrate(conn: &mut SqliteConnection, foreign_keys: bool) -> Result<(), DbError> {
+    diesel::sql_query("PRAGMA foreign_keys = OFF").execute(conn)?;
+    let migrated = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map(|_| ())
+        .map_err(|e| DbError::Migration(e.to_string()));
+    if foreign_keys {
+        diesel::sql_query("PRAGMA foreign_keys = ON").execute(conn)?;
+    }
+    migrated
+}
+
 pub fn get_connection() -> Result<r2d2::PooledConnection<ConnectionManager<SqliteConnection>>, DbError> {
-    let db_pool = DB_POOL.lock().unwrap();
-    match &*db_pool {
-        Some(pool) => Ok(pool.get()?),
+    // Cloned so the lock is not held while waiting for a connection
+    let pool = DB_POOL.lock().unwrap().clone();
+    match pool {
+        Some(pool) => checkout(&pool),
         None => Err(DbError::PoolNotInitialized),
     }
 }
 
+// r2d2 only fails a checkout once the connection timeout ran out
+fn checkout(
+    pool: &DbPool,
+) -> Result<r2d2::PooledConnection<ConnectionManager<SqliteConnection>>, DbError> {
+    pool.get().map_err(|_| {
+        POOL_TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+        DbError::Busy
+    })
+}
+
+/// Snapshot of the connection pool for the diagnostics in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatus {
+    pub max_size: u32,
+    pub connections: u32,
+    pub idle_connections: u32,
+    pub timeouts: u64,
+}
+
+pub fn pool_status() -> Option<PoolStatus> {
+    let db_pool = DB_POOL.lock().unwrap();
+    let pool = db_pool.as_ref()?;
+    let state = pool.state();
+    Some(PoolStatus {
+        max_size: pool.max_size(),
+        connections: state.connections,
+        idle_connections: state.idle_connections,
+        timeouts: POOL_TIMEOUTS.load(Ordering::Relaxed),
+    })
+}
+
 pub fn get_authors() -> Result<Vec<AuthorModel>, DbError> {
     let mut conn = get_connection()?;
     let authors = Author::table
@@ -74,12 +453,24 @@ pub fn get_author(id: ID) -> Result<AuthorModel, DbError> {
     Ok(author)
 }
 
+// Like get_author, but a deleted author is None rather than an error
+pub fn find_author(id: ID) -> Result<Option<AuthorModel>, DbError> {
+    let mut conn = get_connection()?;
+    let author = Author::table
+        .find(id)
+        .select(AuthorModel::as_select())
+        .first(&mut conn)
+        .optional()?;
+    Ok(author)
+}
+
 pub fn create_author(new_author: &NewAuthor) -> Result<AuthorModel, DbError> {
     let mut conn = get_connection()?;
     let author = diesel::insert_into(Author::table)
         .values(new_author)
         .returning(AuthorModel::as_returning())
         .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Authors]);
     Ok(author)
 }
 
@@ -89,16 +480,187 @@ pub fn update_author(id: ID, author: &NewAuthor) -> Result<AuthorModel, DbError>
         .set(author)
         .returning(AuthorModel::as_returning())
         .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books]);
     Ok(author)
 }
 
-pub fn delete_author(id: ID) -> Result<usize, DbError> {
+// Returns the removed author and the ids of its books so the deletion can be undone
+pub fn delete_author(id: ID) -> Result<DeletedItem, DbError> {
     let mut conn = get_connection()?;
-    let count = diesel::delete(Author::table.find(id))
-        .execute(&mut conn)?;
+    let (author, books) = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let author = Author::table
+            .find(id)
+            .select(AuthorModel::as_select())
+            .first(conn)?;
+        // The books lose the author, a restore links them again. Done here rather than
+        // left to ON DELETE SET NULL, which only runs with foreign keys enforced.
+        let books = Books::table
+            .filter(Books::AuthorFK.eq(id))
+            .select(Books::id)
+            .load::<ID>(conn)?;
+        diesel::update(Books::table.filter(Books::id.eq_any(&books)))
+            .set(Books::AuthorFK.eq(None::<ID>))
+            .execute(conn)?;
+        forget_merge_dismissals(conn, &[id])?;
+        diesel::delete(Author::table.find(id)).execute(conn)?;
+        Ok((author, books))
+    })?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books]);
+    Ok(DeletedItem::Author { author, books })
+}
+
+// Authors that no book references, candidates for cleanup
+pub fn get_authors_without_books() -> Result<Vec<AuthorModel>, DbError> {
+    let mut conn = get_connection()?;
+    let authors = Author::table
+        .filter(diesel::dsl::not(diesel::dsl::exists(
+            Books::table.filter(Books::AuthorFK.eq(Author::Id.nullable())),
+        )))
+        .order(Author::Name.asc())
+        .select(AuthorModel::as_select())
+        .load(&mut conn)?;
+    Ok(authors)
+}
+
+// One grouped query, authors without books are kept with zero totals
+pub fn get_author_export_rows() -> Result<Vec<AuthorExportRow>, DbError> {
+    let mut conn = get_connection()?;
+    let rows = diesel::sql_query(
+        "SELECT Author.Name AS name, \
+                COUNT(Books.id) AS books, \
+                COUNT(Books.finished) AS finished, \
+                COUNT(Books.bought) AS owned, \
+                Author.published_works AS published_works, \
+                COALESCE(SUM(Books.price * Books.quantity), 0.0) AS spent, \
+                MIN(Books.bought) AS first_bought, \
+                MAX(Books.bought) AS last_bought \
+         FROM Author LEFT JOIN Books ON Books.AuthorFK = Author.Id \
+         GROUP BY Author.Id \
+         ORDER BY Author.Name COLLATE NOCASE",
+    )
+    .load::<AuthorExportRow>(&mut conn)?;
+    Ok(rows)
+}
+
+// Every year any book was added, bought or finished in, newest first
+pub fn get_distinct_years() -> Result<Vec<i32>, DbError> {
+    let mut conn = get_connection()?;
+    let rows = diesel::sql_query(
+        "SELECT DISTINCT CAST(strftime('%Y', date) AS INTEGER) AS year \
+         FROM (SELECT added AS date FROM Books \
+               UNION SELECT bought FROM Books \
+               UNION SELECT finished FROM Books) \
+         WHERE date IS NOT NULL \
+         ORDER BY year DESC",
+    )
+    .load::<YearRow>(&mut conn)?;
+    Ok(rows.into_iter().map(|row| row.year).collect())
+}
+
+// Deletes the given authors in one transaction, skipping any that gained a book meanwhile
+pub fn delete_authors_without_books(ids: &[ID]) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let unused = Author::table
+            .filter(Author::Id.eq_any(ids))
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                Books::table.filter(Books::AuthorFK.eq(Author::Id.nullable())),
+            )))
+            .select(Author::Id)
+            .load::<ID>(conn)?;
+        forget_merge_dismissals(conn, &unused)?;
+        diesel::delete(Author::table.filter(Author::Id.eq_any(&unused))).execute(conn)
+    })?;
+    cached::invalidate(&[QueryKind::Authors]);
     Ok(count)
 }
 
+// Dismissed merge suggestions involving authors about to be deleted
+fn forget_merge_dismissals(conn: &mut SqliteConnection, ids: &[ID]) -> QueryResult<usize> {
+    diesel::delete(
+        AuthorMergeDismissals::table.filter(
+            AuthorMergeDismissals::author_a
+                .eq_any(ids)
+                .or(AuthorMergeDismissals::author_b.eq_any(ids)),
+        ),
+    )
+    .execute(conn)
+}
+
+/// Moves every book of `remove` to `keep` and deletes `remove` in one transaction.
+/// Links and the published works count the kept author lacks are taken over.
+/// Returns how many books moved.
+pub fn merge_authors(keep: ID, remove: ID) -> Result<usize, DbError> {
+    if keep == remove {
+        return Err(DbError::InvalidInput(
+            "An author cannot be merged into itself".to_string(),
+        ));
+    }
+    let mut conn = get_connection()?;
+    let moved = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let kept = Author::table
+            .find(keep)
+            .select(AuthorModel::as_select())
+            .first(conn)?;
+        let removed = Author::table
+            .find(remove)
+            .select(AuthorModel::as_select())
+            .first(conn)?;
+        // The removed name stays findable as an alias of the kept author
+        let kept_name = kept.Name.as_deref().unwrap_or_default().to_lowercase();
+        let removed_name = removed
+            .Name
+            .as_deref()
+            .filter(|name| name.trim().to_lowercase() != kept_name.trim());
+        let aliases = join_aliases(
+            kept.aliases()
+                .chain(removed_name.map(str::trim))
+                .chain(removed.aliases()),
+        );
+        diesel::update(Author::table.find(keep))
+            .set((
+                Author::website.eq(kept.website.or(removed.website)),
+                Author::Aliases.eq(aliases),
+                Author::wikipedia.eq(kept.wikipedia.or(removed.wikipedia)),
+                Author::published_works.eq(kept.published_works.or(removed.published_works)),
+            ))
+            .execute(conn)?;
+
+        let moved = diesel::update(Books::table.filter(Books::AuthorFK.eq(remove)))
+            .set(Books::AuthorFK.eq(keep))
+            .execute(conn)?;
+        forget_merge_dismissals(conn, &[remove])?;
+        diesel::delete(Author::table.find(remove)).execute(conn)?;
+        Ok(moved)
+    })?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books]);
+    Ok(moved)
+}
+
+/// Author pairs marked as different people, each with the lower id first
+pub fn get_merge_dismissals() -> Result<Vec<(ID, ID)>, DbError> {
+    let mut conn = get_connection()?;
+    let pairs = AuthorMergeDismissals::table
+        .select((
+            AuthorMergeDismissals::author_a,
+            AuthorMergeDismissals::author_b,
+        ))
+        .load::<(ID, ID)>(&mut conn)?;
+    Ok(pairs)
+}
+
+// Remembers that two authors are different people, so they aren't suggested again
+pub fn dismiss_author_merge(a: ID, b: ID) -> Result<(), DbError> {
+    let mut conn = get_connection()?;
+    diesel::insert_or_ignore_into(AuthorMergeDismissals::table)
+        .values((
+            AuthorMergeDismissals::author_a.eq(a.min(b)),
+            AuthorMergeDismissals::author_b.eq(a.max(b)),
+        ))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
 // Book CRUD Operations
 pub fn get_books() -> Result<Vec<BookWithAuthor>, DbError> {
     let mut conn = get_connection()?;
@@ -125,6 +687,91 @@ pub fn get_books() -> Result<Vec<BookWithAuthor>, DbError> {
 }
 
 // New function to get books by author
+diesel::define_sql_function! {
+    /// SQLite's lower(), used for case-insensitive ordering
+    fn lower(x: Nullable<Text>) -> Nullable<Text>;
+}
+
+/// Books ordered by the database instead of in memory, optionally one page at a time.
+/// Ordering matches the in-memory `sort_books`, including where books without a date go.
+pub fn get_books_sorted(
+    field: &SortField,
+    direction: &SortDirection,
+    secondary: Option<&SortField>,
+    empty: EmptyValues,
+    offset: i64,
+    limit: Option<i64>,
+) -> Result<Vec<BookWithAuthor>, DbError> {
+    let mut conn = get_connection()?;
+    let mut query = Books::table
+        .left_join(Author::table)
+        .select((BookModel::as_select(), Option::<AuthorModel>::as_select()))
+        .into_boxed();
+
+    query = order_books_by(query, field, direction, empty);
+    if let Some(secondary) = secondary {
+        query = order_books_by(query, secondary, &SortDirection::Ascending, empty);
+    }
+    // Ties never depend on the row order SQLite happens to return
+    query = query
+        .then_order_by(lower(Books::title.nullable()).asc())
+        .then_order_by(Books::id.asc());
+
+    query = query.offset(offset);
+    if let Some(limit) = limit {
+        query = query.limit(limit);
+    }
+
+    let books = query
+        .load::<(BookModel, Option<AuthorModel>)>(&mut conn)?
+        .into_iter()
+        .map(|(book, author)| BookWithAuthor { book, author })
+        .collect();
+    Ok(books)
+}
+
+type SortedBooksQuery<'a> = diesel::helper_types::IntoBoxed<
+    'a,
+    diesel::dsl::Select<
+        diesel::dsl::LeftJoin<Books::table, Author::table>,
+        (
+            diesel::dsl::AsSelect<BookModel, Sqlite>,
+            diesel::dsl::AsSelect<Option<AuthorModel>, Sqlite>,
+        ),
+    >,
+    Sqlite,
+>;
+
+// Appends one sort key, books without the value go first or last whatever the direction,
+// as in sort_books
+fn order_books_by<'a>(
+    query: SortedBooksQuery<'a>,
+    field: &SortField,
+    direction: &SortDirection,
+    empty: EmptyValues,
+) -> SortedBooksQuery<'a> {
+    let empty_last = matches!(empty, EmptyValues::Last);
+    let query = match (field, empty_last) {
+        (SortField::Price, true) => query.then_order_by(Books::price.is_null().asc()),
+        (SortField::Price, false) => query.then_order_by(Books::price.is_null().desc()),
+        (SortField::DateAdded, true) => query.then_order_by(Books::added.is_null().asc()),
+        (SortField::DateAdded, false) => query.then_order_by(Books::added.is_null().desc()),
+        _ => query,
+    };
+
+    let ascending = matches!(direction, SortDirection::Ascending);
+    match (field, ascending) {
+        (SortField::Title, true) => query.then_order_by(lower(Books::title.nullable()).asc()),
+        (SortField::Title, false) => query.then_order_by(lower(Books::title.nullable()).desc()),
+        (SortField::Author, true) => query.then_order_by(lower(Author::Name).asc()),
+        (SortField::Author, false) => query.then_order_by(lower(Author::Name).desc()),
+        (SortField::Price, true) => query.then_order_by(Books::price.asc()),
+        (SortField::Price, false) => query.then_order_by(Books::price.desc()),
+        (SortField::DateAdded, true) => query.then_order_by(Books::added.asc()),
+        (SortField::DateAdded, false) => query.then_order_by(Books::added.desc()),
+    }
+}
+
 pub fn get_books_by_author(author_id: ID) -> Result<Vec<BookWithAuthor>, DbError> {
     let mut conn = get_connection()?;
 
@@ -168,12 +815,22 @@ pub fn get_book(id: ID) -> Result<BookWithAuthor, DbError> {
     Ok(BookWithAuthor { book, author })
 }
 
+// Like get_book, but a deleted book is None rather than an error
+pub fn find_book(id: ID) -> Result<Option<BookWithAuthor>, DbError> {
+    match get_book(id) {
+        Ok(book) => Ok(Some(book)),
+        Err(DbError::Query(diesel::result::Error::NotFound)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 pub fn create_book(new_book: &NewBook) -> Result<BookModel, DbError> {
     let mut conn = get_connection()?;
     let book = diesel::insert_into(Books::table)
         .values(new_book)
         .returning(BookModel::as_returning())
         .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Books]);
     Ok(book)
 }
 
@@ -183,12 +840,2720 @@ pub fn update_book(id: ID, book: &NewBook) -> Result<BookModel, DbError> {
         .set(book)
         .returning(BookModel::as_returning())
         .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Books]);
     Ok(book)
 }
 
-pub fn delete_book(id: ID) -> Result<usize, DbError> {
+/// Pins or unpins a single book without touching its other fields
+pub fn set_book_pinned(id: ID, pinned: bool) -> Result<BookModel, DbError> {
     let mut conn = get_connection()?;
-    let count = diesel::delete(Books::table.find(id))
-        .execute(&mut conn)?;
+    let book = diesel::update(Books::table.find(id))
+        .set(Books::pinned.eq(pinned))
+        .returning(BookModel::as_returning())
+        .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Books]);
+    Ok(book)
+}
+
+/// History stored alongside a book, lost when the book is deleted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelatedRecords {
+    pub journal_entries: usize,
+    pub loans: usize,
+    pub quotes: usize,
+    pub reading_sessions: usize,
+}
+
+impl RelatedRecords {
+    pub fn is_empty(&self) -> bool {
+        self.journal_entries == 0
+            && self.loans == 0
+            && self.quotes == 0
+            && self.reading_sessions == 0
+    }
+
+    /// Deleting a book with history takes an extra acknowledgment, a plain book doesn't
+    pub fn can_delete(related: Option<&RelatedRecords>, acknowledged: bool) -> bool {
+        related.is_some_and(|related| related.is_empty() || acknowledged)
+    }
+
+    /// e.g. "5 journal entries, 1 loan record", empty when there is nothing
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, one: &str, many: &str| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        };
+        let mut parts = Vec::new();
+        if self.journal_entries > 0 {
+            parts.push(plural(self.journal_entries, "journal entry", "journal entries"));
+        }
+        if self.loans > 0 {
+            parts.push(plural(self.loans, "loan record", "loan records"));
+        }
+        if self.quotes > 0 {
+            parts.push(plural(self.quotes, "quote", "quotes"));
+        }
+        if self.reading_sessions > 0 {
+            parts.push(plural(
+                self.reading_sessions,
+                "reading session",
+                "reading sessions",
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+/// What `delete_book_cascade` removed, the book itself is kept for the undo list
+#[derive(Debug, Clone)]
+pub struct DeleteReport {
+    pub item: DeletedItem,
+    pub removed: RelatedRecords,
+}
+
+pub fn count_related_records(id: ID) -> Result<RelatedRecords, DbError> {
+    let mut conn = get_connection()?;
+    let journal_entries: i64 = BookJournal::table
+        .filter(BookJournal::book_fk.eq(id))
+        .count()
+        .get_result(&mut conn)?;
+    let loans: i64 = Loans::table
+        .filter(Loans::book_fk.eq(id))
+        .count()
+        .get_result(&mut conn)?;
+    let quotes: i64 = Quotes::table
+        .filter(Quotes::book_fk.eq(id))
+        .count()
+        .get_result(&mut conn)?;
+    let reading_sessions: i64 = ReadingSessions::table
+        .filter(ReadingSessions::book_fk.eq(id))
+        .count()
+        .get_result(&mut conn)?;
+    Ok(RelatedRecords {
+        journal_entries: journal_entries as usize,
+        loans: loans as usize,
+        quotes: quotes as usize,
+        reading_sessions: reading_sessions as usize,
+    })
+}
+
+// Removes the book together with its journal entries, quotes, loans and reading sessions in
+// one transaction. The book, its author, journal and quotes are returned so the deletion can
+// be undone, loan history and reading sessions are not kept.
+pub fn delete_book_cascade(id: ID) -> Result<DeleteReport, DbError> {
+    let mut conn = get_connection()?;
+    let report = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let book = Books::table
+            .find(id)
+            .select(BookModel::as_select())
+            .first(conn)?;
+        let author = match book.AuthorFK {
+            Some(author_id) => Author::table
+                .find(author_id)
+                .select(AuthorModel::as_select())
+                .first(conn)
+                .optional()?,
+            None => None,
+        };
+        let journal = BookJournal::table
+            .filter(BookJournal::book_fk.eq(id))
+            .order(BookJournal::written_at.asc())
+            .select(JournalEntryModel::as_select())
+            .load(conn)?;
+        let quotes = Quotes::table
+            .filter(Quotes::book_fk.eq(id))
+            .order(Quotes::noted_at.asc())
+            .select(QuoteModel::as_select())
+            .load(conn)?;
+        let fields = BookFields::table
+            .filter(BookFields::BookFK.eq(id))
+            .order(BookFields::Id.asc())
+            .select(BookFieldModel::as_select())
+            .load(conn)?;
+        let loan_history = Loans::table
+            .filter(Loans::book_fk.eq(id))
+            .order(Loans::loaned_on.asc())
+            .select(LoanModel::as_select())
+            .load(conn)?;
+        let sessions = ReadingSessions::table
+            .filter(ReadingSessions::book_fk.eq(id))
+            .order(ReadingSessions::started_at.asc())
+            .select(ReadingSessionModel::as_select())
+            .load(conn)?;
+        let lists = BookListItems::table
+            .filter(BookListItems::book_fk.eq(id))
+            .select(BookListItemModel::as_select())
+            .load(conn)?;
+
+        let journal_entries = diesel::delete(BookJournal::table.filter(BookJournal::book_fk.eq(id)))
+            .execute(conn)?;
+        let loans = diesel::delete(Loans::table.filter(Loans::book_fk.eq(id))).execute(conn)?;
+        let quotes_removed =
+            diesel::delete(Quotes::table.filter(Quotes::book_fk.eq(id))).execute(conn)?;
+        let reading_sessions =
+            diesel::delete(ReadingSessions::table.filter(ReadingSessions::book_fk.eq(id)))
+                .execute(conn)?;
+        diesel::delete(BookFields::table.filter(BookFields::BookFK.eq(id))).execute(conn)?;
+        diesel::delete(BookListItems::table.filter(BookListItems::book_fk.eq(id))).execute(conn)?;
+        diesel::delete(Books::table.find(id)).execute(conn)?;
+        Ok(DeleteReport {
+            item: DeletedItem::Book(Box::new(DeletedBook {
+                book,
+                author,
+                journal,
+                quotes,
+                fields,
+                loans: loan_history,
+                sessions,
+                lists,
+            })),
+            removed: RelatedRecords {
+                journal_entries,
+                loans,
+                quotes: quotes_removed,
+                reading_sessions,
+            },
+        })
+    })?;
+    cached::invalidate(&[QueryKind::Books]);
+    Ok(report)
+}
+
+// Inserts a deleted book or author again, under its old id while that is still free
+pub fn restore_deleted(item: &DeletedItem) -> Result<RestoreOutcome, DbError> {
+    let mut conn = get_connection()?;
+    let outcome = conn.transaction::<_, diesel::result::Error, _>(|conn| match item {
+        DeletedItem::Author { author, books } => {
+            let restored_id = insert_author_keeping_id(conn, author)?;
+            // Books given another author since then keep it
+            diesel::update(
+                Books::table
+                    .filter(Books::id.eq_any(books))
+                    .filter(Books::AuthorFK.is_null().or(Books::AuthorFK.eq(author.Id))),
+            )
+            .set(Books::AuthorFK.eq(restored_id))
+            .execute(conn)?;
+            Ok(RestoreOutcome {
+                label: item.to_string(),
+                original_id: author.Id,
+                restored_id,
+                recreated_author: None,
+            })
+        }
+        DeletedItem::Book(deleted) => {
+            let DeletedBook {
+                book,
+                author,
+                journal,
+                quotes,
+                fields,
+                loans,
+                sessions,
+                lists,
+            } = deleted.as_ref();
+            let mut new_book = NewBook::from(book);
+            let mut recreated_author = None;
+            if let Some(author) = author {
+                let exists = Author::table
+                    .find(author.Id)
+                    .select(Author::Id)
+                    .first::<ID>(conn)
+                    .optional()?
+                    .is_some();
+                if !exists {
+                    new_book.AuthorFK = Some(insert_author_keeping_id(conn, author)?);
+                    recreated_author = Some(author.Id);
+                }
+            }
+            if let Some(series_id) = book.SeriesFK {
+                let exists = Series::table
+                    .find(series_id)
+                    .select(Series::id)
+                    .first::<ID>(conn)
+                    .optional()?
+                    .is_some();
+                if !exists {
+                    new_book.SeriesFK = None;
+                }
+            }
+
+            let id_taken = Books::table
+                .find(book.id)
+                .select(Books::id)
+                .first::<ID>(conn)
+                .optional()?
+                .is_some();
+            let restored_id = if id_taken {
+                diesel::insert_into(Books::table)
+                    .values(&new_book)
+                    .returning(Books::id)
+                    .get_result(conn)?
+            } else {
+                diesel::insert_into(Books::table)
+                    .values((Books::id.eq(book.id), &new_book))
+                    .returning(Books::id)
+                    .get_result(conn)?
+            };
+
+            let entries: Vec<NewJournalEntry> = journal
+                .iter()
+                .map(|entry| NewJournalEntry {
+                    book_fk: restored_id,
+                    written_at: entry.written_at,
+                    body: entry.body.clone(),
+                })
+                .collect();
+            diesel::insert_into(BookJournal::table)
+                .values(&entries)
+                .execute(conn)?;
+            let quotes: Vec<NewQuote> = quotes
+                .iter()
+                .map(|quote| NewQuote {
+                    book_fk: restored_id,
+                    page: quote.page,
+                    text: quote.text.clone(),
+                    noted_at: quote.noted_at,
+                })
+                .collect();
+            diesel::insert_into(Quotes::table)
+                .values(&quotes)
+                .execute(conn)?;
+            let fields: Vec<NewBookField> = fields
+                .iter()
+                .map(|field| NewBookField {
+                    BookFK: restored_id,
+                    key: field.key.clone(),
+                    value: field.value.clone(),
+                })
+                .collect();
+            diesel::insert_into(BookFields::table)
+                .values(&fields)
+                .execute(conn)?;
+            let loans: Vec<NewLoan> = loans
+                .iter()
+                .map(|loan| NewLoan {
+                    book_fk: restored_id,
+                    borrower: loan.borrower.clone(),
+                    loaned_on: loan.loaned_on,
+                    due_on: loan.due_on,
+                    returned_on: loan.returned_on,
+                })
+                .collect();
+            diesel::insert_into(Loans::table)
+                .values(&loans)
+                .execute(conn)?;
+            let sessions: Vec<NewReadingSession> = sessions
+                .iter()
+                .map(|session| NewReadingSession {
+                    book_fk: restored_id,
+                    started_at: session.started_at,
+                    ended_at: session.ended_at,
+                    pages: session.pages,
+                })
+                .collect();
+            diesel::insert_into(ReadingSessions::table)
+                .values(&sessions)
+                .execute(conn)?;
+            let existing_lists: Vec<ID> = BookLists::table
+                .filter(BookLists::id.eq_any(lists.iter().map(|item| item.list_fk)))
+                .select(BookLists::id)
+                .load(conn)?;
+            let lists: Vec<NewBookListItem> = lists
+                .iter()
+                .filter(|item| existing_lists.contains(&item.list_fk))
+                .map(|item| NewBookListItem {
+                    list_fk: item.list_fk,
+                    book_fk: restored_id,
+                    position: item.position,
+                })
+                .collect();
+            diesel::insert_into(BookListItems::table)
+                .values(&lists)
+                .execute(conn)?;
+
+            Ok(RestoreOutcome {
+                label: item.to_string(),
+                original_id: book.id,
+                restored_id,
+                recreated_author,
+            })
+        }
+    })?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books]);
+    Ok(outcome)
+}
+
+// Inserts the author under its old id, or as a new row when another author took the id
+fn insert_author_keeping_id(conn: &mut SqliteConnection, author: &AuthorModel) -> QueryResult<ID> {
+    let new_author = NewAuthor::from(author);
+    let id_taken = Author::table
+        .find(author.Id)
+        .select(Author::Id)
+        .first::<ID>(conn)
+        .optional()?
+        .is_some();
+
+    if id_taken {
+        diesel::insert_into(Author::table)
+            .values(&new_author)
+            .returning(Author::Id)
+            .get_result(conn)
+    } else {
+        diesel::insert_into(Author::table)
+            .values((Author::Id.eq(author.Id), &new_author))
+            .returning(Author::Id)
+            .get_result(conn)
+    }
+}
+
+// Journal CRUD Operations
+pub fn get_journal(book_id: ID) -> Result<Vec<JournalEntryModel>, DbError> {
+    let mut conn = get_connection()?;
+    let entries = BookJournal::table
+        .filter(BookJournal::book_fk.eq(book_id))
+        .order((BookJournal::written_at.desc(), BookJournal::id.desc()))
+        .select(JournalEntryModel::as_select())
+        .load(&mut conn)?;
+    Ok(entries)
+}
+
+pub fn create_journal_entry(entry: &NewJournalEntry) -> Result<JournalEntryModel, DbError> {
+    let mut conn = get_connection()?;
+    let entry = diesel::insert_into(BookJournal::table)
+        .values(entry)
+        .returning(JournalEntryModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(entry)
+}
+
+pub fn delete_journal_entry(id: ID) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = diesel::delete(BookJournal::table.find(id)).execute(&mut conn)?;
+    Ok(count)
+}
+
+// Custom Field Operations
+pub fn get_fields_for_book(book_id: ID) -> Result<Vec<BookFieldModel>, DbError> {
+    let mut conn = get_connection()?;
+    let fields = BookFields::table
+        .filter(BookFields::BookFK.eq(book_id))
+        .order(BookFields::Id.asc())
+        .select(BookFieldModel::as_select())
+        .load(&mut conn)?;
+    Ok(fields)
+}
+
+/// Custom fields of every book in the order they were added, books without any are left out
+pub fn get_all_book_fields() -> Result<HashMap<ID, Vec<BookFieldModel>>, DbError> {
+    let mut conn = get_connection()?;
+    let fields = BookFields::table
+        .order(BookFields::Id.asc())
+        .select(BookFieldModel::as_select())
+        .load::<BookFieldModel>(&mut conn)?;
+    let mut by_book: HashMap<ID, Vec<BookFieldModel>> = HashMap::new();
+    for field in fields {
+        by_book.entry(field.BookFK).or_default().push(field);
+    }
+    Ok(by_book)
+}
+
+/// Replaces the custom fields of the book with the given key/value pairs. Keys and values
+/// are trimmed, pairs without a key are dropped and a repeated key keeps its last value.
+pub fn set_fields_for_book(
+    book_id: ID,
+    fields: &[(String, String)],
+) -> Result<Vec<BookFieldModel>, DbError> {
+    let mut rows: Vec<NewBookField> = Vec::new();
+    for (key, value) in fields {
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let value = value.trim().to_string();
+        match rows.iter_mut().find(|row| row.key == key) {
+            Some(row) => row.value = value,
+            None => rows.push(NewBookField {
+                BookFK: book_id,
+                key: key.to_string(),
+                value,
+            }),
+        }
+    }
+
+    let mut conn = get_connection()?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(BookFields::table.filter(BookFields::BookFK.eq(book_id))).execute(conn)?;
+        diesel::insert_into(BookFields::table)
+            .values(&rows)
+            .execute(conn)
+    })?;
+    get_fields_for_book(book_id)
+}
+
+// Quote CRUD Operations
+pub fn get_quotes(book_id: ID) -> Result<Vec<QuoteModel>, DbError> {
+    let mut conn = get_connection()?;
+    let quotes = Quotes::table
+        .filter(Quotes::book_fk.eq(book_id))
+        .order((Quotes::noted_at.desc(), Quotes::id.desc()))
+        .select(QuoteModel::as_select())
+        .load(&mut conn)?;
+    Ok(quotes)
+}
+
+/// Quotes of every book, newest first
+pub fn get_all_quotes() -> Result<Vec<QuoteModel>, DbError> {
+    let mut conn = get_connection()?;
+    let quotes = Quotes::table
+        .order((Quotes::noted_at.desc(), Quotes::id.desc()))
+        .select(QuoteModel::as_select())
+        .load(&mut conn)?;
+    Ok(quotes)
+}
+
+/// Number of quotes per book, books without any are left out
+pub fn get_quote_counts() -> Result<HashMap<ID, usize>, DbError> {
+    let mut conn = get_connection()?;
+    let counts = Quotes::table
+        .group_by(Quotes::book_fk)
+        .select((Quotes::book_fk, diesel::dsl::count_star()))
+        .load::<(ID, i64)>(&mut conn)?;
+    Ok(counts
+        .into_iter()
+        .map(|(book_id, count)| (book_id, count as usize))
+        .collect())
+}
+
+pub fn create_quote(quote: &NewQuote) -> Result<QuoteModel, DbError> {
+    let mut conn = get_connection()?;
+    let quote = diesel::insert_into(Quotes::table)
+        .values(quote)
+        .returning(QuoteModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(quote)
+}
+
+pub fn update_quote(id: ID, quote: &NewQuote) -> Result<QuoteModel, DbError> {
+    let mut conn = get_connection()?;
+    let quote = diesel::update(Quotes::table.find(id))
+        .set(quote)
+        .returning(QuoteModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(quote)
+}
+
+pub fn delete_quote(id: ID) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = diesel::delete(Quotes::table.find(id)).execute(&mut conn)?;
     Ok(count)
-}
\ No newline at end of file
+}
+
+// Loan Operations
+pub fn get_active_loans() -> Result<Vec<LoanModel>, DbError> {
+    let mut conn = get_connection()?;
+    let loans = Loans::table
+        .filter(Loans::returned_on.is_null())
+        .order(Loans::loaned_on.asc())
+        .select(LoanModel::as_select())
+        .load(&mut conn)?;
+    Ok(loans)
+}
+
+pub fn get_loans_for_book(book_id: ID) -> Result<Vec<LoanModel>, DbError> {
+    let mut conn = get_connection()?;
+    let loans = Loans::table
+        .filter(Loans::book_fk.eq(book_id))
+        .order((Loans::loaned_on.desc(), Loans::id.desc()))
+        .select(LoanModel::as_select())
+        .load(&mut conn)?;
+    Ok(loans)
+}
+
+pub fn create_loan(loan: &NewLoan) -> Result<LoanModel, DbError> {
+    let mut conn = get_connection()?;
+    let loan = diesel::insert_into(Loans::table)
+        .values(loan)
+        .returning(LoanModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(loan)
+}
+
+pub fn mark_loan_returned(id: ID, returned_on: NaiveDateTime) -> Result<LoanModel, DbError> {
+    let mut conn = get_connection()?;
+    let loan = diesel::update(Loans::table.find(id))
+        .set(Loans::returned_on.eq(Some(returned_on)))
+        .returning(LoanModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(loan)
+}
+
+// Reading session Operations
+/// Finished sessions of every book, oldest first
+pub fn get_reading_sessions() -> Result<Vec<ReadingSessionModel>, DbError> {
+    let mut conn = get_connection()?;
+    let sessions = ReadingSessions::table
+        .filter(ReadingSessions::ended_at.is_not_null())
+        .order(ReadingSessions::started_at.asc())
+        .select(ReadingSessionModel::as_select())
+        .load(&mut conn)?;
+    Ok(sessions)
+}
+
+/// Stores the session as running, it gets its end once the timer is stopped
+pub fn start_reading_session(
+    book_id: ID,
+    started_at: NaiveDateTime,
+) -> Result<ReadingSessionModel, DbError> {
+    let mut conn = get_connection()?;
+    let session = diesel::insert_into(ReadingSessions::table)
+        .values(&NewReadingSession {
+            book_fk: book_id,
+            started_at,
+            ended_at: None,
+            pages: None,
+        })
+        .returning(ReadingSessionModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(session)
+}
+
+pub fn finish_reading_session(
+    id: ID,
+    ended_at: NaiveDateTime,
+    pages: Option<i32>,
+) -> Result<ReadingSessionModel, DbError> {
+    let mut conn = get_connection()?;
+    let session = diesel::update(ReadingSessions::table.find(id))
+        .set((
+            ReadingSessions::ended_at.eq(Some(ended_at)),
+            ReadingSessions::pages.eq(pages),
+        ))
+        .returning(ReadingSessionModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(session)
+}
+
+pub fn delete_reading_session(id: ID) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = diesel::delete(ReadingSessions::table.find(id)).execute(&mut conn)?;
+    Ok(count)
+}
+
+/// Removes sessions a previous run left running, returns how many there were
+pub fn discard_unfinished_sessions() -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = diesel::delete(ReadingSessions::table.filter(ReadingSessions::ended_at.is_null()))
+        .execute(&mut conn)?;
+    Ok(count)
+}
+
+// Series CRUD Operations
+pub fn get_series() -> Result<Vec<SeriesModel>, DbError> {
+    let mut conn = get_connection()?;
+    let series = Series::table
+        .order(Series::name.asc())
+        .select(SeriesModel::as_select())
+        .load(&mut conn)?;
+    Ok(series)
+}
+
+pub fn create_series(new_series: &NewSeries) -> Result<SeriesModel, DbError> {
+    let mut conn = get_connection()?;
+    let series = diesel::insert_into(Series::table)
+        .values(new_series)
+        .returning(SeriesModel::as_returning())
+        .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Series]);
+    Ok(series)
+}
+
+pub fn update_series(id: ID, series: &NewSeries) -> Result<SeriesModel, DbError> {
+    let mut conn = get_connection()?;
+    let series = diesel::update(Series::table.find(id))
+        .set(series)
+        .returning(SeriesModel::as_returning())
+        .get_result(&mut conn)?;
+    cached::invalidate(&[QueryKind::Series]);
+    Ok(series)
+}
+
+// Unlinks the series' books before removing it, the books themselves are kept
+pub fn delete_series(id: ID) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::update(Books::table.filter(Books::SeriesFK.eq(id)))
+            .set(Books::SeriesFK.eq(None::<ID>))
+            .execute(conn)?;
+        diesel::delete(Series::table.find(id)).execute(conn)
+    })?;
+    cached::invalidate(&[QueryKind::Series, QueryKind::Books]);
+    Ok(count)
+}
+
+pub fn get_book_lists() -> Result<Vec<BookListModel>, DbError> {
+    let mut conn = get_connection()?;
+    let lists = BookLists::table
+        .order(BookLists::name.asc())
+        .select(BookListModel::as_select())
+        .load(&mut conn)?;
+    Ok(lists)
+}
+
+pub fn create_book_list(name: &str) -> Result<BookListModel, DbError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DbError::InvalidInput("A list needs a name".to_string()));
+    }
+    let mut conn = get_connection()?;
+    let list = diesel::insert_into(BookLists::table)
+        .values(BookLists::name.eq(name))
+        .returning(BookListModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(list)
+}
+
+pub fn rename_book_list(id: ID, name: &str) -> Result<BookListModel, DbError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DbError::InvalidInput("A list needs a name".to_string()));
+    }
+    let mut conn = get_connection()?;
+    let list = diesel::update(BookLists::table.find(id))
+        .set(BookLists::name.eq(name))
+        .returning(BookListModel::as_returning())
+        .get_result(&mut conn)?;
+    Ok(list)
+}
+
+/// Removes the list and its entries, the books themselves are left alone
+pub fn delete_book_list(id: ID) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(BookListItems::table.filter(BookListItems::list_fk.eq(id)))
+            .execute(conn)?;
+        diesel::delete(BookLists::table.find(id)).execute(conn)
+    })?;
+    Ok(count)
+}
+
+/// Books of the list in reading order
+pub fn get_book_list_items(list_id: ID) -> Result<Vec<ID>, DbError> {
+    let mut conn = get_connection()?;
+    list_items(&mut conn, list_id).map_err(DbError::from)
+}
+
+fn list_items(conn: &mut SqliteConnection, list_id: ID) -> QueryResult<Vec<ID>> {
+    BookListItems::table
+        .filter(BookListItems::list_fk.eq(list_id))
+        .order((BookListItems::position.asc(), BookListItems::book_fk.asc()))
+        .select(BookListItems::book_fk)
+        .load(conn)
+}
+
+/// Appends the book to the end of the list, a book already on it keeps its place.
+/// Returns the list in its new order.
+pub fn add_book_to_list(list_id: ID, book_id: ID) -> Result<Vec<ID>, DbError> {
+    let mut conn = get_connection()?;
+    let items = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let last: Option<i32> = BookListItems::table
+            .filter(BookListItems::list_fk.eq(list_id))
+            .select(diesel::dsl::max(BookListItems::position))
+            .first(conn)?;
+        diesel::insert_or_ignore_into(BookListItems::table)
+            .values(&NewBookListItem {
+                list_fk: list_id,
+                book_fk: book_id,
+                position: last.map_or(0, |last| last + 1),
+            })
+            .execute(conn)?;
+        list_items(conn, list_id)
+    })?;
+    Ok(items)
+}
+
+/// Takes the book off the list, the book itself is kept
+pub fn remove_book_from_list(list_id: ID, book_id: ID) -> Result<Vec<ID>, DbError> {
+    let mut conn = get_connection()?;
+    let items = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(
+            BookListItems::table
+                .filter(BookListItems::list_fk.eq(list_id))
+                .filter(BookListItems::book_fk.eq(book_id)),
+        )
+        .execute(conn)?;
+        list_items(conn, list_id)
+    })?;
+    Ok(items)
+}
+
+/// Swaps the book with the one before it (`earlier`) or after it in the list. Moving past
+/// either end leaves the order as it is.
+pub fn move_book_in_list(list_id: ID, book_id: ID, earlier: bool) -> Result<Vec<ID>, DbError> {
+    let mut conn = get_connection()?;
+    let items = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let in_list = BookListItems::table.filter(BookListItems::list_fk.eq(list_id));
+        let position: i32 = in_list
+            .filter(BookListItems::book_fk.eq(book_id))
+            .select(BookListItems::position)
+            .first(conn)?;
+        // Neighbour by position, so gaps left by removed books don't matter
+        let neighbour: Option<(ID, i32)> = if earlier {
+            in_list
+                .filter(BookListItems::position.lt(position))
+                .order(BookListItems::position.desc())
+                .select((BookListItems::book_fk, BookListItems::position))
+                .first(conn)
+                .optional()?
+        } else {
+            in_list
+                .filter(BookListItems::position.gt(position))
+                .order(BookListItems::position.asc())
+                .select((BookListItems::book_fk, BookListItems::position))
+                .first(conn)
+                .optional()?
+        };
+
+        if let Some((other, other_position)) = neighbour {
+            diesel::update(in_list.filter(BookListItems::book_fk.eq(book_id)))
+                .set(BookListItems::position.eq(other_position))
+                .execute(conn)?;
+            diesel::update(in_list.filter(BookListItems::book_fk.eq(other)))
+                .set(BookListItems::position.eq(position))
+                .execute(conn)?;
+        }
+        list_items(conn, list_id)
+    })?;
+    Ok(items)
+}
+
+/// Dry run for `import_books`: every row paired with the library book of the same title
+/// and author, ignoring case. Each book is offered to the first row that matches it.
+/// Author names the library doesn't have by exact name go through the same resolution
+/// as a move, with similar names to map them to instead of creating them outright.
+pub fn plan_import(books: &[ImportedBook]) -> Result<ImportPlan, DbError> {
+    let mut conn = get_connection()?;
+    let unknown_authors = find_unknown_authors(
+        books.iter().filter_map(|book| book.author.as_deref()),
+        &named_authors(&mut conn)?,
+    );
+    let library = Books::table
+        .left_join(Author::table)
+        .select((Books::id, Books::title, Author::Name.nullable()))
+        .order(Books::id.asc())
+        .load::<(ID, String, Option<String>)>(&mut conn)?;
+    let mut by_key: HashMap<String, Vec<(ID, String)>> = HashMap::new();
+    for (id, title, author) in library.into_iter().rev() {
+        let label = match &author {
+            Some(author) => format!("{} — {}", title, author),
+            None => title.clone(),
+        };
+        by_key
+            .entry(import::book_key(&title, author.as_deref()))
+            .or_default()
+            .push((id, label));
+    }
+
+    let books = books
+        .iter()
+        .map(|book| {
+            let existing = by_key
+                .get_mut(&import::book_key(&book.title, book.author.as_deref()))
+                .and_then(Vec::pop);
+            PlannedBook {
+                book: book.clone(),
+                decision: match existing {
+                    Some(_) => ImportDecision::Skip,
+                    None => ImportDecision::AddAsNew,
+                },
+                existing,
+            }
+        })
+        .collect();
+    Ok(ImportPlan {
+        books,
+        unknown_authors,
+    })
+}
+
+/// Applies a planned import in one transaction. Authors are mapped or skipped as resolved
+/// in the plan, otherwise matched by exact name and created when missing. Added books
+/// without an added date are added now. Overwriting keeps the book's author, and its
+/// values where the row has none.
+pub fn import_books(plan: &ImportPlan) -> Result<ImportCounts, DbError> {
+    let mut conn = get_connection()?;
+    let now = chrono::Local::now().naive_local();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        for planned in &plan.books {
+            let book = &planned.book;
+            if plan.skips(planned) {
+                continue;
+            }
+            let existing = match (planned.decision, &planned.existing) {
+                (ImportDecision::Overwrite, Some((id, _))) => Some(*id),
+                _ => None,
+            };
+
+            // The author already matches ignoring case, the book keeps it
+            if let Some(id) = existing {
+                let mut current = Books::table
+                    .find(id)
+                    .select(BookModel::as_select())
+                    .first::<BookModel>(conn)
+                    .map(|current| NewBook::from(&current))?;
+                current.title = book.title.clone();
+                current.price = book.price.or(current.price);
+                current.bought = book.bought.or(current.bought);
+                current.finished = book.finished.or(current.finished);
+                current.added = book.added.or(current.added);
+                current.acquired_from = book.acquired_from.clone().or(current.acquired_from);
+                diesel::update(Books::table.find(id))
+                    .set(&current)
+                    .execute(conn)?;
+                continue;
+            }
+
+            let resolution = book
+                .author
+                .as_deref()
+                .and_then(|name| plan.resolution_for(name));
+            let author_fk = match (&book.author, resolution) {
+                (Some(_), Some(AuthorResolution::MapTo(id, _))) => Some(*id),
+                (Some(name), _) => Some(find_or_create_author(
+                    conn,
+                    &AuthorModel {
+                        Id: 0, // Not used for the lookup
+                        Name: Some(name.clone()),
+                        website: None,
+                        wikipedia: None,
+                        published_works: None,
+                        Aliases: None,
+                    },
+                )?),
+                (None, _) => None,
+            };
+            diesel::insert_into(Books::table)
+                .values(&NewBook {
+                    title: book.title.clone(),
+                    price: book.price,
+                    bought: book.bought,
+                    finished: book.finished,
+                    added: Some(book.added.unwrap_or(now)),
+                    AuthorFK: author_fk,
+                    SeriesFK: None,
+                    gift_from: None,
+                    quantity: 1,
+                    cover_path: None,
+                    genre: None,
+                    page_count: None,
+                    pinned: false,
+                    format: None,
+                    duration_minutes: None,
+                    language: None,
+                    acquired_from: book.acquired_from.clone(),
+                    rating: None,
+                })
+                .execute(conn)?;
+        }
+        Ok(())
+    })?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books]);
+    Ok(plan.counts())
+}
+
+// Looks up an author by exact name in the given connection, creating it when missing
+fn find_or_create_author(conn: &mut SqliteConnection, author: &AuthorModel) -> QueryResult<ID> {
+    let existing = match &author.Name {
+        Some(name) => Author::table
+            .filter(Author::Name.eq(name))
+            .select(Author::Id)
+            .first::<ID>(conn)
+            .optional()?,
+        None => Author::table
+            .filter(Author::Name.is_null())
+            .select(Author::Id)
+            .first::<ID>(conn)
+            .optional()?,
+    };
+
+    match existing {
+        Some(id) => Ok(id),
+        None => diesel::insert_into(Author::table)
+            .values(&NewAuthor {
+                Name: author.Name.clone(),
+                website: author.website.clone(),
+                wikipedia: author.wikipedia.clone(),
+                published_works: author.published_works,
+                Aliases: author.Aliases.clone(),
+            })
+            .returning(Author::Id)
+            .get_result(conn),
+    }
+}
+
+// Looks up a series by exact name in the given connection, creating it when missing
+fn find_or_create_series(conn: &mut SqliteConnection, series: &SeriesModel) -> QueryResult<ID> {
+    let existing = Series::table
+        .filter(Series::name.eq(&series.name))
+        .select(Series::id)
+        .first::<ID>(conn)
+        .optional()?;
+
+    match existing {
+        Some(id) => Ok(id),
+        None => diesel::insert_into(Series::table)
+            .values(&NewSeries {
+                name: series.name.clone(),
+                total_volumes: series.total_volumes,
+            })
+            .returning(Series::id)
+            .get_result(conn),
+    }
+}
+
+fn validate_move_target(target_url: &str) -> Result<String, DbError> {
+    if target_url.trim().is_empty() {
+        return Err(DbError::InvalidInput("Target database path is empty".to_string()));
+    }
+    let target_path = resolve_database_path(target_url)?;
+    if database_path().as_ref() == Some(&target_path) {
+        return Err(DbError::InvalidInput(
+            "Target database is the one currently open".to_string(),
+        ));
+    }
+    Ok(target_path.to_string_lossy().into_owned())
+}
+
+fn open_move_target(target_url: &str) -> Result<SqliteConnection, DbError> {
+    let mut target = SqliteConnection::establish(target_url)
+        .map_err(|e| DbError::Connection(e.to_string()))?;
+    target
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|e| DbError::Migration(e.to_string()))?;
+    Ok(target)
+}
+
+/// Suggestions offered for an author name the target database doesn't have
+pub const AUTHOR_SUGGESTIONS_MAX: usize = 3;
+
+/// What to do with the books of an author the target database doesn't have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorResolution {
+    MapTo(ID, String), // Existing author in the target, with its name
+    CreateNew,
+    Skip, // The books stay in the current database
+}
+
+/// Incoming author name without an exact match in the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownAuthor {
+    pub name: String,
+    pub books: usize,
+    pub suggestions: Vec<(ID, String)>, // Similar names already in the target
+    pub resolution: Option<AuthorResolution>, // None until chosen, names with suggestions start so
+}
+
+/// Incoming author names without an exact match among `known`, in the order they first
+/// appear, each with the books naming it and the most similar known names. Moves and
+/// imports both resolve authors through this, so they suggest the same people.
+pub fn find_unknown_authors<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    known: &[(ID, String)],
+) -> Vec<UnknownAuthor> {
+    let known_names: Vec<&str> = known.iter().map(|(_, name)| name.as_str()).collect();
+    let mut unknown_authors: Vec<UnknownAuthor> = Vec::new();
+    for name in names {
+        if let Some(unknown) = unknown_authors.iter_mut().find(|a| a.name == name) {
+            unknown.books += 1;
+        } else if !known_names.contains(&name) {
+            let suggestions: Vec<(ID, String)> =
+                fuzzy::similar_names(name, &known_names, AUTHOR_SUGGESTIONS_MAX)
+                    .into_iter()
+                    .map(|index| known[index].clone())
+                    .collect();
+            unknown_authors.push(UnknownAuthor {
+                name: name.to_string(),
+                books: 1,
+                resolution: suggestions
+                    .is_empty()
+                    .then_some(AuthorResolution::CreateNew),
+                suggestions,
+            });
+        }
+    }
+    unknown_authors
+}
+
+// Every author with a name, what incoming names are resolved against
+fn named_authors(conn: &mut SqliteConnection) -> QueryResult<Vec<(ID, String)>> {
+    let authors = Author::table
+        .filter(Author::Name.is_not_null())
+        .select((Author::Id, Author::Name))
+        .load::<(ID, Option<String>)>(conn)?;
+    Ok(authors
+        .into_iter()
+        .filter_map(|(id, name)| name.map(|name| (id, name)))
+        .collect())
+}
+
+/// Books chosen for a move together with a preview of what the target will receive
+#[derive(Debug, Clone)]
+pub struct MovePlan {
+    pub target_url: String,
+    pub book_ids: Vec<ID>, // Only the books that will actually be moved
+    pub preview: BulkPreview,
+    pub unknown_authors: Vec<UnknownAuthor>,
+}
+
+impl MovePlan {
+    /// True once every unknown author has a resolution
+    pub fn is_resolved(&self) -> bool {
+        self.unknown_authors
+            .iter()
+            .all(|author| author.resolution.is_some())
+    }
+
+    fn resolution_for(&self, name: &str) -> Option<&AuthorResolution> {
+        self.unknown_authors
+            .iter()
+            .find(|author| author.name == name)
+            .and_then(|author| author.resolution.as_ref())
+    }
+}
+
+/// Dry run of `move_books_to`: books already in the target (same title and author)
+/// are skipped and new series are listed. Authors the target doesn't have by exact name
+/// are returned with similar names to map to, instead of being created outright.
+/// Nothing is moved yet, although a missing target file is created with an empty schema.
+pub fn plan_move_books(target_url: &str, book_ids: &[ID]) -> Result<MovePlan, DbError> {
+    let target_url = validate_move_target(target_url)?;
+
+    let mut source = get_connection()?;
+    let books = Books::table
+        .filter(Books::id.eq_any(book_ids))
+        .left_join(Author::table)
+        .select((BookModel::as_select(), Option::<AuthorModel>::as_select()))
+        .load::<(BookModel, Option<AuthorModel>)>(&mut source)?;
+    let series = Series::table
+        .filter(Series::id.eq_any(books.iter().filter_map(|(b, _)| b.SeriesFK)))
+        .select(SeriesModel::as_select())
+        .load::<SeriesModel>(&mut source)?;
+
+    let mut target = open_move_target(&target_url)?;
+    let target_authors = named_authors(&mut target)?;
+
+    let mut preview = BulkPreview::default();
+    let mut planned_ids = Vec::new();
+    let mut moved_author_names: Vec<String> = Vec::new();
+    let mut new_series: Vec<String> = Vec::new();
+
+    for (book, author) in &books {
+        let author_name = author.as_ref().and_then(|a| a.Name.clone());
+        let label = match &author_name {
+            Some(name) => format!("{} by {}", book.title, name),
+            None => book.title.clone(),
+        };
+
+        let duplicate = Books::table
+            .left_join(Author::table)
+            .filter(Books::title.eq(&book.title))
+            .filter(Author::Name.is(author_name.clone()))
+            .select(Books::id)
+            .first::<ID>(&mut target)
+            .optional()?;
+        if duplicate.is_some() {
+            preview.to_skip.push(format!("{} (already in the target)", label));
+            continue;
+        }
+
+        planned_ids.push(book.id);
+        preview.to_create.push(format!("Book: {}", label));
+        moved_author_names.extend(author_name);
+
+        if let Some(s) = book.SeriesFK.and_then(|id| series.iter().find(|s| s.id == id)) {
+            if !new_series.contains(&s.name) {
+                let exists = Series::table
+                    .filter(Series::name.eq(&s.name))
+                    .select(Series::id)
+                    .first::<ID>(&mut target)
+                    .optional()?;
+                if exists.is_none() {
+                    preview.to_create.push(format!("Series: {}", s.name));
+                    new_series.push(s.name.clone());
+                }
+            }
+        }
+    }
+
+    let missing = book_ids.len().saturating_sub(books.len());
+    if missing > 0 {
+        preview
+            .warnings
+            .push(format!("{} selected books no longer exist", missing));
+    }
+    if !preview.to_skip.is_empty() {
+        preview
+            .warnings
+            .push("Skipped books stay in the current database".to_string());
+    }
+
+    let unknown_authors = find_unknown_authors(
+        moved_author_names.iter().map(String::as_str),
+        &target_authors,
+    );
+    if unknown_authors.iter().any(|author| author.resolution.is_none()) {
+        preview.warnings.push(
+            "Some authors are close to ones already in the target, choose what to do with them"
+                .to_string(),
+        );
+    }
+
+    Ok(MovePlan {
+        target_url,
+        book_ids: planned_ids,
+        preview,
+        unknown_authors,
+    })
+}
+
+/// Moves the planned books, with their authors and series, into another database file.
+/// Each unknown author name is handled as resolved in the plan, for every book carrying it.
+/// The books are only removed from the current database once the target commit succeeded.
+pub fn move_books_to(plan: &MovePlan) -> Result<usize, DbError> {
+    if !plan.is_resolved() {
+        return Err(DbError::InvalidInput(
+            "Choose what to do with every unknown author first".to_string(),
+        ));
+    }
+    let target_url = validate_move_target(&plan.target_url)?;
+    let book_ids = &plan.book_ids;
+
+    let mut source = get_connection()?;
+    let mut books = Books::table
+        .filter(Books::id.eq_any(book_ids))
+        .select(BookModel::as_select())
+        .load::<BookModel>(&mut source)?;
+    let authors = Author::table
+        .filter(Author::Id.eq_any(books.iter().filter_map(|b| b.AuthorFK)))
+        .select(AuthorModel::as_select())
+        .load::<AuthorModel>(&mut source)?;
+    let series = Series::table
+        .filter(Series::id.eq_any(books.iter().filter_map(|b| b.SeriesFK)))
+        .select(SeriesModel::as_select())
+        .load::<SeriesModel>(&mut source)?;
+    let journal = BookJournal::table
+        .filter(BookJournal::book_fk.eq_any(book_ids))
+        .select(JournalEntryModel::as_select())
+        .load::<JournalEntryModel>(&mut source)?;
+    let quotes = Quotes::table
+        .filter(Quotes::book_fk.eq_any(book_ids))
+        .select(QuoteModel::as_select())
+        .load::<QuoteModel>(&mut source)?;
+    let fields = BookFields::table
+        .filter(BookFields::BookFK.eq_any(book_ids))
+        .order(BookFields::Id.asc())
+        .select(BookFieldModel::as_select())
+        .load::<BookFieldModel>(&mut source)?;
+    let loans = Loans::table
+        .filter(Loans::book_fk.eq_any(book_ids))
+        .select(LoanModel::as_select())
+        .load::<LoanModel>(&mut source)?;
+    // A session still running stays behind, the timer is tied to this database
+    let sessions = ReadingSessions::table
+        .filter(ReadingSessions::book_fk.eq_any(book_ids))
+        .filter(ReadingSessions::ended_at.is_not_null())
+        .select(ReadingSessionModel::as_select())
+        .load::<ReadingSessionModel>(&mut source)?;
+
+    // Books of skipped authors stay where they are
+    let author_of =
+        |book: &BookModel| book.AuthorFK.and_then(|id| authors.iter().find(|a| a.Id == id));
+    books.retain(|book| {
+        let name = author_of(book).and_then(|author| author.Name.as_deref());
+        name.and_then(|name| plan.resolution_for(name)) != Some(&AuthorResolution::Skip)
+    });
+
+    let mut target = open_move_target(&target_url)?;
+
+    target.transaction::<_, diesel::result::Error, _>(|conn| {
+        for book in &books {
+            let author = author_of(book);
+            let resolution = author
+                .and_then(|author| author.Name.as_deref())
+                .and_then(|name| plan.resolution_for(name));
+            let author_fk = match (author, resolution) {
+                (Some(_), Some(AuthorResolution::MapTo(id, _))) => Some(*id),
+                (Some(author), _) => Some(find_or_create_author(conn, author)?),
+                (None, _) => None,
+            };
+            let series_fk = match book.SeriesFK.and_then(|id| series.iter().find(|s| s.id == id)) {
+                Some(s) => Some(find_or_create_series(conn, s)?),
+                None => None,
+            };
+
+            let new_id = diesel::insert_into(Books::table)
+                .values(&NewBook {
+                    title: book.title.clone(),
+                    price: book.price,
+                    bought: book.bought,
+                    finished: book.finished,
+                    added: book.added,
+                    AuthorFK: author_fk,
+                    SeriesFK: series_fk,
+                    gift_from: book.gift_from.clone(),
+                    quantity: book.quantity,
+                    cover_path: book.cover_path.clone(),
+                    genre: book.genre.clone(),
+                    page_count: book.page_count,
+                    pinned: book.pinned,
+                    format: book.format.clone(),
+                    duration_minutes: book.duration_minutes,
+                    language: book.language.clone(),
+                    acquired_from: book.acquired_from.clone(),
+                    rating: book.rating,
+                })
+                .returning(Books::id)
+                .get_result::<ID>(conn)?;
+
+            for entry in journal.iter().filter(|entry| entry.book_fk == book.id) {
+                diesel::insert_into(BookJournal::table)
+                    .values(&NewJournalEntry {
+                        book_fk: new_id,
+                        written_at: entry.written_at,
+                        body: entry.body.clone(),
+                    })
+                    .execute(conn)?;
+            }
+
+            for quote in quotes.iter().filter(|quote| quote.book_fk == book.id) {
+                diesel::insert_into(Quotes::table)
+                    .values(&NewQuote {
+                        book_fk: new_id,
+                        page: quote.page,
+                        text: quote.text.clone(),
+                        noted_at: quote.noted_at,
+                    })
+                    .execute(conn)?;
+            }
+
+            for field in fields.iter().filter(|field| field.BookFK == book.id) {
+                diesel::insert_into(BookFields::table)
+                    .values(&NewBookField {
+                        BookFK: new_id,
+                        key: field.key.clone(),
+                        value: field.value.clone(),
+                    })
+                    .execute(conn)?;
+            }
+
+            for loan in loans.iter().filter(|loan| loan.book_fk == book.id) {
+                diesel::insert_into(Loans::table)
+                    .values(&NewLoan {
+                        book_fk: new_id,
+                        borrower: loan.borrower.clone(),
+                        loaned_on: loan.loaned_on,
+                        due_on: loan.due_on,
+                        returned_on: loan.returned_on,
+                    })
+                    .execute(conn)?;
+            }
+
+            for session in sessions.iter().filter(|session| session.book_fk == book.id) {
+                diesel::insert_into(ReadingSessions::table)
+                    .values(&NewReadingSession {
+                        book_fk: new_id,
+                        started_at: session.started_at,
+                        ended_at: session.ended_at,
+                        pages: session.pages,
+                    })
+                    .execute(conn)?;
+            }
+        }
+        Ok(())
+    })?;
+
+    // Target committed, now it is safe to remove the books from this database
+    let moved_ids: Vec<ID> = books.iter().map(|b| b.id).collect();
+    let count = source.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(BookJournal::table.filter(BookJournal::book_fk.eq_any(&moved_ids)))
+            .execute(conn)?;
+        diesel::delete(Loans::table.filter(Loans::book_fk.eq_any(&moved_ids))).execute(conn)?;
+        diesel::delete(Quotes::table.filter(Quotes::book_fk.eq_any(&moved_ids))).execute(conn)?;
+        diesel::delete(ReadingSessions::table.filter(ReadingSessions::book_fk.eq_any(&moved_ids)))
+            .execute(conn)?;
+        diesel::delete(BookFields::table.filter(BookFields::BookFK.eq_any(&moved_ids)))
+            .execute(conn)?;
+        diesel::delete(BookListItems::table.filter(BookListItems::book_fk.eq_any(&moved_ids)))
+            .execute(conn)?;
+        diesel::delete(Books::table.filter(Books::id.eq_any(&moved_ids))).execute(conn)
+    })?;
+    cached::invalidate(&[QueryKind::Books]);
+    Ok(count)
+}
+
+/// Books a bulk bought date is written to
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoughtDateScope {
+    Selection(Vec<ID>),
+    AddedBetween(NaiveDate, NaiveDate), // Both days included
+}
+
+/// Which books `set_bought_date_bulk` changes, books that already have a bought date
+/// are left alone unless `overwrite` is set
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoughtDateFilter {
+    pub scope: BoughtDateScope,
+    pub overwrite: bool,
+}
+
+impl BoughtDateFilter {
+    // Every book in the scope, whatever its bought date
+    fn scope_query(&self) -> Books::BoxedQuery<'_, Sqlite> {
+        let query = Books::table.into_boxed();
+        match &self.scope {
+            BoughtDateScope::Selection(ids) => query.filter(Books::id.eq_any(ids)),
+            BoughtDateScope::AddedBetween(from, to) => {
+                let end = to.succ_opt().unwrap_or(NaiveDate::MAX);
+                query
+                    .filter(Books::added.ge(from.and_time(NaiveTime::MIN)))
+                    .filter(Books::added.lt(end.and_time(NaiveTime::MIN)))
+            }
+        }
+    }
+
+    // The books the update writes to
+    fn query(&self) -> Books::BoxedQuery<'_, Sqlite> {
+        if self.overwrite {
+            self.scope_query()
+        } else {
+            self.scope_query().filter(Books::bought.is_null())
+        }
+    }
+}
+
+/// A bulk bought date together with a preview of the books it changes
+#[derive(Debug, Clone)]
+pub struct BoughtDatePlan {
+    pub filter: BoughtDateFilter,
+    pub date: NaiveDateTime,
+    pub preview: BulkPreview,
+}
+
+/// Bought dates as they were before a bulk change, what its undo writes back
+pub type BoughtDateBackup = Vec<(ID, Option<NaiveDateTime>)>;
+
+/// Dry run of `set_bought_date_bulk`, books keeping their bought date are listed as skipped
+pub fn plan_bought_date_bulk(
+    filter: BoughtDateFilter,
+    date: NaiveDateTime,
+) -> Result<BoughtDatePlan, DbError> {
+    let mut conn = get_connection()?;
+    let books = filter
+        .scope_query()
+        .select((Books::title, Books::bought))
+        .order(Books::title.asc())
+        .load::<(String, Option<NaiveDateTime>)>(&mut conn)?;
+
+    let mut preview = BulkPreview::default();
+    let mut replaced = 0;
+    for (title, bought) in books {
+        match bought {
+            None => preview.to_update.push(title),
+            Some(previous) if filter.overwrite => {
+                replaced += 1;
+                let was = previous.format("%Y-%m-%d");
+                preview.to_update.push(format!("{} (was {})", title, was));
+            }
+            Some(previous) => {
+                let was = previous.format("%Y-%m-%d");
+                preview.to_skip.push(format!("{} (bought {})", title, was));
+            }
+        }
+    }
+    if replaced > 0 {
+        preview
+            .warnings
+            .push(format!("{} existing bought dates will be replaced", replaced));
+    }
+
+    Ok(BoughtDatePlan {
+        filter,
+        date,
+        preview,
+    })
+}
+
+/// Sets the bought date of every book the filter matches with a single UPDATE.
+/// The previous dates are read in the same transaction and returned for the undo.
+pub fn set_bought_date_bulk(
+    filter: &BoughtDateFilter,
+    date: NaiveDateTime,
+) -> Result<BoughtDateBackup, DbError> {
+    let mut conn = get_connection()?;
+    let previous = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let previous = filter
+            .query()
+            .select((Books::id, Books::bought))
+            .load::<(ID, Option<NaiveDateTime>)>(conn)?;
+        let ids: Vec<ID> = previous.iter().map(|(id, _)| *id).collect();
+        diesel::update(Books::table.filter(Books::id.eq_any(&ids)))
+            .set(Books::bought.eq(Some(date)))
+            .execute(conn)?;
+        Ok(previous)
+    })?;
+    cached::invalidate(&[QueryKind::Books]);
+    Ok(previous)
+}
+
+// Writes back the dates captured by `set_bought_date_bulk`, books deleted since are ignored
+pub fn restore_bought_dates(backup: &[(ID, Option<NaiveDateTime>)]) -> Result<usize, DbError> {
+    let mut conn = get_connection()?;
+    let count = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let mut count = 0;
+        for (id, bought) in backup {
+            count += diesel::update(Books::table.find(id))
+                .set(Books::bought.eq(bought))
+                .execute(conn)?;
+        }
+        Ok(count)
+    })?;
+    cached::invalidate(&[QueryKind::Books]);
+    Ok(count)
+}
+
+// Backup Operations
+/// Every table of the library read in one transaction, so the backup is a consistent
+/// snapshot. Each list is ordered by id.
+pub fn backup_library(now: NaiveDateTime) -> Result<BackupDocument, DbError> {
+    let mut conn = get_connection()?;
+    let document = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        Ok(BackupDocument {
+            version: BACKUP_VERSION,
+            created: Some(now),
+            authors: Author::table
+                .order(Author::Id.asc())
+                .select(AuthorModel::as_select())
+                .load(conn)?,
+            series: Series::table
+                .order(Series::id.asc())
+                .select(SeriesModel::as_select())
+                .load(conn)?,
+            books: Books::table
+                .order(Books::id.asc())
+                .select(BookModel::as_select())
+                .load(conn)?,
+            quotes: Quotes::table
+                .order(Quotes::id.asc())
+                .select(QuoteModel::as_select())
+                .load(conn)?,
+            fields: BookFields::table
+                .order(BookFields::Id.asc())
+                .select(BookFieldModel::as_select())
+                .load(conn)?,
+            journal: BookJournal::table
+                .order(BookJournal::id.asc())
+                .select(JournalEntryModel::as_select())
+                .load(conn)?,
+            loans: Loans::table
+                .order(Loans::id.asc())
+                .select(LoanModel::as_select())
+                .load(conn)?,
+            reading_sessions: ReadingSessions::table
+                .order(ReadingSessions::id.asc())
+                .select(ReadingSessionModel::as_select())
+                .load(conn)?,
+            lists: BookLists::table
+                .order(BookLists::id.asc())
+                .select(BookListModel::as_select())
+                .load(conn)?,
+            list_items: BookListItems::table
+                .order((BookListItems::list_fk.asc(), BookListItems::book_fk.asc()))
+                .select(BookListItemModel::as_select())
+                .load(conn)?,
+            merge_dismissals: AuthorMergeDismissals::table
+                .order((
+                    AuthorMergeDismissals::author_a.asc(),
+                    AuthorMergeDismissals::author_b.asc(),
+                ))
+                .select((
+                    AuthorMergeDismissals::author_a,
+                    AuthorMergeDismissals::author_b,
+                ))
+                .load(conn)?,
+        })
+    })?;
+    Ok(document)
+}
+
+/// Replaces the whole library with the rows of a backup, keeping their ids. Runs in one
+/// transaction, a backup that doesn't fit the database leaves the library as it was.
+pub fn restore_library(document: &BackupDocument) -> Result<(), DbError> {
+    let mut conn = get_connection()?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // Rows pointing at others go first
+        diesel::delete(BookListItems::table).execute(conn)?;
+        diesel::delete(BookLists::table).execute(conn)?;
+        diesel::delete(BookFields::table).execute(conn)?;
+        diesel::delete(BookJournal::table).execute(conn)?;
+        diesel::delete(Quotes::table).execute(conn)?;
+        diesel::delete(Loans::table).execute(conn)?;
+        diesel::delete(ReadingSessions::table).execute(conn)?;
+        diesel::delete(AuthorMergeDismissals::table).execute(conn)?;
+        diesel::delete(Books::table).execute(conn)?;
+        diesel::delete(Series::table).execute(conn)?;
+        diesel::delete(Author::table).execute(conn)?;
+
+        for author in &document.authors {
+            diesel::insert_into(Author::table)
+                .values((Author::Id.eq(author.Id), &NewAuthor::from(author)))
+                .execute(conn)?;
+        }
+        for series in &document.series {
+            diesel::insert_into(Series::table)
+                .values((
+                    Series::id.eq(series.id),
+                    Series::name.eq(&series.name),
+                    Series::total_volumes.eq(series.total_volumes),
+                ))
+                .execute(conn)?;
+        }
+        for book in &document.books {
+            diesel::insert_into(Books::table)
+                .values((Books::id.eq(book.id), &NewBook::from(book)))
+                .execute(conn)?;
+        }
+        for quote in &document.quotes {
+            diesel::insert_into(Quotes::table)
+                .values((
+                    Quotes::id.eq(quote.id),
+                    &NewQuote {
+                        book_fk: quote.book_fk,
+                        page: quote.page,
+                        text: quote.text.clone(),
+                        noted_at: quote.noted_at,
+                    },
+                ))
+                .execute(conn)?;
+        }
+        for field in &document.fields {
+            diesel::insert_into(BookFields::table)
+                .values((
+                    BookFields::Id.eq(field.Id),
+                    &NewBookField {
+                        BookFK: field.BookFK,
+                        key: field.key.clone(),
+                        value: field.value.clone(),
+                    },
+                ))
+                .execute(conn)?;
+        }
+        for entry in &document.journal {
+            diesel::insert_into(BookJournal::table)
+                .values((
+                    BookJournal::id.eq(entry.id),
+                    &NewJournalEntry {
+                        book_fk: entry.book_fk,
+                        written_at: entry.written_at,
+                        body: entry.body.clone(),
+                    },
+                ))
+                .execute(conn)?;
+        }
+        for loan in &document.loans {
+            diesel::insert_into(Loans::table)
+                .values((
+                    Loans::id.eq(loan.id),
+                    &NewLoan {
+                        book_fk: loan.book_fk,
+                        borrower: loan.borrower.clone(),
+                        loaned_on: loan.loaned_on,
+                        due_on: loan.due_on,
+                        returned_on: loan.returned_on,
+                    },
+                ))
+                .execute(conn)?;
+        }
+        for session in &document.reading_sessions {
+            diesel::insert_into(ReadingSessions::table)
+                .values((
+                    ReadingSessions::id.eq(session.id),
+                    &NewReadingSession {
+                        book_fk: session.book_fk,
+                        started_at: session.started_at,
+                        ended_at: session.ended_at,
+                        pages: session.pages,
+                    },
+                ))
+                .execute(conn)?;
+        }
+        for list in &document.lists {
+            diesel::insert_into(BookLists::table)
+                .values((BookLists::id.eq(list.id), BookLists::name.eq(&list.name)))
+                .execute(conn)?;
+        }
+        diesel::insert_into(BookListItems::table)
+            .values(&document.list_items)
+            .execute(conn)?;
+        for (a, b) in &document.merge_dismissals {
+            diesel::insert_into(AuthorMergeDismissals::table)
+                .values((
+                    AuthorMergeDismissals::author_a.eq(a),
+                    AuthorMergeDismissals::author_b.eq(b),
+                ))
+                .execute(conn)?;
+        }
+        Ok(())
+    })?;
+    cached::invalidate(&[QueryKind::Authors, QueryKind::Books, QueryKind::Series]);
+    Ok(())
+}
+
+// Row of an ad-hoc console query, its shape is only known once the query ran
+struct ConsoleRow {
+    columns: Vec<String>,
+    values: Vec<String>,
+}
+
+impl FromSqlRow<Untyped, Sqlite> for ConsoleRow {
+    fn build_from_row<'a>(row: &impl Row<'a, Sqlite>) -> deserialize::Result<Self> {
+        let mut columns = Vec::with_capacity(row.field_count());
+        let mut values = Vec::with_capacity(row.field_count());
+
+        for index in 0..row.field_count() {
+            let field = row.get(index).ok_or(UnexpectedEndOfRow)?;
+            columns.push(field.field_name().unwrap_or("?").to_string());
+
+            let value = match field.value() {
+                None => "NULL".to_string(),
+                Some(raw) => match raw.value_type() {
+                    Some(SqliteType::Integer | SqliteType::Long | SqliteType::SmallInt) => {
+                        <i64 as FromSql<BigInt, Sqlite>>::from_sql(raw)?.to_string()
+                    }
+                    Some(SqliteType::Double | SqliteType::Float) => {
+                        <f64 as FromSql<Double, Sqlite>>::from_sql(raw)?.to_string()
+                    }
+                    Some(SqliteType::Binary) => format!(
+                        "<{} byte blob>",
+                        <Vec<u8> as FromSql<Binary, Sqlite>>::from_sql(raw)?.len()
+                    ),
+                    _ => <String as FromSql<Text, Sqlite>>::from_sql(raw)?,
+                },
+            };
+            values.push(value);
+        }
+
+        Ok(ConsoleRow { columns, values })
+    }
+}
+
+/// Runs a console query without being able to change anything.
+/// Besides the statement check, the query runs with query_only set inside a transaction
+/// that is always rolled back.
+pub fn run_read_only_query(sql: &str) -> Result<QueryOutput, DbError> {
+    let statement =
+        console::check_read_only(sql).map_err(|e| DbError::InvalidInput(e.to_string()))?;
+    // Newlines keep a trailing line comment from swallowing the closing parenthesis
+    let limited = format!(
+        "SELECT * FROM (\n{}\n) LIMIT {}",
+        statement,
+        CONSOLE_ROW_LIMIT + 1
+    );
+
+    let mut conn = get_connection()?;
+    diesel::sql_query("PRAGMA query_only = ON").execute(&mut conn)?;
+
+    let mut rows = Vec::new();
+    let result = conn.transaction::<(), diesel::result::Error, _>(|conn| {
+        rows = diesel::sql_query(&limited).load::<ConsoleRow>(conn)?;
+        Err(diesel::result::Error::RollbackTransaction)
+    });
+
+    // The connection goes back to the pool, restore it before reporting anything
+    if !is_read_only() {
+        diesel::sql_query("PRAGMA query_only = OFF").execute(&mut conn)?;
+    }
+    match result {
+        Ok(()) | Err(diesel::result::Error::RollbackTransaction) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let truncated = rows.len() > CONSOLE_ROW_LIMIT;
+    rows.truncate(CONSOLE_ROW_LIMIT);
+
+    Ok(QueryOutput {
+        columns: rows.first().map(|row| row.columns.clone()).unwrap_or_default(),
+        rows: rows.into_iter().map(|row| row.values).collect(),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup;
+    use std::sync::{MutexGuard, PoisonError};
+
+    // The pool is global, so the tests using it run one at a time
+    static TEST_DATABASE: Mutex<()> = Mutex::new(());
+    static TEST_DATABASE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+    // Opens an empty, migrated in-memory database in place of the previous one. It lives
+    // while the pool holds a connection, the guard keeps other tests out until then.
+    fn fresh_database(config: &PoolConfig) -> MutexGuard<'static, ()> {
+        let guard = TEST_DATABASE.lock().unwrap_or_else(PoisonError::into_inner);
+        let name = TEST_DATABASE_COUNT.fetch_add(1, Ordering::Relaxed);
+        let url = format!("file:bookshelf-test-{}?mode=memory&cache=shared", name);
+        initialize_pool_with_url(&url, config).unwrap();
+        guard
+    }
+
+    fn noon(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+    }
+
+    fn new_book(title: &str, author: Option<ID>) -> NewBook {
+        NewBook {
+            AuthorFK: author,
+            ..NewBook::from(&BookModel::titled(0, title))
+        }
+    }
+
+    fn new_author(name: &str) -> NewAuthor {
+        NewAuthor {
+            Name: Some(name.to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        }
+    }
+
+    #[test]
+    fn distinct_years_come_from_every_date_newest_first() {
+        let _db = fresh_database(&PoolConfig::default());
+        assert!(get_distinct_years().unwrap().is_empty());
+
+        let day = |year| {
+            chrono::NaiveDate::from_ymd_opt(year, 3, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+        };
+        let book = |title, added, bought, finished| NewBook {
+            added,
+            bought,
+            finished,
+            ..new_book(title, None)
+        };
+        // Each only has one of the three dates, one has none at all
+        create_book(&book("Added", day(2021), None, None)).unwrap();
+        create_book(&book("Bought", None, day(2019), None)).unwrap();
+        create_book(&book("Finished", None, None, day(2023))).unwrap();
+        create_book(&book("Undated", None, None, None)).unwrap();
+        create_book(&book("Every date", day(2021), day(2020), day(2023))).unwrap();
+
+        assert_eq!(get_distinct_years().unwrap(), vec![2023, 2021, 2020, 2019]);
+    }
+
+    #[test]
+    fn the_bought_date_filter_matches_the_scope_and_skips_bought_books() {
+        let _db = fresh_database(&PoolConfig::default());
+        let at = |day: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2025, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+        };
+        let book = |title, added, bought| NewBook {
+            added,
+            bought,
+            ..new_book(title, None)
+        };
+        let before = create_book(&book("Day before", at(9, 23, 59), None)).unwrap();
+        let first = create_book(&book("First day", at(10, 0, 0), None)).unwrap();
+        let owned = create_book(&book("Already bought", at(11, 8, 0), Some(noon(1)))).unwrap();
+        create_book(&book("Last day", at(12, 23, 59), None)).unwrap();
+        create_book(&book("Day after", at(13, 0, 0), None)).unwrap();
+        create_book(&book("Never added", None, None)).unwrap();
+
+        let titles = |filter: BoughtDateFilter| {
+            let mut conn = get_connection().unwrap();
+            let mut titles = filter
+                .query()
+                .select(Books::title)
+                .load::<String>(&mut conn)
+                .unwrap();
+            titles.sort();
+            titles
+        };
+        let between = |overwrite| BoughtDateFilter {
+            scope: BoughtDateScope::AddedBetween(
+                NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(),
+            ),
+            overwrite,
+        };
+        // Both days are included whatever the time, the books around them are not
+        assert_eq!(titles(between(false)), vec!["First day", "Last day"]);
+        assert_eq!(
+            titles(between(true)),
+            vec!["Already bought", "First day", "Last day"]
+        );
+
+        let selection = |overwrite| BoughtDateFilter {
+            scope: BoughtDateScope::Selection(vec![before.id, first.id, owned.id]),
+            overwrite,
+        };
+        assert_eq!(titles(selection(false)), vec!["Day before", "First day"]);
+        assert_eq!(
+            titles(selection(true)),
+            vec!["Already bought", "Day before", "First day"]
+        );
+        let nothing = BoughtDateFilter {
+            scope: BoughtDateScope::Selection(Vec::new()),
+            overwrite: true,
+        };
+        assert!(titles(nothing).is_empty());
+
+        // Only the filter without overwrite adds the condition on the bought date
+        let sql = |filter: &BoughtDateFilter| {
+            diesel::debug_query::<Sqlite, _>(&filter.query()).to_string()
+        };
+        assert!(sql(&between(false)).contains("`Books`.`bought` IS NULL"));
+        assert!(!sql(&between(true)).contains("`bought` IS NULL"));
+    }
+
+    #[test]
+    fn a_bulk_bought_date_replaces_existing_dates_only_when_asked() {
+        let _db = fresh_database(&PoolConfig::default());
+        let unbought = create_book(&new_book("Unbought", None)).unwrap();
+        let owned = create_book(&NewBook {
+            bought: Some(noon(1)),
+            ..new_book("Owned", None)
+        })
+        .unwrap();
+        let bought = |id| get_book(id).unwrap().book.bought;
+        let filter = |overwrite| BoughtDateFilter {
+            scope: BoughtDateScope::Selection(vec![unbought.id, owned.id]),
+            overwrite,
+        };
+
+        let plan = plan_bought_date_bulk(filter(false), noon(20)).unwrap();
+        assert_eq!(plan.preview.to_update, vec!["Unbought"]);
+        assert_eq!(plan.preview.to_skip, vec!["Owned (bought 2025-03-01)"]);
+        assert!(plan.preview.warnings.is_empty());
+
+        let backup = set_bought_date_bulk(&filter(false), noon(20)).unwrap();
+        assert_eq!(backup, vec![(unbought.id, None)]);
+        assert_eq!(bought(unbought.id), Some(noon(20)));
+        assert_eq!(bought(owned.id), Some(noon(1)));
+
+        let plan = plan_bought_date_bulk(filter(true), noon(25)).unwrap();
+        assert_eq!(
+            plan.preview.to_update,
+            vec!["Owned (was 2025-03-01)", "Unbought (was 2025-03-20)"]
+        );
+        assert_eq!(
+            plan.preview.warnings,
+            vec!["2 existing bought dates will be replaced"]
+        );
+
+        set_bought_date_bulk(&filter(true), noon(25)).unwrap();
+        assert_eq!(bought(unbought.id), Some(noon(25)));
+        assert_eq!(bought(owned.id), Some(noon(25)));
+    }
+
+    #[test]
+    fn undoing_a_bulk_bought_date_restores_the_previous_dates() {
+        let _db = fresh_database(&PoolConfig::default());
+        let unbought = create_book(&new_book("Unbought", None)).unwrap();
+        let owned = create_book(&NewBook {
+            bought: Some(noon(1)),
+            ..new_book("Owned", None)
+        })
+        .unwrap();
+        let deleted = create_book(&new_book("Deleted later", None)).unwrap();
+        let filter = BoughtDateFilter {
+            scope: BoughtDateScope::Selection(vec![unbought.id, owned.id, deleted.id]),
+            overwrite: true,
+        };
+
+        let mut backup = set_bought_date_bulk(&filter, noon(20)).unwrap();
+        backup.sort();
+        assert_eq!(
+            backup,
+            vec![
+                (unbought.id, None),
+                (owned.id, Some(noon(1))),
+                (deleted.id, None)
+            ]
+        );
+        delete_book_cascade(deleted.id).unwrap();
+
+        // The deleted book is not counted as restored
+        assert_eq!(restore_bought_dates(&backup).unwrap(), 2);
+        assert_eq!(get_book(unbought.id).unwrap().book.bought, None);
+        assert_eq!(get_book(owned.id).unwrap().book.bought, Some(noon(1)));
+    }
+
+    #[test]
+    fn merging_authors_moves_the_books_and_forgets_the_dismissals() {
+        let _db = fresh_database(&PoolConfig::default());
+        let keep = create_author(&new_author("Stanisław Lem")).unwrap();
+        let remove = create_author(&NewAuthor {
+            website: Some("https://lem.pl".to_string()),
+            ..new_author("Stanislaw Lem")
+        })
+        .unwrap();
+        let other = create_author(&new_author("Frank Herbert")).unwrap();
+        let solaris = create_book(&new_book("Solaris", Some(keep.Id))).unwrap();
+        let eden = create_book(&new_book("Eden", Some(remove.Id))).unwrap();
+        let fiasco = create_book(&new_book("Fiasco", Some(remove.Id))).unwrap();
+        let dune = create_book(&new_book("Dune", Some(other.Id))).unwrap();
+
+        // Stored lower id first whichever way round, and only once
+        dismiss_author_merge(remove.Id, keep.Id).unwrap();
+        dismiss_author_merge(keep.Id, remove.Id).unwrap();
+        dismiss_author_merge(other.Id, remove.Id).unwrap();
+        let mut dismissed = get_merge_dismissals().unwrap();
+        dismissed.sort();
+        assert_eq!(dismissed, vec![(keep.Id, remove.Id), (remove.Id, other.Id)]);
+
+        assert_eq!(merge_authors(keep.Id, remove.Id).unwrap(), 2);
+        let author_of = |id| get_book(id).unwrap().book.AuthorFK;
+        assert_eq!(author_of(solaris.id), Some(keep.Id));
+        assert_eq!(author_of(eden.id), Some(keep.Id));
+        assert_eq!(author_of(fiasco.id), Some(keep.Id));
+        assert_eq!(author_of(dune.id), Some(other.Id));
+
+        let kept = get_author(keep.Id).unwrap();
+        assert_eq!(kept.Aliases.as_deref(), Some("Stanislaw Lem"));
+        assert_eq!(kept.website.as_deref(), Some("https://lem.pl"));
+        assert!(get_author(remove.Id).is_err());
+        assert!(get_merge_dismissals().unwrap().is_empty());
+
+        assert!(matches!(
+            merge_authors(keep.Id, keep.Id),
+            Err(DbError::InvalidInput(_))
+        ));
+    }
+
+    // A library using every table, the same one each call
+    fn fill_every_table() {
+        let lem = create_author(&NewAuthor {
+            website: Some("https://lem.pl".to_string()),
+            ..new_author("Stanisław Lem")
+        })
+        .unwrap();
+        let other = create_author(&new_author("Stanislaw Lem")).unwrap();
+        let cycle = create_series(&NewSeries {
+            name: "Pirx".to_string(),
+            total_volumes: Some(2),
+        })
+        .unwrap();
+        let book = create_book(&NewBook {
+            SeriesFK: Some(cycle.id),
+            price: Some(39.9),
+            finished: Some(noon(2)),
+            ..new_book("Tales of Pirx the Pilot", Some(lem.Id))
+        })
+        .unwrap();
+        let other_book = create_book(&new_book("Solaris", Some(other.Id))).unwrap();
+
+        create_quote(&NewQuote {
+            book_fk: book.id,
+            page: Some(12),
+            text: "A quote\non two lines".to_string(),
+            noted_at: noon(3),
+        })
+        .unwrap();
+        set_fields_for_book(book.id, &[("Signed".to_string(), "yes".to_string())]).unwrap();
+        create_journal_entry(&NewJournalEntry {
+            book_fk: book.id,
+            written_at: noon(4),
+            body: "Halfway".to_string(),
+        })
+        .unwrap();
+        create_loan(&NewLoan {
+            book_fk: other_book.id,
+            borrower: "Ania".to_string(),
+            loaned_on: noon(5),
+            due_on: Some(noon(20)),
+            returned_on: None,
+        })
+        .unwrap();
+        let session = start_reading_session(book.id, noon(6)).unwrap();
+        finish_reading_session(
+            session.id,
+            noon(6) + chrono::Duration::minutes(30),
+            Some(25),
+        )
+        .unwrap();
+        let club = create_book_list("Book club").unwrap();
+        add_book_to_list(club.id, other_book.id).unwrap();
+        add_book_to_list(club.id, book.id).unwrap();
+        dismiss_author_merge(other.Id, lem.Id).unwrap();
+    }
+
+    #[test]
+    fn a_restored_backup_brings_back_every_table() {
+        let _db = fresh_database(&PoolConfig::default());
+        fill_every_table();
+        let written = BackupDocument::from_database(noon(10)).unwrap();
+        let path = test_folder("backup-round-trip").join("backup.json");
+        written.save(&path).unwrap();
+        assert_eq!(written.version, BACKUP_VERSION);
+        for (table, rows) in [
+            ("authors", written.authors.len()),
+            ("series", written.series.len()),
+            ("books", written.books.len()),
+            ("quotes", written.quotes.len()),
+            ("fields", written.fields.len()),
+            ("journal", written.journal.len()),
+            ("loans", written.loans.len()),
+            ("reading sessions", written.reading_sessions.len()),
+            ("lists", written.lists.len()),
+            ("list items", written.list_items.len()),
+            ("merge dismissals", written.merge_dismissals.len()),
+        ] {
+            assert!(rows > 0, "no {} in the backup", table);
+        }
+
+        // Every table changes after the backup was written
+        let first = written.books[0].id;
+        delete_book_cascade(first).unwrap();
+        create_book(&new_book("Written later", None)).unwrap();
+        create_author(&new_author("Frank Herbert")).unwrap();
+        create_book_list("Later").unwrap();
+
+        BackupDocument::load(&path).unwrap().restore().unwrap();
+        let restored = BackupDocument::from_database(noon(10)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&written).unwrap()
+        );
+        assert_eq!(get_book_list_items(written.lists[0].id).unwrap().len(), 2);
+        assert!(backup::diff(&written, &restored).is_empty());
+    }
+
+    #[test]
+    fn a_backup_without_every_table_is_not_restored() {
+        let _db = fresh_database(&PoolConfig::default());
+        fill_every_table();
+        let mut old = BackupDocument::from_database(noon(10)).unwrap();
+        old.version = 1;
+        old.books.clear();
+
+        assert!(matches!(
+            old.restore(),
+            Err(backup::BackupError::NotRestorable(1))
+        ));
+        assert_eq!(get_books().unwrap().len(), 2);
+
+        // A backup naming a missing author leaves the library as it was
+        let mut broken = BackupDocument::from_database(noon(10)).unwrap();
+        broken.authors.clear();
+        assert!(broken.restore().is_err());
+        assert_eq!(get_authors().unwrap().len(), 2);
+        assert_eq!(get_books().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn nightly_backups_hold_every_table_and_only_the_newest_are_kept() {
+        let _db = fresh_database(&PoolConfig::default());
+        fill_every_table();
+        let dir = test_folder("nightly-rotation");
+        let by_hand = dir.join("backup-2025-03-01-120000.json");
+        std::fs::write(&by_hand, "{}").unwrap();
+
+        let mut written = Vec::new();
+        for day in 1..=4 {
+            written.push(backup::write_nightly(&dir, noon(day), 2).unwrap());
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "backup-2025-03-01-120000.json",
+                "nightly-2025-03-03-120000.json",
+                "nightly-2025-03-04-120000.json",
+            ]
+        );
+
+        // What the nightly run writes can be restored as it is
+        let nightly = BackupDocument::load(&written[3]).unwrap();
+        assert_eq!(nightly.version, BACKUP_VERSION);
+        assert_eq!(
+            serde_json::to_value(&nightly).unwrap(),
+            serde_json::to_value(BackupDocument::from_database(noon(4)).unwrap()).unwrap()
+        );
+        nightly.restore().unwrap();
+
+        // Keeping none still keeps the backup just written
+        backup::write_nightly(&dir, noon(5), 0).unwrap();
+        assert!(dir.join("nightly-2025-03-05-120000.json").exists());
+        assert!(!dir.join("nightly-2025-03-04-120000.json").exists());
+        assert!(by_hand.exists());
+    }
+
+    #[test]
+    fn the_delete_summary_names_each_kind_of_history() {
+        let plain = RelatedRecords::default();
+        assert!(plain.is_empty());
+        assert_eq!(plain.summary(), "");
+        let history = RelatedRecords {
+            journal_entries: 5,
+            loans: 1,
+            quotes: 0,
+            reading_sessions: 2,
+        };
+        assert!(!history.is_empty());
+        assert_eq!(
+            history.summary(),
+            "5 journal entries, 1 loan record, 2 reading sessions"
+        );
+        let one_each = RelatedRecords {
+            journal_entries: 1,
+            loans: 2,
+            quotes: 1,
+            reading_sessions: 1,
+        };
+        assert_eq!(
+            one_each.summary(),
+            "1 journal entry, 2 loan records, 1 quote, 1 reading session"
+        );
+
+        // Not counted yet blocks, history needs the acknowledgment, a plain book doesn't
+        assert!(!RelatedRecords::can_delete(None, true));
+        assert!(RelatedRecords::can_delete(Some(&plain), false));
+        assert!(!RelatedRecords::can_delete(Some(&history), false));
+        assert!(RelatedRecords::can_delete(Some(&history), true));
+    }
+
+    #[test]
+    fn the_counted_history_is_what_the_delete_removes() {
+        let _db = fresh_database(&PoolConfig::default());
+        let dune = create_book(&new_book("Dune", None)).unwrap();
+        let emma = create_book(&new_book("Emma", None)).unwrap();
+        for day in [1, 2] {
+            create_journal_entry(&NewJournalEntry {
+                book_fk: dune.id,
+                written_at: noon(day),
+                body: "Another chapter".to_string(),
+            })
+            .unwrap();
+        }
+        create_loan(&NewLoan {
+            book_fk: dune.id,
+            borrower: "Ania".to_string(),
+            loaned_on: noon(3),
+            due_on: None,
+            returned_on: None,
+        })
+        .unwrap();
+
+        let counted = count_related_records(dune.id).unwrap();
+        assert_eq!(
+            counted,
+            RelatedRecords {
+                journal_entries: 2,
+                loans: 1,
+                quotes: 0,
+                reading_sessions: 0,
+            }
+        );
+        assert!(count_related_records(emma.id).unwrap().is_empty());
+
+        // A plain book reports nothing removed, and leaves the other book's history alone
+        assert!(delete_book_cascade(emma.id).unwrap().removed.is_empty());
+        assert_eq!(count_related_records(dune.id).unwrap(), counted);
+        assert_eq!(delete_book_cascade(dune.id).unwrap().removed, counted);
+        assert!(count_related_records(dune.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_deleted_book_comes_back_with_everything_deleted_along_with_it() {
+        let _db = fresh_database(&PoolConfig::default());
+        let author = create_author(&new_author("Frank Herbert")).unwrap();
+        let book = create_book(&new_book("Dune", Some(author.Id))).unwrap();
+        create_journal_entry(&NewJournalEntry {
+            book_fk: book.id,
+            written_at: noon(1),
+            body: "Started on the train".to_string(),
+        })
+        .unwrap();
+        create_quote(&NewQuote {
+            book_fk: book.id,
+            page: Some(12),
+            text: "Fear is the mind-killer.".to_string(),
+            noted_at: noon(2),
+        })
+        .unwrap();
+        set_fields_for_book(book.id, &[("Signed".to_string(), "yes".to_string())]).unwrap();
+        create_loan(&NewLoan {
+            book_fk: book.id,
+            borrower: "Ania".to_string(),
+            loaned_on: noon(3),
+            due_on: Some(noon(20)),
+            returned_on: Some(noon(10)),
+        })
+        .unwrap();
+        let session = start_reading_session(book.id, noon(4)).unwrap();
+        finish_reading_session(session.id, noon(4) + chrono::Duration::minutes(45), Some(30))
+            .unwrap();
+        start_reading_session(book.id, noon(5)).unwrap();
+        let club = create_book_list("Book club").unwrap();
+        let gone = create_book_list("Summer").unwrap();
+        let other = create_book(&new_book("Emma", None)).unwrap();
+        add_book_to_list(club.id, other.id).unwrap();
+        add_book_to_list(club.id, book.id).unwrap();
+        add_book_to_list(gone.id, book.id).unwrap();
+        let session_count = |conn: &mut SqliteConnection| {
+            ReadingSessions::table
+                .filter(ReadingSessions::book_fk.eq(book.id))
+                .count()
+                .get_result::<i64>(conn)
+                .unwrap()
+        };
+
+        let report = delete_book_cascade(book.id).unwrap();
+        assert_eq!(
+            report.removed,
+            RelatedRecords {
+                journal_entries: 1,
+                loans: 1,
+                quotes: 1,
+                reading_sessions: 2,
+            }
+        );
+        assert!(find_book(book.id).unwrap().is_none());
+        assert!(get_journal(book.id).unwrap().is_empty());
+        assert!(get_quotes(book.id).unwrap().is_empty());
+        assert!(get_fields_for_book(book.id).unwrap().is_empty());
+        assert!(get_loans_for_book(book.id).unwrap().is_empty());
+        assert_eq!(session_count(&mut get_connection().unwrap()), 0);
+        assert_eq!(get_book_list_items(club.id).unwrap(), vec![other.id]);
+
+        // A list deleted since then is not brought back for the book
+        delete_book_list(gone.id).unwrap();
+
+        let outcome = restore_deleted(&report.item).unwrap();
+        assert_eq!(outcome.restored_id, book.id);
+        assert_eq!(outcome.recreated_author, None);
+        let restored = get_book(book.id).unwrap();
+        assert_eq!(restored.author.map(|author| author.Id), Some(author.Id));
+        let journal = get_journal(book.id).unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal[0].body, "Started on the train");
+        let quotes = get_quotes(book.id).unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].page, Some(12));
+        assert_eq!(quotes[0].text, "Fear is the mind-killer.");
+        let fields = get_fields_for_book(book.id).unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].key, "Signed");
+        assert_eq!(fields[0].value, "yes");
+        let loans = get_loans_for_book(book.id).unwrap();
+        assert_eq!(loans.len(), 1);
+        assert_eq!(loans[0].borrower, "Ania");
+        assert_eq!(loans[0].returned_on, Some(noon(10)));
+        let sessions = get_reading_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!((sessions[0].book_fk, sessions[0].pages), (book.id, Some(30)));
+        assert_eq!(session_count(&mut get_connection().unwrap()), 2);
+        assert_eq!(get_book_list_items(club.id).unwrap(), vec![other.id, book.id]);
+        assert!(get_book_lists().unwrap().iter().all(|list| list.id != gone.id));
+    }
+
+    // An empty folder of its own for each path test
+    fn test_folder(name: &str) -> PathBuf {
+        let folder = env::temp_dir().join("bookshelf-tests").join(name);
+        let _ = std::fs::remove_dir_all(&folder);
+        std::fs::create_dir_all(&folder).unwrap();
+        folder
+    }
+
+    #[test]
+    fn database_paths_resolve_against_the_home_and_executable_folders() {
+        let base = test_folder("resolve-base");
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            resolve_database_path_in("~/books.sqlite", &base, None),
+            Ok(home.join("books.sqlite"))
+        );
+        assert_eq!(
+            resolve_database_path_in("./books.sqlite", &base, None),
+            Ok(base.join("books.sqlite"))
+        );
+        let absolute = test_folder("resolve-absolute").join("books.sqlite");
+        assert_eq!(
+            resolve_database_path_in(&absolute.to_string_lossy(), &base, None),
+            Ok(absolute)
+        );
+    }
+
+    #[test]
+    fn a_database_path_in_a_missing_folder_is_rejected() {
+        let base = test_folder("resolve-missing");
+        assert_eq!(
+            resolve_database_path_in("shelves/books.sqlite", &base, None),
+            Err(PathError::MissingParent(base.join("shelves")))
+        );
+    }
+
+    #[test]
+    fn a_database_only_in_the_working_directory_is_moved_next_to_the_executable() {
+        let base = test_folder("resolve-legacy-base");
+        let legacy = test_folder("resolve-legacy-cwd");
+        std::fs::write(legacy.join("books.db"), b"shelf").unwrap();
+        std::fs::write(legacy.join("books.db-wal"), b"journal").unwrap();
+        assert_eq!(
+            resolve_database_path_in("books.db", &base, Some(&legacy)),
+            Ok(base.join("books.db"))
+        );
+        assert_eq!(std::fs::read(base.join("books.db")).unwrap(), b"shelf");
+        assert_eq!(std::fs::read(base.join("books.db-wal")).unwrap(), b"journal");
+        assert!(take_legacy_database_notice().unwrap().starts_with("Moved the database"));
+
+        // From then on the copy next to the executable is used and nothing is said
+        std::fs::write(legacy.join("books.db"), b"stale").unwrap();
+        assert_eq!(
+            resolve_database_path_in("books.db", &base, Some(&legacy)),
+            Ok(base.join("books.db"))
+        );
+        assert_eq!(std::fs::read(base.join("books.db")).unwrap(), b"shelf");
+        assert_eq!(take_legacy_database_notice(), None);
+    }
+
+    #[test]
+    fn a_second_book_with_the_same_isbn_is_a_duplicate() {
+        let _db = fresh_database(&PoolConfig::default());
+        // Books have no ISBN yet, the test adds the unique column a later migration would
+        let mut conn = get_connection().unwrap();
+        diesel::sql_query("ALTER TABLE Books ADD COLUMN isbn TEXT")
+            .execute(&mut conn)
+            .unwrap();
+        diesel::sql_query("CREATE UNIQUE INDEX books_isbn ON Books (isbn)")
+            .execute(&mut conn)
+            .unwrap();
+
+        let insert_book = |conn: &mut SqliteConnection, title: &str| -> Result<usize, DbError> {
+            let count = diesel::sql_query(
+                "INSERT INTO Books (title, quantity, pinned, isbn) VALUES (?, 1, 0, ?)",
+            )
+            .bind::<Text, _>(title)
+            .bind::<Text, _>("9780441013593")
+            .execute(conn)?;
+            Ok(count)
+        };
+        assert_eq!(insert_book(&mut conn, "Dune").unwrap(), 1);
+        match insert_book(&mut conn, "Dune (reprint)") {
+            Err(DbError::Duplicate(message)) => {
+                assert_eq!(message, "A book with this ISBN already exists")
+            }
+            other => panic!("expected a duplicate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn the_embedded_migrations_run_on_an_empty_database() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        migrate(&mut conn, true).unwrap();
+        assert!(!conn.has_pending_migration(MIGRATIONS).unwrap());
+
+        let book = diesel::insert_into(Books::table)
+            .values(&new_book("Dune", None))
+            .returning(BookModel::as_returning())
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(book.title, "Dune");
+    }
+
+    #[test]
+    fn custom_fields_are_set_overwritten_and_deleted_with_their_book() {
+        let _db = fresh_database(&PoolConfig::default());
+        let book = create_book(&new_book("Dune", None)).unwrap();
+        let pairs = |fields: &[BookFieldModel]| -> Vec<(String, String)> {
+            fields
+                .iter()
+                .map(|field| (field.key.clone(), field.value.clone()))
+                .collect()
+        };
+        let owned = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+
+        let set = set_fields_for_book(
+            book.id,
+            &owned(&[(" Shelf ", "A3 "), ("", "dropped"), ("Signed", "no"), ("Signed", "yes")]),
+        )
+        .unwrap();
+        assert_eq!(pairs(&set), owned(&[("Shelf", "A3"), ("Signed", "yes")]));
+        assert_eq!(pairs(&get_fields_for_book(book.id).unwrap()), pairs(&set));
+
+        set_fields_for_book(book.id, &owned(&[("Shelf", "B1")])).unwrap();
+        let fields = get_fields_for_book(book.id).unwrap();
+        assert_eq!(pairs(&fields), owned(&[("Shelf", "B1")]));
+        assert_eq!(get_all_book_fields().unwrap()[&book.id], fields);
+
+        delete_book_cascade(book.id).unwrap();
+        assert!(get_fields_for_book(book.id).unwrap().is_empty());
+        assert!(get_all_book_fields().unwrap().is_empty());
+    }
+
+    #[test]
+    fn foreign_keys_are_enforced_after_migrating() {
+        let _db = fresh_database(&PoolConfig::default());
+        match create_book(&new_book("Dune", Some(999))) {
+            Err(DbError::InvalidInput(_)) => {}
+            other => panic!("expected invalid input, got {:?}", other),
+        }
+        assert!(get_books().unwrap().is_empty());
+
+        // The rebuilt Books table clears the author itself when it goes
+        let author = create_author(&new_author("Frank Herbert")).unwrap();
+        let book = create_book(&new_book("Dune", Some(author.Id))).unwrap();
+        let mut conn = get_connection().unwrap();
+        diesel::delete(Author::table.find(author.Id))
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(get_book(book.id).unwrap().book.AuthorFK, None);
+    }
+
+    #[test]
+    fn deleting_an_author_leaves_its_books_without_one() {
+        for foreign_keys in [true, false] {
+            let _db = fresh_database(&PoolConfig {
+                foreign_keys,
+                ..PoolConfig::default()
+            });
+            let author = create_author(&new_author("Frank Herbert")).unwrap();
+            let book = create_book(&new_book("Dune", Some(author.Id))).unwrap();
+
+            let deleted = delete_author(author.Id).unwrap();
+            assert_eq!(get_book(book.id).unwrap().book.AuthorFK, None);
+
+            restore_deleted(&deleted).unwrap();
+            assert_eq!(get_book(book.id).unwrap().book.AuthorFK, Some(author.Id));
+        }
+    }
+
+    fn imported(title: &str, author: Option<&str>) -> ImportedBook {
+        ImportedBook {
+            title: title.to_string(),
+            author: author.map(str::to_string),
+            price: None,
+            bought: None,
+            finished: None,
+            added: Some(noon(1)),
+            acquired_from: None,
+        }
+    }
+
+    fn author_names() -> Vec<String> {
+        get_authors()
+            .unwrap()
+            .into_iter()
+            .filter_map(|author| author.Name)
+            .collect()
+    }
+
+    #[test]
+    fn planning_an_import_writes_nothing_and_applying_it_does_what_it_previewed() {
+        let _db = fresh_database(&PoolConfig::default());
+        let herbert = create_author(&new_author("Frank Herbert")).unwrap();
+        create_book(&new_book("Dune", Some(herbert.Id))).unwrap();
+        let rows = vec![
+            imported("dune", Some("Frank Herbert")),
+            imported("Dune Messiah", Some("Frank Herbert")),
+            imported("Solaris", Some("Stanisław Lem")),
+            imported("Ubik", None),
+        ];
+
+        let mut plan = plan_import(&rows).unwrap();
+        assert_eq!(get_books().unwrap().len(), 1);
+        assert_eq!(author_names(), vec!["Frank Herbert"]);
+
+        plan.decide_all(ImportDecision::Overwrite);
+        let preview = plan.preview();
+        assert_eq!(
+            preview.to_create,
+            vec![
+                "Book: Dune Messiah by Frank Herbert",
+                "Book: Solaris by Stanisław Lem",
+                "Book: Ubik",
+                "Author: Stanisław Lem",
+            ]
+        );
+        assert_eq!(
+            preview.to_update,
+            vec!["Dune — Frank Herbert (overwritten with dune by Frank Herbert)"]
+        );
+        assert!(preview.to_skip.is_empty());
+
+        let counts = import_books(&plan).unwrap();
+        assert_eq!(
+            counts,
+            ImportCounts {
+                added: 3,
+                overwritten: 1,
+                skipped: 0,
+            }
+        );
+        let mut titles: Vec<String> = get_books()
+            .unwrap()
+            .into_iter()
+            .map(|pair| pair.book.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Dune Messiah", "Solaris", "Ubik", "dune"]);
+        assert_eq!(author_names(), vec!["Frank Herbert", "Stanisław Lem"]);
+
+        // Planned again against the result, every row is already there
+        let replanned = plan_import(&rows).unwrap().preview();
+        assert!(replanned.to_create.is_empty());
+        assert_eq!(replanned.to_skip.len(), rows.len());
+    }
+
+    #[test]
+    fn each_duplicate_is_skipped_overwritten_or_added_as_decided() {
+        let _db = fresh_database(&PoolConfig::default());
+        let lem = create_author(&new_author("Stanisław Lem")).unwrap();
+        for title in ["Solaris", "Eden", "Fiasco"] {
+            create_book(&NewBook {
+                price: Some(10.0),
+                acquired_from: Some("Empik".to_string()),
+                ..new_book(title, Some(lem.Id))
+            })
+            .unwrap();
+        }
+        let rows: Vec<ImportedBook> = ["Solaris", "Eden", "Fiasco"]
+            .into_iter()
+            .map(|title| ImportedBook {
+                price: Some(25.0),
+                ..imported(title, Some("Stanisław Lem"))
+            })
+            .collect();
+
+        let mut plan = plan_import(&rows).unwrap();
+        assert_eq!(plan.duplicates().count(), 3);
+        plan.books[1].decision = ImportDecision::Overwrite;
+        plan.books[2].decision = ImportDecision::AddAsNew;
+        assert_eq!(
+            import_books(&plan).unwrap(),
+            ImportCounts {
+                added: 1,
+                overwritten: 1,
+                skipped: 1,
+            }
+        );
+
+        let books = get_books().unwrap();
+        let priced = |title: &str| -> Vec<(Option<f32>, Option<String>)> {
+            books
+                .iter()
+                .filter(|pair| pair.book.title == title)
+                .map(|pair| (pair.book.price, pair.book.acquired_from.clone()))
+                .collect()
+        };
+        let empik = Some("Empik".to_string());
+        // Skipped as it was, overwritten keeping what the row leaves empty, added next to it
+        assert_eq!(priced("Solaris"), vec![(Some(10.0), empik.clone())]);
+        assert_eq!(priced("Eden"), vec![(Some(25.0), empik.clone())]);
+        let mut fiasco = priced("Fiasco");
+        fiasco.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(fiasco, vec![(Some(10.0), empik), (Some(25.0), None)]);
+        assert_eq!(author_names(), vec!["Stanisław Lem"]);
+    }
+
+    #[test]
+    fn a_skipped_row_creates_no_author() {
+        let _db = fresh_database(&PoolConfig::default());
+        let lem = create_author(&new_author("Stanisław Lem")).unwrap();
+        create_book(&new_book("Solaris", Some(lem.Id))).unwrap();
+
+        // Matches the book ignoring case, but the author name is not an exact match
+        let mut plan = plan_import(&[imported("SOLARIS", Some("stanisław lem"))]).unwrap();
+        plan.unknown_authors[0].resolution = Some(AuthorResolution::CreateNew);
+        assert!(plan.authors_to_create().is_empty());
+        assert!(plan.preview().is_noop());
+
+        plan.decide_all(ImportDecision::AddAsNew);
+        assert_eq!(plan.authors_to_create(), vec!["stanisław lem"]);
+        import_books(&plan).unwrap();
+        assert_eq!(author_names(), vec!["Stanisław Lem", "stanisław lem"]);
+    }
+
+    #[test]
+    fn unknown_authors_are_counted_once_with_similar_known_names() {
+        let known = vec![(1, "J.R.R. Tolkien".to_string()), (2, "Frank Herbert".to_string())];
+        let names = ["Tolkien, J.R.R.", "Terry Pratchett", "Tolkien, J.R.R.", "Frank Herbert"];
+        let unknown = find_unknown_authors(names, &known);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownAuthor {
+                    name: "Tolkien, J.R.R.".to_string(),
+                    books: 2,
+                    suggestions: vec![(1, "J.R.R. Tolkien".to_string())],
+                    resolution: None,
+                },
+                UnknownAuthor {
+                    name: "Terry Pratchett".to_string(),
+                    books: 1,
+                    suggestions: Vec::new(),
+                    resolution: Some(AuthorResolution::CreateNew),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn an_imported_author_close_to_a_known_one_is_resolved_for_every_row() {
+        let _db = fresh_database(&PoolConfig::default());
+        let tolkien = create_author(&new_author("J.R.R. Tolkien")).unwrap();
+        let rows = [
+            imported("The Hobbit", Some("Tolkien, J.R.R.")),
+            imported("Mort", Some("Terry Pratchett")),
+            imported("The Silmarillion", Some("Tolkien, J.R.R.")),
+        ];
+
+        // Not applied until the close name is resolved
+        let mut plan = plan_import(&rows).unwrap();
+        assert!(!plan.is_resolved());
+        assert_eq!(
+            plan.unknown_authors[0].suggestions,
+            vec![(tolkien.Id, "J.R.R. Tolkien".to_string())]
+        );
+        assert_eq!(plan.preview().warnings.len(), 1);
+
+        // Skipping the name skips each of its rows
+        plan.unknown_authors[0].resolution = Some(AuthorResolution::Skip);
+        assert_eq!(plan.counts().skipped, 2);
+        assert_eq!(
+            plan.preview().to_skip,
+            vec![
+                "The Hobbit by Tolkien, J.R.R. (author skipped)",
+                "The Silmarillion by Tolkien, J.R.R. (author skipped)",
+            ]
+        );
+
+        // Mapping it puts every row on the existing author, the other name is created
+        plan.unknown_authors[0].resolution =
+            Some(AuthorResolution::MapTo(tolkien.Id, "J.R.R. Tolkien".to_string()));
+        assert_eq!(plan.authors_to_create(), vec!["Terry Pratchett"]);
+        import_books(&plan).unwrap();
+        assert_eq!(author_names(), vec!["J.R.R. Tolkien", "Terry Pratchett"]);
+        let by_tolkien = get_books()
+            .unwrap()
+            .into_iter()
+            .filter(|pair| pair.book.AuthorFK == Some(tolkien.Id))
+            .count();
+        assert_eq!(by_tolkien, 2);
+    }
+}
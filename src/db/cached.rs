@@ -0,0 +1,196 @@
+// src/db/cached.rs
+use crate::db::{self, DbError};
+use crate::models::{AuthorModel, BookWithAuthor, SeriesModel};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Fallback expiry for changes made outside the app, mutations invalidate right away
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Reads served from the cache, each invalidated on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryKind {
+    Books,
+    Authors,
+    Series,
+}
+
+struct Entry<T> {
+    value: T,
+    stored_at: Instant,
+}
+
+struct State<T> {
+    entry: Option<Entry<T>>,
+    generation: u64, // Bumped on every invalidation
+}
+
+/// One cached query result, shared by all tasks
+pub struct ReadCache<T> {
+    state: Mutex<State<T>>,
+    ttl: Duration,
+}
+
+impl<T: Clone> ReadCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            state: Mutex::new(State {
+                entry: None,
+                generation: 0,
+            }),
+            ttl,
+        }
+    }
+
+    /// Returns the cached value while it is fresh, otherwise loads and stores it.
+    /// The lock is not held while loading, so a slow query never blocks other reads.
+    pub fn get_or_load<E>(&self, load: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let generation = {
+            let state = self.state.lock().unwrap();
+            if let Some(entry) = &state.entry {
+                if entry.stored_at.elapsed() < self.ttl {
+                    return Ok(entry.value.clone());
+                }
+            }
+            state.generation
+        };
+
+        let value = load()?;
+
+        // An invalidation during the load means the value may predate the change, so
+        // it is returned to this caller but not kept for the next one
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            state.entry = Some(Entry {
+                value: value.clone(),
+                stored_at: Instant::now(),
+            });
+        }
+        Ok(value)
+    }
+
+    pub fn invalidate(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entry = None;
+        state.generation += 1;
+    }
+}
+
+static BOOKS: Lazy<ReadCache<Vec<BookWithAuthor>>> = Lazy::new(|| ReadCache::new(CACHE_TTL));
+static AUTHORS: Lazy<ReadCache<Vec<AuthorModel>>> = Lazy::new(|| ReadCache::new(CACHE_TTL));
+static SERIES: Lazy<ReadCache<Vec<SeriesModel>>> = Lazy::new(|| ReadCache::new(CACHE_TTL));
+
+pub fn get_books() -> Result<Vec<BookWithAuthor>, DbError> {
+    BOOKS.get_or_load(db::get_books)
+}
+
+pub fn get_authors() -> Result<Vec<AuthorModel>, DbError> {
+    AUTHORS.get_or_load(db::get_authors)
+}
+
+pub fn get_series() -> Result<Vec<SeriesModel>, DbError> {
+    SERIES.get_or_load(db::get_series)
+}
+
+/// Drops the cached results of the given queries, called after every write
+pub fn invalidate(kinds: &[QueryKind]) {
+    for kind in kinds {
+        match kind {
+            QueryKind::Books => BOOKS.invalidate(),
+            QueryKind::Authors => AUTHORS.invalidate(),
+            QueryKind::Series => SERIES.invalidate(),
+        }
+    }
+}
+
+pub fn invalidate_all() {
+    invalidate(&[QueryKind::Books, QueryKind::Authors, QueryKind::Series]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    // Load function that counts its calls and returns the call number
+    fn counting(calls: &Cell<u32>) -> impl Fn() -> Result<u32, ()> + '_ {
+        || {
+            calls.set(calls.get() + 1);
+            Ok(calls.get())
+        }
+    }
+
+    #[test]
+    fn a_second_read_within_the_ttl_is_served_from_the_cache() {
+        let cache = ReadCache::new(CACHE_TTL);
+        let calls = Cell::new(0);
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(1));
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(1));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn an_invalidated_value_is_loaded_again() {
+        let cache = ReadCache::new(CACHE_TTL);
+        let calls = Cell::new(0);
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(1));
+        cache.invalidate();
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(2));
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(2));
+    }
+
+    #[test]
+    fn an_expired_value_is_loaded_again() {
+        let cache = ReadCache::new(Duration::from_millis(20));
+        let calls = Cell::new(0);
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(1));
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get_or_load(counting(&calls)), Ok(2));
+    }
+
+    #[test]
+    fn a_load_that_raced_an_invalidation_is_not_kept() {
+        let cache = ReadCache::new(CACHE_TTL);
+        // A write lands while the read is still running, its result predates the write
+        let stale = cache.get_or_load(|| {
+            cache.invalidate();
+            Ok::<_, ()>("before the write")
+        });
+        assert_eq!(stale, Ok("before the write"));
+        assert_eq!(
+            cache.get_or_load(|| Ok::<_, ()>("after the write")),
+            Ok("after the write")
+        );
+        assert_eq!(
+            cache.get_or_load(|| Ok::<_, ()>("not loaded")),
+            Ok("after the write")
+        );
+    }
+
+    #[test]
+    fn a_failed_load_is_not_cached() {
+        let cache = ReadCache::new(CACHE_TTL);
+        assert_eq!(cache.get_or_load(|| Err("locked")), Err::<u32, _>("locked"));
+        assert_eq!(cache.get_or_load(|| Ok::<_, &str>(3)), Ok(3));
+    }
+
+    #[test]
+    fn concurrent_reads_of_a_fresh_value_load_nothing() {
+        let cache = ReadCache::new(CACHE_TTL);
+        let calls = AtomicU32::new(0);
+        let load = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(vec![1, 2, 3])
+        };
+        assert_eq!(cache.get_or_load(load), Ok(vec![1, 2, 3]));
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| assert_eq!(cache.get_or_load(load), Ok(vec![1, 2, 3])));
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,202 @@
+// src/draft.rs
+use crate::models::{BookFormat, ID};
+use crate::paths;
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DraftError {
+    #[error("Draft file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Draft format error: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Book form contents autosaved while adding or editing, so they survive a crash.
+/// Fields missing from an older file fall back to their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BookDraft {
+    pub saved_at: Option<NaiveDateTime>, // Only set in the file, stamped when written
+    pub book_id: Option<ID>,             // Book being edited, None while adding
+    pub title: String,
+    pub price: String,
+    pub bought_date: String,
+    pub finished_date: String,
+    pub is_gift: bool,
+    pub gift_from: String,
+    pub quantity: i32,
+    pub cover_path: Option<String>,
+    pub genre: String,
+    pub page_count: String,
+    pub format: Option<BookFormat>,
+    pub duration_minutes: String,
+    pub language: Option<String>,
+    pub acquired_from: String,
+    pub rating: Option<i32>,
+    pub author_id: Option<ID>,
+    pub series_id: Option<ID>,
+    pub journal_draft: String,
+    pub custom_fields: Vec<(String, String)>,
+}
+
+impl Default for BookDraft {
+    fn default() -> Self {
+        Self {
+            saved_at: None,
+            book_id: None,
+            title: String::new(),
+            price: String::new(),
+            bought_date: String::new(),
+            finished_date: String::new(),
+            is_gift: false,
+            gift_from: String::new(),
+            quantity: 1,
+            cover_path: None,
+            genre: String::new(),
+            page_count: String::new(),
+            format: None,
+            duration_minutes: String::new(),
+            language: None,
+            acquired_from: String::new(),
+            rating: None,
+            author_id: None,
+            series_id: None,
+            journal_draft: String::new(),
+            custom_fields: Vec::new(),
+        }
+    }
+}
+
+/// Loads the draft left by a previous run, None when there is none or it is unreadable
+pub fn load() -> Option<BookDraft> {
+    load_from(&paths::draft_file())
+}
+
+pub fn save(draft: &BookDraft) -> Result<(), DraftError> {
+    save_to(&paths::draft_file(), draft)
+}
+
+pub fn delete() -> Result<(), DraftError> {
+    delete_at(&paths::draft_file())
+}
+
+fn load_from(path: &Path) -> Option<BookDraft> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+fn save_to(path: &Path, draft: &BookDraft) -> Result<(), DraftError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let stamped = BookDraft {
+        saved_at: Some(Local::now().naive_local()),
+        ..draft.clone()
+    };
+    fs::write(path, serde_json::to_string_pretty(&stamped)?)?;
+    Ok(())
+}
+
+fn delete_at(path: &Path) -> Result<(), DraftError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn filled_draft() -> BookDraft {
+        BookDraft {
+            saved_at: None,
+            book_id: Some(4),
+            title: "Solaris".to_string(),
+            price: "39,90".to_string(),
+            bought_date: "2025-03-01".to_string(),
+            finished_date: "-3d".to_string(),
+            is_gift: true,
+            gift_from: "Ania".to_string(),
+            quantity: 2,
+            cover_path: Some("covers/solaris.jpg".to_string()),
+            genre: "Science fiction".to_string(),
+            page_count: "204".to_string(),
+            format: Some(BookFormat::Audiobook),
+            duration_minutes: "540".to_string(),
+            language: Some("pl".to_string()),
+            acquired_from: "Antykwariat".to_string(),
+            rating: Some(5),
+            author_id: Some(7),
+            series_id: Some(2),
+            journal_draft: "Long notes\nover \"two\" lines".to_string(),
+            custom_fields: vec![("Signed".to_string(), "yes".to_string())],
+        }
+    }
+
+    // An empty folder of its own for each file test
+    fn draft_path(name: &str) -> std::path::PathBuf {
+        let folder = std::env::temp_dir().join("bookshelf-tests").join(name);
+        let _ = fs::remove_dir_all(&folder);
+        folder.join("draft.json")
+    }
+
+    #[test]
+    fn every_form_field_survives_the_file() {
+        let path = draft_path("draft-round-trip");
+        let draft = filled_draft();
+        save_to(&path, &draft).unwrap();
+
+        let loaded = load_from(&path).unwrap();
+        assert!(loaded.saved_at.is_some());
+        assert_eq!(
+            BookDraft {
+                saved_at: None,
+                ..loaded
+            },
+            draft
+        );
+
+        delete_at(&path).unwrap();
+        assert_eq!(load_from(&path), None);
+        // Deleting again, as a discard after a save does, is fine
+        delete_at(&path).unwrap();
+    }
+
+    #[test]
+    fn drafts_from_other_versions_fall_back_to_defaults() {
+        let older: BookDraft = serde_json::from_str(
+            r#"{"saved_at": "2025-03-01T12:30:00", "title": "Dune", "author_id": 3,
+                "shelf": "living room"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            older,
+            BookDraft {
+                saved_at: NaiveDate::from_ymd_opt(2025, 3, 1)
+                    .unwrap()
+                    .and_hms_opt(12, 30, 0),
+                title: "Dune".to_string(),
+                author_id: Some(3),
+                ..BookDraft::default()
+            }
+        );
+        assert_eq!(older.quantity, 1);
+    }
+
+    #[test]
+    fn an_unreadable_draft_is_ignored() {
+        let path = draft_path("draft-unreadable");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(load_from(&path), None);
+    }
+}
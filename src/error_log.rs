@@ -0,0 +1,83 @@
+// src/error_log.rs
+use crate::paths;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+/// Size at which the log is rotated
+const LOG_MAX_BYTES: u64 = 512 * 1024;
+/// Rotated files kept next to the current log, older ones are deleted
+const LOG_KEEP_FILES: usize = 3;
+
+// Lines are written on a background thread so logging never blocks the UI
+static LOGGER: Lazy<Sender<String>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in receiver {
+            write_line(&paths::log_file(), &line);
+        }
+    });
+    sender
+});
+
+/// Queues an error for the log file
+pub fn error(message: &str) {
+    let _ = LOGGER.send(format_line("ERROR", message));
+}
+
+/// Logs panics before the default hook prints them.
+/// Written synchronously, the process may be gone before the logger thread runs.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        write_line(&paths::log_file(), &format_line("PANIC", &info.to_string()));
+        default_hook(info);
+    }));
+}
+
+fn format_line(level: &str, message: &str) -> String {
+    format!(
+        "{} {} {}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        level,
+        message.replace('\n', " ")
+    )
+}
+
+// Appends a line, a failing log must never take the app down so errors are dropped
+fn write_line(path: &Path, line: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    rotate_if_full(path);
+
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+// bookshelf.log becomes bookshelf.log.1, .1 becomes .2 and so on, the oldest is dropped
+fn rotate_if_full(path: &Path) {
+    let full = fs::metadata(path)
+        .map(|meta| meta.len() >= LOG_MAX_BYTES)
+        .unwrap_or(false);
+    if !full {
+        return;
+    }
+
+    let _ = fs::remove_file(rotated_path(path, LOG_KEEP_FILES));
+    for index in (1..LOG_KEEP_FILES).rev() {
+        let _ = fs::rename(rotated_path(path, index), rotated_path(path, index + 1));
+    }
+    let _ = fs::rename(path, rotated_path(path, 1));
+}
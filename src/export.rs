@@ -0,0 +1,559 @@
+// src/export.rs
+use crate::models::{BookWithAuthor, ID};
+use crate::report::html_escape;
+use crate::stats;
+use crate::ui::{format_price, genre_color};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{Local, NaiveDateTime};
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Export file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("PDF error: {0}")]
+    Pdf(#[from] printpdf::Error),
+}
+
+// A4 portrait, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const LINE_HEIGHT: f32 = 6.0;
+const PRICE_COLUMN: f32 = 165.0; // Left edge of the price column
+const TITLE_MAX_CHARS: usize = 70; // Fits before the price column at the row font size
+
+const TITLE_SIZE: f32 = 16.0;
+const HEADING_SIZE: f32 = 12.0;
+const ROW_SIZE: f32 = 10.0;
+const FOOTER_SIZE: f32 = 8.0;
+
+enum Line {
+    Heading(String),
+    Book { title: String, price: String },
+    Note(String),
+}
+
+/// Writes a printable catalog of the books grouped by author, with a total value
+/// at the end and page numbers in the footer.
+pub fn export_catalog_pdf(
+    books: &[BookWithAuthor],
+    path: &Path,
+    currency: &str,
+) -> Result<(), ExportError> {
+    let pages = paginate(catalog_lines(books, currency));
+    let generated_at = Local::now().naive_local();
+
+    let (document, first_page, first_layer) =
+        PdfDocument::new("Book catalog", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Catalog");
+    let regular = document.add_builtin_font(BuiltinFont::Helvetica)?;
+    let bold = document.add_builtin_font(BuiltinFont::HelveticaBold)?;
+    let fonts = (&regular, &bold);
+
+    let page_count = pages.len();
+    for (index, lines) in pages.iter().enumerate() {
+        let layer = if index == 0 {
+            document.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = document.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Catalog");
+            document.get_page(page).get_layer(layer)
+        };
+
+        let mut y = PAGE_HEIGHT - MARGIN;
+        if index == 0 {
+            layer.use_text("Book catalog", TITLE_SIZE, Mm(MARGIN), Mm(y), &bold);
+            y -= LINE_HEIGHT;
+            layer.use_text(
+                pdf_text(&generated_line(books.len(), generated_at)),
+                FOOTER_SIZE,
+                Mm(MARGIN),
+                Mm(y),
+                &regular,
+            );
+            y -= LINE_HEIGHT * 2.0;
+        }
+
+        for line in lines {
+            draw_line(&layer, line, y, fonts);
+            y -= LINE_HEIGHT;
+        }
+
+        layer.use_text(
+            format!("Page {} of {}", index + 1, page_count),
+            FOOTER_SIZE,
+            Mm(PAGE_WIDTH / 2.0 - 10.0),
+            Mm(MARGIN / 2.0),
+            &regular,
+        );
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    document.save(&mut BufWriter::new(File::create(path)?))?;
+    Ok(())
+}
+
+fn generated_line(books: usize, generated_at: NaiveDateTime) -> String {
+    format!(
+        "{} books, generated {}",
+        books,
+        generated_at.format("%Y-%m-%d %H:%M")
+    )
+}
+
+// Authors in alphabetical order, books without an author last, each author's books by title
+fn group_by_author(books: &[BookWithAuthor]) -> Vec<(String, Vec<&BookWithAuthor>)> {
+    let mut by_author: BTreeMap<(bool, String), Vec<&BookWithAuthor>> = BTreeMap::new();
+    for pair in books {
+        let author = pair.author.as_ref().and_then(|a| a.Name.clone());
+        let key = (
+            author.is_none(),
+            author.unwrap_or_else(|| "No Author".to_string()),
+        );
+        by_author.entry(key).or_default().push(pair);
+    }
+    by_author
+        .into_iter()
+        .map(|((_, author), mut books)| {
+            books.sort_by_key(|pair| pair.book.title.to_lowercase());
+            (author, books)
+        })
+        .collect()
+}
+
+fn catalog_lines(books: &[BookWithAuthor], currency: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut total_value = 0.0;
+    let mut unpriced = 0;
+    for (author, books) in group_by_author(books) {
+        lines.push(Line::Heading(format!("{} ({})", author, books.len())));
+        for pair in books {
+            let title = if pair.book.quantity > 1 {
+                format!("{} (x{})", pair.book.title, pair.book.quantity)
+            } else {
+                pair.book.title.clone()
+            };
+            match pair.book.total_value() {
+                Some(value) => total_value += value,
+                None => unpriced += 1,
+            }
+            lines.push(Line::Book {
+                title,
+                price: pair
+                    .book
+                    .price
+                    .map(|price| format_price(price, currency, false))
+                    .unwrap_or_else(|| "-".to_string()),
+            });
+        }
+    }
+
+    lines.push(Line::Note(String::new()));
+    lines.push(Line::Heading(format!(
+        "Total value: {}",
+        format_price(total_value, currency, false)
+    )));
+    if unpriced > 0 {
+        lines.push(Line::Note(format!(
+            "{} books have no price and are not counted",
+            unpriced
+        )));
+    }
+    lines
+}
+
+// Splits the lines into pages, a heading is never left alone at the bottom of a page
+fn paginate(lines: Vec<Line>) -> Vec<Vec<Line>> {
+    let rows_per_page = ((PAGE_HEIGHT - MARGIN * 2.0) / LINE_HEIGHT) as usize;
+    let first_page_rows = rows_per_page - 3; // Title and generated line
+
+    let mut pages: Vec<Vec<Line>> = vec![Vec::new()];
+    let mut lines = lines.into_iter().peekable();
+    while let Some(line) = lines.next() {
+        let used = pages.last().map_or(0, Vec::len);
+        let capacity = if pages.len() == 1 {
+            first_page_rows
+        } else {
+            rows_per_page
+        };
+        let needed = if matches!(line, Line::Heading(_)) && lines.peek().is_some() {
+            2
+        } else {
+            1
+        };
+        if used + needed > capacity {
+            pages.push(Vec::new());
+        }
+        if let Some(page) = pages.last_mut() {
+            page.push(line);
+        }
+    }
+    pages
+}
+
+fn draw_line(
+    layer: &printpdf::PdfLayerReference,
+    line: &Line,
+    y: f32,
+    (regular, bold): (&IndirectFontRef, &IndirectFontRef),
+) {
+    match line {
+        Line::Heading(text) => {
+            layer.use_text(pdf_text(text), HEADING_SIZE, Mm(MARGIN), Mm(y), bold);
+        }
+        Line::Book { title, price } => {
+            layer.use_text(
+                pdf_text(&truncate(title, TITLE_MAX_CHARS)),
+                ROW_SIZE,
+                Mm(MARGIN + 5.0),
+                Mm(y),
+                regular,
+            );
+            layer.use_text(pdf_text(price), ROW_SIZE, Mm(PRICE_COLUMN), Mm(y), regular);
+        }
+        Line::Note(text) => {
+            layer.use_text(pdf_text(text), FOOTER_SIZE, Mm(MARGIN), Mm(y), regular);
+        }
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars - 3).collect();
+    format!("{}...", kept)
+}
+
+// The built-in PDF fonts only cover Latin-1, other letters fall back to their base letter
+fn pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ą' => 'a',
+            'ć' => 'c',
+            'ę' => 'e',
+            'ł' => 'l',
+            'ń' => 'n',
+            'ś' => 's',
+            'ź' | 'ż' => 'z',
+            'Ą' => 'A',
+            'Ć' => 'C',
+            'Ę' => 'E',
+            'Ł' => 'L',
+            'Ń' => 'N',
+            'Ś' => 'S',
+            'Ź' | 'Ż' => 'Z',
+            '‘' | '’' => '\'',
+            '“' | '”' => '"',
+            '–' | '—' => '-',
+            c if (c as u32) < 0x100 => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+/// How the books on a shared shelf page are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShelfGrouping {
+    #[default]
+    Author,
+    Status,
+}
+
+impl ShelfGrouping {
+    pub const ALL: [ShelfGrouping; 2] = [ShelfGrouping::Author, ShelfGrouping::Status];
+}
+
+impl fmt::Display for ShelfGrouping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShelfGrouping::Author => write!(f, "By author"),
+            ShelfGrouping::Status => write!(f, "By read status"),
+        }
+    }
+}
+
+/// Choices made before writing a shelf page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ShelfOptions {
+    pub grouping: ShelfGrouping,
+    pub include_prices: bool, // Off by default, the page is meant to be shared
+}
+
+/// Writes a single self-contained HTML page showing the books as cards, ready to share
+pub fn export_shelf_html(
+    books: &[BookWithAuthor],
+    options: ShelfOptions,
+    path: &Path,
+    currency: &str,
+) -> Result<(), ExportError> {
+    let covers = cover_data_uris(books);
+    let page = shelf_html(books, &covers, options, Local::now().naive_local(), currency);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, page)?;
+    Ok(())
+}
+
+/// Covers of the books as data URIs by book id, missing or unreadable files are left out
+pub fn cover_data_uris(books: &[BookWithAuthor]) -> HashMap<ID, String> {
+    books
+        .iter()
+        .filter_map(|pair| {
+            let path = Path::new(pair.book.cover_path.as_deref()?);
+            let bytes = fs::read(path).ok()?;
+            let mime = match path.extension().and_then(|ext| ext.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("png") => "image/png",
+                _ => "image/jpeg",
+            };
+            Some((pair.book.id, format!("data:{};base64,{}", mime, BASE64.encode(bytes))))
+        })
+        .collect()
+}
+
+/// Shelf page as HTML, built only from its arguments. Books without a cover in `covers`
+/// get a placeholder colored from their title
+pub fn shelf_html(
+    books: &[BookWithAuthor],
+    covers: &HashMap<ID, String>,
+    options: ShelfOptions,
+    generated_at: NaiveDateTime,
+    currency: &str,
+) -> String {
+    let groups: Vec<(String, Vec<&BookWithAuthor>)> = match options.grouping {
+        ShelfGrouping::Author => group_by_author(books),
+        ShelfGrouping::Status => stats::group_by_read_status(books)
+            .into_iter()
+            .map(|section| (section.key.label().to_string(), section.books))
+            .collect(),
+    };
+
+    let finished = books.iter().filter(|pair| pair.book.finished.is_some()).count();
+    let authors = books
+        .iter()
+        .filter_map(|pair| pair.book.AuthorFK)
+        .collect::<HashSet<_>>()
+        .len();
+    let mut body = format!(
+        "<header>\n<h1>My bookshelf</h1>\n<p>{} books, {} finished, {} authors. \
+         Generated {}</p>\n</header>\n",
+        books.len(),
+        finished,
+        authors,
+        generated_at.format("%Y-%m-%d")
+    );
+
+    for (heading, books) in groups {
+        body.push_str(&format!(
+            "<section>\n<h2>{} ({})</h2>\n<div class=\"grid\">\n",
+            html_escape(&heading),
+            books.len()
+        ));
+        for pair in books {
+            body.push_str(&shelf_card(pair, covers.get(&pair.book.id), options, currency));
+        }
+        body.push_str("</div>\n</section>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>My bookshelf</title>\n<style>\n\
+         body {{ font-family: sans-serif; margin: 2em; color: #222; background: #fafafa; }}\n\
+         header p {{ color: #666; }}\n\
+         .grid {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(140px, 1fr)); \
+         gap: 1em; }}\n\
+         .card {{ background: #fff; border-radius: 6px; box-shadow: 0 1px 3px #0003; \
+         overflow: hidden; }}\n\
+         .cover {{ width: 100%; aspect-ratio: 2 / 3; object-fit: cover; display: flex; \
+         align-items: center; justify-content: center; color: #fff; font-size: 3em; }}\n\
+         .info {{ padding: 0.5em; font-size: 0.9em; }}\n\
+         .title {{ font-weight: bold; }}\n\
+         .author, .price {{ color: #666; }}\n\
+         </style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        body
+    )
+}
+
+fn shelf_card(
+    pair: &BookWithAuthor,
+    cover: Option<&String>,
+    options: ShelfOptions,
+    currency: &str,
+) -> String {
+    let title = html_escape(&pair.book.title);
+    let cover = match cover {
+        Some(uri) => format!(
+            "<img class=\"cover\" src=\"{}\" alt=\"Cover of {}\">",
+            uri, title
+        ),
+        None => {
+            let color = genre_color(&pair.book.title);
+            let initial = pair
+                .book
+                .title
+                .trim()
+                .chars()
+                .next()
+                .map_or_else(String::new, |c| c.to_uppercase().to_string());
+            format!(
+                "<div class=\"cover\" style=\"background: rgb({}, {}, {})\">{}</div>",
+                (color.r * 255.0).round() as u8,
+                (color.g * 255.0).round() as u8,
+                (color.b * 255.0).round() as u8,
+                html_escape(&initial)
+            )
+        }
+    };
+
+    let mut info = format!("<div class=\"title\">{}</div>", title);
+    if let Some(name) = pair.author.as_ref().and_then(|author| author.Name.as_deref()) {
+        info.push_str(&format!("<div class=\"author\">{}</div>", html_escape(name)));
+    }
+    if let Some(price) = pair.book.price.filter(|_| options.include_prices) {
+        info.push_str(&format!(
+            "<div class=\"price\">{}</div>",
+            html_escape(&format_price(price, currency, false))
+        ));
+    }
+    format!(
+        "<div class=\"card\">{}<div class=\"info\">{}</div></div>\n",
+        cover, info
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AuthorModel, BookModel};
+    use chrono::NaiveDate;
+
+    fn shelf() -> Vec<BookWithAuthor> {
+        let lem = AuthorModel {
+            Id: 1,
+            Name: Some("Stanisław Lem".to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        };
+        let finished = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        let mut solaris = BookModel::titled(1, "Solaris");
+        solaris.AuthorFK = Some(1);
+        solaris.finished = Some(finished);
+        solaris.price = Some(12.5);
+        let mut tricky = BookModel::titled(2, "The \"Best\" <script>alert(1)</script>");
+        tricky.AuthorFK = Some(1);
+        tricky.price = Some(30.0);
+        let loose = BookModel::titled(3, "anonymous notes");
+
+        vec![
+            BookWithAuthor {
+                book: solaris,
+                author: Some(lem.clone()),
+            },
+            BookWithAuthor {
+                book: tricky,
+                author: Some(lem),
+            },
+            BookWithAuthor {
+                book: loose,
+                author: None,
+            },
+        ]
+    }
+
+    fn generated_at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 6)
+            .unwrap()
+            .and_hms_opt(18, 30, 0)
+            .unwrap()
+    }
+
+    fn page(covers: &HashMap<ID, String>, options: ShelfOptions) -> String {
+        shelf_html(&shelf(), covers, options, generated_at(), " zł")
+    }
+
+    #[test]
+    fn the_shelf_page_has_a_header_and_a_section_per_author() {
+        let html = page(&HashMap::new(), ShelfOptions::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>My bookshelf</h1>"));
+        assert!(html.contains("3 books, 1 finished, 1 authors. Generated 2024-05-06"));
+        assert!(html.contains("<h2>Stanisław Lem (2)</h2>"));
+        assert!(html.contains("<h2>No Author (1)</h2>"));
+        assert!(html.find("Stanisław Lem (2)") < html.find("No Author (1)"));
+        assert!(html.contains("<div class=\"author\">Stanisław Lem</div>"));
+    }
+
+    #[test]
+    fn grouping_by_status_uses_the_reading_sections() {
+        let options = ShelfOptions {
+            grouping: ShelfGrouping::Status,
+            include_prices: false,
+        };
+        let html = page(&HashMap::new(), options);
+
+        assert!(html.contains("<h2>Finished (1)</h2>"));
+        assert!(html.contains("<h2>Wishlist (2)</h2>"));
+        assert!(!html.contains("<h2>Stanisław Lem"));
+    }
+
+    #[test]
+    fn quotes_and_angle_brackets_in_titles_are_escaped() {
+        let covers = HashMap::from([(2, "data:image/png;base64,AAAA".to_string())]);
+        let html = page(&covers, ShelfOptions::default());
+
+        let escaped = "The &quot;Best&quot; &lt;script&gt;alert(1)&lt;/script&gt;";
+        assert!(html.contains(&format!("<div class=\"title\">{}</div>", escaped)));
+        assert!(html.contains(&format!("alt=\"Cover of {}\"", escaped)));
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("\"Best\""));
+    }
+
+    #[test]
+    fn a_cover_is_embedded_and_books_without_one_get_a_placeholder() {
+        let covers = HashMap::from([(1, "data:image/jpeg;base64,AAAA".to_string())]);
+        let html = page(&covers, ShelfOptions::default());
+
+        assert!(html.contains("<img class=\"cover\" src=\"data:image/jpeg;base64,AAAA\""));
+        assert_eq!(html.matches("<img ").count(), 1);
+        assert!(html.contains("\">A</div>"));
+        assert_eq!(
+            html.matches("<div class=\"cover\" style=\"background: rgb(")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn prices_are_left_out_unless_asked_for() {
+        let private = page(&HashMap::new(), ShelfOptions::default());
+        assert!(!private.contains("class=\"price\""));
+        assert!(!private.contains("12.50"));
+
+        let options = ShelfOptions {
+            grouping: ShelfGrouping::Author,
+            include_prices: true,
+        };
+        let priced = page(&HashMap::new(), options);
+        assert!(priced.contains("<div class=\"price\">12.50 zł</div>"));
+        assert!(priced.contains("<div class=\"price\">30.00 zł</div>"));
+        assert_eq!(priced.matches("class=\"price\"").count(), 2);
+    }
+}
@@ -0,0 +1,382 @@
+// src/fuzzy.rs
+
+/// Scores how well `query` fuzzily matches `candidate`, None when it doesn't match at all.
+/// Every query character must appear in order; consecutive runs and word starts score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (index, c) in candidate.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if *c != query[query_index] {
+            continue;
+        }
+
+        score += 1;
+        if previous_match
+            .map(|prev| prev + 1 == index)
+            .unwrap_or(false)
+        {
+            score += 5; // Consecutive characters
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3; // Start of a word
+        }
+
+        previous_match = Some(index);
+        query_index += 1;
+    }
+
+    if query_index == query.len() {
+        // Prefer shorter candidates when the match quality is otherwise equal
+        Some(score * 100 - candidate.len() as i32)
+    } else {
+        None
+    }
+}
+
+/// Returns the indices of the candidates matching `query`, best matches first
+pub fn fuzzy_filter<T: AsRef<str>>(query: &str, candidates: &[T]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| {
+            fuzzy_score(query, candidate.as_ref()).map(|score| (index, score))
+        })
+        .collect();
+
+    // Stable sort keeps the original order for equal scores
+    scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Names whose similarity reaches this are offered as likely the same person
+pub const NAME_SIMILARITY_MIN: f32 = 0.8;
+
+// Case, spaces and punctuation dropped, so "J. K. Rowling" and "J.K. Rowling" compare equal
+fn normalize_name(name: &str) -> Vec<char> {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// "Tolkien, J.R.R." as "J.R.R. Tolkien", the way catalogues and exports often write names
+fn reading_order(name: &str) -> Option<String> {
+    let (surname, first_names) = name.split_once(',')?;
+    let (surname, first_names) = (surname.trim(), first_names.trim());
+    (!surname.is_empty() && !first_names.is_empty() && !first_names.contains(','))
+        .then(|| format!("{} {}", first_names, surname))
+}
+
+/// Similarity of two names from 0.0 to 1.0, based on the edit distance of their letters.
+/// A name written surname first also compares in reading order, the better one counts.
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let letters_similarity = |a: &str, b: &str| {
+        let (a, b) = (normalize_name(a), normalize_name(b));
+        let longest = a.len().max(b.len());
+        if longest == 0 {
+            return 0.0;
+        }
+        1.0 - edit_distance(&a, &b) as f32 / longest as f32
+    };
+    let reordered = |name: &str| reading_order(name).unwrap_or_else(|| name.to_string());
+    letters_similarity(a, b).max(letters_similarity(&reordered(a), &reordered(b)))
+}
+
+/// Indices of the candidates similar enough to `name`, most similar first
+pub fn similar_names<T: AsRef<str>>(name: &str, candidates: &[T], limit: usize) -> Vec<usize> {
+    let mut scored: Vec<(usize, f32)> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| (index, name_similarity(name, candidate.as_ref())))
+        .filter(|(_, similarity)| *similarity >= NAME_SIMILARITY_MIN)
+        .collect();
+
+    scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    scored.into_iter().take(limit).map(|(index, _)| index).collect()
+}
+
+/// Longest edit distance between two author names still suggested as a typo
+pub const NAME_TYPO_MAX: usize = 2;
+
+/// Names with fewer letters are never matched as typos, "Lem" and "Lee" are different people
+pub const NAME_TYPO_MIN_LETTERS: usize = 6;
+
+/// Why two author names are suggested as the same person
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatch {
+    SameLetters, // Equal once case, spaces and punctuation are ignored
+    Initials,    // "J. R. R. Tolkien" and "John Ronald Reuel Tolkien"
+    Typo(usize), // Letters this many edits apart
+}
+
+impl std::fmt::Display for NameMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameMatch::SameLetters => write!(f, "same name written differently"),
+            NameMatch::Initials => write!(f, "initials of the same first names"),
+            NameMatch::Typo(1) => write!(f, "1 letter apart"),
+            NameMatch::Typo(distance) => write!(f, "{} letters apart", distance),
+        }
+    }
+}
+
+// Base letter of common Latin letters with diacritics, expects lowercase input
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'ą' | 'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ć' | 'ç' | 'č' => 'c',
+        'ď' => 'd',
+        'ę' | 'è' | 'é' | 'ê' | 'ë' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ł' => 'l',
+        'ń' | 'ñ' | 'ň' => 'n',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' => 's',
+        'ť' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ů' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+// Lowercase words of a name, split at anything that isn't a letter or digit
+fn name_words(name: &str, fold_diacritics: bool) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            if fold_diacritics {
+                word.chars().map(fold_diacritic).collect()
+            } else {
+                word.to_string()
+            }
+        })
+        .collect()
+}
+
+// Same surname and the same first names, where at least one side is only an initial
+fn initials_match(a: &[String], b: &[String]) -> bool {
+    if a.len() < 2 || a.len() != b.len() || a.last() != b.last() {
+        return false;
+    }
+    let mut initials = 0;
+    for (x, y) in a.iter().zip(b).take(a.len() - 1) {
+        if x == y {
+            continue;
+        }
+        let (short, long) = if x.chars().count() <= y.chars().count() {
+            (x, y)
+        } else {
+            (y, x)
+        };
+        if short.chars().count() != 1 || !long.starts_with(short.as_str()) {
+            return false;
+        }
+        initials += 1;
+    }
+    initials > 0
+}
+
+/// Decides whether two author names likely belong to the same person. Names differing only
+/// in diacritics ("Lem" and "Łem") are different people unless `fold_diacritics` is set.
+pub fn author_name_match(a: &str, b: &str, fold_diacritics: bool) -> Option<NameMatch> {
+    let (words_a, words_b) = (
+        name_words(a, fold_diacritics),
+        name_words(b, fold_diacritics),
+    );
+    let letters_a: Vec<char> = words_a.iter().flat_map(|word| word.chars()).collect();
+    let letters_b: Vec<char> = words_b.iter().flat_map(|word| word.chars()).collect();
+    if letters_a.is_empty() || letters_b.is_empty() {
+        return None;
+    }
+
+    if letters_a == letters_b {
+        return Some(NameMatch::SameLetters);
+    }
+    let folded = |letters: &[char]| -> Vec<char> {
+        letters.iter().copied().map(fold_diacritic).collect()
+    };
+    if !fold_diacritics && folded(&letters_a) == folded(&letters_b) {
+        return None;
+    }
+    if initials_match(&words_a, &words_b) {
+        return Some(NameMatch::Initials);
+    }
+
+    let distance = edit_distance(&letters_a, &letters_b);
+    let shortest = letters_a.len().min(letters_b.len());
+    (distance <= NAME_TYPO_MAX && shortest >= NAME_TYPO_MIN_LETTERS)
+        .then_some(NameMatch::Typo(distance))
+}
+
+/// Every pair of names that likely belongs to one person, as indices into `names`
+pub fn similar_name_pairs<T: AsRef<str>>(
+    names: &[T],
+    fold_diacritics: bool,
+) -> Vec<(usize, usize, NameMatch)> {
+    let mut pairs = Vec::new();
+    for (i, a) in names.iter().enumerate() {
+        for (j, b) in names.iter().enumerate().skip(i + 1) {
+            if let Some(reason) = author_name_match(a.as_ref(), b.as_ref(), fold_diacritics) {
+                pairs.push((i, j, reason));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_written_surname_first_are_suggested_for_the_same_person() {
+        let known = ["J.R.R. Tolkien", "Terry Pratchett", "Christopher Tolkien"];
+        assert_eq!(similar_names("Tolkien, J.R.R.", &known, 3), vec![0]);
+        assert_eq!(similar_names("J. R. R. Tolkien", &known, 3), vec![0]);
+        assert_eq!(similar_names("Pratchett, Terry", &known, 3), vec![1]);
+        assert!(similar_names("Le Guin, Ursula", &known, 3).is_empty());
+    }
+
+    #[test]
+    fn similar_names_are_ordered_and_limited() {
+        let known = ["Stanislaw Lem", "Stanisław Lem", "Frank Herbert"];
+        assert_eq!(similar_names("Stanisław Lem", &known, 3), vec![1, 0]);
+        assert_eq!(similar_names("Stanisław Lem", &known, 1), vec![1]);
+        assert_eq!(name_similarity("", ""), 0.0);
+    }
+
+    #[test]
+    fn names_equal_but_for_case_and_punctuation_have_the_same_letters() {
+        let same = Some(NameMatch::SameLetters);
+        assert_eq!(
+            author_name_match("J. K. Rowling", "j.k. rowling", false),
+            same
+        );
+        assert_eq!(author_name_match("Le Guin", "LeGuin", false), same);
+        assert_eq!(
+            author_name_match("Ursula K. Le Guin", "ursula k le-guin", false),
+            same
+        );
+        assert_eq!(author_name_match("", "...", false), None);
+        assert_eq!(author_name_match("Lem", "", true), None);
+    }
+
+    #[test]
+    fn initials_match_the_first_names_they_stand_for() {
+        let initials = Some(NameMatch::Initials);
+        assert_eq!(
+            author_name_match("J. R. R. Tolkien", "John Ronald Reuel Tolkien", false),
+            initials
+        );
+        assert_eq!(
+            author_name_match("Ursula K. Le Guin", "Ursula Kroeber Le Guin", false),
+            initials
+        );
+        // Another first name, another surname or a missing first name is someone else
+        assert_eq!(
+            author_name_match("John Tolkien", "Christopher Tolkien", false),
+            None
+        );
+        assert_eq!(
+            author_name_match("C. Tolkien", "Christopher Toller", false),
+            None
+        );
+        assert_eq!(author_name_match("S. Lem", "Lem", false), None);
+    }
+
+    #[test]
+    fn typos_match_up_to_two_edits_apart() {
+        assert_eq!(
+            author_name_match("Brandon Sanderson", "Brandon Sandersen", false),
+            Some(NameMatch::Typo(1))
+        );
+        assert_eq!(
+            author_name_match("Terry Pratchett", "Terry Prachet", false),
+            Some(NameMatch::Typo(2))
+        );
+        assert_eq!(
+            author_name_match("Brandon Sanderson", "Brendan Sandersen", false),
+            None
+        );
+    }
+
+    #[test]
+    fn short_names_are_never_typos() {
+        assert_eq!(author_name_match("Lem", "Lee", false), None);
+        assert_eq!(author_name_match("Smith", "Smyth", false), None);
+        assert_eq!(
+            author_name_match("Asimov", "Asimow", false),
+            Some(NameMatch::Typo(1))
+        );
+    }
+
+    #[test]
+    fn diacritics_tell_names_apart_unless_folded() {
+        assert_eq!(author_name_match("Lem", "Łem", false), None);
+        assert_eq!(
+            author_name_match("Stanisław Lem", "Stanislaw Lem", false),
+            None
+        );
+        assert_eq!(
+            author_name_match("Lem", "Łem", true),
+            Some(NameMatch::SameLetters)
+        );
+        assert_eq!(
+            author_name_match("Stanisław Lem", "stanislaw lem", true),
+            Some(NameMatch::SameLetters)
+        );
+        assert_eq!(
+            author_name_match("S. Lem", "Stanisław Łem", true),
+            Some(NameMatch::Initials)
+        );
+    }
+
+    #[test]
+    fn every_matching_pair_is_listed_once_in_index_order() {
+        let names = [
+            "Stanisław Lem",
+            "Frank Herbert",
+            "Stanislaw Lem",
+            "Frank Herbertt",
+        ];
+        assert_eq!(
+            similar_name_pairs(&names, false),
+            vec![(1, 3, NameMatch::Typo(1))]
+        );
+        assert_eq!(
+            similar_name_pairs(&names, true),
+            vec![(0, 2, NameMatch::SameLetters), (1, 3, NameMatch::Typo(1))]
+        );
+    }
+}
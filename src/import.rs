@@ -0,0 +1,711 @@
+// src/import.rs
+use crate::bulk::BulkPreview;
+use crate::db::{AuthorResolution, UnknownAuthor};
+use crate::fuzzy::name_similarity;
+use crate::models::ID;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("The file has no header row")]
+    NoHeader,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MappingError {
+    #[error("Pick the column that holds the title")]
+    TitleUnmapped,
+
+    #[error("{0} is picked for more than one column")]
+    DuplicateField(ImportField),
+}
+
+/// Book field a CSV column is imported into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportField {
+    Title,
+    Author,
+    Price,
+    Bought,
+    Finished,
+    Added,
+    AcquiredFrom,
+    Ignore,
+}
+
+impl ImportField {
+    pub const ALL: [ImportField; 8] = [
+        ImportField::Title,
+        ImportField::Author,
+        ImportField::Price,
+        ImportField::Bought,
+        ImportField::Finished,
+        ImportField::Added,
+        ImportField::AcquiredFrom,
+        ImportField::Ignore,
+    ];
+
+    // Header names other apps use for the field, compared loosely
+    fn header_names(self) -> &'static [&'static str] {
+        match self {
+            ImportField::Title => &["title", "book title", "book", "name"],
+            ImportField::Author => &["author", "authors", "writer", "author name", "by"],
+            ImportField::Price => &["price", "cost", "purchase price", "amount paid", "paid"],
+            ImportField::Bought => &["bought", "date bought", "purchased", "purchase date"],
+            ImportField::Finished => &["finished", "date finished", "date read", "read"],
+            ImportField::Added => &["added", "date added", "created"],
+            ImportField::AcquiredFrom => {
+                &["acquired from", "bought from", "store", "shop", "source"]
+            }
+            ImportField::Ignore => &[],
+        }
+    }
+}
+
+impl fmt::Display for ImportField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportField::Title => write!(f, "Title"),
+            ImportField::Author => write!(f, "Author"),
+            ImportField::Price => write!(f, "Price"),
+            ImportField::Bought => write!(f, "Bought"),
+            ImportField::Finished => write!(f, "Finished"),
+            ImportField::Added => write!(f, "Added"),
+            ImportField::AcquiredFrom => write!(f, "Acquired from"),
+            ImportField::Ignore => write!(f, "Ignore"),
+        }
+    }
+}
+
+/// Header similarity needed before a column is mapped automatically
+const HEADER_SIMILARITY_MIN: f32 = 0.75;
+
+/// Rows of a CSV file as text, nothing is interpreted until the columns are mapped
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Book read from one CSV row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedBook {
+    pub title: String,
+    pub author: Option<String>,
+    pub price: Option<f32>,
+    pub bought: Option<NaiveDateTime>,
+    pub finished: Option<NaiveDateTime>,
+    pub added: Option<NaiveDateTime>,
+    pub acquired_from: Option<String>,
+}
+
+/// Books a mapping produces, with a note for every row left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappedRows {
+    pub books: Vec<ImportedBook>,
+    pub skipped: Vec<String>,
+}
+
+/// What happens to an imported row that matches a book already in the library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportDecision {
+    Skip,
+    Overwrite, // The row's values replace the book's, empty cells keep what the book has
+    AddAsNew,
+}
+
+impl ImportDecision {
+    pub const ALL: [ImportDecision; 3] = [
+        ImportDecision::Skip,
+        ImportDecision::Overwrite,
+        ImportDecision::AddAsNew,
+    ];
+}
+
+impl fmt::Display for ImportDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportDecision::Skip => write!(f, "Skip"),
+            ImportDecision::Overwrite => write!(f, "Overwrite"),
+            ImportDecision::AddAsNew => write!(f, "Add as new"),
+        }
+    }
+}
+
+/// One row of a planned import with the library book it matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedBook {
+    pub book: ImportedBook,
+    pub existing: Option<(ID, String)>, // Id and "Title — Author" of the matching book
+    pub decision: ImportDecision,
+}
+
+impl PlannedBook {
+    /// True when applying the plan inserts the row as a new book
+    pub fn adds(&self) -> bool {
+        !matches!(
+            (self.decision, &self.existing),
+            (ImportDecision::Skip, _) | (ImportDecision::Overwrite, Some(_))
+        )
+    }
+}
+
+/// Dry run of an import, computed before anything is written. Rows matching a book are
+/// skipped until decided otherwise, the rest are added.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportPlan {
+    pub books: Vec<PlannedBook>,
+    pub unknown_authors: Vec<UnknownAuthor>, // Names the library lacks, in file order
+}
+
+impl ImportPlan {
+    /// True once every unknown author has a resolution
+    pub fn is_resolved(&self) -> bool {
+        self.unknown_authors
+            .iter()
+            .all(|author| author.resolution.is_some())
+    }
+
+    /// How the rows naming this author are handled, None for names the library has
+    pub fn resolution_for(&self, name: &str) -> Option<&AuthorResolution> {
+        self.unknown_authors
+            .iter()
+            .find(|author| author.name == name)
+            .and_then(|author| author.resolution.as_ref())
+    }
+
+    /// True when the row is left out, by its decision or because its author is skipped
+    pub fn skips(&self, planned: &PlannedBook) -> bool {
+        planned.decision == ImportDecision::Skip
+            || planned
+                .book
+                .author
+                .as_deref()
+                .and_then(|name| self.resolution_for(name))
+                == Some(&AuthorResolution::Skip)
+    }
+
+    /// Indices of the rows that match a book of the library
+    pub fn duplicates(&self) -> impl Iterator<Item = usize> + '_ {
+        self.books
+            .iter()
+            .enumerate()
+            .filter(|(_, planned)| planned.existing.is_some())
+            .map(|(index, _)| index)
+    }
+
+    pub fn has_duplicates(&self) -> bool {
+        self.duplicates().next().is_some()
+    }
+
+    /// Gives every duplicate row the same decision
+    pub fn decide_all(&mut self, decision: ImportDecision) {
+        for planned in self.books.iter_mut().filter(|p| p.existing.is_some()) {
+            planned.decision = decision;
+        }
+    }
+
+    /// What applying the plan will write, with the decisions taken so far
+    pub fn preview(&self) -> BulkPreview {
+        let mut preview = BulkPreview::default();
+        for planned in &self.books {
+            let label = match &planned.book.author {
+                Some(author) => format!("{} by {}", planned.book.title, author),
+                None => planned.book.title.clone(),
+            };
+            let author_skipped = planned.decision != ImportDecision::Skip && self.skips(planned);
+            match (planned.decision, &planned.existing) {
+                _ if author_skipped => preview.to_skip.push(format!("{} (author skipped)", label)),
+                (ImportDecision::Skip, Some((_, existing))) => preview
+                    .to_skip
+                    .push(format!("{} (already in the library as {})", label, existing)),
+                (ImportDecision::Skip, None) => preview.to_skip.push(label),
+                (ImportDecision::Overwrite, Some((_, existing))) => preview
+                    .to_update
+                    .push(format!("{} (overwritten with {})", existing, label)),
+                _ => preview.to_create.push(format!("Book: {}", label)),
+            }
+        }
+        preview.to_create.extend(
+            self.authors_to_create()
+                .into_iter()
+                .map(|name| format!("Author: {}", name)),
+        );
+        if !self.is_resolved() {
+            preview.warnings.push(
+                "Some authors are close to ones already in the library, choose what to do with \
+                 them"
+                    .to_string(),
+            );
+        }
+        preview
+    }
+
+    /// Authors resolved as new for the rows that end up added, a skipped row creates none
+    pub fn authors_to_create(&self) -> Vec<&str> {
+        self.unknown_authors
+            .iter()
+            .filter(|author| author.resolution == Some(AuthorResolution::CreateNew))
+            .filter(|author| {
+                self.books.iter().any(|planned| {
+                    planned.adds() && planned.book.author.as_ref() == Some(&author.name)
+                })
+            })
+            .map(|author| author.name.as_str())
+            .collect()
+    }
+
+    /// Rows that end up added, overwriting a book and skipped
+    pub fn counts(&self) -> ImportCounts {
+        let mut counts = ImportCounts::default();
+        for planned in &self.books {
+            match (planned.decision, &planned.existing) {
+                _ if self.skips(planned) => counts.skipped += 1,
+                (ImportDecision::Overwrite, Some(_)) => counts.overwritten += 1,
+                _ => counts.added += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// What an import did, or will do, with its rows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportCounts {
+    pub added: usize,
+    pub overwritten: usize,
+    pub skipped: usize,
+}
+
+impl fmt::Display for ImportCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} added", self.added)?;
+        if self.overwritten > 0 {
+            write!(f, ", {} overwritten", self.overwritten)?;
+        }
+        if self.skipped > 0 {
+            write!(f, ", {} skipped", self.skipped)?;
+        }
+        Ok(())
+    }
+}
+
+/// Title and author ignoring case, how imported rows are matched to books of the library
+pub fn book_key(title: &str, author: Option<&str>) -> String {
+    format!(
+        "{}\u{1f}{}",
+        title.trim().to_lowercase(),
+        author.unwrap_or_default().trim().to_lowercase()
+    )
+}
+
+/// Reads the header and rows of a CSV file, rows may have fewer or more cells than the header
+pub fn read_csv(path: &Path) -> Result<CsvTable, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|header| header.trim_start_matches('\u{feff}').to_string())
+        .collect();
+    if headers.iter().all(|header| header.is_empty()) {
+        return Err(ImportError::NoHeader);
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(str::to_string).collect());
+    }
+    Ok(CsvTable { headers, rows })
+}
+
+// Best matching field and its score, None when two fields match equally well
+fn guess_field(header: &str) -> Option<(ImportField, f32)> {
+    let mut scores: Vec<(ImportField, f32)> = ImportField::ALL
+        .iter()
+        .map(|field| {
+            let score = field
+                .header_names()
+                .iter()
+                .map(|name| name_similarity(header, name))
+                .fold(0.0, f32::max);
+            (*field, score)
+        })
+        .filter(|(_, score)| *score >= HEADER_SIMILARITY_MIN)
+        .collect();
+    scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    match scores.as_slice() {
+        [] => None,
+        [(_, best), (_, second), ..] if best == second => None,
+        [best, ..] => Some(*best),
+    }
+}
+
+/// Field for every header going by its name. Each field is given to the column that
+/// matches it best, ambiguous and unknown headers are ignored
+pub fn guess_mapping<T: AsRef<str>>(headers: &[T]) -> Vec<ImportField> {
+    let mut guesses: Vec<(usize, ImportField, f32)> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, header)| {
+            guess_field(header.as_ref()).map(|(field, score)| (index, field, score))
+        })
+        .collect();
+    guesses.sort_by(|(_, _, a), (_, _, b)| b.total_cmp(a));
+
+    let mut mapping = vec![ImportField::Ignore; headers.len()];
+    for (index, field, _) in guesses {
+        if !mapping.contains(&field) {
+            mapping[index] = field;
+        }
+    }
+    mapping
+}
+
+// Presets are keyed by header name, so they apply to files with columns in another order
+fn preset_key(header: &str) -> String {
+    header.trim().to_lowercase()
+}
+
+/// Saved preset as a field per header, headers the preset doesn't know are ignored
+pub fn preset_mapping<T: AsRef<str>>(
+    headers: &[T],
+    preset: &BTreeMap<String, ImportField>,
+) -> Vec<ImportField> {
+    headers
+        .iter()
+        .map(|header| {
+            preset
+                .get(&preset_key(header.as_ref()))
+                .copied()
+                .unwrap_or(ImportField::Ignore)
+        })
+        .collect()
+}
+
+/// Mapping as saved in a preset, ignored columns are left out
+pub fn mapping_preset<T: AsRef<str>>(
+    headers: &[T],
+    mapping: &[ImportField],
+) -> BTreeMap<String, ImportField> {
+    headers
+        .iter()
+        .zip(mapping)
+        .filter(|(_, field)| **field != ImportField::Ignore)
+        .map(|(header, field)| (preset_key(header.as_ref()), *field))
+        .collect()
+}
+
+/// A mapping can be imported once the title has a column and no field has two
+pub fn validate_mapping(mapping: &[ImportField]) -> Result<(), MappingError> {
+    for (index, field) in mapping.iter().enumerate() {
+        if *field != ImportField::Ignore && mapping[..index].contains(field) {
+            return Err(MappingError::DuplicateField(*field));
+        }
+    }
+    if !mapping.contains(&ImportField::Title) {
+        return Err(MappingError::TitleUnmapped);
+    }
+    Ok(())
+}
+
+// Currency symbols and spaces dropped. With both separators present the first one
+// groups thousands, a lone comma is the decimal point
+fn parse_import_price(value: &str) -> Option<f32> {
+    let digits: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-'))
+        .collect();
+    let normalized = match (digits.find('.'), digits.find(',')) {
+        (Some(dot), Some(comma)) if dot < comma => digits.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => digits.replace(',', ""),
+        _ => digits.replace(',', "."),
+    };
+    normalized
+        .parse::<f32>()
+        .ok()
+        .filter(|price| price.is_finite() && *price >= 0.0)
+}
+
+fn parse_import_date(value: &str) -> Option<NaiveDateTime> {
+    const DATE_TIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"];
+    const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"];
+
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Reads one row with the given mapping. Empty cells leave the field unset, values that
+/// can't be read reject the row
+pub fn map_row(row: &[String], mapping: &[ImportField]) -> Result<ImportedBook, String> {
+    let mut book = ImportedBook {
+        title: String::new(),
+        author: None,
+        price: None,
+        bought: None,
+        finished: None,
+        added: None,
+        acquired_from: None,
+    };
+
+    for (value, field) in row.iter().zip(mapping) {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let date = || parse_import_date(value).ok_or(format!("unknown date '{}'", value));
+        match field {
+            ImportField::Title => book.title = value.to_string(),
+            ImportField::Author => book.author = Some(value.to_string()),
+            ImportField::Price => {
+                book.price =
+                    Some(parse_import_price(value).ok_or(format!("invalid price '{}'", value))?)
+            }
+            ImportField::Bought => book.bought = Some(date()?),
+            ImportField::Finished => book.finished = Some(date()?),
+            ImportField::Added => book.added = Some(date()?),
+            ImportField::AcquiredFrom => book.acquired_from = Some(value.to_string()),
+            ImportField::Ignore => {}
+        }
+    }
+
+    if book.title.is_empty() {
+        return Err("no title".to_string());
+    }
+    Ok(book)
+}
+
+/// Books from every row of the table, rows that can't be read are listed by line number
+pub fn apply_mapping(
+    table: &CsvTable,
+    mapping: &[ImportField],
+) -> Result<MappedRows, MappingError> {
+    validate_mapping(mapping)?;
+
+    let mut mapped = MappedRows {
+        books: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for (index, row) in table.rows.iter().enumerate() {
+        match map_row(row, mapping) {
+            Ok(book) => mapped.books.push(book),
+            // Line 1 is the header
+            Err(e) => mapped.skipped.push(format!("Line {}: {}", index + 2, e)),
+        }
+    }
+    Ok(mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn planned(title: &str, existing: Option<ID>, decision: ImportDecision) -> PlannedBook {
+        PlannedBook {
+            book: ImportedBook {
+                title: title.to_string(),
+                author: None,
+                price: None,
+                bought: None,
+                finished: None,
+                added: None,
+                acquired_from: None,
+            },
+            existing: existing.map(|id| (id, title.to_string())),
+            decision,
+        }
+    }
+
+    #[test]
+    fn headers_of_other_apps_are_guessed() {
+        let headers = ["Title", "Author", "My Rating", "Date Read", "Date Added", "Bookshelves"];
+        assert_eq!(
+            guess_mapping(&headers),
+            vec![
+                ImportField::Title,
+                ImportField::Author,
+                ImportField::Ignore,
+                ImportField::Finished,
+                ImportField::Added,
+                ImportField::Ignore,
+            ]
+        );
+        assert_eq!(
+            guess_mapping(&["book title", "WRITER", "Cost", "Purchase date"]),
+            vec![
+                ImportField::Title,
+                ImportField::Author,
+                ImportField::Price,
+                ImportField::Bought,
+            ]
+        );
+    }
+
+    #[test]
+    fn the_store_column_is_guessed_as_acquired_from() {
+        for header in ["Store", "store", "Acquired from", "Bought From", "Shop"] {
+            assert_eq!(
+                guess_mapping(&["Title", header]),
+                vec![ImportField::Title, ImportField::AcquiredFrom],
+                "{}",
+                header
+            );
+        }
+    }
+
+    #[test]
+    fn a_field_is_guessed_for_one_column_only() {
+        // Both match the title equally well, the first keeps it
+        assert_eq!(
+            guess_mapping(&["Title", "Book"]),
+            vec![ImportField::Title, ImportField::Ignore]
+        );
+        // A closer match wins over an earlier column
+        assert_eq!(
+            guess_mapping(&["Writers", "Author"]),
+            vec![ImportField::Ignore, ImportField::Author]
+        );
+    }
+
+    #[test]
+    fn a_mapping_needs_a_title_and_no_field_twice() {
+        use ImportField::*;
+        assert_eq!(validate_mapping(&[Title, Author, Ignore, Ignore]), Ok(()));
+        assert_eq!(
+            validate_mapping(&[Author, Price, Ignore]),
+            Err(MappingError::TitleUnmapped)
+        );
+        assert_eq!(
+            validate_mapping(&[Title, Author, Author]),
+            Err(MappingError::DuplicateField(Author))
+        );
+        let table = CsvTable {
+            headers: cells(&["Author"]),
+            rows: vec![cells(&["Stanisław Lem"])],
+        };
+        assert_eq!(
+            apply_mapping(&table, &[Author]),
+            Err(MappingError::TitleUnmapped)
+        );
+    }
+
+    #[test]
+    fn rows_are_read_with_the_mapping_and_bad_ones_listed_by_line() {
+        use ImportField::*;
+        let mapping = [Title, Author, Price, Bought, AcquiredFrom, Ignore];
+        let table = CsvTable {
+            headers: cells(&["Title", "Author", "Price", "Bought", "Store", "Notes"]),
+            rows: vec![
+                cells(&["Solaris", "Stanisław Lem", "1.234,50 zł", "2024-05-14", "Empik", "x"]),
+                cells(&["", "Nobody", "", "", "", ""]),
+                cells(&["Dune", "", "12.5", "14.05.2024", "", ""]),
+                cells(&["Ubik", "", "free", "", "", ""]),
+                cells(&["Emma", "", "", "last week", "", ""]),
+                cells(&["Mort"]),
+            ],
+        };
+
+        let mapped = apply_mapping(&table, &mapping).unwrap();
+        let bought = NaiveDate::from_ymd_opt(2024, 5, 14)
+            .unwrap()
+            .and_hms_opt(0, 0, 0);
+        assert_eq!(
+            mapped.books[0],
+            ImportedBook {
+                title: "Solaris".to_string(),
+                author: Some("Stanisław Lem".to_string()),
+                price: Some(1234.5),
+                bought,
+                finished: None,
+                added: None,
+                acquired_from: Some("Empik".to_string()),
+            }
+        );
+        assert_eq!(mapped.books[1].author, None);
+        assert_eq!(mapped.books[1].price, Some(12.5));
+        assert_eq!(mapped.books[1].bought, bought);
+        assert_eq!(mapped.books[2].title, "Mort");
+        assert_eq!(
+            mapped.skipped,
+            vec![
+                "Line 3: no title",
+                "Line 5: invalid price 'free'",
+                "Line 6: unknown date 'last week'",
+            ]
+        );
+    }
+
+    #[test]
+    fn a_preset_maps_columns_by_header_in_any_order() {
+        use ImportField::*;
+        let preset = mapping_preset(&["Title", "Store", "Notes"], &[Title, AcquiredFrom, Ignore]);
+        assert_eq!(preset.len(), 2);
+        assert_eq!(
+            preset_mapping(&["notes", " STORE ", "title", "Year"], &preset),
+            vec![Ignore, AcquiredFrom, Title, Ignore]
+        );
+    }
+
+    #[test]
+    fn duplicate_decisions_decide_what_each_row_does() {
+        let mut plan = ImportPlan {
+            books: vec![
+                planned("Solaris", Some(1), ImportDecision::Skip),
+                planned("Dune", Some(2), ImportDecision::Skip),
+                planned("Ubik", None, ImportDecision::AddAsNew),
+            ],
+            unknown_authors: Vec::new(),
+        };
+        assert_eq!(plan.duplicates().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(
+            plan.counts(),
+            ImportCounts {
+                added: 1,
+                overwritten: 0,
+                skipped: 2,
+            }
+        );
+
+        plan.decide_all(ImportDecision::Overwrite);
+        plan.books[1].decision = ImportDecision::AddAsNew;
+        assert_eq!(plan.books[2].decision, ImportDecision::AddAsNew);
+        assert_eq!(
+            plan.counts(),
+            ImportCounts {
+                added: 2,
+                overwritten: 1,
+                skipped: 0,
+            }
+        );
+        assert_eq!(plan.counts().to_string(), "2 added, 1 overwritten");
+        let preview = plan.preview();
+        assert_eq!(preview.to_create, vec!["Book: Dune", "Book: Ubik"]);
+        assert_eq!(
+            preview.to_update,
+            vec!["Solaris (overwritten with Solaris)"]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+// src/instance_lock.rs
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("the library is already open in another Bookshelf window (process {0})")]
+    Held(u32),
+
+    #[error("cannot create the lock file {0}: {1}")]
+    Io(PathBuf, io::Error),
+}
+
+/// Lock file kept next to the database while it is open, e.g. `library.db.lock`.
+/// None for databases that are not plain files, like `:memory:` and `file:` URIs.
+pub fn lock_path(database: &Path) -> Option<PathBuf> {
+    let name = database.file_name()?.to_string_lossy();
+    if name == ":memory:" || database.to_string_lossy().starts_with("file:") {
+        return None;
+    }
+    Some(database.with_file_name(format!("{}.lock", name)))
+}
+
+/// Proof that this process holds the lock, the file is removed when it is released
+/// or dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl InstanceLock {
+    pub fn release(self) {
+        // Dropping does the work
+    }
+}
+
+impl Drop for InstanceLock {
+    // Only our own lock is removed, another instance may have replaced a stale one
+    fn drop(&mut self) {
+        if read_pid(&self.path) == Some(self.pid) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Takes the lock for this process. A lock left behind by a process that is no longer
+/// running is replaced.
+pub fn acquire(path: &Path) -> Result<InstanceLock, LockError> {
+    acquire_as(path, std::process::id(), process_alive)
+}
+
+// The pid and the liveness check are passed in so other processes can be simulated
+fn acquire_as(
+    path: &Path,
+    pid: u32,
+    is_alive: impl Fn(u32) -> bool,
+) -> Result<InstanceLock, LockError> {
+    // Second attempt after removing a stale lock, losing that race to another instance
+    // means it now holds the lock
+    for _ in 0..2 {
+        match create_with_pid(path, pid) {
+            Ok(()) => {
+                return Ok(InstanceLock {
+                    path: path.to_path_buf(),
+                    pid,
+                });
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => match read_pid(path) {
+                Some(owner) if owner != pid && is_alive(owner) => {
+                    return Err(LockError::Held(owner));
+                }
+                // Left by a crash or a pid reused by this process
+                Some(_) => {
+                    let _ = fs::remove_file(path);
+                }
+                // Released in the meantime, the next attempt may take it
+                None if !path.exists() => {}
+                // Never taken for stale, whoever wrote it may still be running
+                None => return Err(unreadable(path)),
+            },
+            Err(e) => return Err(LockError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    match read_pid(path) {
+        Some(owner) => Err(LockError::Held(owner)),
+        None => Err(unreadable(path)),
+    }
+}
+
+// The pid is written to a file of its own and linked into place, so the lock file never
+// exists without it. Fails with `AlreadyExists` while another lock is there.
+fn create_with_pid(path: &Path, pid: u32) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!("{}.{}.tmp", name, pid));
+    fs::write(&temp, pid.to_string())?;
+    let linked = fs::hard_link(&temp, path);
+    let _ = fs::remove_file(&temp);
+    linked
+}
+
+fn unreadable(path: &Path) -> LockError {
+    LockError::Io(
+        path.to_path_buf(),
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the lock file holds no process id",
+        ),
+    )
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> bool {
+    // `kill -0` only checks that the process exists, nothing is sent
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    let filter = format!("PID eq {}", pid);
+    match std::process::Command::new("tasklist")
+        .args(["/FI", &filter, "/NH", "/FO", "CSV"])
+        .output()
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid)),
+        Err(_) => true,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_alive(_pid: u32) -> bool {
+    true // Without a way to check, a lock is never taken for stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const OURS: u32 = 1000;
+    const OTHER: u32 = 2000;
+
+    fn lock_in(name: &str) -> PathBuf {
+        let folder = env::temp_dir().join("bookshelf-tests").join(name);
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+        folder.join("library.db.lock")
+    }
+
+    fn files_in(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn a_lock_holds_the_pid_until_it_is_released() {
+        let path = lock_in("lock-release");
+        let lock = acquire_as(&path, OURS, |_| true).unwrap();
+        assert_eq!(read_pid(&path), Some(OURS));
+        // The temporary pid file is gone once linked
+        assert_eq!(files_in(&path), vec!["library.db.lock"]);
+
+        lock.release();
+        assert!(!path.exists());
+        assert!(acquire_as(&path, OURS, |_| true).is_ok());
+    }
+
+    #[test]
+    fn a_lock_of_a_running_process_is_held() {
+        let path = lock_in("lock-held");
+        let _other = acquire_as(&path, OTHER, |_| true).unwrap();
+
+        let result = acquire_as(&path, OURS, |pid| pid == OTHER);
+        assert!(matches!(result, Err(LockError::Held(OTHER))));
+        assert_eq!(read_pid(&path), Some(OTHER));
+    }
+
+    #[test]
+    fn a_lock_of_a_process_gone_is_replaced() {
+        let path = lock_in("lock-stale");
+        fs::write(&path, OTHER.to_string()).unwrap();
+
+        let lock = acquire_as(&path, OURS, |pid| pid == OURS).unwrap();
+        assert_eq!(read_pid(&path), Some(OURS));
+
+        // The process that left it doesn't remove the lock now held by another
+        drop(InstanceLock {
+            path: path.clone(),
+            pid: OTHER,
+        });
+        assert_eq!(read_pid(&path), Some(OURS));
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn an_empty_or_unreadable_lock_is_never_taken_for_stale() {
+        let path = lock_in("lock-empty");
+        for contents in ["", "not a pid"] {
+            fs::write(&path, contents).unwrap();
+            let result = acquire_as(&path, OURS, |_| false);
+            assert!(matches!(result, Err(LockError::Io(ref file, _)) if *file == path));
+            assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+        }
+        assert_eq!(files_in(&path), vec!["library.db.lock"]);
+    }
+}
@@ -0,0 +1,74 @@
+// src/language.rs
+use std::fmt;
+
+/// Languages offered in the book form before any book has one, as ISO 639-1 codes
+const KNOWN_LANGUAGES: [(&str, &str); 16] = [
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("nl", "Dutch"),
+    ("no", "Norwegian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("uk", "Ukrainian"),
+];
+
+/// Stored form of a language code, None for a blank one
+pub fn normalize_code(code: &str) -> Option<String> {
+    let code = code.trim().to_lowercase();
+    (!code.is_empty()).then_some(code)
+}
+
+/// Display name of a language code, codes that aren't known are shown as they are
+pub fn language_name(code: &str) -> String {
+    KNOWN_LANGUAGES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code.trim()))
+        .map_or_else(|| code.trim().to_string(), |(_, name)| name.to_string())
+}
+
+/// Language picked in the book form or a filter, holding the stored code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Language(pub String);
+
+impl Language {
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = language_name(&self.0);
+        if name == self.0 {
+            write!(f, "{}", name)
+        } else {
+            write!(f, "{} ({})", name, self.0)
+        }
+    }
+}
+
+/// Built-in languages plus any other codes already in use, ordered by display name
+pub fn language_choices<'a>(used: impl IntoIterator<Item = &'a str>) -> Vec<Language> {
+    let mut codes: Vec<String> = KNOWN_LANGUAGES
+        .iter()
+        .map(|(code, _)| code.to_string())
+        .collect();
+    for code in used.into_iter().filter_map(normalize_code) {
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+
+    let mut choices: Vec<Language> = codes.into_iter().map(Language).collect();
+    choices.sort_by_cached_key(|language| language_name(language.code()).to_lowercase());
+    choices
+}
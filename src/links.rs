@@ -0,0 +1,39 @@
+// src/links.rs
+
+/// True for absolute http(s) URLs, the only kind the app will open
+pub fn is_web_url(url: &str) -> bool {
+    let url = url.trim();
+    ["http://", "https://"]
+        .iter()
+        .any(|scheme| url.len() > scheme.len() && url[..scheme.len()].eq_ignore_ascii_case(scheme))
+}
+
+// Percent-encodes everything except unreserved characters, spaces become '+'
+fn encode_query(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.trim().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+pub fn wikipedia_search_url(query: &str) -> String {
+    format!(
+        "https://en.wikipedia.org/w/index.php?search={}",
+        encode_query(query)
+    )
+}
+
+/// Opens the URL in the default browser without blocking the UI
+pub fn open_in_browser(url: &str) -> Result<(), String> {
+    if !is_web_url(url) {
+        return Err(format!("Not a web address: {}", url));
+    }
+    open::that_detached(url.trim()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,34 @@
+// src/loans.rs
+use crate::models::{LoanModel, ID};
+use chrono::NaiveDateTime;
+
+/// Whole days a loan is past its due date, `None` while it is not overdue.
+/// Compared by calendar day, so a book due today is not overdue until tomorrow.
+pub fn overdue_days(due_on: Option<NaiveDateTime>, now: NaiveDateTime) -> Option<i64> {
+    let days = (now.date() - due_on?.date()).num_days();
+    (days > 0).then_some(days)
+}
+
+/// Calendar days between lending and return, or until `now` for an active loan
+pub fn loan_duration_days(loan: &LoanModel, now: NaiveDateTime) -> i64 {
+    let end = loan.returned_on.unwrap_or(now);
+    (end.date() - loan.loaned_on.date()).num_days().max(0)
+}
+
+pub fn is_active(loan: &LoanModel) -> bool {
+    loan.returned_on.is_none()
+}
+
+/// The open loan of a book, if it is currently lent out
+pub fn active_loan_for(loans: &[LoanModel], book_id: ID) -> Option<&LoanModel> {
+    loans
+        .iter()
+        .find(|loan| loan.book_fk == book_id && is_active(loan))
+}
+
+pub fn count_overdue(loans: &[LoanModel], now: NaiveDateTime) -> usize {
+    loans
+        .iter()
+        .filter(|loan| is_active(loan) && overdue_days(loan.due_on, now).is_some())
+        .count()
+}
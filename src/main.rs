@@ -1,20 +1,87 @@
+mod backup;
+mod bulk;
+mod cli;
+mod config;
+mod console;
+mod covers;
 mod db;
+mod draft;
+mod error_log;
+mod export;
+mod fuzzy;
+mod import;
+mod instance_lock;
+mod language;
+mod links;
+mod loans;
 mod models;
+mod notifications;
+mod paths;
+mod quotes;
+mod report;
 mod schema;
+mod sessions;
+mod stats;
+mod trash;
 mod ui;
 
-use crate::ui::{BookshelfApp, Message};
-use iced::window::icon::from_file_data;
+use crate::cli::{CliCommand, USAGE};
+use crate::ui::{BookshelfApp, Message, WINDOW_HEIGHT, WINDOW_WIDTH};
+use iced::window::icon::{self, from_file_data};
 use iced::{window, Size};
 
+// Used when no custom icon is present or it cannot be read
+const DEFAULT_ICON: &[u8] = include_bytes!("assets/icon.png");
+
+/// Prefers an icon.png dropped into the app data dir, falling back to the built-in icon.
+/// Failures are logged, a missing icon never stops the app from starting.
+fn load_window_icon() -> Option<window::Icon> {
+    let custom = paths::icon_file();
+    if custom.exists() {
+        match icon::from_file(&custom) {
+            Ok(icon) => return Some(icon),
+            Err(e) => error_log::error(&format!(
+                "Failed to load window icon {}: {}",
+                custom.display(),
+                e
+            )),
+        }
+    }
+
+    match from_file_data(DEFAULT_ICON, None) {
+        Ok(icon) => Some(icon),
+        Err(e) => {
+            error_log::error(&format!("Failed to load the built-in window icon: {}", e));
+            None
+        }
+    }
+}
+
 fn main() -> iced::Result {
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(CliCommand::Run(cli)) => cli,
+        Ok(CliCommand::Help) => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Ok(CliCommand::Version) => {
+            println!("bookshelf {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Err(e) => {
+            eprintln!("bookshelf: {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
     dotenv::dotenv().ok();
+    error_log::install_panic_hook();
 
-    let icon = from_file_data(include_bytes!("assets/icon.png"), None).ok();
+    let icon = load_window_icon();
 
     // Create window settings
     let window_settings = window::Settings {
-        size: Size::new(1024.0, 768.0), // Note: now uses Size struct
+        size: Size::new(WINDOW_WIDTH, WINDOW_HEIGHT), // Note: now uses Size struct
         position: window::Position::Centered,
         min_size: Some(Size::new(800.0, 600.0)), // Note: uses Size struct
         max_size: None,
@@ -30,12 +97,16 @@ fn main() -> iced::Result {
 
     // New application initialization approach
     iced::application("Bookshelf App", BookshelfApp::update, BookshelfApp::view)
+        .subscription(BookshelfApp::subscription)
+        .theme(BookshelfApp::theme)
         .window(window_settings)
         .antialiasing(true)
-        .exit_on_close_request(true)
-        .run_with(|| {
+        // Closing goes through the app, which asks about unsaved work and releases the
+        // database lock first
+        .exit_on_close_request(false)
+        .run_with(move || {
             (
-                BookshelfApp::new(), // Initialize your app state
+                BookshelfApp::with_database_url(cli.database_url), // Initialize your app state
                 iced::Task::perform(async {}, |_| Message::Initialize),
             )
         })
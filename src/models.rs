@@ -1,8 +1,12 @@
 // src/models.rs
-use crate::schema::{Author, Books};
+use crate::schema::{
+    Author, BookFields, BookJournal, BookListItems, BookLists, Books, Loans, Quotes,
+    ReadingSessions, Series,
+};
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 pub type ID = i32;
 
@@ -12,6 +16,39 @@ pub type ID = i32;
 pub struct AuthorModel {
     pub Id: ID,
     pub Name: Option<String>,
+    pub website: Option<String>,
+    pub wikipedia: Option<String>,
+    #[serde(default)]
+    pub published_works: Option<i32>, // Declared size of the author's body of work
+    #[serde(default)]
+    pub Aliases: Option<String>, // Pen names, comma separated
+}
+
+impl AuthorModel {
+    /// Pen names the author also publishes under
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        split_aliases(self.Aliases.as_deref().unwrap_or_default())
+    }
+}
+
+/// Aliases typed as a comma separated list, blanks skipped
+pub fn split_aliases(aliases: &str) -> impl Iterator<Item = &str> {
+    aliases
+        .split(',')
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+}
+
+/// Aliases as stored in the Aliases column, repeats (ignoring case) are dropped and
+/// None is returned when nothing is left
+pub fn join_aliases<'a>(aliases: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut kept: Vec<&str> = Vec::new();
+    for alias in aliases {
+        if !kept.iter().any(|seen| seen.to_lowercase() == alias.to_lowercase()) {
+            kept.push(alias);
+        }
+    }
+    (!kept.is_empty()).then(|| kept.join(", "))
 }
 
 impl Eq for AuthorModel {}
@@ -23,20 +60,137 @@ impl PartialEq for AuthorModel {
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = Author)]
+#[diesel(treat_none_as_null = true)]
 pub struct NewAuthor {
     pub Name: Option<String>,
+    pub website: Option<String>,
+    pub wikipedia: Option<String>,
+    pub published_works: Option<i32>,
+    pub Aliases: Option<String>,
+}
+
+impl From<&AuthorModel> for NewAuthor {
+    fn from(author: &AuthorModel) -> Self {
+        NewAuthor {
+            Name: author.Name.clone(),
+            website: author.website.clone(),
+            wikipedia: author.wikipedia.clone(),
+            published_works: author.published_works,
+            Aliases: author.Aliases.clone(),
+        }
+    }
+}
+
+/// Physical or digital form of a book, stored by name in the format column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookFormat {
+    Paperback,
+    Hardcover,
+    Ebook,
+    Audiobook,
+}
+
+impl BookFormat {
+    pub const ALL: [BookFormat; 4] = [
+        BookFormat::Paperback,
+        BookFormat::Hardcover,
+        BookFormat::Ebook,
+        BookFormat::Audiobook,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BookFormat::Paperback => "Paperback",
+            BookFormat::Hardcover => "Hardcover",
+            BookFormat::Ebook => "Ebook",
+            BookFormat::Audiobook => "Audiobook",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BookFormat> {
+        BookFormat::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for BookFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
 }
 
 #[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
 #[diesel(table_name = Books)]
 pub struct BookModel {
-    pub id: ID,
+    #[serde(default)]
+    pub id: ID, // 0 for books read from a backup written without ids
     pub title: String,
     pub price: Option<f32>,
     pub bought: Option<NaiveDateTime>,
     pub finished: Option<NaiveDateTime>,
     pub added: Option<NaiveDateTime>,
     pub AuthorFK: Option<ID>,
+    pub SeriesFK: Option<ID>,
+    pub gift_from: Option<String>, // Who gave the book, None unless it was a gift
+    pub quantity: i32, // Copies owned, at least 1
+    pub cover_path: Option<String>, // Image in the covers directory
+    pub genre: Option<String>, // Free text, colors the book in the list
+    pub page_count: Option<i32>,
+    pub pinned: bool, // Kept above the sorted list
+    pub format: Option<String>, // Name of a BookFormat
+    pub duration_minutes: Option<i32>, // Listening time, only kept for audiobooks
+    pub language: Option<String>, // Lowercase ISO 639-1 code like "pl"
+    pub acquired_from: Option<String>, // Shop or person the book came from, free text
+    pub rating: Option<i32>, // 1 to RATING_MAX stars
+}
+
+impl BookModel {
+    pub fn is_gift(&self) -> bool {
+        self.gift_from.is_some()
+    }
+
+    /// Price of all owned copies, None when the book has no price
+    pub fn total_value(&self) -> Option<f32> {
+        self.price.map(|price| price * self.quantity as f32)
+    }
+
+    /// None when no format is set or the stored name is not known
+    pub fn book_format(&self) -> Option<BookFormat> {
+        self.format.as_deref().and_then(BookFormat::from_name)
+    }
+
+    pub fn is_audiobook(&self) -> bool {
+        self.book_format() == Some(BookFormat::Audiobook)
+    }
+}
+
+#[cfg(test)]
+impl BookModel {
+    /// A book with only a title set, tests fill in the fields they look at
+    pub fn titled(id: ID, title: &str) -> Self {
+        BookModel {
+            id,
+            title: title.to_string(),
+            price: None,
+            bought: None,
+            finished: None,
+            added: None,
+            AuthorFK: None,
+            SeriesFK: None,
+            gift_from: None,
+            quantity: 1,
+            cover_path: None,
+            genre: None,
+            page_count: None,
+            pinned: false,
+            format: None,
+            duration_minutes: None,
+            language: None,
+            acquired_from: None,
+            rating: None,
+        }
+    }
 }
 
 impl Eq for BookModel {}
@@ -48,6 +202,7 @@ impl PartialEq for BookModel {
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = Books)]
+#[diesel(treat_none_as_null = true)]
 pub struct NewBook {
     pub title: String,
     pub price: Option<f32>,
@@ -55,14 +210,125 @@ pub struct NewBook {
     pub finished: Option<NaiveDateTime>,
     pub added: Option<NaiveDateTime>,
     pub AuthorFK: Option<ID>,
+    pub SeriesFK: Option<ID>,
+    pub gift_from: Option<String>,
+    pub quantity: i32,
+    pub cover_path: Option<String>,
+    pub genre: Option<String>,
+    pub page_count: Option<i32>,
+    pub pinned: bool,
+    pub format: Option<String>,
+    pub duration_minutes: Option<i32>,
+    pub language: Option<String>,
+    pub acquired_from: Option<String>,
+    pub rating: Option<i32>,
+}
+
+impl From<&BookModel> for NewBook {
+    fn from(book: &BookModel) -> Self {
+        NewBook {
+            title: book.title.clone(),
+            price: book.price,
+            bought: book.bought,
+            finished: book.finished,
+            added: book.added,
+            AuthorFK: book.AuthorFK,
+            SeriesFK: book.SeriesFK,
+            gift_from: book.gift_from.clone(),
+            quantity: book.quantity,
+            cover_path: book.cover_path.clone(),
+            genre: book.genre.clone(),
+            page_count: book.page_count,
+            pinned: book.pinned,
+            format: book.format.clone(),
+            duration_minutes: book.duration_minutes,
+            language: book.language.clone(),
+            acquired_from: book.acquired_from.clone(),
+            rating: book.rating,
+        }
+    }
+}
+
+impl NewBook {
+    /// Fills an empty bought date with the finished date when the book has just been finished.
+    /// Only a change of the finished date counts, so clearing bought on a finished book sticks.
+    pub fn assume_bought_when_finished(&mut self, previously_finished: Option<NaiveDateTime>) {
+        if self.bought.is_none() && self.finished.is_some() && self.finished != previously_finished
+        {
+            self.bought = self.finished;
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BookWithAuthor {
     pub book: BookModel,
     pub author: Option<AuthorModel>,
 }
 
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = Series)]
+pub struct SeriesModel {
+    pub id: ID,
+    pub name: String,
+    pub total_volumes: Option<i32>,
+}
+
+impl Eq for SeriesModel {}
+impl PartialEq for SeriesModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = Series)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewSeries {
+    pub name: String,
+    pub total_volumes: Option<i32>,
+}
+
+impl std::fmt::Display for SeriesModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Named, ordered list of books, e.g. a book club's reading order
+#[derive(
+    Debug, Clone, PartialEq, Eq, Queryable, Selectable, Identifiable, Serialize, Deserialize,
+)]
+#[diesel(table_name = BookLists)]
+pub struct BookListModel {
+    pub id: ID,
+    pub name: String,
+}
+
+impl std::fmt::Display for BookListModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Queryable, Selectable, Insertable, Serialize, Deserialize,
+)]
+#[diesel(table_name = BookListItems)]
+pub struct BookListItemModel {
+    pub list_fk: ID,
+    pub book_fk: ID,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = BookListItems)]
+pub struct NewBookListItem {
+    pub list_fk: ID,
+    pub book_fk: ID,
+    pub position: i32,
+}
+
 // Implement Display for AuthorModel for use in the pick_list
 impl std::fmt::Display for AuthorModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -75,3 +341,127 @@ impl std::fmt::Display for AuthorModel {
         )
     }
 }
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = BookJournal)]
+pub struct JournalEntryModel {
+    pub id: ID,
+    pub book_fk: ID,
+    pub written_at: NaiveDateTime,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = BookJournal)]
+pub struct NewJournalEntry {
+    pub book_fk: ID,
+    pub written_at: NaiveDateTime,
+    pub body: String,
+}
+
+/// A custom key/value pair of a book, e.g. "Signed" = "yes"
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = BookFields)]
+#[diesel(primary_key(Id))]
+pub struct BookFieldModel {
+    pub Id: ID,
+    pub BookFK: ID,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = BookFields)]
+pub struct NewBookField {
+    pub BookFK: ID,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = Quotes)]
+pub struct QuoteModel {
+    pub id: ID,
+    pub book_fk: ID,
+    pub page: Option<i32>,
+    pub text: String, // May span several lines
+    pub noted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = Quotes)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewQuote {
+    pub book_fk: ID,
+    pub page: Option<i32>,
+    pub text: String,
+    pub noted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = Loans)]
+pub struct LoanModel {
+    pub id: ID,
+    pub book_fk: ID,
+    pub borrower: String,
+    pub loaned_on: NaiveDateTime,
+    pub due_on: Option<NaiveDateTime>,
+    pub returned_on: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = Loans)]
+pub struct NewLoan {
+    pub book_fk: ID,
+    pub borrower: String,
+    pub loaned_on: NaiveDateTime,
+    pub due_on: Option<NaiveDateTime>,
+    pub returned_on: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = ReadingSessions)]
+pub struct ReadingSessionModel {
+    pub id: ID,
+    pub book_fk: ID,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>, // None while the timer runs
+    pub pages: Option<i32>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = ReadingSessions)]
+pub struct NewReadingSession {
+    pub book_fk: ID,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+    pub pages: Option<i32>,
+}
+
+/// One calendar year taken from a book date
+#[derive(Debug, Clone, QueryableByName)]
+pub struct YearRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub year: i32,
+}
+
+/// One author with totals over their books, as exported to the authors CSV
+#[derive(Debug, Clone, QueryableByName)]
+pub struct AuthorExportRow {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub name: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub books: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub finished: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub owned: i64, // Books with a bought date
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub published_works: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub spent: f64, // Price times copies, books without a price count as nothing
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
+    pub first_bought: Option<NaiveDateTime>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
+    pub last_bought: Option<NaiveDateTime>,
+}
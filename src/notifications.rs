@@ -0,0 +1,17 @@
+// src/notifications.rs
+use crate::error_log;
+
+/// Shows a desktop notification without waiting for it, failures only end up in the log
+pub fn send(summary: &str, body: &str) {
+    let (summary, body) = (summary.to_string(), body.to_string());
+    std::thread::spawn(move || {
+        let shown = notify_rust::Notification::new()
+            .appname("Bookshelf")
+            .summary(&summary)
+            .body(&body)
+            .show();
+        if let Err(e) = shown {
+            error_log::error(&format!("Failed to show a notification: {}", e));
+        }
+    });
+}
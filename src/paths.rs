@@ -0,0 +1,45 @@
+// src/paths.rs
+use std::path::PathBuf;
+
+// Tests get a folder of their own, so they never touch the real settings or draft
+fn user_dir(system_dir: Option<PathBuf>) -> PathBuf {
+    if cfg!(test) {
+        return std::env::temp_dir().join("bookshelf-tests");
+    }
+    system_dir.unwrap_or_else(|| PathBuf::from(".")).join("bookshelf")
+}
+
+/// Directory for files the app writes on its own (exports, settings, logs)
+pub fn app_data_dir() -> PathBuf {
+    user_dir(dirs::data_dir())
+}
+
+pub fn exports_dir() -> PathBuf {
+    app_data_dir().join("exports")
+}
+
+pub fn covers_dir() -> PathBuf {
+    app_data_dir().join("covers")
+}
+
+/// Optional custom window icon, the built-in one is used when absent
+pub fn icon_file() -> PathBuf {
+    app_data_dir().join("icon.png")
+}
+
+pub fn log_file() -> PathBuf {
+    app_data_dir().join("logs").join("bookshelf.log")
+}
+
+pub fn config_dir() -> PathBuf {
+    user_dir(dirs::config_dir())
+}
+
+pub fn settings_file() -> PathBuf {
+    config_dir().join("settings.json")
+}
+
+/// Autosaved book form, present only while a draft is unsaved
+pub fn draft_file() -> PathBuf {
+    config_dir().join("draft.json")
+}
@@ -0,0 +1,42 @@
+// src/quotes.rs
+use crate::models::QuoteModel;
+
+/// Citation put on the clipboard, e.g. `"Fear is the mind-killer." — Frank Herbert, Dune,
+/// p. 8`. The author and the page are left out when they are not known.
+pub fn format_citation(quote: &QuoteModel, title: &str, author: Option<&str>) -> String {
+    let mut source = Vec::new();
+    if let Some(author) = author.map(str::trim).filter(|author| !author.is_empty()) {
+        source.push(author.to_string());
+    }
+    if !title.trim().is_empty() {
+        source.push(title.trim().to_string());
+    }
+    if let Some(page) = quote.page {
+        source.push(format!("p. {}", page));
+    }
+
+    let text = format!("\"{}\"", quote.text.trim());
+    if source.is_empty() {
+        text
+    } else {
+        format!("{} — {}", text, source.join(", "))
+    }
+}
+
+/// Case-insensitive search over the quote text, an empty query matches everything
+pub fn matches_search(quote: &QuoteModel, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty() || quote.text.to_lowercase().contains(&query)
+}
+
+/// Page typed in the quote form, empty means no page
+pub fn parse_page(value: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<i32>() {
+        Ok(page) if page > 0 => Ok(Some(page)),
+        _ => Err(format!("'{}' is not a page number", value)),
+    }
+}
@@ -0,0 +1,941 @@
+// src/report.rs
+use crate::models::{AuthorExportRow, AuthorModel, BookWithAuthor};
+use crate::stats;
+use chrono::{Datelike, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Report file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Report format error: {0}")]
+    Format(#[from] serde_json::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportFormat::Json => write!(f, "JSON"),
+            ReportFormat::Csv => write!(f, "CSV"),
+        }
+    }
+}
+
+/// How dates are written to CSV exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CsvDateFormat {
+    #[default]
+    Iso, // 2024-03-05, read the same way by spreadsheets in every locale
+    DayMonthYear, // 05.03.2024
+    MonthDayYear, // 03/05/2024
+}
+
+impl CsvDateFormat {
+    pub const ALL: [CsvDateFormat; 3] = [
+        CsvDateFormat::Iso,
+        CsvDateFormat::DayMonthYear,
+        CsvDateFormat::MonthDayYear,
+    ];
+
+    fn pattern(self, with_time: bool) -> &'static str {
+        match (self, with_time) {
+            (CsvDateFormat::Iso, false) => "%Y-%m-%d",
+            (CsvDateFormat::Iso, true) => "%Y-%m-%dT%H:%M:%S",
+            (CsvDateFormat::DayMonthYear, false) => "%d.%m.%Y",
+            (CsvDateFormat::DayMonthYear, true) => "%d.%m.%Y %H:%M:%S",
+            (CsvDateFormat::MonthDayYear, false) => "%m/%d/%Y",
+            (CsvDateFormat::MonthDayYear, true) => "%m/%d/%Y %H:%M:%S",
+        }
+    }
+}
+
+impl fmt::Display for CsvDateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvDateFormat::Iso => write!(f, "ISO 8601 (2024-03-05)"),
+            CsvDateFormat::DayMonthYear => write!(f, "Day first (05.03.2024)"),
+            CsvDateFormat::MonthDayYear => write!(f, "Month first (03/05/2024)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DecimalSeparator {
+    #[default]
+    Point,
+    Comma,
+}
+
+impl DecimalSeparator {
+    pub const ALL: [DecimalSeparator; 2] = [DecimalSeparator::Point, DecimalSeparator::Comma];
+}
+
+impl fmt::Display for DecimalSeparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalSeparator::Point => write!(f, "Point (12.50)"),
+            DecimalSeparator::Comma => write!(f, "Comma (12,50)"),
+        }
+    }
+}
+
+/// Which CSV fields are put in quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CsvQuoting {
+    #[default]
+    Necessary, // Fields holding a separator, a quote or a line break
+    Always,
+    NonNumeric, // Every field that does not read as a number
+}
+
+impl CsvQuoting {
+    pub const ALL: [CsvQuoting; 3] = [
+        CsvQuoting::Necessary,
+        CsvQuoting::Always,
+        CsvQuoting::NonNumeric,
+    ];
+
+    fn style(self) -> csv::QuoteStyle {
+        match self {
+            CsvQuoting::Necessary => csv::QuoteStyle::Necessary,
+            CsvQuoting::Always => csv::QuoteStyle::Always,
+            CsvQuoting::NonNumeric => csv::QuoteStyle::NonNumeric,
+        }
+    }
+}
+
+impl fmt::Display for CsvQuoting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvQuoting::Necessary => write!(f, "Quote only when needed"),
+            CsvQuoting::Always => write!(f, "Quote every field"),
+            CsvQuoting::NonNumeric => write!(f, "Quote all but numbers"),
+        }
+    }
+}
+
+/// How CSV exports write dates, amounts and quotes, kept in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportProfile {
+    pub date_format: CsvDateFormat,
+    pub decimal_separator: DecimalSeparator,
+    pub byte_order_mark: bool, // Lets Excel recognise the file as UTF-8
+    pub quoting: CsvQuoting,
+}
+
+impl ExportProfile {
+    /// Fields are separated by semicolons with comma decimals, as Excel expects in the
+    /// locales that use them
+    pub fn delimiter(&self) -> u8 {
+        match self.decimal_separator {
+            DecimalSeparator::Point => b',',
+            DecimalSeparator::Comma => b';',
+        }
+    }
+}
+
+/// A CSV field with its type, the export profile decides how it is written
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvCell {
+    Text(String),
+    Integer(i64),
+    Money(f64), // Always two decimals
+    Date(Option<NaiveDateTime>),
+    Timestamp(NaiveDateTime),
+}
+
+impl CsvCell {
+    pub fn format(&self, profile: &ExportProfile) -> String {
+        match self {
+            CsvCell::Text(text) => text.clone(),
+            CsvCell::Integer(value) => value.to_string(),
+            CsvCell::Money(amount) => {
+                let amount = format!("{:.2}", amount);
+                match profile.decimal_separator {
+                    DecimalSeparator::Point => amount,
+                    DecimalSeparator::Comma => amount.replace('.', ","),
+                }
+            }
+            CsvCell::Date(date) => date.map_or_else(String::new, |date| {
+                date.format(profile.date_format.pattern(false)).to_string()
+            }),
+            CsvCell::Timestamp(date) => date
+                .format(profile.date_format.pattern(true))
+                .to_string(),
+        }
+    }
+}
+
+impl From<&str> for CsvCell {
+    fn from(text: &str) -> Self {
+        CsvCell::Text(text.to_string())
+    }
+}
+
+impl From<String> for CsvCell {
+    fn from(text: String) -> Self {
+        CsvCell::Text(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthSpend {
+    pub month: String, // YYYY-MM of the bought date
+    pub books: usize,
+    pub spent: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearFinished {
+    pub year: i32,
+    pub finished: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorTotals {
+    pub author: String,
+    pub books: usize,
+    pub bought: usize,
+    pub finished: usize,
+    pub spent: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageCount {
+    pub language: Option<String>, // ISO 639-1 code, None for books without one
+    pub books: usize,
+    pub finished: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GiverCount {
+    pub giver: String,
+    pub books: usize,
+}
+
+/// Aggregated numbers over the whole library, meant for spreadsheet analysis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub generated_at: NaiveDateTime,
+    pub app_version: String,
+    pub total_books: usize,
+    pub total_copies: usize, // Books counted once per owned copy
+    pub total_spent: f32,
+    pub spend_per_month: Vec<MonthSpend>,
+    pub finished_per_year: Vec<YearFinished>,
+    pub authors: Vec<AuthorTotals>,
+    pub gifts: Vec<GiverCount>,
+    pub languages: Vec<LanguageCount>,
+}
+
+/// Builds the report from the loaded books and authors, every author gets a row
+pub fn build(books: &[BookWithAuthor], authors: &[AuthorModel]) -> Report {
+    let mut months: BTreeMap<String, MonthSpend> = BTreeMap::new();
+    let mut years: BTreeMap<i32, usize> = BTreeMap::new();
+
+    for pair in books {
+        if let Some(bought) = pair.book.bought {
+            let month = format!("{:04}-{:02}", bought.year(), bought.month());
+            let entry = months.entry(month.clone()).or_insert(MonthSpend {
+                month,
+                books: 0,
+                spent: 0.0,
+            });
+            entry.books += 1;
+            entry.spent += pair.book.total_value().unwrap_or(0.0);
+        }
+
+        if let Some(finished) = pair.book.finished {
+            *years.entry(finished.year()).or_default() += 1;
+        }
+    }
+
+    let mut author_totals: Vec<AuthorTotals> = authors
+        .iter()
+        .map(|author| {
+            let written: Vec<&BookWithAuthor> = books
+                .iter()
+                .filter(|pair| pair.book.AuthorFK == Some(author.Id))
+                .collect();
+
+            AuthorTotals {
+                author: author
+                    .Name
+                    .clone()
+                    .unwrap_or_else(|| "Unnamed Author".to_string()),
+                books: written.len(),
+                bought: written.iter().filter(|p| p.book.bought.is_some()).count(),
+                finished: written.iter().filter(|p| p.book.finished.is_some()).count(),
+                spent: written
+                    .iter()
+                    .filter(|p| p.book.bought.is_some())
+                    .filter_map(|p| p.book.total_value())
+                    .sum(),
+            }
+        })
+        .collect();
+    author_totals.sort_by(|a, b| b.books.cmp(&a.books).then_with(|| a.author.cmp(&b.author)));
+
+    Report {
+        generated_at: Local::now().naive_local(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        total_books: books.len(),
+        total_copies: books.iter().map(|pair| pair.book.quantity.max(0) as usize).sum(),
+        total_spent: months.values().map(|m| m.spent).sum(),
+        spend_per_month: months.into_values().collect(),
+        finished_per_year: years
+            .into_iter()
+            .map(|(year, finished)| YearFinished { year, finished })
+            .collect(),
+        authors: author_totals,
+        gifts: stats::gifts_by_giver(books)
+            .into_iter()
+            .map(|totals| GiverCount {
+                giver: totals.giver,
+                books: totals.books,
+            })
+            .collect(),
+        languages: stats::books_per_language(books)
+            .into_iter()
+            .map(|totals| LanguageCount {
+                language: totals.code,
+                books: totals.books,
+                finished: totals.finished,
+            })
+            .collect(),
+    }
+}
+
+/// Writes the records as CSV the way the profile says. Fields holding the separator,
+/// a quote or a line break are quoted whatever the quoting policy.
+pub fn write_csv<I, R>(records: I, profile: &ExportProfile) -> Result<String, ReportError>
+where
+    I: IntoIterator<Item = R>,
+    R: IntoIterator<Item = CsvCell>,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(profile.delimiter())
+        .quote_style(profile.quoting.style())
+        .from_writer(Vec::new());
+    for record in records {
+        writer.write_record(record.into_iter().map(|cell| cell.format(profile)))?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    let csv = String::from_utf8_lossy(&bytes);
+    Ok(if profile.byte_order_mark {
+        format!("\u{feff}{}", csv)
+    } else {
+        csv.into_owned()
+    })
+}
+
+/// Header and the first `rows` records as the profile would write them, for showing
+/// before an export. The byte order mark is left out, it is invisible anyway.
+pub fn preview_csv<R>(records: Vec<R>, rows: usize, profile: &ExportProfile) -> String
+where
+    R: IntoIterator<Item = CsvCell>,
+{
+    let profile = ExportProfile {
+        byte_order_mark: false,
+        ..*profile
+    };
+    write_csv(records.into_iter().take(rows + 1), &profile)
+        .unwrap_or_else(|e| format!("Preview unavailable: {}", e))
+}
+
+impl Report {
+    pub fn to_json(&self) -> Result<String, ReportError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Long format CSV rows with one row per value, easy to pivot in a spreadsheet
+    pub fn csv_rows(&self) -> Vec<[CsvCell; 4]> {
+        let mut rows = vec![["section", "key", "metric", "value"].map(CsvCell::from)];
+        let mut push = |section: &str, key: &str, metric: &str, value: CsvCell| {
+            rows.push([section.into(), key.into(), metric.into(), value]);
+        };
+        let count = |count: usize| CsvCell::Integer(count as i64);
+        let money = |amount: f32| CsvCell::Money(amount.into());
+
+        push(
+            "meta",
+            "generated_at",
+            "value",
+            CsvCell::Timestamp(self.generated_at),
+        );
+        push("meta", "app_version", "value", self.app_version.clone().into());
+        push("total", "all", "books", count(self.total_books));
+        push("total", "all", "copies", count(self.total_copies));
+        push("total", "all", "spent", money(self.total_spent));
+
+        for month in &self.spend_per_month {
+            push("month", &month.month, "books", count(month.books));
+            push("month", &month.month, "spent", money(month.spent));
+        }
+
+        for year in &self.finished_per_year {
+            push(
+                "year",
+                &year.year.to_string(),
+                "finished",
+                count(year.finished),
+            );
+        }
+
+        for author in &self.authors {
+            push("author", &author.author, "books", count(author.books));
+            push("author", &author.author, "bought", count(author.bought));
+            push("author", &author.author, "finished", count(author.finished));
+            push("author", &author.author, "spent", money(author.spent));
+        }
+
+        for gift in &self.gifts {
+            push("gift", &gift.giver, "books", count(gift.books));
+        }
+
+        for language in &self.languages {
+            let key = language.language.as_deref().unwrap_or("none");
+            push("language", key, "books", count(language.books));
+            push("language", key, "finished", count(language.finished));
+        }
+
+        rows
+    }
+
+    pub fn to_csv(&self, profile: &ExportProfile) -> Result<String, ReportError> {
+        write_csv(self.csv_rows(), profile)
+    }
+
+    pub fn write(
+        &self,
+        format: ReportFormat,
+        profile: &ExportProfile,
+        path: &Path,
+    ) -> Result<(), ReportError> {
+        let contents = match format {
+            ReportFormat::Json => self.to_json()?,
+            ReportFormat::Csv => self.to_csv(profile)?,
+        };
+        write_file(path, &contents)
+    }
+}
+
+/// Wide format CSV with one row per author, dates without a time
+pub fn authors_to_csv(
+    rows: &[AuthorExportRow],
+    profile: &ExportProfile,
+) -> Result<String, ReportError> {
+    let header = [
+        "name",
+        "books",
+        "finished",
+        "owned",
+        "published_works",
+        "completion",
+        "spent",
+        "first_bought",
+        "last_bought",
+    ];
+    let records = rows.iter().map(|row| {
+        let completion =
+            stats::AuthorCompletion::new(row.owned.max(0) as usize, row.published_works);
+        let optional = |value: Option<i64>| value.map_or_else(|| "".into(), CsvCell::Integer);
+        [
+            row.name.clone().unwrap_or_default().into(),
+            CsvCell::Integer(row.books),
+            CsvCell::Integer(row.finished),
+            CsvCell::Integer(row.owned),
+            optional(row.published_works.map(i64::from)),
+            optional(
+                completion
+                    .completion()
+                    .map(|completion| (completion * 100.0).round() as i64),
+            ),
+            CsvCell::Money(row.spent),
+            CsvCell::Date(row.first_bought),
+            CsvCell::Date(row.last_bought),
+        ]
+    });
+    write_csv(
+        std::iter::once(header.map(CsvCell::from)).chain(records),
+        profile,
+    )
+}
+
+pub fn write_authors_csv(
+    rows: &[AuthorExportRow],
+    profile: &ExportProfile,
+    path: &Path,
+) -> Result<(), ReportError> {
+    write_file(path, &authors_to_csv(rows, profile)?)
+}
+
+/// Number of books listed by value in the library value report
+pub const VALUE_REPORT_TOP: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuedBook {
+    pub title: String,
+    pub author: String,
+    pub copies: i32,
+    pub value: f32, // Price times copies
+}
+
+/// Purchase value of the library, for insurance claims
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueReport {
+    pub generated_at: NaiveDateTime,
+    pub total_books: usize,
+    pub total_copies: usize,
+    pub priced_books: usize,
+    pub total_value: f32,
+    pub most_valuable: Vec<ValuedBook>, // At most VALUE_REPORT_TOP, highest value first
+    pub unpriced: Vec<ValuedBook>,      // Books left out of the total, value is zero
+}
+
+/// Sums what the library cost, books without a price are listed apart rather than guessed
+pub fn build_value_report(books: &[BookWithAuthor]) -> ValueReport {
+    let valued = |pair: &BookWithAuthor| ValuedBook {
+        title: pair.book.title.clone(),
+        author: pair
+            .author
+            .as_ref()
+            .and_then(|author| author.Name.clone())
+            .unwrap_or_default(),
+        copies: pair.book.quantity,
+        value: pair.book.total_value().unwrap_or(0.0),
+    };
+
+    let (priced, unpriced): (Vec<&BookWithAuthor>, Vec<&BookWithAuthor>) =
+        books.iter().partition(|pair| pair.book.price.is_some());
+
+    let mut most_valuable: Vec<ValuedBook> = priced.iter().map(|pair| valued(pair)).collect();
+    most_valuable.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    let total_value = most_valuable.iter().map(|book| book.value).sum();
+    most_valuable.truncate(VALUE_REPORT_TOP);
+
+    let mut unpriced: Vec<ValuedBook> = unpriced.iter().map(|pair| valued(pair)).collect();
+    unpriced.sort_by(|a, b| a.title.cmp(&b.title));
+
+    ValueReport {
+        generated_at: Local::now().naive_local(),
+        total_books: books.len(),
+        total_copies: books.iter().map(|pair| pair.book.quantity.max(0) as usize).sum(),
+        priced_books: priced.len(),
+        total_value,
+        most_valuable,
+        unpriced,
+    }
+}
+
+/// Escapes text for use inside HTML elements and quoted attributes
+pub fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Wraps a body in a standalone HTML page with print friendly styling
+pub fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n\
+         body {{ font-family: sans-serif; margin: 2em; color: #222; }}\n\
+         table {{ border-collapse: collapse; margin-bottom: 2em; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}\n\
+         td.number {{ text-align: right; }}\n\
+         table.dense {{ width: 100%; font-size: 10pt; }}\n\
+         table.dense th, table.dense td {{ padding: 2px 6px; }}\n\
+         @media print {{\n\
+         body {{ margin: 0; color: #000; }}\n\
+         h1 {{ font-size: 14pt; }}\n\
+         th, td {{ border-color: #000; }}\n\
+         tr {{ break-inside: avoid; }}\n\
+         thead {{ display: table-header-group; }}\n\
+         }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+        title = html_escape(title),
+        body = body
+    )
+}
+
+impl ValueReport {
+    pub fn to_html(&self, currency: &str) -> String {
+        let price = |value: f32| format!("{:.2}{}", value, html_escape(currency));
+        let rows = |books: &[ValuedBook], with_value: bool| {
+            books
+                .iter()
+                .map(|book| {
+                    let value = if with_value {
+                        format!("<td class=\"number\">{}</td>", price(book.value))
+                    } else {
+                        String::new()
+                    };
+                    format!(
+                        "<tr><td>{}</td><td>{}</td><td class=\"number\">{}</td>{}</tr>\n",
+                        html_escape(&book.title),
+                        html_escape(&book.author),
+                        book.copies,
+                        value
+                    )
+                })
+                .collect::<String>()
+        };
+
+        let mut body = format!(
+            "<p>Generated on {}</p>\n<table>\n\
+             <tr><th>Books</th><td class=\"number\">{}</td></tr>\n\
+             <tr><th>Copies</th><td class=\"number\">{}</td></tr>\n\
+             <tr><th>Books with a price</th><td class=\"number\">{}</td></tr>\n\
+             <tr><th>Books without a price</th><td class=\"number\">{}</td></tr>\n\
+             <tr><th>Total purchase value</th><td class=\"number\">{}</td></tr>\n\
+             </table>\n",
+            self.generated_at.format("%Y-%m-%d %H:%M"),
+            self.total_books,
+            self.total_copies,
+            self.priced_books,
+            self.unpriced.len(),
+            price(self.total_value)
+        );
+
+        body.push_str(&format!(
+            "<h2>Most valuable books</h2>\n<table>\n\
+             <tr><th>Title</th><th>Author</th><th>Copies</th><th>Value</th></tr>\n{}</table>\n",
+            rows(&self.most_valuable, true)
+        ));
+
+        if !self.unpriced.is_empty() {
+            body.push_str(&format!(
+                "<h2>Books without a price ({})</h2>\n\
+                 <p>These books are not included in the total purchase value.</p>\n<table>\n\
+                 <tr><th>Title</th><th>Author</th><th>Copies</th></tr>\n{}</table>\n",
+                self.unpriced.len(),
+                rows(&self.unpriced, false)
+            ));
+        }
+
+        html_document("Library value report", &body)
+    }
+
+    pub fn write_html(&self, currency: &str, path: &Path) -> Result<(), ReportError> {
+        write_html(path, &self.to_html(currency))
+    }
+}
+
+/// Dense table of the given books for printing from the browser, headed by the date and
+/// a description of the filters that produced the list
+pub fn books_print_html(
+    title: &str,
+    description: &str,
+    books: &[BookWithAuthor],
+    generated_at: NaiveDateTime,
+    currency: &str,
+) -> String {
+    let date = |value: Option<NaiveDateTime>| {
+        value.map_or_else(String::new, |d| d.format("%Y-%m-%d").to_string())
+    };
+
+    let rows: String = books
+        .iter()
+        .map(|pair| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"number\">{}</td>\
+                 <td class=\"number\">{}</td><td>{}</td><td>{}</td></tr>\n",
+                html_escape(&pair.book.title),
+                html_escape(
+                    pair.author
+                        .as_ref()
+                        .and_then(|author| author.Name.as_deref())
+                        .unwrap_or("")
+                ),
+                pair.book.price.map_or_else(String::new, |price| {
+                    html_escape(&format!("{:.2}{}", price, currency))
+                }),
+                pair.book.quantity,
+                date(pair.book.bought),
+                date(pair.book.finished)
+            )
+        })
+        .collect();
+
+    let body = format!(
+        "<p>{}<br>{} books, printed {}</p>\n<table class=\"dense\">\n<thead>\
+         <tr><th>Title</th><th>Author</th><th>Price</th><th>Copies</th><th>Bought</th>\
+         <th>Finished</th></tr></thead>\n<tbody>\n{}</tbody>\n</table>\n",
+        html_escape(description),
+        books.len(),
+        generated_at.format("%Y-%m-%d %H:%M"),
+        rows
+    );
+    html_document(title, &body)
+}
+
+pub fn write_html(path: &Path, contents: &str) -> Result<(), ReportError> {
+    write_file(path, contents)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), ReportError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import;
+
+    #[test]
+    fn a_title_with_quotes_commas_and_line_breaks_survives_export_and_import() {
+        let title = "He said \"hi\", then left\nor did he?";
+        let profile = ExportProfile {
+            byte_order_mark: true,
+            ..ExportProfile::default()
+        };
+        let csv = write_csv(
+            [["title", "author"].map(CsvCell::from), [title, "Anon"].map(CsvCell::from)],
+            &profile,
+        )
+        .unwrap();
+
+        let folder = std::env::temp_dir().join("bookshelf-tests");
+        std::fs::create_dir_all(&folder).unwrap();
+        let path = folder.join("round-trip.csv");
+        write_file(&path, &csv).unwrap();
+        let table = import::read_csv(&path).unwrap();
+        let mapping = import::guess_mapping(&table.headers);
+        let book = import::map_row(&table.rows[0], &mapping).unwrap();
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(book.title, title);
+        assert_eq!(book.author.as_deref(), Some("Anon"));
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, 7, 9)
+            .unwrap()
+    }
+
+    // Every profile the settings can produce
+    fn every_profile() -> Vec<ExportProfile> {
+        let mut profiles = Vec::new();
+        for date_format in CsvDateFormat::ALL {
+            for decimal_separator in DecimalSeparator::ALL {
+                for quoting in CsvQuoting::ALL {
+                    for byte_order_mark in [false, true] {
+                        profiles.push(ExportProfile {
+                            date_format,
+                            decimal_separator,
+                            byte_order_mark,
+                            quoting,
+                        });
+                    }
+                }
+            }
+        }
+        profiles
+    }
+
+    #[test]
+    fn cells_are_formatted_as_the_profile_says() {
+        let comma_day_first = ExportProfile {
+            date_format: CsvDateFormat::DayMonthYear,
+            decimal_separator: DecimalSeparator::Comma,
+            ..ExportProfile::default()
+        };
+        let month_first = ExportProfile {
+            date_format: CsvDateFormat::MonthDayYear,
+            ..ExportProfile::default()
+        };
+        let iso = ExportProfile::default();
+
+        let date = CsvCell::Date(Some(at(5, 14)));
+        assert_eq!(date.format(&iso), "2024-03-05");
+        assert_eq!(date.format(&comma_day_first), "05.03.2024");
+        assert_eq!(date.format(&month_first), "03/05/2024");
+        assert_eq!(CsvCell::Date(None).format(&month_first), "");
+
+        let timestamp = CsvCell::Timestamp(at(5, 14));
+        assert_eq!(timestamp.format(&iso), "2024-03-05T14:07:09");
+        assert_eq!(timestamp.format(&comma_day_first), "05.03.2024 14:07:09");
+        assert_eq!(timestamp.format(&month_first), "03/05/2024 14:07:09");
+
+        // Money keeps its trailing zeros
+        assert_eq!(CsvCell::Money(12.5).format(&iso), "12.50");
+        assert_eq!(CsvCell::Money(12.5).format(&comma_day_first), "12,50");
+        assert_eq!(CsvCell::Money(-3.0).format(&comma_day_first), "-3,00");
+        assert_eq!(CsvCell::Integer(1200).format(&comma_day_first), "1200");
+        assert_eq!(CsvCell::from("1.5").format(&comma_day_first), "1.5");
+    }
+
+    #[test]
+    fn each_quoting_policy_quotes_its_fields() {
+        let record = || {
+            [
+                CsvCell::from("Dune"),
+                CsvCell::Integer(3),
+                CsvCell::Money(12.5),
+            ]
+        };
+        let written = |quoting, decimal_separator| {
+            let profile = ExportProfile {
+                quoting,
+                decimal_separator,
+                ..ExportProfile::default()
+            };
+            write_csv([record()], &profile).unwrap()
+        };
+
+        assert_eq!(
+            written(CsvQuoting::Necessary, DecimalSeparator::Point),
+            "Dune,3,12.50\n"
+        );
+        assert_eq!(
+            written(CsvQuoting::Always, DecimalSeparator::Point),
+            "\"Dune\",\"3\",\"12.50\"\n"
+        );
+        assert_eq!(
+            written(CsvQuoting::NonNumeric, DecimalSeparator::Point),
+            "\"Dune\",3,12.50\n"
+        );
+        // Comma decimals don't read as numbers, so they are quoted like text
+        assert_eq!(
+            written(CsvQuoting::NonNumeric, DecimalSeparator::Comma),
+            "\"Dune\";3;\"12,50\"\n"
+        );
+        assert_eq!(
+            written(CsvQuoting::Necessary, DecimalSeparator::Comma),
+            "Dune;3;12,50\n"
+        );
+    }
+
+    #[test]
+    fn separators_in_titles_are_quoted_only_for_the_chosen_delimiter() {
+        let record = [CsvCell::from("Eats, Shoots; Leaves")];
+        let point = ExportProfile::default();
+        let comma = ExportProfile {
+            decimal_separator: DecimalSeparator::Comma,
+            ..ExportProfile::default()
+        };
+        assert_eq!(point.delimiter(), b',');
+        assert_eq!(comma.delimiter(), b';');
+        assert_eq!(
+            write_csv([record.clone()], &point).unwrap(),
+            "\"Eats, Shoots; Leaves\"\n"
+        );
+        assert_eq!(
+            write_csv([record], &comma).unwrap(),
+            "\"Eats, Shoots; Leaves\"\n"
+        );
+        assert_eq!(
+            write_csv([[CsvCell::from("Eats, Shoots")]], &comma).unwrap(),
+            "Eats, Shoots\n"
+        );
+    }
+
+    #[test]
+    fn every_option_combination_reads_back_as_written() {
+        let records = || {
+            vec![
+                vec![
+                    CsvCell::from("title"),
+                    CsvCell::from("notes"),
+                    CsvCell::from("price"),
+                    CsvCell::from("bought"),
+                ],
+                vec![
+                    CsvCell::from("Eats, Shoots; Leaves"),
+                    CsvCell::from("First line\nsecond \"quoted\" line"),
+                    CsvCell::Money(12.5),
+                    CsvCell::Date(Some(at(5, 14))),
+                ],
+            ]
+        };
+
+        for profile in every_profile() {
+            let csv = write_csv(records(), &profile).unwrap();
+            let bom = [0xEF, 0xBB, 0xBF];
+            assert_eq!(csv.as_bytes().starts_with(&bom), profile.byte_order_mark);
+            assert_eq!(
+                csv.matches('\u{feff}').count(),
+                usize::from(profile.byte_order_mark)
+            );
+
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .delimiter(profile.delimiter())
+                .from_reader(csv.trim_start_matches('\u{feff}').as_bytes());
+            let read: Vec<Vec<String>> = reader
+                .records()
+                .map(|record| record.unwrap().iter().map(String::from).collect())
+                .collect();
+            let expected: Vec<Vec<String>> = records()
+                .into_iter()
+                .map(|record| record.iter().map(|cell| cell.format(&profile)).collect())
+                .collect();
+            assert_eq!(read, expected, "{:?}", profile);
+        }
+    }
+
+    #[test]
+    fn the_preview_shows_the_header_and_the_first_rows_without_the_mark() {
+        let profile = ExportProfile {
+            byte_order_mark: true,
+            decimal_separator: DecimalSeparator::Comma,
+            ..ExportProfile::default()
+        };
+        let records: Vec<[CsvCell; 2]> = std::iter::once(["title", "price"].map(CsvCell::from))
+            .chain((1..=5).map(|n| {
+                [
+                    CsvCell::from(format!("Book {}", n)),
+                    CsvCell::Money(n as f64),
+                ]
+            }))
+            .collect();
+
+        assert_eq!(
+            preview_csv(records, 3, &profile),
+            "title;price\nBook 1;1,00\nBook 2;2,00\nBook 3;3,00\n"
+        );
+    }
+}
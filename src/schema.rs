@@ -2,6 +2,50 @@ diesel::table! {
     Author (Id) {
         Id -> Integer,
         Name -> Nullable<Text>,
+        website -> Nullable<Text>,
+        wikipedia -> Nullable<Text>,
+        published_works -> Nullable<Integer>,
+        Aliases -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    AuthorMergeDismissals (author_a, author_b) {
+        author_a -> Integer,
+        author_b -> Integer,
+    }
+}
+
+diesel::table! {
+    BookFields (Id) {
+        Id -> Integer,
+        BookFK -> Integer,
+        key -> Text,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    BookJournal (id) {
+        id -> Integer,
+        book_fk -> Integer,
+        written_at -> Timestamp,
+        body -> Text,
+    }
+}
+
+diesel::table! {
+    BookListItems (list_fk, book_fk) {
+        list_fk -> Integer,
+        book_fk -> Integer,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    BookLists (id) {
+        id -> Integer,
+        name -> Text,
     }
 }
 
@@ -14,12 +58,80 @@ diesel::table! {
         added -> Nullable<Timestamp>,
         AuthorFK -> Nullable<Integer>,
         id -> Integer,
+        SeriesFK -> Nullable<Integer>,
+        gift_from -> Nullable<Text>,
+        quantity -> Integer,
+        cover_path -> Nullable<Text>,
+        genre -> Nullable<Text>,
+        page_count -> Nullable<Integer>,
+        pinned -> Bool,
+        format -> Nullable<Text>,
+        duration_minutes -> Nullable<Integer>,
+        language -> Nullable<Text>,
+        acquired_from -> Nullable<Text>,
+        rating -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    Loans (id) {
+        id -> Integer,
+        book_fk -> Integer,
+        borrower -> Text,
+        loaned_on -> Timestamp,
+        due_on -> Nullable<Timestamp>,
+        returned_on -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    Quotes (id) {
+        id -> Integer,
+        book_fk -> Integer,
+        page -> Nullable<Integer>,
+        text -> Text,
+        noted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ReadingSessions (id) {
+        id -> Integer,
+        book_fk -> Integer,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+        pages -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    Series (id) {
+        id -> Integer,
+        name -> Text,
+        total_volumes -> Nullable<Integer>,
     }
 }
 
+diesel::joinable!(BookFields -> Books (BookFK));
+diesel::joinable!(BookJournal -> Books (book_fk));
+diesel::joinable!(BookListItems -> BookLists (list_fk));
+diesel::joinable!(BookListItems -> Books (book_fk));
 diesel::joinable!(Books -> Author (AuthorFK));
+diesel::joinable!(Books -> Series (SeriesFK));
+diesel::joinable!(Loans -> Books (book_fk));
+diesel::joinable!(Quotes -> Books (book_fk));
+diesel::joinable!(ReadingSessions -> Books (book_fk));
 
 diesel::allow_tables_to_appear_in_same_query!(
     Author,
+    AuthorMergeDismissals,
+    BookFields,
+    BookJournal,
+    BookListItems,
+    BookLists,
     Books,
+    Loans,
+    Quotes,
+    ReadingSessions,
+    Series,
 );
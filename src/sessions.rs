@@ -0,0 +1,121 @@
+// src/sessions.rs
+use crate::models::{ReadingSessionModel, ID};
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime};
+use std::collections::HashMap;
+
+/// Time spent in the sessions of one book
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionTotal {
+    pub minutes: i64,
+    pub sessions: usize,
+}
+
+/// Whole minutes a session lasted, zero while it is still running
+pub fn session_minutes(session: &ReadingSessionModel) -> i64 {
+    session
+        .ended_at
+        .map_or(0, |ended| (ended - session.started_at).num_minutes().max(0))
+}
+
+/// Minutes of the session that fall between `from` and `to`. A session read across
+/// midnight counts toward both days.
+pub fn minutes_between(
+    session: &ReadingSessionModel,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> i64 {
+    let Some(ended) = session.ended_at else {
+        return 0;
+    };
+    let start = session.started_at.max(from);
+    let end = ended.min(to);
+    (end - start).num_minutes().max(0)
+}
+
+/// Totals of the finished sessions per book, books without any are left out
+pub fn totals_by_book(sessions: &[ReadingSessionModel]) -> HashMap<ID, SessionTotal> {
+    sessions
+        .iter()
+        .filter(|session| session.ended_at.is_some())
+        .fold(HashMap::new(), |mut totals, session| {
+            let total: &mut SessionTotal = totals.entry(session.book_fk).or_default();
+            total.minutes += session_minutes(session);
+            total.sessions += 1;
+            totals
+        })
+}
+
+/// Midnight of the Monday starting the week `now` is in
+pub fn week_start(now: NaiveDateTime) -> NaiveDateTime {
+    let monday = now.date() - Duration::days(now.weekday().num_days_from_monday() as i64);
+    monday.and_time(NaiveTime::MIN)
+}
+
+/// Minutes read this week so far, Monday to Sunday
+pub fn minutes_this_week(sessions: &[ReadingSessionModel], now: NaiveDateTime) -> i64 {
+    let from = week_start(now);
+    let to = from + Duration::days(7);
+    sessions
+        .iter()
+        .map(|session| minutes_between(session, from, to))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // March 2025, the 3rd is a Monday
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn session(
+        book_fk: ID,
+        started_at: NaiveDateTime,
+        ended_at: Option<NaiveDateTime>,
+    ) -> ReadingSessionModel {
+        ReadingSessionModel {
+            id: 0,
+            book_fk,
+            started_at,
+            ended_at,
+            pages: None,
+        }
+    }
+
+    #[test]
+    fn a_session_across_midnight_counts_toward_both_days_and_weeks() {
+        // Sunday 23:30 to Monday 00:45
+        let late = session(1, at(2, 23, 30), Some(at(3, 0, 45)));
+        assert_eq!(session_minutes(&late), 75);
+        assert_eq!(minutes_between(&late, at(2, 0, 0), at(3, 0, 0)), 30);
+        assert_eq!(minutes_between(&late, at(3, 0, 0), at(4, 0, 0)), 45);
+
+        assert_eq!(week_start(at(3, 0, 45)), at(3, 0, 0));
+        let sessions = [late];
+        assert_eq!(minutes_this_week(&sessions, at(5, 12, 0)), 45);
+        assert_eq!(minutes_this_week(&sessions, at(2, 23, 59)), 30);
+    }
+
+    #[test]
+    fn zero_length_and_running_sessions_add_no_time() {
+        let empty = session(1, at(4, 20, 0), Some(at(4, 20, 0)));
+        let running = session(1, at(4, 21, 0), None);
+        let read = session(1, at(4, 18, 0), Some(at(4, 19, 30)));
+        assert_eq!(session_minutes(&empty), 0);
+        assert_eq!(session_minutes(&running), 0);
+
+        let totals = totals_by_book(&[empty, running, read, session(2, at(5, 8, 0), None)]);
+        let expected = SessionTotal {
+            minutes: 90,
+            sessions: 2,
+        };
+        assert_eq!(totals.get(&1), Some(&expected));
+        assert_eq!(totals.get(&2), None);
+    }
+}
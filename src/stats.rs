@@ -0,0 +1,881 @@
+// src/stats.rs
+use crate::models::{BookWithAuthor, SeriesModel, ID};
+use crate::ui::{format_duration, format_rating, NO_AUTHOR_GROUP, UNKNOWN_AUTHOR};
+use chrono::{Datelike, NaiveDateTime};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+fn in_year(date: Option<NaiveDateTime>, year: i32) -> bool {
+    date.map(|d| d.year() == year).unwrap_or(false)
+}
+
+/// Books whose finished date falls in the given year
+pub fn finished_in_year(books: &[BookWithAuthor], year: i32) -> Vec<&BookWithAuthor> {
+    books
+        .iter()
+        .filter(|pair| in_year(pair.book.finished, year))
+        .collect()
+}
+
+/// Books whose bought date falls in the given year
+pub fn bought_in_year(books: &[BookWithAuthor], year: i32) -> Vec<&BookWithAuthor> {
+    books
+        .iter()
+        .filter(|pair| in_year(pair.book.bought, year))
+        .collect()
+}
+
+/// Sum of the prices of the given books times their copies, unpriced books count as zero
+pub fn total_spent(books: &[&BookWithAuthor]) -> f32 {
+    books.iter().filter_map(|pair| pair.book.total_value()).sum()
+}
+
+/// Average price of one copy, None when nothing has a price. Unpriced books count as
+/// free unless `priced_only` leaves them out
+pub fn average_price(books: &[BookWithAuthor], priced_only: bool) -> Option<f32> {
+    let prices: Vec<f32> = books.iter().filter_map(|pair| pair.book.price).collect();
+    if prices.is_empty() {
+        return None;
+    }
+    let count = if priced_only { prices.len() } else { books.len() };
+    Some(prices.iter().sum::<f32>() / count as f32)
+}
+
+/// Whole days between starting and finishing a book, None when either is missing or
+/// the book was finished before it was started
+pub fn reading_duration_days(
+    start: Option<NaiveDateTime>,
+    finished: Option<NaiveDateTime>,
+) -> Option<i64> {
+    let days = (finished?.date() - start?.date()).num_days();
+    (days >= 0).then_some(days)
+}
+
+/// Bought books never finished for at least `min_days`, with their age in days,
+/// the longest waiting first
+pub fn stale_unread(
+    books: &[BookWithAuthor],
+    now: NaiveDateTime,
+    min_days: i64,
+) -> Vec<(&BookWithAuthor, i64)> {
+    let mut stale: Vec<(&BookWithAuthor, i64)> = books
+        .iter()
+        .filter(|pair| pair.book.finished.is_none())
+        .filter_map(|pair| {
+            let days = (now.date() - pair.book.bought?.date()).num_days();
+            (days >= min_days).then_some((pair, days))
+        })
+        .collect();
+    stale.sort_by(|(a, a_days), (b, b_days)| {
+        b_days
+            .cmp(a_days)
+            .then_with(|| a.book.title.cmp(&b.book.title))
+    });
+    stale
+}
+
+/// The most and the least expensive book by the price of one copy, unpriced books are
+/// left out. On a tie the lower id wins, so the same books show every time.
+pub fn price_extremes(
+    books: &[BookWithAuthor],
+) -> (Option<&BookWithAuthor>, Option<&BookWithAuthor>) {
+    let priced = || {
+        books
+            .iter()
+            .filter_map(|pair| pair.book.price.map(|price| (price, pair)))
+    };
+    let most = priced().max_by(|(a, a_pair), (b, b_pair)| {
+        a.total_cmp(b).then_with(|| b_pair.book.id.cmp(&a_pair.book.id))
+    });
+    let least = priced().min_by(|(a, a_pair), (b, b_pair)| {
+        a.total_cmp(b).then_with(|| a_pair.book.id.cmp(&b_pair.book.id))
+    });
+    (most.map(|(_, pair)| pair), least.map(|(_, pair)| pair))
+}
+
+/// Counts shown in the strip above the book list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListSummary {
+    pub total: usize,
+    pub finished: usize,
+    pub unread: usize,
+}
+
+pub fn list_summary(books: &[BookWithAuthor]) -> ListSummary {
+    let finished = books
+        .iter()
+        .filter(|pair| pair.book.finished.is_some())
+        .count();
+
+    ListSummary {
+        total: books.len(),
+        finished,
+        unread: books.len() - finished,
+    }
+}
+
+/// Author with the most books in the given set, ties broken alphabetically
+pub fn top_author(books: &[&BookWithAuthor]) -> Option<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for pair in books {
+        if let Some(name) = pair.author.as_ref().and_then(|a| a.Name.clone()) {
+            *counts.entry(name).or_default() += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(a_name, a_count), (b_name, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_name.cmp(a_name))
+        })
+}
+
+/// Highest rated book in the given set, unrated books are left out. On a tie the lower
+/// id wins, so the same book shows every time.
+pub fn favorite_book<'a>(books: &[&'a BookWithAuthor]) -> Option<&'a BookWithAuthor> {
+    books
+        .iter()
+        .filter_map(|pair| pair.book.rating.map(|rating| (rating, *pair)))
+        .max_by(|(a, a_pair), (b, b_pair)| {
+            a.cmp(b).then_with(|| b_pair.book.id.cmp(&a_pair.book.id))
+        })
+        .map(|(_, pair)| pair)
+}
+
+/// Every year that appears in any of the book dates, newest first
+pub fn years_with_data(books: &[BookWithAuthor]) -> Vec<i32> {
+    let mut years: Vec<i32> = books
+        .iter()
+        .flat_map(|pair| [pair.book.added, pair.book.bought, pair.book.finished])
+        .flatten()
+        .map(|date| date.year())
+        .collect();
+
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+    years
+}
+
+/// Pages of the given books that are read rather than listened to
+pub fn pages_read(books: &[&BookWithAuthor]) -> i64 {
+    books
+        .iter()
+        .filter(|pair| !pair.book.is_audiobook())
+        .filter_map(|pair| pair.book.page_count)
+        .map(i64::from)
+        .sum()
+}
+
+/// Total listening time of the audiobooks among the given books, in minutes
+pub fn listening_minutes(books: &[&BookWithAuthor]) -> i32 {
+    books
+        .iter()
+        .filter(|pair| pair.book.is_audiobook())
+        .filter_map(|pair| pair.book.duration_minutes)
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearInReview {
+    pub year: i32,
+    pub currency: String,
+    pub finished_titles: Vec<String>,
+    pub pages_read: i64, // Finished books with a page count, audiobooks excluded
+    pub listening_minutes: i32, // Finished audiobooks
+    pub bought_count: usize,
+    pub top_author: Option<(String, usize)>,
+    pub favorite: Option<BookWithAuthor>, // Highest rated of the finished books
+    pub total_spent: f32,
+}
+
+/// Builds the yearly summary from the loaded books
+pub fn year_in_review(books: &[BookWithAuthor], year: i32, currency: &str) -> YearInReview {
+    let finished = finished_in_year(books, year);
+    let bought = bought_in_year(books, year);
+
+    YearInReview {
+        year,
+        currency: currency.to_string(),
+        finished_titles: finished
+            .iter()
+            .map(|pair| pair.book.title.clone())
+            .collect(),
+        pages_read: pages_read(&finished),
+        listening_minutes: listening_minutes(&finished),
+        bought_count: bought.len(),
+        top_author: top_author(&finished),
+        favorite: favorite_book(&finished).cloned(),
+        total_spent: total_spent(&bought),
+    }
+}
+
+impl YearInReview {
+    pub fn finished_count(&self) -> usize {
+        self.finished_titles.len()
+    }
+
+    /// Plain text version of the summary, suitable for pasting elsewhere
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![format!("My {} in books", self.year)];
+
+        if self.finished_titles.is_empty() {
+            lines.push("No books finished this year.".to_string());
+        } else {
+            lines.push(format!("Books finished: {}", self.finished_count()));
+            for title in &self.finished_titles {
+                lines.push(format!("  - {}", title));
+            }
+        }
+
+        if self.pages_read > 0 {
+            lines.push(format!("Pages read: {}", self.pages_read));
+        }
+        if self.listening_minutes > 0 {
+            lines.push(format!(
+                "Listening time: {}",
+                format_duration(self.listening_minutes)
+            ));
+        }
+
+        if let Some((name, count)) = &self.top_author {
+            lines.push(format!("Top author: {} ({} finished)", name, count));
+        }
+        if let Some(pair) = &self.favorite {
+            let stars = format_rating(pair.book.rating.unwrap_or_default());
+            lines.push(format!("Favorite: {} ({})", pair.book.title, stars));
+        }
+
+        lines.push(format!("Books bought: {}", self.bought_count));
+        lines.push(format!(
+            "Total spent: {:.2}{}",
+            self.total_spent, self.currency
+        ));
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesProgress {
+    pub series_id: ID,
+    pub name: String,
+    pub owned: usize,
+    pub finished: usize,
+    pub total: Option<usize>,
+}
+
+impl SeriesProgress {
+    /// True when more volumes are owned than the series declares
+    pub fn exceeds_total(&self) -> bool {
+        self.total.map(|total| self.owned > total).unwrap_or(false)
+    }
+
+    /// Owned volumes clamped to the declared total
+    pub fn owned_clamped(&self) -> usize {
+        self.total
+            .map(|total| self.owned.min(total))
+            .unwrap_or(self.owned)
+    }
+
+    /// Fraction of the series owned, None when the total is unknown
+    pub fn completion(&self) -> Option<f32> {
+        match self.total {
+            Some(0) | None => None,
+            Some(total) => Some(self.owned_clamped() as f32 / total as f32),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match self.total {
+            Some(total) => format!(
+                "{}: own {} of {}, finished {}",
+                self.name,
+                self.owned_clamped(),
+                total,
+                self.finished
+            ),
+            None => format!(
+                "{}: own {}, finished {}",
+                self.name, self.owned, self.finished
+            ),
+        }
+    }
+}
+
+/// Owned and finished counts for every series, in the order the series are given
+pub fn series_progress(books: &[BookWithAuthor], series: &[SeriesModel]) -> Vec<SeriesProgress> {
+    series
+        .iter()
+        .map(|s| {
+            let in_series: Vec<&BookWithAuthor> = books
+                .iter()
+                .filter(|pair| pair.book.SeriesFK == Some(s.id))
+                .collect();
+
+            SeriesProgress {
+                series_id: s.id,
+                name: s.name.clone(),
+                owned: in_series
+                    .iter()
+                    .filter(|pair| pair.book.bought.is_some())
+                    .count(),
+                finished: in_series
+                    .iter()
+                    .filter(|pair| pair.book.finished.is_some())
+                    .count(),
+                total: s.total_volumes.map(|total| total.max(0) as usize),
+            }
+        })
+        .collect()
+}
+
+/// Books of one series in the book list grouped by series
+#[derive(Debug, Clone)]
+pub struct SeriesGroup<'a> {
+    pub progress: Option<SeriesProgress>, // None for the books outside any known series
+    pub books: Vec<&'a BookWithAuthor>,
+}
+
+/// Splits the listed books by series in the order the series are given, keeping their
+/// order within each group. Progress counts every book of the series, listed or not,
+/// books without a series come last and series without listed books are left out
+pub fn group_by_series<'a>(
+    listed: &'a [BookWithAuthor],
+    books: &[BookWithAuthor],
+    series: &[SeriesModel],
+) -> Vec<SeriesGroup<'a>> {
+    let mut groups: Vec<SeriesGroup> = series_progress(books, series)
+        .into_iter()
+        .map(|progress| SeriesGroup {
+            progress: Some(progress),
+            books: Vec::new(),
+        })
+        .collect();
+    let mut others = Vec::new();
+    for pair in listed {
+        let position = pair
+            .book
+            .SeriesFK
+            .and_then(|series_id| series.iter().position(|s| s.id == series_id));
+        match position {
+            Some(position) => groups[position].books.push(pair),
+            None => others.push(pair),
+        }
+    }
+
+    groups.push(SeriesGroup {
+        progress: None,
+        books: others,
+    });
+    groups.retain(|group| !group.books.is_empty());
+    groups
+}
+
+/// Series progress ordered by completion, most complete first and unknown totals last
+pub fn series_by_completion(
+    books: &[BookWithAuthor],
+    series: &[SeriesModel],
+) -> Vec<SeriesProgress> {
+    let mut progress = series_progress(books, series);
+    progress.sort_by(|a, b| match (a.completion(), b.completion()) {
+        (Some(a_c), Some(b_c)) => b_c
+            .partial_cmp(&a_c)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.name.cmp(&b.name),
+    });
+    progress
+}
+
+/// How much of an author's published work is owned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuthorCompletion {
+    pub owned: usize,
+    pub published: Option<usize>, // None when the author has no declared count
+}
+
+impl AuthorCompletion {
+    pub fn new(owned: usize, published: Option<i32>) -> Self {
+        AuthorCompletion {
+            owned,
+            published: published.map(|published| published.max(0) as usize),
+        }
+    }
+
+    /// True when more books are owned than the author is declared to have published
+    pub fn exceeds_published(&self) -> bool {
+        self.published
+            .map(|published| self.owned > published)
+            .unwrap_or(false)
+    }
+
+    /// Fraction of the published works owned, capped at 1. None when the count is
+    /// unknown or zero
+    pub fn completion(&self) -> Option<f32> {
+        match self.published {
+            Some(0) | None => None,
+            Some(published) => Some(self.owned.min(published) as f32 / published as f32),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match (self.published, self.completion()) {
+            (Some(published), Some(completion)) => format!(
+                "Own {} of {} published ({:.0}%)",
+                self.owned.min(published),
+                published,
+                completion * 100.0
+            ),
+            _ => format!("Own {}", self.owned),
+        }
+    }
+}
+
+/// Number of gifts received from one person
+#[derive(Debug, Clone, PartialEq)]
+pub struct GiverTotals {
+    pub giver: String,
+    pub books: usize,
+}
+
+/// Gifts grouped by giver, most generous first. Names are matched ignoring case and
+/// surrounding spaces, the first spelling seen is the one shown.
+pub fn gifts_by_giver(books: &[BookWithAuthor]) -> Vec<GiverTotals> {
+    let mut totals: Vec<GiverTotals> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for giver in books.iter().filter_map(|pair| pair.book.gift_from.as_deref()) {
+        let giver = giver.trim();
+        if giver.is_empty() {
+            continue;
+        }
+        match positions.get(&giver.to_lowercase()) {
+            Some(&index) => totals[index].books += 1,
+            None => {
+                positions.insert(giver.to_lowercase(), totals.len());
+                totals.push(GiverTotals {
+                    giver: giver.to_string(),
+                    books: 1,
+                });
+            }
+        }
+    }
+
+    totals.sort_by(|a, b| b.books.cmp(&a.books).then_with(|| a.giver.cmp(&b.giver)));
+    totals
+}
+
+/// Authors ranked by how many of their books were finished, at most `n` of them. Books
+/// are grouped by author, finished books without one count under "Unknown"
+pub fn top_authors_by_finished(books: &[BookWithAuthor], n: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<Option<ID>, (String, usize)> = HashMap::new();
+    for pair in books.iter().filter(|pair| pair.book.finished.is_some()) {
+        let name = match &pair.author {
+            Some(author) => author
+                .Name
+                .clone()
+                .unwrap_or_else(|| "Unnamed Author".to_string()),
+            None => UNKNOWN_AUTHOR.to_string(),
+        };
+        counts.entry(pair.book.AuthorFK).or_insert((name, 0)).1 += 1;
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_values().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(n);
+    ranked
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageTotals {
+    pub code: Option<String>, // None for books without a language
+    pub books: usize,
+    pub finished: usize,
+}
+
+/// Books and finished books per language, most books first and books without a
+/// language last
+pub fn books_per_language(books: &[BookWithAuthor]) -> Vec<LanguageTotals> {
+    let mut totals: Vec<LanguageTotals> = Vec::new();
+    for pair in books {
+        let code = pair.book.language.clone();
+        let index = match totals.iter().position(|totals| totals.code == code) {
+            Some(index) => index,
+            None => {
+                totals.push(LanguageTotals {
+                    code,
+                    books: 0,
+                    finished: 0,
+                });
+                totals.len() - 1
+            }
+        };
+        totals[index].books += 1;
+        if pair.book.finished.is_some() {
+            totals[index].finished += 1;
+        }
+    }
+
+    totals.sort_by(|a, b| {
+        a.code
+            .is_none()
+            .cmp(&b.code.is_none())
+            .then_with(|| b.books.cmp(&a.books))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    totals
+}
+
+/// Read status buckets used to group an author's books
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionKey {
+    Finished,
+    OwnedUnread,
+    Wishlist,
+}
+
+impl SectionKey {
+    pub fn label(self) -> &'static str {
+        match self {
+            SectionKey::Finished => "Finished",
+            SectionKey::OwnedUnread => "Owned, unread",
+            SectionKey::Wishlist => "Wishlist",
+        }
+    }
+
+    fn of(pair: &BookWithAuthor) -> Self {
+        if pair.book.finished.is_some() {
+            SectionKey::Finished
+        } else if pair.book.bought.is_some() {
+            SectionKey::OwnedUnread
+        } else {
+            SectionKey::Wishlist
+        }
+    }
+
+    // Date the books of this section are ordered by
+    fn sort_date(self, pair: &BookWithAuthor) -> Option<NaiveDateTime> {
+        match self {
+            SectionKey::Finished => pair.book.finished,
+            SectionKey::OwnedUnread => pair.book.bought,
+            SectionKey::Wishlist => pair.book.added,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadStatusSection<'a> {
+    pub key: SectionKey,
+    pub books: Vec<&'a BookWithAuthor>,
+}
+
+impl ReadStatusSection<'_> {
+    pub fn total_spent(&self) -> f32 {
+        total_spent(&self.books)
+    }
+}
+
+/// Books of one author in the book list grouped by author
+#[derive(Debug, Clone)]
+pub struct AuthorGroup<'a> {
+    pub author_id: Option<ID>, // None for the books without an author
+    pub name: String,
+    pub books: Vec<&'a BookWithAuthor>,
+}
+
+impl AuthorGroup<'_> {
+    pub fn total_spent(&self) -> f32 {
+        total_spent(&self.books)
+    }
+}
+
+/// Splits books by author, keeping their order within each group. Authors are ordered
+/// by name ignoring case, books without an author come last
+pub fn group_by_author(books: &[BookWithAuthor]) -> Vec<AuthorGroup<'_>> {
+    let mut groups: Vec<AuthorGroup> = Vec::new();
+    let mut positions: HashMap<Option<ID>, usize> = HashMap::new();
+    for pair in books {
+        let author_id = pair.author.as_ref().map(|author| author.Id);
+        let position = *positions.entry(author_id).or_insert_with(|| {
+            groups.push(AuthorGroup {
+                author_id,
+                name: pair
+                    .author
+                    .as_ref()
+                    .map_or_else(|| NO_AUTHOR_GROUP.to_string(), |author| author.to_string()),
+                books: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[position].books.push(pair);
+    }
+
+    groups.sort_by(|a, b| {
+        a.author_id
+            .is_none()
+            .cmp(&b.author_id.is_none())
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.author_id.cmp(&b.author_id))
+    });
+    groups
+}
+
+/// Splits books into finished, owned and wishlist sections, newest first within each,
+/// books without the relevant date go last and empty sections are left out
+pub fn group_by_read_status(books: &[BookWithAuthor]) -> Vec<ReadStatusSection<'_>> {
+    [
+        SectionKey::Finished,
+        SectionKey::OwnedUnread,
+        SectionKey::Wishlist,
+    ]
+    .into_iter()
+    .filter_map(|key| {
+        let mut section: Vec<&BookWithAuthor> = books
+            .iter()
+            .filter(|pair| SectionKey::of(pair) == key)
+            .collect();
+        section.sort_by_key(|pair| Reverse(key.sort_date(pair)));
+
+        (!section.is_empty()).then_some(ReadStatusSection {
+            key,
+            books: section,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BookModel;
+    use chrono::NaiveDate;
+
+    fn book(id: ID, title: &str) -> BookWithAuthor {
+        BookWithAuthor {
+            book: BookModel::titled(id, title),
+            author: None,
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn top_authors_count_only_finished_books_and_keep_the_first_n() {
+        let author = |id: ID, name: &str| crate::models::AuthorModel {
+            Id: id,
+            Name: Some(name.to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        };
+        let by = |id: ID, title: &str, writer: Option<(ID, &str)>, finished: bool| {
+            let mut pair = book(id, title);
+            pair.book.AuthorFK = writer.map(|(author_id, _)| author_id);
+            pair.author = writer.map(|(author_id, name)| author(author_id, name));
+            pair.book.finished = finished.then(|| date(2024, 1, 1));
+            pair
+        };
+        let lem = Some((1, "Stanisław Lem"));
+        let austen = Some((2, "Jane Austen"));
+        let herbert = Some((3, "Frank Herbert"));
+        let books = vec![
+            by(1, "Solaris", lem, true),
+            by(2, "Eden", lem, true),
+            by(3, "Fiasco", lem, false),
+            by(4, "Emma", austen, true),
+            by(5, "Persuasion", austen, false),
+            by(6, "Dune", herbert, false),
+            by(7, "Beowulf", None, true),
+            by(8, "The Pearl", None, true),
+            by(9, "Gawain", None, false),
+        ];
+
+        let ranked = top_authors_by_finished(&books, 5);
+        assert_eq!(
+            ranked,
+            vec![
+                ("Stanisław Lem".to_string(), 2),
+                (UNKNOWN_AUTHOR.to_string(), 2),
+                ("Jane Austen".to_string(), 1),
+            ]
+        );
+        assert_eq!(top_authors_by_finished(&books, 1), ranked[..1].to_vec());
+        assert!(top_authors_by_finished(&books, 0).is_empty());
+    }
+
+    #[test]
+    fn the_average_price_leaves_unpriced_books_out_only_when_asked() {
+        let mut books = vec![
+            book(1, "Dune"),
+            book(2, "Emma"),
+            book(3, "Ulysses"),
+            book(4, "Mort"),
+        ];
+        books[0].book.price = Some(10.0);
+        books[1].book.price = Some(20.0);
+        books[2].book.price = Some(0.0); // Priced as free, unlike the unpriced Mort
+        assert_eq!(average_price(&books, true), Some(10.0));
+        assert_eq!(average_price(&books, false), Some(7.5));
+
+        books.iter_mut().for_each(|pair| pair.book.price = None);
+        assert_eq!(average_price(&books, true), None);
+        assert_eq!(average_price(&books, false), None);
+        assert_eq!(average_price(&[], true), None);
+    }
+
+    #[test]
+    fn the_favorite_is_the_highest_rated_book_finished_that_year() {
+        let mut books = vec![
+            book(3, "Emma"),
+            book(1, "Dune"),
+            book(2, "Ulysses"),
+            book(4, "Persuasion"),
+        ];
+        for (pair, rating) in books.iter_mut().zip([Some(5), Some(5), None, Some(5)]) {
+            pair.book.rating = rating;
+            pair.book.finished = Some(date(2025, 3, 1));
+        }
+        books[3].book.finished = Some(date(2024, 3, 1));
+
+        let review = year_in_review(&books, 2025, "zł");
+        assert_eq!(review.favorite.map(|pair| pair.book.id), Some(1));
+        assert!(year_in_review(&books, 2023, "zł").favorite.is_none());
+    }
+
+    #[test]
+    fn reading_duration_needs_both_dates_in_order() {
+        let start = Some(date(2025, 3, 1));
+        assert_eq!(reading_duration_days(None, start), None);
+        assert_eq!(reading_duration_days(start, None), None);
+        assert_eq!(reading_duration_days(start, Some(date(2025, 2, 27))), None);
+        assert_eq!(reading_duration_days(start, start), Some(0));
+        assert_eq!(reading_duration_days(start, Some(date(2025, 3, 15))), Some(14));
+    }
+
+    #[test]
+    fn price_extremes_skip_unpriced_books_and_break_ties_by_id() {
+        let mut books = vec![book(4, "Emma"), book(2, "Dune"), book(3, "Ulysses"), book(1, "Ada")];
+        for (pair, price) in books.iter_mut().zip([Some(30.0), Some(30.0), Some(5.0), None]) {
+            pair.book.price = price;
+        }
+        books.push(book(5, "Persuasion"));
+        books[4].book.price = Some(5.0);
+
+        let (most, least) = price_extremes(&books);
+        assert_eq!(most.map(|pair| pair.book.id), Some(2));
+        assert_eq!(least.map(|pair| pair.book.id), Some(3));
+        assert_eq!(price_extremes(&books[3..4]), (None, None));
+    }
+
+    fn series(id: ID, name: &str, total_volumes: Option<i32>) -> SeriesModel {
+        SeriesModel {
+            id,
+            name: name.to_string(),
+            total_volumes,
+        }
+    }
+
+    fn volume(id: ID, series_id: ID, bought: bool, finished: bool) -> BookWithAuthor {
+        let mut pair = book(id, &format!("Volume {}", id));
+        pair.book.SeriesFK = Some(series_id);
+        pair.book.bought = bought.then(|| date(2024, 1, 1));
+        pair.book.finished = finished.then(|| date(2024, 2, 1));
+        pair
+    }
+
+    #[test]
+    fn series_progress_counts_owned_and_finished_volumes_and_clamps_to_the_total() {
+        let all_series = [
+            series(1, "Discworld", Some(41)),
+            series(2, "Dune", Some(2)),
+            series(3, "Witcher", None),
+        ];
+        let books = vec![
+            volume(1, 1, true, true),
+            volume(2, 1, true, false),
+            volume(3, 1, false, true), // Borrowed, finished but not owned
+            volume(4, 2, true, true),
+            volume(5, 2, true, false),
+            volume(6, 2, true, false),
+            book(7, "Emma"),
+        ];
+
+        let progress = series_progress(&books, &all_series);
+        let counts: Vec<_> = progress
+            .iter()
+            .map(|p| (p.series_id, p.owned, p.finished, p.total))
+            .collect();
+        assert_eq!(counts, vec![(1, 2, 2, Some(41)), (2, 3, 1, Some(2)), (3, 0, 0, None)]);
+
+        assert_eq!(progress[0].summary(), "Discworld: own 2 of 41, finished 2");
+        assert!(!progress[0].exceeds_total());
+        assert!(progress[1].exceeds_total());
+        assert_eq!(progress[1].owned_clamped(), 2);
+        assert_eq!(progress[1].completion(), Some(1.0));
+        assert_eq!(progress[2].completion(), None);
+        assert_eq!(progress[2].summary(), "Witcher: own 0, finished 0");
+
+        let empty = series_progress(&books, &[series(4, "Odd", Some(-3))]);
+        assert_eq!(empty[0].total, Some(0));
+        assert_eq!(empty[0].completion(), None);
+    }
+
+    #[test]
+    fn series_by_completion_puts_the_most_complete_first_and_unknown_totals_last() {
+        let all_series = [
+            series(1, "Witcher", None),
+            series(2, "Discworld", Some(4)),
+            series(3, "Dune", Some(2)),
+            series(4, "Alpha", Some(4)),
+            series(5, "Asimov", None),
+        ];
+        let books = vec![
+            volume(1, 2, true, false),
+            volume(2, 3, true, false),
+            volume(3, 3, true, false),
+            volume(4, 3, true, false), // One more than the total, still counts as complete
+            volume(5, 4, true, false),
+        ];
+
+        let order: Vec<_> = series_by_completion(&books, &all_series)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(order, vec!["Dune", "Alpha", "Discworld", "Asimov", "Witcher"]);
+    }
+
+    #[test]
+    fn books_group_under_their_series_with_progress_over_all_books() {
+        let all_series = [series(1, "Discworld", Some(41)), series(2, "Dune", Some(6))];
+        let books = vec![
+            book(1, "Emma"),
+            volume(2, 2, true, true),
+            volume(3, 1, true, false),
+            volume(4, 2, true, false),
+            volume(5, 9, true, false), // Series not loaded yet
+        ];
+        let listed = vec![books[0].clone(), books[1].clone(), books[3].clone(), books[4].clone()];
+
+        let groups = group_by_series(&listed, &books, &all_series);
+        let shape: Vec<_> = groups
+            .iter()
+            .map(|group| {
+                let name = group.progress.as_ref().map(|p| p.name.as_str());
+                let ids: Vec<ID> = group.books.iter().map(|pair| pair.book.id).collect();
+                (name, ids)
+            })
+            .collect();
+        assert_eq!(shape, vec![(Some("Dune"), vec![2, 4]), (None, vec![1, 5])]);
+        assert_eq!(groups[0].progress.as_ref().map(|p| p.owned), Some(2));
+        assert!(group_by_series(&[], &books, &all_series).is_empty());
+    }
+}
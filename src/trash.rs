@@ -0,0 +1,133 @@
+// src/trash.rs
+use crate::models::{
+    AuthorModel, BookFieldModel, BookListItemModel, BookModel, JournalEntryModel, LoanModel,
+    QuoteModel, ReadingSessionModel, ID,
+};
+use chrono::NaiveDateTime;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Deletions remembered per session, older ones are dropped
+pub const RECENTLY_DELETED_MAX: usize = 10;
+
+/// A removed record with everything needed to insert it again
+#[derive(Debug, Clone)]
+pub enum DeletedItem {
+    Book(Box<DeletedBook>), // Boxed, a book with its records is large
+    Author {
+        author: AuthorModel,
+        books: Vec<ID>, // Linked to the author again on restore
+    },
+}
+
+/// A removed book and the records deleted along with it
+#[derive(Debug, Clone)]
+pub struct DeletedBook {
+    pub book: BookModel,
+    pub author: Option<AuthorModel>, // Recreated on restore if it is gone by then
+    pub journal: Vec<JournalEntryModel>,
+    pub quotes: Vec<QuoteModel>,
+    pub fields: Vec<BookFieldModel>,
+    pub loans: Vec<LoanModel>,
+    pub sessions: Vec<ReadingSessionModel>,
+    pub lists: Vec<BookListItemModel>, // Put back into the lists that still exist
+}
+
+impl fmt::Display for DeletedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeletedItem::Book(deleted) => {
+                match deleted.author.as_ref().and_then(|a| a.Name.as_deref()) {
+                    Some(name) => write!(f, "Book: {} by {}", deleted.book.title, name),
+                    None => write!(f, "Book: {}", deleted.book.title),
+                }
+            }
+            DeletedItem::Author { author, .. } => {
+                write!(
+                    f,
+                    "Author: {}",
+                    author.Name.as_deref().unwrap_or("Unnamed Author")
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeletedEntry {
+    pub deleted_at: NaiveDateTime,
+    pub item: DeletedItem,
+}
+
+/// Bounded list of this session's deletions, newest first
+#[derive(Debug, Clone, Default)]
+pub struct RecentlyDeleted {
+    entries: VecDeque<DeletedEntry>,
+}
+
+impl RecentlyDeleted {
+    pub fn push(&mut self, item: DeletedItem, deleted_at: NaiveDateTime) {
+        self.entries.push_front(DeletedEntry { deleted_at, item });
+        self.entries.truncate(RECENTLY_DELETED_MAX);
+    }
+
+    /// Removes the entry so it can be restored, `put_back` returns it if that fails
+    pub fn take(&mut self, index: usize) -> Option<DeletedEntry> {
+        self.entries.remove(index)
+    }
+
+    /// Returns an entry whose restore failed to its place by deletion time
+    pub fn put_back(&mut self, entry: DeletedEntry) {
+        let index = self
+            .entries
+            .iter()
+            .position(|other| other.deleted_at < entry.deleted_at)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+        self.entries.truncate(RECENTLY_DELETED_MAX);
+    }
+
+    /// Drops a deleted author once restoring a book brought it back
+    pub fn forget_author(&mut self, id: ID) {
+        self.entries.retain(
+            |entry| !matches!(&entry.item, DeletedItem::Author { author, .. } if author.Id == id),
+        );
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &DeletedEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// What restoring an entry did, ids may differ from the deleted ones
+#[derive(Debug, Clone)]
+pub struct RestoreOutcome {
+    pub label: String,
+    pub original_id: ID,
+    pub restored_id: ID,
+    pub recreated_author: Option<ID>, // Original id of an author inserted again for a book
+}
+
+impl RestoreOutcome {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Restored {}", self.label);
+        if self.restored_id != self.original_id {
+            summary.push_str(&format!(
+                " as #{}, its old id #{} is taken",
+                self.restored_id, self.original_id
+            ));
+        }
+        if self.recreated_author.is_some() {
+            summary.push_str(", its author was recreated");
+        }
+        summary
+    }
+}
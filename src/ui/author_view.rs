@@ -1,12 +1,26 @@
 // src/ui/author_view.rs
 use crate::db;
-use crate::models::{AuthorModel, BookWithAuthor, NewAuthor, ID};
+use crate::fuzzy::{self, NameMatch};
+use crate::links;
+use crate::paths;
+use crate::report;
+use crate::models::{join_aliases, split_aliases, AuthorModel, BookWithAuthor, NewAuthor, ID};
+use crate::stats::{self, AuthorCompletion, SectionKey};
+use crate::trash::DeletedItem;
 use crate::ui::components::searchable_dropdown::SearchableDropdown;
-use crate::ui::{BookshelfApp, Message, Mode};
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Row};
+use crate::ui::{settings_view, trash_view};
+use crate::ui::{
+    author_index_letter, author_index_letters, first_author_with_letter, format_price,
+    sort_authors, AuthorSort, BookshelfApp, FontSizes, Message, Mode, CURRENCY, LIST_MAX_WIDTH,
+};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, progress_bar, row, scrollable, text,
+    text_input, Column, Row,
+};
+use iced::widget::scrollable::RelativeOffset;
 use iced::Fill;
 use iced::{Element, Length};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // Book statistics struct
 #[derive(Debug, Clone, Default)]
@@ -39,10 +53,11 @@ fn calculate_author_stats(books_with_author: &[BookWithAuthor]) -> HashMap<ID, B
 }
 
 // Handler functions for author-related messages
-pub fn handle_load_authors(_: &mut BookshelfApp) -> iced::Task<Message> {
+pub fn handle_load_authors(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.authors_loading = true;
     iced::Task::perform(
         async {
-            match db::get_authors() {
+            match db::cached::get_authors() {
                 Ok(authors) => Ok(authors),
                 Err(e) => Err(e.to_string()),
             }
@@ -55,13 +70,15 @@ pub fn handle_authors_loaded(
     app: &mut BookshelfApp,
     result: Result<Vec<AuthorModel>, String>,
 ) -> iced::Task<Message> {
+    app.authors_loading = false;
     match result {
-        Ok(authors) => {
+        Ok(mut authors) => {
+            sort_authors(&mut authors);
             app.authors = authors.clone();
             app.author_dropdown = SearchableDropdown::new(authors, app.selected_author.clone());
         }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(e);
         }
     }
     iced::Task::none()
@@ -71,6 +88,10 @@ pub fn handle_add_author_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
     app.mode = Mode::Add;
     app.current_author = None;
     app.author_name = String::new();
+    app.author_website = String::new();
+    app.author_wikipedia = String::new();
+    app.author_published_works = String::new();
+    app.author_aliases = String::new();
     iced::Task::none()
 }
 
@@ -78,6 +99,13 @@ pub fn handle_edit_author_mode(app: &mut BookshelfApp, author: AuthorModel) -> i
     app.mode = Mode::Edit;
     app.current_author = Some(author.clone());
     app.author_name = author.Name.unwrap_or_default();
+    app.author_website = author.website.unwrap_or_default();
+    app.author_wikipedia = author.wikipedia.unwrap_or_default();
+    app.author_published_works = author
+        .published_works
+        .map(|count| count.to_string())
+        .unwrap_or_default();
+    app.author_aliases = author.Aliases.unwrap_or_default();
     iced::Task::none()
 }
 
@@ -95,6 +123,7 @@ pub fn handle_view_author_details(
 ) -> iced::Task<Message> {
     app.mode = Mode::ViewDetails;
     app.current_author = Some(author.clone());
+    app.collapsed_sections.clear();
 
     // Load books by this author
     iced::Task::perform(
@@ -117,20 +146,100 @@ pub fn handle_author_books_loaded(
             app.author_books = books;
         }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(e);
         }
     }
     iced::Task::none()
 }
 
+pub fn handle_toggle_author_section(app: &mut BookshelfApp, key: SectionKey) -> iced::Task<Message> {
+    if !app.collapsed_sections.remove(&key) {
+        app.collapsed_sections.insert(key);
+    }
+    iced::Task::none()
+}
+
 pub fn handle_author_name_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
     app.author_name = value;
     iced::Task::none()
 }
 
+pub fn handle_author_website_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.author_website = value;
+    iced::Task::none()
+}
+
+pub fn handle_author_wikipedia_changed(
+    app: &mut BookshelfApp,
+    value: String,
+) -> iced::Task<Message> {
+    app.author_wikipedia = value;
+    iced::Task::none()
+}
+
+pub fn handle_author_published_works_changed(
+    app: &mut BookshelfApp,
+    value: String,
+) -> iced::Task<Message> {
+    app.author_published_works = value;
+    iced::Task::none()
+}
+
+pub fn handle_author_aliases_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.author_aliases = value;
+    iced::Task::none()
+}
+
+pub fn handle_author_sort_selected(
+    app: &mut BookshelfApp,
+    sort: AuthorSort,
+) -> iced::Task<Message> {
+    app.author_sort = sort;
+    iced::Task::none()
+}
+
+// Empty inputs clear the link, anything else has to be an http(s) address
+fn parse_link(value: &str, label: &str) -> Result<Option<String>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        Ok(None)
+    } else if links::is_web_url(value) {
+        Ok(Some(value.to_string()))
+    } else {
+        Err(format!("{} must start with http:// or https://", label))
+    }
+}
+
 pub fn handle_save_author(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let (website, wikipedia) = match (
+        parse_link(&app.author_website, "Website"),
+        parse_link(&app.author_wikipedia, "Wikipedia link"),
+    ) {
+        (Ok(website), Ok(wikipedia)) => (website, wikipedia),
+        (Err(e), _) | (_, Err(e)) => {
+            app.error = Some(e);
+            return iced::Task::none();
+        }
+    };
+
+    let published_works = if app.author_published_works.trim().is_empty() {
+        None
+    } else {
+        match app.author_published_works.trim().parse::<i32>() {
+            Ok(count) if count >= 0 => Some(count),
+            _ => {
+                app.error = Some("Published works must be a whole number".to_string());
+                return iced::Task::none();
+            }
+        }
+    };
+
     let new_author = NewAuthor {
         Name: Some(app.author_name.clone()),
+        website,
+        wikipedia,
+        published_works,
+        Aliases: join_aliases(split_aliases(&app.author_aliases)),
     };
 
     // Extract author_id outside the closure if we're in edit mode
@@ -165,7 +274,7 @@ pub fn handle_author_saved(
             app.update(Message::LoadAuthors)
         }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(e);
             iced::Task::none()
         }
     }
@@ -192,7 +301,7 @@ pub fn handle_delete_author(_: &mut BookshelfApp, id: ID) ->
     iced::Task::perform(
         async move {
             match db::delete_author(id) {
-                Ok(count) => Ok(count),
+                Ok(deleted) => Ok(deleted),
                 Err(e) => Err(e.to_string()),
             }
         },
@@ -202,23 +311,369 @@ pub fn handle_delete_author(_: &mut BookshelfApp, id: ID) ->
 
 pub fn handle_author_deleted(
     app: &mut BookshelfApp,
-    result: Result<usize, String>,
+    result: Result<DeletedItem, String>,
 ) -> iced::Task<Message> {
     app.mode = Mode::View; // Ensure we go back to view mode
 
     match result {
-        Ok(_) => app.update(Message::LoadAuthors),
+        Ok(deleted) => {
+            trash_view::record_deletion(app, deleted);
+            app.update(Message::LoadAuthors)
+        }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(e);
             app.update(Message::LoadAuthors) // Always go back to author list even on error
         }
     }
 }
 
+pub fn handle_export_authors(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let path = paths::exports_dir().join("authors.csv");
+    let profile = app.config.export_profile;
+
+    iced::Task::perform(
+        async move {
+            let rows = match db::get_author_export_rows() {
+                Ok(rows) => rows,
+                Err(e) => return Err(e.to_string()),
+            };
+            match report::write_authors_csv(&rows, &profile, &path) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::AuthorsExported,
+    )
+}
+
+pub fn handle_authors_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => app.notify_finished(format!("Saved authors to {}", path)),
+        Err(e) => app.notify_failed(format!("Failed to export authors: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_find_orphan_authors(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_authors_without_books() {
+                Ok(authors) => Ok(authors),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::OrphanAuthorsLoaded,
+    )
+}
+
+pub fn handle_orphan_authors_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<AuthorModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(authors) => {
+            // Everything starts checked, the user unticks what should stay
+            app.orphan_selection = authors.iter().map(|author| author.Id).collect();
+            app.orphan_authors = Some(authors);
+        }
+        Err(e) => {
+            app.report_error(e);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_toggle_orphan_author(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if !app.orphan_selection.remove(&id) {
+        app.orphan_selection.insert(id);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_delete_orphan_authors(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let ids: Vec<ID> = app.orphan_selection.iter().copied().collect();
+
+    iced::Task::perform(
+        async move {
+            match db::delete_authors_without_books(&ids) {
+                Ok(count) => Ok(count),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::OrphanAuthorsDeleted,
+    )
+}
+
+pub fn handle_orphan_authors_deleted(
+    app: &mut BookshelfApp,
+    result: Result<usize, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(count) => {
+            app.orphan_authors = None;
+            app.orphan_selection.clear();
+            app.status_message = Some(format!("Deleted {} authors without books", count));
+            app.update(Message::LoadAuthors)
+        }
+        Err(e) => {
+            app.report_error(e);
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_close_orphan_authors(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.orphan_authors = None;
+    app.orphan_selection.clear();
+    iced::Task::none()
+}
+
+/// Two authors that look like the same person, offered for merging
+#[derive(Debug, Clone)]
+pub struct MergeSuggestion {
+    pub pair: (ID, ID), // Lower id first, the way dismissals are stored
+    pub names: (String, String),
+    pub books: (usize, usize),
+    pub reason: NameMatch,
+    pub keep: ID, // Author left after the merge, the one with more books unless chosen
+}
+
+impl MergeSuggestion {
+    fn involves(&self, id: ID) -> bool {
+        self.pair.0 == id || self.pair.1 == id
+    }
+
+    // The author that disappears in the merge
+    fn removed(&self) -> ID {
+        if self.keep == self.pair.0 {
+            self.pair.1
+        } else {
+            self.pair.0
+        }
+    }
+}
+
+// Pairs of named authors with similar names, leaving out the dismissed ones
+fn merge_suggestions(
+    authors: &[AuthorModel],
+    books: &[BookWithAuthor],
+    dismissed: &[(ID, ID)],
+    fold_diacritics: bool,
+) -> Vec<MergeSuggestion> {
+    let mut counts: HashMap<ID, usize> = HashMap::new();
+    for id in books.iter().filter_map(|pair| pair.book.AuthorFK) {
+        *counts.entry(id).or_default() += 1;
+    }
+    let named: Vec<(ID, &str)> = authors
+        .iter()
+        .filter_map(|author| author.Name.as_deref().map(|name| (author.Id, name)))
+        .collect();
+    let names: Vec<&str> = named.iter().map(|(_, name)| *name).collect();
+
+    fuzzy::similar_name_pairs(&names, fold_diacritics)
+        .into_iter()
+        .filter_map(|(i, j, reason)| {
+            let (a, b) = if named[i].0 < named[j].0 {
+                (named[i], named[j])
+            } else {
+                (named[j], named[i])
+            };
+            let pair = (a.0, b.0);
+            if dismissed.contains(&pair) {
+                return None;
+            }
+            let books = (
+                counts.get(&a.0).copied().unwrap_or(0),
+                counts.get(&b.0).copied().unwrap_or(0),
+            );
+            Some(MergeSuggestion {
+                pair,
+                names: (a.1.to_string(), b.1.to_string()),
+                books,
+                reason,
+                keep: if books.1 > books.0 { b.0 } else { a.0 },
+            })
+        })
+        .collect()
+}
+
+pub fn handle_find_merge_suggestions(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_merge_dismissals() {
+                Ok(pairs) => Ok(pairs),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::MergeDismissalsLoaded,
+    )
+}
+
+pub fn handle_merge_dismissals_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<(ID, ID)>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(dismissed) => {
+            app.merge_suggestions = Some(merge_suggestions(
+                &app.authors,
+                &app.books,
+                &dismissed,
+                app.config.merge_fold_diacritics,
+            ));
+        }
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_merge_keep_selected(
+    app: &mut BookshelfApp,
+    pair: (ID, ID),
+    keep: ID,
+) -> iced::Task<Message> {
+    if let Some(suggestion) = app
+        .merge_suggestions
+        .iter_mut()
+        .flatten()
+        .find(|suggestion| suggestion.pair == pair)
+    {
+        suggestion.keep = keep;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_accept_merge(app: &mut BookshelfApp, pair: (ID, ID)) -> iced::Task<Message> {
+    let Some(suggestion) = app
+        .merge_suggestions
+        .iter()
+        .flatten()
+        .find(|suggestion| suggestion.pair == pair)
+    else {
+        return iced::Task::none();
+    };
+    let (keep, remove) = (suggestion.keep, suggestion.removed());
+
+    iced::Task::perform(
+        async move {
+            match db::merge_authors(keep, remove) {
+                Ok(moved) => Ok((keep, remove, moved)),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::AuthorsMerged,
+    )
+}
+
+pub fn handle_authors_merged(
+    app: &mut BookshelfApp,
+    result: Result<(ID, ID, usize), String>,
+) -> iced::Task<Message> {
+    let (keep, remove, moved) = match result {
+        Ok(merged) => merged,
+        Err(e) => {
+            app.report_error(format!("Failed to merge authors: {}", e));
+            return iced::Task::none();
+        }
+    };
+    let name_of = |id: ID| {
+        app.authors
+            .iter()
+            .find(|author| author.Id == id)
+            .and_then(|author| author.Name.clone())
+            .unwrap_or_else(|| "Unnamed Author".to_string())
+    };
+    app.status_message = Some(format!(
+        "Merged '{}' into '{}', {} books moved",
+        name_of(remove),
+        name_of(keep),
+        moved
+    ));
+
+    // Other pairs of the removed author are gone, the kept author now has its books
+    if let Some(suggestions) = &mut app.merge_suggestions {
+        suggestions.retain(|suggestion| !suggestion.involves(remove));
+        for suggestion in suggestions.iter_mut() {
+            if suggestion.pair.0 == keep {
+                suggestion.books.0 += moved;
+            } else if suggestion.pair.1 == keep {
+                suggestion.books.1 += moved;
+            }
+        }
+    }
+    iced::Task::batch(vec![
+        app.update(Message::LoadAuthors),
+        app.update(Message::LoadBooks),
+    ])
+}
+
+pub fn handle_dismiss_merge(_: &mut BookshelfApp, pair: (ID, ID)) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::dismiss_author_merge(pair.0, pair.1) {
+                Ok(()) => Ok(pair),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::MergeDismissed,
+    )
+}
+
+pub fn handle_merge_dismissed(
+    app: &mut BookshelfApp,
+    result: Result<(ID, ID), String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(pair) => {
+            if let Some(suggestions) = &mut app.merge_suggestions {
+                suggestions.retain(|suggestion| suggestion.pair != pair);
+            }
+        }
+        Err(e) => app.report_error(format!("Failed to dismiss the suggestion: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_toggle_merge_fold_diacritics(
+    app: &mut BookshelfApp,
+    fold: bool,
+) -> iced::Task<Message> {
+    app.config.merge_fold_diacritics = fold;
+    iced::Task::batch(vec![
+        settings_view::save_config(&app.config),
+        handle_find_merge_suggestions(app),
+    ])
+}
+
+pub fn author_list_id() -> scrollable::Id {
+    scrollable::Id::new("author_list")
+}
+
+pub fn handle_jump_to_letter(app: &mut BookshelfApp, letter: char) -> iced::Task<Message> {
+    let Some(index) = first_author_with_letter(&app.authors, letter) else {
+        return iced::Task::none();
+    };
+
+    // Author rows have the same height, so the index maps onto the scroll position
+    let y = if app.authors.len() > 1 {
+        index as f32 / (app.authors.len() - 1) as f32
+    } else {
+        0.0
+    };
+    scrollable::snap_to(author_list_id(), RelativeOffset { x: 0.0, y })
+}
+
 // View functions for authors
 pub fn view(app: &BookshelfApp) -> Element<Message> {
     match app.mode {
-        Mode::View => view_author_list(app),
+        Mode::View => match (&app.orphan_authors, &app.merge_suggestions) {
+            (Some(orphans), _) => view_orphan_authors(orphans, &app.orphan_selection, app.fonts()),
+            (None, Some(suggestions)) => view_merge_suggestions(app, suggestions),
+            (None, None) => view_author_list(app),
+        },
         Mode::ViewDetails => view_author_details(app),
         Mode::Add | Mode::Edit => view_author_form(app),
         Mode::ConfirmDelete(id, ref name) => view_delete_confirmation(app, id, name),
@@ -226,41 +681,111 @@ pub fn view(app: &BookshelfApp) -> Element<Message> {
 }
 
 fn view_author_list(app: &BookshelfApp) -> Element<Message> {
+    let fonts = app.fonts();
     let add_button = button("Add New Author")
         .on_press(Message::AddAuthorMode)
         .style(button::primary);
 
     let author_list = if app.authors.is_empty() {
-        column![text("No authors found").size(16)]
+        let label = if app.authors_loading {
+            "Loading…"
+        } else {
+            "No authors found"
+        };
+        column![text(label).size(fonts.body())]
             .spacing(5)
             .width(Length::Fill)
     } else {
         create_authors_list(app)
     };
 
+    // Jumping to a letter only makes sense while the list is in name order
+    let letter_index: Element<Message> = match app.author_sort {
+        AuthorSort::Name => create_letter_index(app),
+        AuthorSort::Completion => column![].into(),
+    };
+
     column![
         row![
-            text("Authors").size(24),
+            text("Authors").size(fonts.title()),
             iced::widget::horizontal_space(),
+            text("Sort by:").size(fonts.body()),
+            pick_list(
+                AuthorSort::ALL,
+                Some(app.author_sort),
+                Message::AuthorSortSelected
+            ),
+            button("Export authors…")
+                .on_press(Message::ExportAuthors)
+                .style(button::secondary),
+            button("Find authors with no books")
+                .on_press(Message::FindOrphanAuthors)
+                .style(button::secondary),
+            button("Suggest author merges")
+                .on_press(Message::FindMergeSuggestions)
+                .style(button::secondary),
             add_button
         ]
+        .spacing(10)
         .padding(10)
+        .align_y(iced::alignment::Vertical::Center)
         .width(Length::Fill),
-        scrollable(container(author_list).padding(10).width(Length::Fill)).height(Length::Fill)
+        letter_index,
+        scrollable(container(author_list).padding(10).width(Length::Fill))
+            .id(author_list_id())
+            .height(Length::Fill)
     ]
     .spacing(20)
     .padding(20)
     .into()
 }
 
+// Row of letter buttons, letters without authors are disabled
+fn create_letter_index(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let present: HashSet<char> = app.authors.iter().map(author_index_letter).collect();
+
+    author_index_letters(&app.authors)
+        .into_iter()
+        .fold(row![].spacing(4), |index, letter| {
+            index.push(
+                button(text(letter.to_string()).size(fonts.small()))
+                    .on_press_maybe(
+                        present
+                            .contains(&letter)
+                            .then_some(Message::JumpToAuthorLetter(letter)),
+                    )
+                    .style(button::text)
+                    .padding(4),
+            )
+        })
+        .wrap()
+        .into()
+}
+
 fn create_authors_list<'a>(app: &BookshelfApp) -> Column<Message> {
     let mut list = column![].spacing(10).width(Length::Fill);
 
     let author_stats = calculate_author_stats(&app.books);
+    let completion = |author: &AuthorModel| {
+        let owned = author_stats.get(&author.Id).map_or(0, |stats| stats.bought);
+        AuthorCompletion::new(owned, author.published_works)
+    };
+
+    let mut authors: Vec<&AuthorModel> = app.authors.iter().collect();
+    if app.author_sort == AuthorSort::Completion {
+        // Stable, so authors with the same completion stay in name order
+        authors.sort_by(|a, b| match (completion(a).completion(), completion(b).completion()) {
+            (Some(a_c), Some(b_c)) => b_c.partial_cmp(&a_c).unwrap_or(std::cmp::Ordering::Equal),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
 
-    for author in &app.authors {
+    for author in authors {
         list = list.push(
-            container(create_author_row(&author_stats, author))
+            container(create_author_row(&author_stats, author, app.fonts()))
                 .padding(10)
                 .style(container::bordered_box),
         );
@@ -272,6 +797,7 @@ fn create_authors_list<'a>(app: &BookshelfApp) -> Column<Message> {
 fn create_author_row<'a>(
     author_stats: &HashMap<ID, BookStats>,
     author: &AuthorModel,
+    fonts: FontSizes,
 ) -> Row<'a, Message> {
     let author_name = author
         .Name
@@ -279,19 +805,25 @@ fn create_author_row<'a>(
         .unwrap_or_else(|| "Unnamed Author".to_string());
 
     let stats = author_stats.get(&author.Id).cloned().unwrap_or_default();
+    let completion = AuthorCompletion::new(stats.bought, author.published_works);
 
-    row![
-        column![
-            text(author_name).size(18),
-            row![
-                text(format!("Bought: {}", stats.bought)).size(14),
-                text(format!("Not bought: {}", stats.not_bought)).size(14),
-                text(format!("Finished: {}", stats.finished)).size(14),
-            ]
-            .spacing(10)
+    let mut details = column![
+        text(author_name).size(fonts.item()),
+        row![
+            text(format!("Bought: {}", stats.bought)).size(fonts.small()),
+            text(format!("Not bought: {}", stats.not_bought)).size(fonts.small()),
+            text(format!("Finished: {}", stats.finished)).size(fonts.small()),
         ]
-        .spacing(5)
-        .width(Length::Fill),
+        .spacing(10)
+    ]
+    .spacing(5)
+    .width(Length::Fill);
+    if completion.published.is_some() {
+        details = details.push(create_author_completion(&completion, fonts));
+    }
+
+    row![
+        details,
         button("View")
             .on_press(Message::ViewAuthorDetails(author.clone()))
             .style(button::secondary),
@@ -312,7 +844,156 @@ fn create_author_row<'a>(
     .align_y(iced::alignment::Vertical::Center)
 }
 
+/// Owned of published summary with a thin progress bar, hidden ratio when the count is zero
+fn create_author_completion<'a>(
+    completion: &AuthorCompletion,
+    fonts: FontSizes,
+) -> Column<'a, Message> {
+    let mut content = column![text(completion.summary()).size(fonts.small())].spacing(5);
+
+    if let Some(fraction) = completion.completion() {
+        content = content.push(progress_bar(0.0..=1.0, fraction).height(6));
+    }
+
+    if completion.exceeds_published() {
+        content = content.push(
+            text(format!(
+                "You own {} books, more than the declared number of published works",
+                completion.owned
+            ))
+            .size(fonts.tiny()),
+        );
+    }
+
+    content
+}
+
+fn view_orphan_authors<'a>(
+    orphans: &[AuthorModel],
+    selection: &HashSet<ID>,
+    fonts: FontSizes,
+) -> Element<'a, Message> {
+    let mut list = column![].spacing(10).width(Length::Fill);
+
+    if orphans.is_empty() {
+        list = list.push(text("Every author has at least one book").size(fonts.body()));
+    }
+
+    for author in orphans {
+        let id = author.Id;
+        let name = author
+            .Name
+            .clone()
+            .unwrap_or_else(|| "Unnamed Author".to_string());
+
+        list = list.push(
+            container(
+                checkbox(name, selection.contains(&id))
+                    .on_toggle(move |_| Message::ToggleOrphanAuthor(id))
+                    .text_size(fonts.item()),
+            )
+            .padding(10)
+            .width(Length::Fill)
+            .style(container::bordered_box),
+        );
+    }
+
+    column![
+        row![
+            text("Authors with no books").size(fonts.title()),
+            iced::widget::horizontal_space(),
+            button(text(format!("Delete selected ({})", selection.len())))
+                .on_press_maybe((!selection.is_empty()).then_some(Message::DeleteOrphanAuthors))
+                .style(button::danger),
+            button("Back")
+                .on_press(Message::CloseOrphanAuthors)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .padding(10)
+        .width(Length::Fill),
+        scrollable(container(list).padding(10).width(Length::Fill)).height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
+fn view_merge_suggestions<'a>(
+    app: &'a BookshelfApp,
+    suggestions: &'a [MergeSuggestion],
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let mut list = column![].spacing(10).width(Length::Fill);
+
+    if suggestions.is_empty() {
+        list = list.push(text("No authors look like duplicates").size(fonts.body()));
+    }
+
+    for suggestion in suggestions {
+        let pair = suggestion.pair;
+        let keep_button = |id: ID, name: &str, books: usize| {
+            button(text(format!("Keep {} ({} books)", name, books)).size(fonts.small()))
+                .on_press(Message::MergeKeepSelected(pair, id))
+                .style(if suggestion.keep == id {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+        };
+
+        list = list.push(
+            container(
+                column![
+                    text(format!("{} and {}", suggestion.names.0, suggestion.names.1))
+                        .size(fonts.item()),
+                    text(suggestion.reason.to_string()).size(fonts.small()),
+                    row![
+                        keep_button(pair.0, &suggestion.names.0, suggestion.books.0),
+                        keep_button(pair.1, &suggestion.names.1, suggestion.books.1),
+                        iced::widget::horizontal_space(),
+                        button("Merge")
+                            .on_press(Message::AcceptMerge(pair))
+                            .style(button::primary),
+                        button("Not the same")
+                            .on_press(Message::DismissMerge(pair))
+                            .style(button::secondary),
+                    ]
+                    .spacing(10)
+                    .align_y(iced::alignment::Vertical::Center),
+                ]
+                .spacing(5),
+            )
+            .padding(10)
+            .width(Length::Fill)
+            .style(container::bordered_box),
+        );
+    }
+
+    column![
+        row![
+            text("Possible duplicate authors").size(fonts.title()),
+            iced::widget::horizontal_space(),
+            checkbox("Ignore diacritics", app.config.merge_fold_diacritics)
+                .on_toggle(Message::ToggleMergeFoldDiacritics)
+                .text_size(fonts.body()),
+            button("Back")
+                .on_press(Message::CloseMergeSuggestions)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .padding(10)
+        .align_y(iced::alignment::Vertical::Center)
+        .width(Length::Fill),
+        scrollable(container(list).padding(10).width(Length::Fill)).height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
 fn view_author_details(app: &BookshelfApp) -> Element<Message> {
+    let fonts = app.fonts();
     if let Some(author) = &app.current_author {
         let author_name = author
             .Name
@@ -338,9 +1019,12 @@ fn view_author_details(app: &BookshelfApp) -> Element<Message> {
             .style(button::danger);
 
         let header = row![
-            text(format!("Author: {}", author_name)).size(24),
+            text(format!("Author: {}", author_name)).size(fonts.title()),
             iced::widget::horizontal_space(),
             back_button,
+            button("Print view")
+                .on_press(Message::TogglePrintView)
+                .style(button::secondary),
             edit_button,
             delete_button,
         ]
@@ -348,63 +1032,80 @@ fn view_author_details(app: &BookshelfApp) -> Element<Message> {
         .padding(10)
         .width(Length::Fill);
 
+        let mut link_row = row![].spacing(10).padding([0, 10]);
+        if let Some(website) = &author.website {
+            link_row = link_row.push(
+                button("Website")
+                    .on_press(Message::OpenUrl(website.clone()))
+                    .style(button::secondary),
+            );
+        }
+        link_row = link_row.push(match &author.wikipedia {
+            Some(wikipedia) => button("Wikipedia")
+                .on_press(Message::OpenUrl(wikipedia.clone()))
+                .style(button::secondary),
+            None => button("Search Wikipedia")
+                .on_press(Message::OpenUrl(links::wikipedia_search_url(&author_name)))
+                .style(button::secondary),
+        });
+
+        let aliases: Vec<&str> = author.aliases().collect();
+        let aliases_row = if aliases.is_empty() {
+            container(column![])
+        } else {
+            container(text(format!("Also known as: {}", aliases.join(", "))).size(fonts.body()))
+                .padding([0, 10])
+        };
+
+        let owned = app
+            .author_books
+            .iter()
+            .filter(|pair| pair.book.bought.is_some())
+            .count();
+        let completion = AuthorCompletion::new(owned, author.published_works);
+        let completion_row = if completion.published.is_some() {
+            container(create_author_completion(&completion, fonts))
+                .padding([0, 10])
+                .max_width(fonts.width(LIST_MAX_WIDTH))
+        } else {
+            container(column![])
+        };
+
         let book_count = app.author_books.len();
         let book_list = if book_count == 0 {
-            column![text("No books found for this author").size(16)]
+            column![text("No books found for this author").size(fonts.body())]
                 .spacing(5)
                 .width(Length::Fill)
                 .padding(20)
         } else {
             let mut col =
-                column![text(format!("Books by {} ({})", author_name, book_count)).size(20)]
+                column![text(format!("Books by {} ({})", author_name, book_count)).size(fonts.heading())]
                     .spacing(15)
                     .width(Length::Fill)
                     .padding(20);
 
-            for pair in &app.author_books {
-                let price_text = pair
-                    .book
-                    .price
-                    .map(|p| format!("{:.2}zł", p))
-                    .unwrap_or_else(|| "No price".to_string());
-
-                let status_text = {
-                    let mut statuses = Vec::new();
-
-                    if pair.book.bought.is_some() {
-                        statuses.push("Bought");
-                    } else {
-                        statuses.push("Not bought");
-                    }
-
-                    if pair.book.finished.is_some() {
-                        statuses.push("Finished");
-                    }
-
-                    statuses.join(" · ")
-                };
-
-                let book_row = row![
-                    column![
-                        text(&pair.book.title).size(18),
-                        row![text(price_text).size(14), text(status_text).size(14)].spacing(10)
-                    ]
-                    .spacing(8)
-                    .width(Length::Fill),
-                    button("View in Books")
-                        .on_press(Message::TabSelected(crate::ui::Tab::Books))
-                        .style(button::secondary)
-                        .padding(8),
-                ]
-                .spacing(15)
-                .padding(10)
-                .align_y(iced::alignment::Vertical::Center);
+            for section in stats::group_by_read_status(&app.author_books) {
+                let collapsed = app.collapsed_sections.contains(&section.key);
+                let header = format!(
+                    "{} {} ({}) · {}",
+                    if collapsed { "▸" } else { "▾" },
+                    section.key.label(),
+                    section.books.len(),
+                    format_price(section.total_spent(), CURRENCY, app.privacy_mode)
+                );
 
                 col = col.push(
-                    container(book_row)
-                        .padding(10)
-                        .style(container::bordered_box),
+                    button(text(header).size(fonts.item()))
+                        .on_press(Message::ToggleAuthorSection(section.key))
+                        .style(button::text)
+                        .padding(0),
                 );
+
+                if !collapsed {
+                    for pair in section.books {
+                        col = col.push(create_author_book_row(pair, fonts, app.privacy_mode));
+                    }
+                }
             }
 
             col
@@ -412,6 +1113,9 @@ fn view_author_details(app: &BookshelfApp) -> Element<Message> {
 
         column![
             header,
+            aliases_row,
+            link_row,
+            completion_row,
             scrollable(container(book_list).width(Length::Fill)).height(Length::Fill)
         ]
         .spacing(20)
@@ -423,7 +1127,61 @@ fn view_author_details(app: &BookshelfApp) -> Element<Message> {
     }
 }
 
+fn create_author_book_row<'a>(
+    pair: &'a BookWithAuthor,
+    fonts: FontSizes,
+    privacy_mode: bool,
+) -> Element<'a, Message> {
+    let price_text = pair
+        .book
+        .price
+        .map(|p| format_price(p, CURRENCY, privacy_mode))
+        .unwrap_or_else(|| "No price".to_string());
+
+    let status_text = {
+        let mut statuses = Vec::new();
+
+        if pair.book.bought.is_some() {
+            statuses.push("Bought");
+        } else {
+            statuses.push("Not bought");
+        }
+
+        if pair.book.finished.is_some() {
+            statuses.push("Finished");
+        }
+
+        statuses.join(" · ")
+    };
+
+    let book_row = row![
+        column![
+            text(&pair.book.title).size(fonts.item()),
+            row![
+                text(price_text).size(fonts.small()),
+                text(status_text).size(fonts.small())
+            ]
+            .spacing(10)
+        ]
+        .spacing(8)
+        .width(Length::Fill),
+        button("View in Books")
+            .on_press(Message::TabSelected(crate::ui::Tab::Books))
+            .style(button::secondary)
+            .padding(8),
+    ]
+    .spacing(15)
+    .padding(10)
+    .align_y(iced::alignment::Vertical::Center);
+
+    container(book_row)
+        .padding(10)
+        .style(container::bordered_box)
+        .into()
+}
+
 fn view_author_form(app: &BookshelfApp) -> Element<Message> {
+    let fonts = app.fonts();
     let title = match app.mode {
         Mode::Add => "Add New Author",
         Mode::Edit => "Edit Author",
@@ -431,11 +1189,27 @@ fn view_author_form(app: &BookshelfApp) -> Element<Message> {
     };
 
     let form = column![
-        text(title).size(24),
-        text("Name:").size(16),
+        text(title).size(fonts.title()),
+        text("Name:").size(fonts.body()),
         text_input("Enter author name", &app.author_name)
             .on_input(Message::AuthorNameChanged)
             .padding(10),
+        text("Website:").size(fonts.body()),
+        text_input("https://... (optional)", &app.author_website)
+            .on_input(Message::AuthorWebsiteChanged)
+            .padding(10),
+        text("Wikipedia:").size(fonts.body()),
+        text_input("https://en.wikipedia.org/wiki/... (optional)", &app.author_wikipedia)
+            .on_input(Message::AuthorWikipediaChanged)
+            .padding(10),
+        text("Published works:").size(fonts.body()),
+        text_input("Number of books published (optional)", &app.author_published_works)
+            .on_input(Message::AuthorPublishedWorksChanged)
+            .padding(10),
+        text("Also known as:").size(fonts.body()),
+        text_input("Pen names, separated by commas (optional)", &app.author_aliases)
+            .on_input(Message::AuthorAliasesChanged)
+            .padding(10),
         row![
             button("Save")
                 .on_press(Message::SaveAuthor)
@@ -448,7 +1222,7 @@ fn view_author_form(app: &BookshelfApp) -> Element<Message> {
     ]
     .spacing(10)
     .padding(20)
-    .max_width(500);
+    .max_width(fonts.width(LIST_MAX_WIDTH));
 
     container(form)
         .width(Length::Fill)
@@ -463,16 +1237,17 @@ fn view_delete_confirmation<'a>(
     id: ID,
     name: &str,
 ) -> Element<'a, Message> {
+    let fonts = app.fonts();
     let confirmation = column![
-        text(format!("Are you sure you want to delete the author:")).size(20),
-        text(format!("\"{}\"?", name)).size(24),
-        text("This action cannot be undone.").size(16),
+        text(format!("Are you sure you want to delete the author:")).size(fonts.heading()),
+        text(format!("\"{}\"?", name)).size(fonts.title()),
+        text("This action cannot be undone.").size(fonts.body()),
         if !app.author_books.is_empty() {
             text(format!(
                 "Warning: This author has {} books associated with them.",
                 app.author_books.len()
             ))
-            .size(16)
+            .size(fonts.body())
         } else {
             text("")
         },
@@ -504,3 +1279,55 @@ fn view_delete_confirmation<'a>(
         .style(container::bordered_box)
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BookModel;
+
+    fn author(id: ID, name: &str) -> AuthorModel {
+        AuthorModel {
+            Id: id,
+            Name: Some(name.to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        }
+    }
+
+    fn book_by(id: ID, author: ID) -> BookWithAuthor {
+        let mut book = BookModel::titled(id, "Book");
+        book.AuthorFK = Some(author);
+        BookWithAuthor { book, author: None }
+    }
+
+    #[test]
+    fn dismissed_pairs_are_not_suggested_again() {
+        let authors = [
+            author(1, "Frank Herbert"),
+            author(2, "Stanisław Lem"),
+            author(3, "Frank Herbertt"),
+            author(4, "Stanislaw Lem"),
+        ];
+        let books = [book_by(1, 1), book_by(2, 3), book_by(3, 3)];
+        let pairs = |dismissed: &[(ID, ID)], fold| {
+            merge_suggestions(&authors, &books, dismissed, fold)
+                .into_iter()
+                .map(|suggestion| suggestion.pair)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(pairs(&[], true), vec![(1, 3), (2, 4)]);
+        assert_eq!(pairs(&[(1, 3)], true), vec![(2, 4)]);
+        assert_eq!(pairs(&[(1, 3), (2, 4)], true), vec![]);
+        // Without folding the diacritic pair isn't suggested in the first place
+        assert_eq!(pairs(&[], false), vec![(1, 3)]);
+
+        // The author with more books is the one kept
+        let suggestions = merge_suggestions(&authors, &books, &[(2, 4)], true);
+        assert_eq!(suggestions[0].books, (1, 2));
+        assert_eq!(suggestions[0].keep, 3);
+        assert_eq!(suggestions[0].reason, NameMatch::Typo(1));
+    }
+}
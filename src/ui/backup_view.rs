@@ -0,0 +1,336 @@
+// src/ui/backup_view.rs
+use crate::backup::{self, BackupDocument, LibraryDiff};
+use crate::paths;
+use crate::ui::{BookshelfApp, Message};
+use chrono::Local;
+use iced::widget::{button, column, row, scrollable, text, text_input, Column};
+use iced::{Element, Length};
+use std::path::PathBuf;
+
+/// The two backups picked for a comparison and its result
+#[derive(Debug, Clone, Default)]
+pub struct BackupCompare {
+    pub older: String,
+    pub newer: String, // Empty compares against the live database
+    pub running: bool,
+    pub result: Option<LibraryDiff>,
+    pub error: Option<String>, // Shown inline under the inputs
+    pub confirming_restore: bool, // Restoring the older backup waits for a second click
+    pub restoring: bool,
+}
+
+// Reads a backup file, or the open database for an empty path
+fn load_document(path: &str) -> Result<BackupDocument, String> {
+    let path = path.trim();
+    let document = if path.is_empty() {
+        BackupDocument::from_database(Local::now().naive_local())
+    } else {
+        BackupDocument::load(&PathBuf::from(path))
+    };
+    document.map_err(|e| format!("{}: {}", if path.is_empty() { "Library" } else { path }, e))
+}
+
+// Handler functions for backups
+pub fn handle_write_backup(_app: &mut BookshelfApp) -> iced::Task<Message> {
+    let now = Local::now().naive_local();
+    let path = paths::exports_dir().join(format!("backup-{}.json", now.format("%Y-%m-%d-%H%M%S")));
+
+    iced::Task::perform(
+        async move {
+            match BackupDocument::from_database(now).and_then(|document| document.save(&path)) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BackupWritten,
+    )
+}
+
+pub fn handle_backup_written(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => {
+            app.notify_finished(format!("Saved backup to {}", path));
+            // The usual next step is comparing a later state against it
+            if app.backup_compare.older.is_empty() {
+                app.backup_compare.older = path;
+            }
+        }
+        Err(e) => app.notify_failed(format!("Failed to write backup: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_older_changed(app: &mut BookshelfApp, path: String) -> iced::Task<Message> {
+    app.backup_compare.older = path;
+    iced::Task::none()
+}
+
+pub fn handle_newer_changed(app: &mut BookshelfApp, path: String) -> iced::Task<Message> {
+    app.backup_compare.newer = path;
+    iced::Task::none()
+}
+
+pub fn handle_compare(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let older = app.backup_compare.older.clone();
+    let newer = app.backup_compare.newer.clone();
+    app.backup_compare.running = true;
+    app.backup_compare.error = None;
+
+    iced::Task::perform(
+        async move {
+            let older = load_document(&older)?;
+            let newer = load_document(&newer)?;
+            Ok(backup::diff(&older, &newer))
+        },
+        Message::BackupsCompared,
+    )
+}
+
+pub fn handle_compared(
+    app: &mut BookshelfApp,
+    result: Result<LibraryDiff, String>,
+) -> iced::Task<Message> {
+    app.backup_compare.running = false;
+    match result {
+        Ok(diff) => app.backup_compare.result = Some(diff),
+        Err(e) => app.backup_compare.error = Some(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_save_diff(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(diff) = &app.backup_compare.result else {
+        return iced::Task::none();
+    };
+    let report = diff.to_text();
+    let path = paths::exports_dir().join("library-diff.txt");
+
+    iced::Task::perform(
+        async move {
+            let written = match path.parent() {
+                Some(parent) => std::fs::create_dir_all(parent),
+                None => Ok(()),
+            }
+            .and_then(|()| std::fs::write(&path, report));
+            match written {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BackupDiffSaved,
+    )
+}
+
+pub fn handle_diff_saved(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => app.notify_finished(format!("Saved comparison to {}", path)),
+        Err(e) => app.notify_failed(format!("Failed to save comparison: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_close_diff(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.backup_compare.result = None;
+    iced::Task::none()
+}
+
+pub fn handle_restore(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !app.read_only && !app.backup_compare.older.trim().is_empty() {
+        app.backup_compare.confirming_restore = true;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_cancel_restore(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.backup_compare.confirming_restore = false;
+    iced::Task::none()
+}
+
+pub fn handle_confirm_restore(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let compare = &mut app.backup_compare;
+    if !compare.confirming_restore || compare.restoring {
+        return iced::Task::none();
+    }
+    compare.confirming_restore = false;
+    compare.restoring = true;
+    let path = compare.older.trim().to_string();
+
+    iced::Task::perform(
+        async move {
+            match BackupDocument::load(&PathBuf::from(&path)).and_then(|doc| doc.restore()) {
+                Ok(()) => Ok(path),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BackupRestored,
+    )
+}
+
+pub fn handle_restored(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    app.backup_compare.restoring = false;
+    match result {
+        Ok(path) => {
+            app.notify_finished(format!("Restored the library from {}", path));
+            // An earlier comparison no longer describes the library
+            app.backup_compare.result = None;
+            iced::Task::batch([
+                app.load_tab_data(app.current_tab.clone()),
+                app.update(Message::LoadAuthors),
+                app.update(Message::LoadReadingSessions),
+            ])
+        }
+        Err(e) => {
+            app.notify_failed(format!("Failed to restore backup: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+// View functions for backups
+pub fn create_backup_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let compare = &app.backup_compare;
+
+    let mut section = column![
+        text("Backups").size(fonts.heading()),
+        text(format!(
+            "JSON backups of the whole library, from books and authors to lists, loans and \
+             reading sessions, are written to {}",
+            paths::exports_dir().display()
+        ))
+        .size(fonts.small()),
+        button("Write JSON backup")
+            .on_press(Message::WriteBackup)
+            .style(button::secondary),
+        text("Compare two backups, or a backup with the library as it is now").size(fonts.body()),
+        text_input("Older backup file", &compare.older)
+            .on_input(Message::BackupOlderChanged)
+            .size(fonts.body()),
+        text_input(
+            "Newer backup file (empty for the current library)",
+            &compare.newer
+        )
+        .on_input(Message::BackupNewerChanged)
+        .size(fonts.body()),
+        row![
+            button(if compare.running {
+                "Comparing..."
+            } else {
+                "Compare"
+            })
+            .on_press_maybe(
+                (!compare.running && !compare.older.trim().is_empty())
+                    .then_some(Message::CompareBackups)
+            )
+            .style(button::primary),
+            button(if compare.restoring {
+                "Restoring..."
+            } else {
+                "Restore older backup"
+            })
+            .on_press_maybe(
+                (!app.read_only && !compare.restoring && !compare.older.trim().is_empty())
+                    .then_some(Message::RestoreBackup)
+            )
+            .style(button::danger),
+        ]
+        .spacing(10),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    if compare.confirming_restore {
+        section = section.push(
+            row![
+                text(
+                    "Every book, author, list, loan and note in the library is replaced by the \
+                     older backup. Compare the two first to see what changes."
+                )
+                .size(fonts.small())
+                .width(Length::Fill),
+                button("Replace library")
+                    .on_press(Message::ConfirmRestoreBackup)
+                    .style(button::danger),
+                button("Cancel")
+                    .on_press(Message::CancelRestoreBackup)
+                    .style(button::secondary),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    if let Some(error) = &compare.error {
+        section = section.push(text(error.clone()).size(fonts.small()).style(text::danger));
+    } else if let Some(diff) = &compare.result {
+        let report: Element<'_, Message> = if diff.is_empty() {
+            text("No differences").size(fonts.body()).into()
+        } else {
+            scrollable(
+                text(diff.to_text())
+                    .size(fonts.body())
+                    .font(iced::Font::MONOSPACE),
+            )
+            .height(fonts.width(320.0))
+            .into()
+        };
+        section = section
+            .push(text(diff.summary()).size(fonts.small()))
+            .push(report)
+            .push(
+                row![
+                    button("Save as text")
+                        .on_press_maybe((!diff.is_empty()).then_some(Message::SaveBackupDiff))
+                        .style(button::secondary),
+                    button("Close")
+                        .on_press(Message::CloseBackupDiff)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+            );
+    }
+
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_restore_waits_for_confirmation_and_never_runs_read_only() {
+        let mut app = BookshelfApp::new();
+        let _ = handle_restore(&mut app);
+        assert!(!app.backup_compare.confirming_restore);
+
+        app.backup_compare.older = "backup.json".to_string();
+        // Without the first click the confirmation does nothing
+        let _ = handle_confirm_restore(&mut app);
+        assert!(!app.backup_compare.restoring);
+
+        let _ = handle_restore(&mut app);
+        assert!(app.backup_compare.confirming_restore);
+        let _ = handle_cancel_restore(&mut app);
+        assert!(!app.backup_compare.confirming_restore);
+
+        let _ = handle_restore(&mut app);
+        let _ = handle_confirm_restore(&mut app);
+        assert!(!app.backup_compare.confirming_restore);
+        assert!(app.backup_compare.restoring);
+
+        let mut app = BookshelfApp::new();
+        app.read_only = true;
+        app.backup_compare.older = "backup.json".to_string();
+        let _ = handle_restore(&mut app);
+        assert!(!app.backup_compare.confirming_restore);
+    }
+}
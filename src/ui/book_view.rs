@@ -1,17 +1,116 @@
 // src/ui/book_view.rs
-use crate::db;
-use crate::models::{BookModel, BookWithAuthor, NewBook, ID};
-use crate::ui::components::searchable_dropdown;
-use crate::ui::{sort_books, BookshelfApp, Message, Mode, LIST_MAX_WIDTH};
-use chrono::{Local, NaiveDateTime};
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Column};
-use iced::{Element, Length};
+use crate::db::cached::QueryKind;
+use crate::db::{self, AuthorResolution, DeleteReport, MovePlan, RelatedRecords};
+use crate::language::{language_choices, language_name, normalize_code, Language};
+use crate::models::{BookFormat, BookModel, BookWithAuthor, NewBook, SeriesModel, ID};
+use crate::ui::changes_view::PendingBookChanges;
+use crate::stats;
+use crate::trash::DeletedItem;
+use crate::ui::components::searchable_dropdown::{self, SearchableDropdown};
+use crate::ui::components::{author_resolution, bulk_preview};
+use crate::ui::{
+    bought_date_view, changes_view, cover_view, custom_field_view, draft_view, hover_view,
+    journal_view, list_view, loan_view, missing_view, quote_view, review_view, series_view,
+    session_view, settings_view, shelf_view, split_view, table_view, trash_view,
+};
+use crate::ui::{
+    acquisition_places, book_genre_color, books_to_markdown, format_duration, format_price,
+    format_rating, format_reading_duration, format_session_total, is_double_click, parse_price,
+    parse_relative_date, pinned_count, pinned_first, sort_books, AcquisitionPlace, BookshelfApp,
+    ListMode, Message, Mode, ScrollTarget, SearchField, Tab, BOOK_QUANTITY_MIN, CURRENCY,
+    FORM_DATE_FORMAT, LIST_MAX_WIDTH, LIST_PADDING, LIST_SPACING, NO_SERIES_GROUP, RATING_MAX,
+    YearChoice, YearField,
+};
+use chrono::{Local, NaiveDateTime, NaiveTime};
+use iced::widget::scrollable::{AbsoluteOffset, RelativeOffset};
+use iced::widget::{
+    button, checkbox, column, container, horizontal_space, mouse_area, pick_list, row, scrollable,
+    text, text_editor, text_input, Column, Row,
+};
+use iced::{Element, Length, Theme};
+use std::time::Instant;
+
+/// How the book list should be scrolled once it reloads after an edit
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollRestore {
+    Offset(AbsoluteOffset),  // List unchanged, return to the exact previous position
+    Reveal(RelativeOffset),  // List reordered, scroll the edited book into view
+    Hidden,                  // Edited book is not in the displayed list
+}
+
+pub fn book_list_id() -> scrollable::Id {
+    scrollable::Id::new("book_list")
+}
+
+pub fn book_title_input_id() -> text_input::Id {
+    text_input::Id::new("book_title")
+}
+
+// Decides where to scroll based on where the edited book ended up in the displayed list,
+// per_row is the number of books shown side by side
+pub fn resolve_scroll_target(
+    target: &ScrollTarget,
+    books: &[BookWithAuthor],
+    per_row: usize,
+) -> ScrollRestore {
+    match books.iter().position(|pair| pair.book.id == target.book_id) {
+        None => ScrollRestore::Hidden,
+        Some(index) if index == target.index && books.len() == target.list_len => {
+            ScrollRestore::Offset(target.offset)
+        }
+        Some(index) => ScrollRestore::Reveal(row_offset(index, books.len(), per_row)),
+    }
+}
+
+// Scroll position that brings the book at index into view, estimated from the row it is
+// on as rows of the list are about the same height
+fn row_offset(index: usize, count: usize, per_row: usize) -> RelativeOffset {
+    let per_row = per_row.max(1);
+    let rows = count.div_ceil(per_row);
+    let y = if rows > 1 {
+        (index / per_row) as f32 / (rows - 1) as f32
+    } else {
+        0.0
+    };
+    RelativeOffset { x: 0.0, y }
+}
+
+fn restore_scroll(app: &mut BookshelfApp, target: ScrollTarget) -> iced::Task<Message> {
+    match resolve_scroll_target(&target, app.displayed_books(), app.books_per_row()) {
+        ScrollRestore::Offset(offset) => {
+            app.highlighted_book = Some(target.book_id);
+            scrollable::scroll_to(book_list_id(), offset)
+        }
+        ScrollRestore::Reveal(offset) => {
+            app.highlighted_book = Some(target.book_id);
+            scrollable::snap_to(book_list_id(), offset)
+        }
+        ScrollRestore::Hidden => {
+            app.status_message = Some("Edited book is hidden by the current filter".to_string());
+            scrollable::snap_to(book_list_id(), RelativeOffset::START)
+        }
+    }
+}
 
 // Handler functions for book-related messages
-pub fn handle_load_books(_: &mut BookshelfApp) -> iced::Task<Message> {
+pub fn handle_load_books(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.books_loading = true;
+    let empty = app.config.sort_empty_values;
+    let secondary = app.config.secondary_sort.clone();
+    let sort = app
+        .config
+        .sort_in_database
+        .then(|| (app.sort_field.clone(), app.sort_direction.clone()));
+
     iced::Task::perform(
-        async {
-            match db::get_books() {
+        async move {
+            let books = match sort {
+                Some((field, direction)) => {
+                    db::get_books_sorted(&field, &direction, secondary.as_ref(), empty, 0, None)
+                }
+                None => db::cached::get_books(),
+            };
+            match books {
                 Ok(books) => Ok(books),
                 Err(e) => Err(e.to_string()),
             }
@@ -20,6 +119,33 @@ pub fn handle_load_books(_: &mut BookshelfApp) -> iced::Task<Message> {
     )
 }
 
+// Reloads from the database, not the cache, so edits made by other programs show up.
+// The list widget is kept, so the scroll position stays and the filters are reapplied
+pub fn handle_idle_refresh(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !app.can_refresh_while_idle() {
+        return iced::Task::none();
+    }
+
+    db::cached::invalidate(&[QueryKind::Books, QueryKind::Authors]);
+    iced::Task::batch([
+        app.update(Message::LoadBooks),
+        app.update(Message::LoadAuthors),
+    ])
+}
+
+// Opens the author dropdown and puts the cursor in its search so typing filters at once
+pub fn handle_author_dropdown_shortcut(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !draft_view::is_editing_book(app) {
+        return iced::Task::none();
+    }
+
+    let task = app.update(Message::ToggleAuthorDropdown);
+    if app.author_dropdown.is_open() {
+        return iced::Task::batch([task, text_input::focus(searchable_dropdown::search_input_id())]);
+    }
+    task
+}
+
 pub fn handle_add_book_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
     app.mode = Mode::Add;
     app.selected_book = None;
@@ -27,38 +153,139 @@ pub fn handle_add_book_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
     app.book_price = String::new();
     app.book_bought_date = String::new();
     app.book_finished_date = String::new();
+    app.book_is_gift = false;
+    app.book_gift_from = String::new();
+    app.book_quantity = 1;
+    app.book_cover_path = None;
+    app.book_genre = String::new();
+    app.book_page_count = String::new();
+    app.book_format = None;
+    app.book_duration_minutes = String::new();
+    app.book_language = None;
+    app.book_acquired_from = String::new();
+    app.book_rating = None;
+    app.acquired_from_dropdown = SearchableDropdown::new(acquisition_places(&app.books), None);
+    app.cover_preview = None;
     app.selected_author = None;
+    app.selected_series = None;
+    app.journal_draft = text_editor::Content::new();
+    app.book_custom_fields = Vec::new();
+    app.draft_baseline = Some(draft_view::snapshot(app));
+    app.form_opened = app.draft_baseline.clone();
 
-    app.update(Message::LoadAuthors)
-
+    iced::Task::batch(vec![
+        app.update(Message::LoadAuthors),
+        app.update(Message::LoadSeries),
+    ])
 }
 
 pub fn handle_edit_book_mode(app: &mut BookshelfApp, pair: &BookWithAuthor)
                              -> iced::Task<Message> {
     app.mode = Mode::Edit;
+    app.status_message = None;
+    app.pending_scroll_target = app
+        .displayed_books()
+        .iter()
+        .position(|displayed| displayed.book.id == pair.book.id)
+        .map(|index| ScrollTarget {
+            book_id: pair.book.id,
+            index,
+            list_len: app.displayed_books().len(),
+            offset: app.book_list_offset,
+        });
     app.selected_book = Some(pair.clone());
     app.book_title = pair.book.title.clone();
     app.book_price = pair.book.price.map_or_else(String::new, |p| p.to_string());
     app.book_bought_date = pair
         .book
         .bought
-        .map_or_else(String::new, |d| d.format("%Y-%m-%d %H:%M:%S").to_string());
+        .map_or_else(String::new, |d| d.format(FORM_DATE_FORMAT).to_string());
     app.book_finished_date = pair
         .book
         .finished
-        .map_or_else(String::new, |d| d.format("%Y-%m-%d %H:%M:%S").to_string());
+        .map_or_else(String::new, |d| d.format(FORM_DATE_FORMAT).to_string());
+    app.book_is_gift = pair.book.is_gift();
+    app.book_gift_from = pair.book.gift_from.clone().unwrap_or_default();
+    app.book_quantity = pair.book.quantity;
+    app.book_cover_path = pair.book.cover_path.clone();
+    app.book_genre = pair.book.genre.clone().unwrap_or_default();
+    app.book_page_count = pair
+        .book
+        .page_count
+        .map_or_else(String::new, |pages| pages.to_string());
+    app.book_format = pair.book.book_format();
+    app.book_duration_minutes = pair
+        .book
+        .duration_minutes
+        .map_or_else(String::new, |minutes| minutes.to_string());
+    app.book_language = pair.book.language.clone();
+    app.book_acquired_from = pair.book.acquired_from.clone().unwrap_or_default();
+    app.book_rating = pair.book.rating;
+    app.acquired_from_dropdown = SearchableDropdown::new(acquisition_places(&app.books), None);
+    app.cover_preview = None;
     app.selected_author = pair.author.clone();
+    // Before the series have loaded only the id is known, SeriesLoaded fills in the name.
+    // Dropping the series here would clear it from the book on save.
+    app.selected_series = pair.book.SeriesFK.map(|series_id| {
+        app.series
+            .iter()
+            .find(|s| s.id == series_id)
+            .cloned()
+            .unwrap_or(SeriesModel {
+                id: series_id,
+                name: String::new(),
+                total_volumes: None,
+            })
+    });
+    app.book_custom_fields = custom_field_view::form_fields(app.custom_fields.get(&pair.book.id));
+
+    app.journal_entries = Vec::new();
+    app.journal_draft = text_editor::Content::new();
+    app.book_quotes = Vec::new();
+    app.quote_draft = text_editor::Content::new();
+    app.quote_page.clear();
+    app.editing_quote = None;
+    app.book_loans = Vec::new();
+    app.loan_borrower.clear();
+    app.loan_due_date.clear();
+    app.draft_baseline = Some(draft_view::snapshot(app));
+    app.form_opened = app.draft_baseline.clone();
 
-    app.update(Message::LoadAuthors)
+    iced::Task::batch(vec![
+        app.update(Message::LoadAuthors),
+        app.update(Message::LoadSeries),
+        app.update(Message::LoadJournal(pair.book.id)),
+        app.update(Message::LoadBookQuotes(pair.book.id)),
+        app.update(Message::LoadBookLoans(pair.book.id)),
+    ])
 }
 
 pub fn handle_view_book_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if app.review_queue.is_some() {
+        return review_view::finish_review(app);
+    }
     app.mode = Mode::View;
     app.selected_book = None;
+    draft_view::clear_draft(app);
 
     app.update(Message::LoadBooks)
 }
 
+pub fn handle_view_book_details(
+    app: &mut BookshelfApp,
+    pair: BookWithAuthor,
+) -> iced::Task<Message> {
+    app.current_book = Some(pair);
+    app.mode = Mode::ViewDetails;
+    iced::Task::none()
+}
+
+pub fn handle_close_book_details(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.current_book = None;
+    app.mode = Mode::View;
+    iced::Task::none()
+}
+
 pub fn handle_book_title_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
     app.book_title = value;
     iced::Task::none()
@@ -85,34 +312,113 @@ pub fn handle_book_finished_date_changed(
     iced::Task::none()
 }
 
+pub fn handle_copy_previous_dates(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some((bought, finished)) = app.last_saved_dates {
+        let form_date = |date: Option<NaiveDateTime>| {
+            date.map_or_else(String::new, |d| d.format(FORM_DATE_FORMAT).to_string())
+        };
+        app.book_bought_date = form_date(bought);
+        app.book_finished_date = form_date(finished);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_book_is_gift_toggled(app: &mut BookshelfApp, is_gift: bool) -> iced::Task<Message> {
+    app.book_is_gift = is_gift;
+    iced::Task::none()
+}
+
+pub fn handle_book_gift_from_changed(
+    app: &mut BookshelfApp,
+    value: String,
+) -> iced::Task<Message> {
+    app.book_gift_from = value;
+    iced::Task::none()
+}
+
+pub fn handle_change_book_quantity(app: &mut BookshelfApp, delta: i32) -> iced::Task<Message> {
+    app.book_quantity = (app.book_quantity + delta).max(BOOK_QUANTITY_MIN);
+    iced::Task::none()
+}
+
 pub fn handle_save_book(app: &mut BookshelfApp) -> iced::Task<Message> {
     let price = if app.book_price.is_empty() {
         None
     } else {
-        match app.book_price.parse::<f32>() {
+        match parse_price(
+            &app.book_price,
+            app.config.max_price,
+            app.config.allow_scientific_prices,
+        ) {
             Ok(p) => Some(p),
-            Err(_) => {
-                app.error = Some("Invalid price format".to_string());
+            Err(e) => {
+                app.error = Some(e);
                 return iced::Task::none();
             }
         }
     };
 
-    let parse_datetime = |s: &str| -> Option<NaiveDateTime> {
-        if s.is_empty() {
-            None
-        } else {
-            match NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-                Ok(dt) => Some(dt),
-                Err(_) => None, // Handle date parsing error
-            }
+    // Shorthand like "-3d" is saved as the date it resolves to, never as typed
+    let now = Local::now().naive_local();
+    let parse_datetime = |label: &str, s: &str| -> Result<Option<NaiveDateTime>, String> {
+        if s.trim().is_empty() {
+            return Ok(None);
+        }
+        parse_relative_date(s, now)
+            .map(Some)
+            .ok_or_else(|| format!("Could not understand the {} date '{}'", label, s.trim()))
+    };
+    let (bought_date, finished_date) = match (
+        parse_datetime("bought", &app.book_bought_date),
+        parse_datetime("finished", &app.book_finished_date),
+    ) {
+        (Ok(bought), Ok(finished)) => (bought, finished),
+        (Err(e), _) | (_, Err(e)) => {
+            app.error = Some(e);
+            return iced::Task::none();
         }
     };
 
-    let bought_date = parse_datetime(&app.book_bought_date);
-    let finished_date = parse_datetime(&app.book_finished_date);
+    let page_count = match app.book_page_count.trim() {
+        "" => None,
+        pages => match pages.parse::<i32>() {
+            Ok(pages) if pages > 0 => Some(pages),
+            _ => {
+                app.error = Some("Pages must be a whole number above zero".to_string());
+                return iced::Task::none();
+            }
+        },
+    };
+
+    // Listening time only means something for audiobooks, other formats drop it
+    let duration_minutes = match app.book_duration_minutes.trim() {
+        _ if app.book_format != Some(BookFormat::Audiobook) => None,
+        "" => None,
+        minutes => match minutes.parse::<i32>() {
+            Ok(minutes) if minutes >= 0 => Some(minutes),
+            _ => {
+                app.error = Some("Listening time must be a whole number of minutes".to_string());
+                return iced::Task::none();
+            }
+        },
+    };
+
+    if app.book_quantity < BOOK_QUANTITY_MIN {
+        app.error = Some(format!("Quantity must be at least {}", BOOK_QUANTITY_MIN));
+        return iced::Task::none();
+    }
+
+    let gift_from = if app.book_is_gift {
+        let giver = app.book_gift_from.trim();
+        if giver.is_empty() {
+            app.error = Some("Enter who gave the book, or untick 'Received as a gift'".to_string());
+            return iced::Task::none();
+        }
+        Some(giver.to_string())
+    } else {
+        None
+    };
 
-    let now = Local::now().naive_local();
     let added_date = app
         .selected_book
         .as_ref()
@@ -122,27 +428,73 @@ pub fn handle_save_book(app: &mut BookshelfApp) -> iced::Task<Message> {
     // Extract book_id outside the closure if we're in edit mode
     let book_id = app.selected_book.as_ref().map(|book| book.book.id);
 
-    let new_book = NewBook {
+    let mut new_book = NewBook {
         title: app.book_title.clone(),
         price,
         bought: bought_date,
         finished: finished_date,
         added: Some(added_date),
         AuthorFK: app.selected_author.as_ref().map(|a| a.Id),
+        SeriesFK: app.selected_series.as_ref().map(|s| s.id),
+        gift_from,
+        quantity: app.book_quantity,
+        cover_path: app.book_cover_path.clone(),
+        genre: Some(app.book_genre.trim().to_string()).filter(|genre| !genre.is_empty()),
+        page_count,
+        format: app.book_format.map(|format| format.name().to_string()),
+        duration_minutes,
+        language: app.book_language.as_deref().and_then(normalize_code),
+        acquired_from: Some(app.book_acquired_from.trim().to_string())
+            .filter(|place| !place.is_empty()),
+        rating: app.book_rating,
+        pinned: app
+            .selected_book
+            .as_ref()
+            .map(|pair| pair.book.pinned)
+            .unwrap_or(false),
     };
+    if app.config.bought_when_finished {
+        let previously_finished = app.selected_book.as_ref().and_then(|b| b.book.finished);
+        new_book.assume_bought_when_finished(previously_finished);
+    }
+
+    // Careful edits show what changed first, an edit without changes saves right away
+    if let Some(old) = app
+        .selected_book
+        .as_ref()
+        .filter(|_| app.config.review_changes_before_save)
+    {
+        let changes = changes_view::diff_book(&old.book, &new_book);
+        if !changes.is_empty() {
+            app.pending_book_changes = Some(PendingBookChanges {
+                book_id: old.book.id,
+                book: new_book,
+                changes,
+            });
+            return iced::Task::none();
+        }
+    }
 
+    app.saving_book = true;
+    save_book(book_id, new_book, app.book_custom_fields.clone())
+}
+
+/// Updates the book with the given id, or creates it when there is none, then replaces
+/// its custom fields
+pub fn save_book(
+    book_id: Option<ID>,
+    new_book: NewBook,
+    fields: Vec<(String, String)>,
+) -> iced::Task<Message> {
     iced::Task::perform(
         async move {
-            if let Some(id) = book_id {
-                match db::update_book(id, &new_book) {
-                    Ok(updated) => Ok(updated),
-                    Err(e) => Err(e.to_string()),
-                }
-            } else {
-                match db::create_book(&new_book) {
-                    Ok(created) => Ok(created),
-                    Err(e) => Err(e.to_string()),
-                }
+            let saved = match book_id {
+                Some(id) => db::update_book(id, &new_book),
+                None => db::create_book(&new_book),
+            };
+            match saved.and_then(|book| db::set_fields_for_book(book.id, &fields).map(|_| book)) {
+                Ok(book) => Ok(book),
+                Err(e) => Err(e.to_string()),
             }
         },
         Message::BookSaved,
@@ -153,16 +505,179 @@ pub fn handle_book_saved(
     app: &mut BookshelfApp,
     result: Result<BookModel, String>,
 ) -> iced::Task<Message> {
-    match result {
+    let add_another = std::mem::take(&mut app.add_another_after_save);
+    app.saving_book = false;
+    // The saved custom fields are fetched again for the details view
+    let reload_fields = match &result {
+        Ok(saved) => {
+            app.last_saved_dates = Some((saved.bought, saved.finished));
+            app.update(Message::LoadCustomFields)
+        }
+        Err(_) => iced::Task::none(),
+    };
+    let task = match result {
+        Ok(saved) if add_another => {
+            draft_view::clear_draft(app);
+            app.books_added_this_session += 1;
+            let task = reopen_add_form(app);
+            app.status_message = Some(format!("Saved '{}', add the next book", saved.title));
+            iced::Task::batch(vec![
+                task,
+                text_input::focus(book_title_input_id()),
+                app.update(Message::LoadBooks),
+            ])
+        }
+        Ok(saved) if app.review_queue.is_some() => review_view::handle_review_saved(app, saved),
         Ok(_) => {
+            if matches!(app.mode, Mode::Add) {
+                app.books_added_this_session += 1;
+            }
             app.mode = Mode::View;
+            draft_view::clear_draft(app);
             app.update(Message::LoadBooks)
         }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(e);
             iced::Task::none()
         }
+    };
+    iced::Task::batch(vec![task, reload_fields])
+}
+
+/// True when the form offers "Save and add another": only when adding a book, editing and
+/// the review queue always return to where they came from
+pub fn offers_add_another(app: &BookshelfApp) -> bool {
+    matches!(app.mode, Mode::Add) && app.review_queue.is_none()
+}
+
+// Empties the add form for the next book, keeping the values picked as sticky in the settings
+fn reopen_add_form(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let sticky = app.config.sticky_fields;
+    let author = app.selected_author.take().filter(|_| sticky.author);
+    let series = app.selected_series.take().filter(|_| sticky.series);
+    let bought_date = std::mem::take(&mut app.book_bought_date);
+    let format = app.book_format.filter(|_| sticky.format);
+    let language = app.book_language.take().filter(|_| sticky.language);
+    let acquired_from = std::mem::take(&mut app.book_acquired_from);
+
+    let task = handle_add_book_mode(app);
+    app.selected_author = author;
+    app.selected_series = series;
+    if sticky.bought_date {
+        app.book_bought_date = bought_date;
+    }
+    app.book_format = format;
+    app.book_language = language;
+    if sticky.acquired_from {
+        app.book_acquired_from = acquired_from;
+    }
+    app.draft_baseline = Some(draft_view::snapshot(app));
+    app.form_opened = app.draft_baseline.clone();
+    task
+}
+
+pub fn handle_toggle_finished_today(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    quick_update_book(app, id, |book, now| {
+        book.finished = if book.finished.is_some() { None } else { Some(now) };
+    })
+}
+
+pub fn handle_toggle_bought_today(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    quick_update_book(app, id, |book, now| {
+        book.bought = if book.bought.is_some() { None } else { Some(now) };
+    })
+}
+
+// Saves a single change to a listed book without leaving the list
+fn quick_update_book(
+    app: &mut BookshelfApp,
+    id: ID,
+    change: impl FnOnce(&mut NewBook, NaiveDateTime),
+) -> iced::Task<Message> {
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == id) else {
+        return iced::Task::none();
+    };
+
+    let mut book = NewBook::from(&pair.book);
+    change(&mut book, Local::now().naive_local());
+    if app.config.bought_when_finished {
+        book.assume_bought_when_finished(pair.book.finished);
+    }
+
+    iced::Task::perform(
+        async move {
+            match db::update_book(id, &book) {
+                Ok(updated) => Ok(updated),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookQuickUpdated,
+    )
+}
+
+pub fn handle_book_quick_updated(
+    app: &mut BookshelfApp,
+    result: Result<BookModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(updated) => {
+            app.status_message = Some(format!("Updated '{}'", updated.title));
+
+            // Patch both lists in place so the focused row keeps its position
+            let lists = std::iter::once(&mut app.books).chain(app.filtered_books.as_mut());
+            for list in lists {
+                if let Some(pair) = list.iter_mut().find(|pair| pair.book.id == updated.id) {
+                    pair.book = updated.clone();
+                }
+            }
+            if let Some(pair) = app
+                .current_book
+                .as_mut()
+                .filter(|pair| pair.book.id == updated.id)
+            {
+                pair.book = updated;
+            }
+        }
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_book_rating_changed(
+    app: &mut BookshelfApp,
+    id: ID,
+    rating: Option<i32>,
+) -> iced::Task<Message> {
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == id) else {
+        return iced::Task::none();
+    };
+    let mut book = NewBook::from(&pair.book);
+    book.rating = rating;
+
+    iced::Task::perform(
+        async move {
+            match db::update_book(id, &book) {
+                Ok(updated) => Ok(updated),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookRatingSaved,
+    )
+}
+
+pub fn handle_book_rating_saved(
+    app: &mut BookshelfApp,
+    result: Result<BookModel, String>,
+) -> iced::Task<Message> {
+    let confirmation = result.as_ref().ok().map(|book| match book.rating {
+        Some(rating) => format!("Rated '{}' {}", book.title, format_rating(rating)),
+        None => format!("Cleared the rating of '{}'", book.title),
+    });
+    let task = handle_book_quick_updated(app, result);
+    if confirmation.is_some() {
+        app.status_message = confirmation;
     }
+    task
 }
 
 // New handler for confirming deletion
@@ -172,6 +687,33 @@ pub fn handle_confirm_delete_book(
     title: String,
 ) -> iced::Task<Message> {
     app.mode = Mode::ConfirmDelete(id, title);
+    app.delete_related = None;
+    app.delete_acknowledged = false;
+
+    iced::Task::perform(
+        async move {
+            match db::count_related_records(id) {
+                Ok(related) => Ok(related),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        move |result| Message::RelatedRecordsCounted(id, result),
+    )
+}
+
+pub fn handle_related_records_counted(
+    app: &mut BookshelfApp,
+    id: ID,
+    result: Result<RelatedRecords, String>,
+) -> iced::Task<Message> {
+    // A late answer for a confirmation that was already closed is dropped
+    if !matches!(app.mode, Mode::ConfirmDelete(confirming, _) if confirming == id) {
+        return iced::Task::none();
+    }
+    match result {
+        Ok(related) => app.delete_related = Some((id, related)),
+        Err(e) => app.report_error(format!("Failed to check the book's history: {}", e)),
+    }
     iced::Task::none()
 }
 
@@ -181,11 +723,31 @@ pub fn handle_cancel_delete_book(app: &mut BookshelfApp) -> iced::Task<Message>
     iced::Task::none()
 }
 
-pub fn handle_delete_book(_: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+// History counted for the book being confirmed, None while the count is pending
+fn counted_related_records(app: &BookshelfApp, id: ID) -> Option<RelatedRecords> {
+    app.delete_related
+        .filter(|(counted, _)| *counted == id)
+        .map(|(_, related)| related)
+}
+
+/// True once the book's history is counted and, if there is any, its loss acknowledged.
+/// A book without history is deleted with the single Confirm click.
+pub fn can_delete_book(app: &BookshelfApp, id: ID) -> bool {
+    RelatedRecords::can_delete(
+        counted_related_records(app, id).as_ref(),
+        app.delete_acknowledged,
+    )
+}
+
+pub fn handle_delete_book(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if !can_delete_book(app, id) {
+        return iced::Task::none();
+    }
+
     iced::Task::perform(
         async move {
-            match db::delete_book(id) {
-                Ok(count) => Ok(count),
+            match db::delete_book_cascade(id) {
+                Ok(deleted) => Ok(deleted),
                 Err(e) => Err(e.to_string()),
             }
         },
@@ -193,156 +755,1153 @@ pub fn handle_delete_book(_: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
     )
 }
 
-pub fn handle_books_loaded(
+pub fn handle_toggle_book_selection(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if !app.selected_books.remove(&id) {
+        app.selected_books.insert(id);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_clear_book_selection(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.selected_books.clear();
+    iced::Task::none()
+}
+
+pub fn handle_move_target_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.move_target = value;
+    iced::Task::none()
+}
+
+// Moves happen in two steps, a dry run plan and then applying exactly that plan
+pub fn handle_move_selected_books(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let target = app.move_target.clone();
+    let book_ids: Vec<ID> = app.selected_books.iter().copied().collect();
+
+    iced::Task::perform(
+        async move {
+            match db::plan_move_books(&target, &book_ids) {
+                Ok(plan) => Ok(plan),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::MovePlanned,
+    )
+}
+
+pub fn handle_move_planned(
     app: &mut BookshelfApp,
-    result: Result<Vec<BookWithAuthor>, String>,
+    result: Result<MovePlan, String>,
 ) -> iced::Task<Message> {
     match result {
-        Ok(books) => {
-            app.books = books;
-            app.filtered_books = None; // Reset filtered books when loading all books
-            app.is_searching = false;
-
-            // Apply sorting directly to the loaded books
-            sort_books(&mut app.books, &app.sort_field, &app.sort_direction);
+        Ok(plan) => {
+            let needs_choices = !plan.is_resolved();
+            app.pending_move = Some(plan);
+            if app.config.confirm_bulk_actions || needs_choices {
+                iced::Task::none()
+            } else {
+                app.update(Message::ApplyPendingMove)
+            }
         }
         Err(e) => {
-            app.error = Some(e);
+            app.report_error(format!("Failed to prepare the move: {}", e));
+            iced::Task::none()
         }
     }
+}
+
+pub fn handle_apply_pending_move(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(plan) = app.pending_move.take() else {
+        return iced::Task::none();
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::move_books_to(&plan) {
+                Ok(count) => Ok(count),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BooksMoved,
+    )
+}
+
+pub fn handle_resolve_move_author(
+    app: &mut BookshelfApp,
+    index: usize,
+    resolution: AuthorResolution,
+) -> iced::Task<Message> {
+    if let Some(author) = app
+        .pending_move
+        .as_mut()
+        .and_then(|plan| plan.unknown_authors.get_mut(index))
+    {
+        author.resolution = Some(resolution);
+    }
     iced::Task::none()
 }
 
-pub fn handle_book_deleted(
+pub fn handle_cancel_pending_move(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.pending_move = None;
+    iced::Task::none()
+}
+
+pub fn handle_books_moved(
     app: &mut BookshelfApp,
     result: Result<usize, String>,
 ) -> iced::Task<Message> {
-    app.mode = Mode::View; // Ensure we go back to view mode
-
     match result {
-        Ok(_) => app.update(Message::LoadBooks),
+        Ok(count) => {
+            app.selected_books.clear();
+            app.notify_finished(format!("Moved {} books to {}", count, app.move_target));
+            app.update(Message::LoadBooks)
+        }
         Err(e) => {
-            app.error = Some(e);
-            app.update(Message::LoadBooks) // Always go back to book list even on error
+            app.notify_failed(format!("Failed to move books: {}", e));
+            iced::Task::none()
         }
     }
 }
 
-// View functions for books
-pub fn view(app: &BookshelfApp) -> Element<Message> {
-    match &app.mode {
-        Mode::View => view_book_list(app),
-        Mode::Add | Mode::Edit => view_book_form(app),
-        Mode::ConfirmDelete(id, title) => view_delete_confirmation(app, *id, title),
-        Mode::ViewDetails => view_book_list(app),
-    }
+fn focused_book_id(app: &BookshelfApp) -> Option<ID> {
+    app.focused_row
+        .and_then(|index| app.displayed_books().get(index))
+        .map(|pair| pair.book.id)
 }
 
-fn view_book_list(app: &BookshelfApp) -> Element<Message> {
-    let add_button = button("Add New Book")
-        .on_press(Message::AddBookMode)
-        .style(button::primary);
-
-    let books_to_display = if app.is_searching {
-        app.filtered_books.as_ref().unwrap_or(&app.books)
+/// Searches the list for the book's title and highlights it, sent after switching to the
+/// Books tab so the other filters are already cleared. Switching tabs reloads the books,
+/// the search waits for them so it isn't run on the old list.
+pub fn handle_show_book_in_list(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if app.books_loading {
+        app.pending_show_in_list = Some(id);
     } else {
-        &app.books
-    };
-
-    let search_status = create_search_status_label(app);
+        show_book_in_list(app, id);
+    }
+    iced::Task::none()
+}
+
+fn show_book_in_list(app: &mut BookshelfApp, id: ID) {
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == id) else {
+        return;
+    };
+    app.search_query = pair.book.title.clone();
+    app.search_term_displayed = pair.book.title.clone();
+    app.highlighted_book = Some(id);
+    app.apply_filters();
+    if !app.config.search_fields.contains(SearchField::Title) {
+        app.status_message = Some("Searching in titles is turned off".to_string());
+    }
+}
+
+pub fn handle_toggle_search_field(
+    app: &mut BookshelfApp,
+    field: SearchField,
+    enabled: bool,
+) -> iced::Task<Message> {
+    // At least one field stays searched, otherwise every search would come back empty
+    if !enabled && app.config.search_fields.enabled_count() <= 1 {
+        return iced::Task::none();
+    }
+
+    app.config.search_fields.set(field, enabled);
+    app.focused_row = None;
+    app.apply_filters();
+    iced::Task::batch(vec![
+        settings_view::save_config(&app.config),
+        app.update(Message::ApplySorting),
+    ])
+}
+
+pub fn handle_year_filter_selected(
+    app: &mut BookshelfApp,
+    choice: YearChoice,
+) -> iced::Task<Message> {
+    app.year_filter = match choice {
+        YearChoice::All => None,
+        YearChoice::Year(year) => Some(year),
+    };
+    app.focused_row = None;
+    app.apply_filters();
+    app.update(Message::ApplySorting)
+}
+
+pub fn handle_year_filter_field_selected(
+    app: &mut BookshelfApp,
+    field: YearField,
+) -> iced::Task<Message> {
+    app.year_filter_field = field;
+    app.focused_row = None;
+    app.apply_filters();
+    app.update(Message::ApplySorting)
+}
+
+// Years offered by the year filter, reloaded with the books so new dates show up
+pub fn load_filter_years() -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::get_distinct_years() {
+                Ok(years) => Ok(years),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::FilterYearsLoaded,
+    )
+}
+
+pub fn handle_acquired_from_filter_selected(
+    app: &mut BookshelfApp,
+    place: Option<AcquisitionPlace>,
+) -> iced::Task<Message> {
+    app.acquired_from_filter = place.map(|place| place.0);
+    app.focused_row = None;
+    app.apply_filters();
+    app.update(Message::ApplySorting)
+}
+
+pub fn handle_toggle_author_group(
+    app: &mut BookshelfApp,
+    author_id: Option<ID>,
+) -> iced::Task<Message> {
+    if !app.collapsed_authors.remove(&author_id) {
+        app.collapsed_authors.insert(author_id);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_toggle_language_filter(app: &mut BookshelfApp, code: String) -> iced::Task<Message> {
+    app.language_filter = (app.language_filter.as_ref() != Some(&code)).then_some(code);
+    app.focused_row = None;
+    app.apply_filters();
+    app.update(Message::ApplySorting)
+}
+
+/// Book open in the details view, the digit keys rate it
+pub fn details_book(app: &BookshelfApp) -> Option<&BookWithAuthor> {
+    let viewing = matches!(app.current_tab, Tab::Books)
+        && matches!(app.mode, Mode::ViewDetails)
+        && app.palette.is_none()
+        && !app.show_recently_deleted;
+    app.current_book.as_ref().filter(|_| viewing)
+}
+
+/// Book the single-key shortcuts act on, None unless the list can be navigated
+pub fn focused_book(app: &BookshelfApp) -> Option<&BookWithAuthor> {
+    if !is_list_navigable(app) {
+        return None;
+    }
+    app.focused_row
+        .and_then(|index| app.displayed_books().get(index))
+}
+
+// Arrow keys only drive the list while it is the visible, unobstructed view
+fn is_list_navigable(app: &BookshelfApp) -> bool {
+    matches!(app.current_tab, Tab::Books) && matches!(app.mode, Mode::View) && app.palette.is_none()
+}
+
+pub fn handle_move_focused_row(app: &mut BookshelfApp, delta: i32) -> iced::Task<Message> {
+    let count = app.displayed_books().len();
+    if !is_list_navigable(app) || count == 0 {
+        return iced::Task::none();
+    }
+
+    // In the grid, up and down move a whole row of cards
+    let per_row = app.books_per_row() as i32;
+    let index = match app.focused_row {
+        None if delta > 0 => 0,
+        None => count - 1,
+        Some(index) => (index as i32 + delta * per_row).clamp(0, count as i32 - 1) as usize,
+    };
+    app.focused_row = Some(index);
+    scrollable::snap_to(book_list_id(), row_offset(index, count, per_row as usize))
+}
+
+/// Whether search results are listed for n / N to step through
+pub fn has_search_matches(app: &BookshelfApp) -> bool {
+    is_list_navigable(app) && app.is_searching && !app.displayed_books().is_empty()
+}
+
+// Steps through the results like find next in an editor, wrapping around at either end
+pub fn handle_step_search_match(app: &mut BookshelfApp, delta: i32) -> iced::Task<Message> {
+    if !has_search_matches(app) {
+        return iced::Task::none();
+    }
+    let count = app.displayed_books().len();
+    let current = app.current_match.min(count - 1) as i32;
+    let index = (current + delta).rem_euclid(count as i32) as usize;
+    app.current_match = index;
+    app.focused_row = Some(index);
+    app.highlighted_book = Some(app.displayed_books()[index].book.id);
+    scrollable::snap_to(book_list_id(), row_offset(index, count, app.books_per_row()))
+}
+
+pub fn handle_open_focused_row(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !is_list_navigable(app) {
+        return iced::Task::none();
+    }
+
+    match app
+        .focused_row
+        .and_then(|index| app.displayed_books().get(index))
+        .cloned()
+    {
+        Some(pair) => app.update(Message::EditBookMode(pair)),
+        None => iced::Task::none(),
+    }
+}
+
+// A single click focuses the row, a second click on the same row opens it for editing
+pub fn handle_book_row_clicked(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    // A click puts the hover card away until the cursor moves to another row
+    hover_view::clear_hover(app);
+
+    // While assigning authors a click hands the book the picked author instead
+    if missing_view::is_assigning_authors(app) {
+        return app.update(Message::AssignAuthorToBook(id));
+    }
+
+    if app.config.open_details_on_click {
+        let Some(pair) = app.books.iter().find(|pair| pair.book.id == id).cloned() else {
+            return iced::Task::none();
+        };
+        app.focused_row = app
+            .displayed_books()
+            .iter()
+            .position(|pair| pair.book.id == id);
+        return app.update(Message::ViewBookDetails(pair));
+    }
+
+    let now = Instant::now();
+    if is_double_click(app.last_row_click, id, now) {
+        app.last_row_click = None;
+        return match app.books.iter().find(|pair| pair.book.id == id).cloned() {
+            Some(pair) => app.update(Message::EditBookMode(pair)),
+            None => iced::Task::none(),
+        };
+    }
+
+    app.last_row_click = Some((id, now));
+    app.focused_row = app
+        .displayed_books()
+        .iter()
+        .position(|pair| pair.book.id == id);
+    iced::Task::none()
+}
+
+pub fn handle_copy_books_as_markdown(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let books = app.displayed_books();
+    let markdown = books_to_markdown(books, CURRENCY);
+    app.status_message = Some(format!("Copied {} books as a Markdown table", books.len()));
+    iced::clipboard::write(markdown)
+}
+
+pub fn handle_books_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<BookWithAuthor>, String>,
+) -> iced::Task<Message> {
+    app.books_loading = false;
+    match result {
+        Ok(books) => {
+            app.books = books;
+            app.focused_row = None;
+
+            // Apply sorting directly to the loaded books, unless the database already did
+            if !app.config.sort_in_database {
+                let empty = app.config.sort_empty_values;
+                let secondary = app.config.secondary_sort.as_ref();
+                sort_books(
+                    &mut app.books,
+                    &app.sort_field,
+                    &app.sort_direction,
+                    secondary,
+                    empty,
+                );
+            }
+            if app.config.pin_books_to_top {
+                pinned_first(&mut app.books);
+            }
+
+            // Keep the active search so returning from a form lands in the same list
+            app.apply_filters();
+            if let Some(id) = app.pending_show_in_list.take() {
+                show_book_in_list(app, id);
+            }
+
+            if let Some(target) = app.pending_scroll_target.take() {
+                return restore_scroll(app, target);
+            }
+        }
+        Err(e) => {
+            app.pending_show_in_list = None;
+            app.report_error(e);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_toggle_book_pinned(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == id) else {
+        return iced::Task::none();
+    };
+    let pinned = !pair.book.pinned;
+
+    iced::Task::perform(
+        async move {
+            match db::set_book_pinned(id, pinned) {
+                Ok(updated) => Ok(updated),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookPinned,
+    )
+}
+
+// Reorders in place instead of reloading, so the search and scroll position stay
+pub fn handle_book_pinned(
+    app: &mut BookshelfApp,
+    result: Result<BookModel, String>,
+) -> iced::Task<Message> {
+    let updated = match result {
+        Ok(updated) => updated,
+        Err(e) => {
+            app.report_error(e);
+            return iced::Task::none();
+        }
+    };
+
+    app.status_message = Some(if updated.pinned {
+        format!("Pinned '{}'", updated.title)
+    } else {
+        format!("Unpinned '{}'", updated.title)
+    });
+    let pin_to_top = app.config.pin_books_to_top;
+    let empty = app.config.sort_empty_values;
+    let secondary = app.config.secondary_sort.as_ref();
+    for books in std::iter::once(&mut app.books).chain(app.filtered_books.as_mut()) {
+        if let Some(pair) = books.iter_mut().find(|pair| pair.book.id == updated.id) {
+            pair.book = updated.clone();
+        }
+        sort_books(books, &app.sort_field, &app.sort_direction, secondary, empty);
+        if pin_to_top {
+            pinned_first(books);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_book_deleted(
+    app: &mut BookshelfApp,
+    result: Result<DeleteReport, String>,
+) -> iced::Task<Message> {
+    app.mode = Mode::View; // Ensure we go back to view mode
+    app.delete_related = None;
+    app.delete_acknowledged = false;
+
+    match result {
+        Ok(report) => {
+            if let DeletedItem::Book(deleted) = &report.item {
+                if !report.removed.is_empty() {
+                    app.status_message = Some(format!(
+                        "Deleted \"{}\" with {}",
+                        deleted.book.title,
+                        report.removed.summary()
+                    ));
+                }
+            }
+            trash_view::record_deletion(app, report.item);
+            app.update(Message::LoadBooks)
+        }
+        Err(e) => {
+            app.report_error(e);
+            app.update(Message::LoadBooks) // Always go back to book list even on error
+        }
+    }
+}
+
+// View functions for books
+pub fn view(app: &BookshelfApp) -> Element<Message> {
+    if let Some(plan) = &app.pending_move {
+        return bulk_preview::view_bulk_preview(
+            &format!("Move books to {}", plan.target_url),
+            &plan.preview,
+            author_resolution::view_author_resolution(
+                "Authors not in the target",
+                &plan.unknown_authors,
+                Message::ResolveMoveAuthor,
+                app.fonts(),
+            ),
+            plan.is_resolved().then_some(Message::ApplyPendingMove),
+            Message::CancelPendingMove,
+            app.fonts(),
+        );
+    }
+    if let Some(plan) = &app.pending_bought_date {
+        return bought_date_view::view_preview(app, plan);
+    }
+
+    match &app.mode {
+        Mode::View => match &app.active_list {
+            Some(active) => list_view::view(app, active),
+            None => view_book_list(app),
+        },
+        Mode::Add | Mode::Edit => match &app.review_queue {
+            Some(queue) => review_view::view(app, queue),
+            None => view_book_form(app),
+        },
+        Mode::ConfirmDelete(id, title) => view_delete_confirmation(app, *id, title),
+        Mode::ViewDetails => view_book_details(app),
+    }
+}
+
+fn view_book_details(app: &BookshelfApp) -> Element<'_, Message> {
+    let Some(pair) = &app.current_book else {
+        return view_book_list(app);
+    };
+    let fonts = app.fonts();
+    let book = &pair.book;
+    let date = |value: Option<NaiveDateTime>| {
+        value.map_or_else(|| "-".to_string(), |d| d.format("%Y-%m-%d").to_string())
+    };
+
+    let header = row![
+        text(format!("Book: {}", book.title)).size(fonts.title()),
+        iced::widget::horizontal_space(),
+        button("Back to Books")
+            .on_press(Message::CloseBookDetails)
+            .style(button::secondary),
+        button("Edit Book")
+            .on_press(Message::EditBookMode(pair.clone()))
+            .style(button::primary),
+        button("Delete Book")
+            .on_press(Message::ConfirmDeleteBook(book.id, book.title.clone()))
+            .style(button::danger),
+    ]
+    .spacing(10)
+    .padding(10)
+    .width(Length::Fill);
+
+    let series = book
+        .SeriesFK
+        .and_then(|series_id| app.series.iter().find(|s| s.id == series_id))
+        .map(|series| series.name.clone());
+    let fields = [
+        ("Title", book.title.clone()),
+        (
+            "Author",
+            pair.author
+                .as_ref()
+                .and_then(|a| a.Name.clone())
+                .unwrap_or_else(|| "No Author".to_string()),
+        ),
+        ("Series", series.unwrap_or_else(|| "-".to_string())),
+        (
+            "Price",
+            book.price.map_or_else(
+                || "No price".to_string(),
+                |p| format_price(p, CURRENCY, app.privacy_mode),
+            ),
+        ),
+        ("Copies", book.quantity.to_string()),
+        ("Bought", date(book.bought)),
+        ("Finished", date(book.finished)),
+        ("Added", date(book.added)),
+        ("Gift from", book.gift_from.clone().unwrap_or_else(|| "-".to_string())),
+        ("Genre", book.genre.clone().unwrap_or_else(|| "-".to_string())),
+        ("Pages", book.page_count.map_or_else(|| "-".to_string(), |p| p.to_string())),
+        (
+            "Format",
+            book.book_format().map_or_else(|| "-".to_string(), |f| f.to_string()),
+        ),
+        (
+            "Listening time",
+            book.duration_minutes
+                .filter(|_| book.is_audiobook())
+                .map_or_else(|| "-".to_string(), format_duration),
+        ),
+        (
+            "Language",
+            book.language.as_deref().map_or_else(|| "-".to_string(), language_name),
+        ),
+        (
+            "Acquired from",
+            book.acquired_from.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "Rating",
+            book.rating
+                .map_or_else(|| "Not rated, press 1 to 5".to_string(), format_rating),
+        ),
+    ];
+
+    let mut content = fields.into_iter().fold(
+        column![].spacing(10).padding(LIST_PADDING),
+        |content, (label, value)| {
+            content.push(
+                row![
+                    text(label).size(fonts.small()).width(fonts.width(140.0)),
+                    text(value).size(fonts.body()),
+                ]
+                .spacing(LIST_SPACING),
+            )
+        },
+    );
+    for field in app.custom_fields.get(&book.id).into_iter().flatten() {
+        content = content.push(
+            row![
+                text(field.key.clone()).size(fonts.small()).width(fonts.width(140.0)),
+                text(field.value.clone()).size(fonts.body()),
+            ]
+            .spacing(LIST_SPACING),
+        );
+    }
+    if let Some(days) = stats::reading_duration_days(book.bought, book.finished) {
+        content = content.push(text(format_reading_duration(days)).size(fonts.small()));
+    }
+    if let Some(path) = &book.cover_path {
+        content = content.push(iced::widget::image(path.as_str()).height(fonts.width(220.0)));
+    }
+
+    column![
+        header,
+        scrollable(container(content).width(Length::Fill)).height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
+fn view_book_list(app: &BookshelfApp) -> Element<Message> {
+    let fonts = app.fonts();
+    let add_button = button("Add New Book")
+        .on_press(Message::AddBookMode)
+        .style(button::primary);
+
+    let books_to_display = app.displayed_books();
+
+    let search_status = create_search_status_label(app);
 
     let book_list_content = if books_to_display.is_empty() {
         create_empty_list_label(app)
+    } else if app.config.group_by_author {
+        create_author_groups(app, books_to_display)
+    } else if app.config.group_by_series {
+        create_series_groups(app, books_to_display)
+    } else {
+        create_pinned_and_other_books(app, books_to_display)
+    };
+
+    let book_list = scrollable(container(book_list_content).width(Length::Fill))
+        .id(book_list_id())
+        .on_scroll(Message::BookListScrolled)
+        .height(Length::Fill);
+    let book_list: Element<Message> = if app.config.show_details_pane {
+        split_view::create_split_panes(app, book_list)
     } else {
-        create_books_list(books_to_display)
+        book_list.into()
     };
 
     column![
         row![
-            text(search_status).size(24),
+            text(search_status).size(fonts.title()),
             iced::widget::horizontal_space(),
+            pick_list(
+                ListMode::ALL,
+                Some(app.config.list_mode),
+                Message::ListModeSelected
+            ),
+            checkbox("Group by author", app.config.group_by_author)
+                .on_toggle(Message::ToggleGroupByAuthor),
+            checkbox("Group by series", app.config.group_by_series)
+                .on_toggle(Message::ToggleGroupBySeries),
+            button("Copy as Markdown")
+                .on_press_maybe(
+                    (!books_to_display.is_empty()).then_some(Message::CopyBooksAsMarkdown)
+                )
+                .style(button::secondary),
+            button("Print view")
+                .on_press(Message::TogglePrintView)
+                .style(button::secondary),
+            button("Import CSV…")
+                .on_press(Message::OpenCsvImport)
+                .style(button::secondary),
+            button("Set bought date…")
+                .on_press(Message::OpenBoughtDateDialog)
+                .style(button::secondary),
             add_button
         ]
+        .spacing(10)
         .padding(15)
         .width(Length::Fill),
-        scrollable(container(book_list_content).width(Length::Fill)).height(Length::Fill)
+        create_stats_strip(app, books_to_display),
+        list_view::create_list_picker(app),
+        create_selection_bar(app),
+        missing_view::create_assign_bar(app),
+        book_list,
     ]
     .spacing(20)
     .padding(25)
     .into()
 }
 
-fn create_books_list(books_to_display: &Vec<BookWithAuthor>) -> Column<Message> {
-    let mut list = column![].spacing(15).width(Length::Fill).padding(20);
+// Pinned books get a block of their own, the rest follow in the active sort
+fn create_pinned_and_other_books<'a>(
+    app: &'a BookshelfApp,
+    books: &'a [BookWithAuthor],
+) -> Column<'a, Message> {
+    let pinned = if app.config.pin_books_to_top {
+        pinned_count(books)
+    } else {
+        0
+    };
+    let books: Vec<&BookWithAuthor> = books.iter().collect();
+    let (pinned_books, other_books) = books.split_at(pinned);
+    if pinned_books.is_empty() {
+        return create_books_layout(app, other_books);
+    }
 
-    for book in books_to_display {
-        let author_name = book
-            .author
-            .as_ref()
-            .and_then(|a| a.Name.clone())
-            .unwrap_or_else(|| "No Author".to_string());
+    let fonts = app.fonts();
+    let mut blocks = column![
+        text(format!("Pinned ({})", pinned_books.len())).size(fonts.heading()),
+        container(create_books_layout(app, pinned_books))
+            .width(Length::Fill)
+            .style(pinned_block_style),
+    ]
+    .spacing(10)
+    .padding([0, 20]);
+    if !other_books.is_empty() {
+        blocks = blocks
+            .push(text("Other books").size(fonts.heading()))
+            .push(create_books_layout(app, other_books));
+    }
+    blocks
+}
+
+// Collapsible author headers with the author's books under them in list order
+fn create_author_groups<'a>(
+    app: &'a BookshelfApp,
+    books: &'a [BookWithAuthor],
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    stats::group_by_author(books).into_iter().fold(
+        column![].spacing(10).padding([0, 20]),
+        |groups, group| {
+            let collapsed = app.collapsed_authors.contains(&group.author_id);
+            let header = format!(
+                "{} {} ({}) · {}",
+                if collapsed { "▸" } else { "▾" },
+                group.name,
+                group.books.len(),
+                format_price(group.total_spent(), CURRENCY, app.privacy_mode)
+            );
+            let groups = groups.push(
+                button(text(header).size(fonts.heading()))
+                    .on_press(Message::ToggleAuthorGroup(group.author_id))
+                    .style(button::text)
+                    .padding(0),
+            );
+            if collapsed {
+                groups
+            } else {
+                groups.push(create_books_layout(app, &group.books))
+            }
+        },
+    )
+}
+
+// Series headers with the owned/finished progress and the listed volumes under them
+fn create_series_groups<'a>(
+    app: &'a BookshelfApp,
+    books: &'a [BookWithAuthor],
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    stats::group_by_series(books, &app.books, &app.series).into_iter().fold(
+        column![].spacing(10).padding([0, 20]),
+        |groups, group| {
+            let header: Element<Message> = match &group.progress {
+                Some(progress) => series_view::create_series_progress(progress, fonts).into(),
+                None => text(NO_SERIES_GROUP).size(fonts.heading()).into(),
+            };
+            groups
+                .push(header)
+                .push(create_books_layout(app, &group.books))
+        },
+    )
+}
+
+fn create_books_layout<'a>(
+    app: &'a BookshelfApp,
+    books: &[&'a BookWithAuthor],
+) -> Column<'a, Message> {
+    match app.config.list_mode {
+        ListMode::List => create_books_list(app, books),
+        ListMode::Grid => create_books_grid(app, books),
+        ListMode::Table => table_view::create_books_table(app, books),
+        ListMode::Shelf => shelf_view::create_books_shelf(app, books),
+    }
+}
+
+fn pinned_block_style(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
+    container::Style {
+        background: Some(palette.primary.weak.color.scale_alpha(0.15).into()),
+        border: iced::Border::default()
+            .rounded(6)
+            .color(palette.primary.weak.color)
+            .width(1),
+        ..container::Style::default()
+    }
+}
+
+fn highlighted_box(theme: &Theme) -> container::Style {
+    let mut style = container::bordered_box(theme);
+    style.border.color = theme.palette().primary;
+    style.border.width = 2.0;
+    style
+}
+
+// Row outline for the book last edited or focused from the keyboard
+pub fn is_book_highlighted(app: &BookshelfApp, book_id: ID) -> bool {
+    app.highlighted_book == Some(book_id) || focused_book_id(app) == Some(book_id)
+}
 
-        let price_text = book
-            .book
-            .price
-            .map(|p| format!("{:.2}zł", p))
-            .unwrap_or_else(|| "No price".to_string());
+fn create_books_list<'a>(
+    app: &'a BookshelfApp,
+    books_to_display: &[&'a BookWithAuthor],
+) -> Column<'a, Message> {
+    let mut list = column![].spacing(15).width(Length::Fill).padding(20);
 
+    for book in books_to_display {
+        let book_id = book.book.id;
         let book_row = row![
-            column![
-                text(&book.book.title).size(18),
-                text(format!("By: {}", author_name)).size(14),
-                text(price_text).size(14),
-            ]
-            .spacing(8)
-            .width(Length::Fill),
-            button("Edit")
-                .on_press(Message::EditBookMode(book.clone()))
-                .style(button::secondary)
-                .padding(8),
-            button("Delete")
-                .on_press(Message::ConfirmDeleteBook(
-                    book.book.id,
-                    book.book.title.clone()
-                ))
-                .style(button::danger)
-                .padding(8),
+            checkbox("", app.selected_books.contains(&book_id))
+                .on_toggle(move |_| Message::ToggleBookSelection(book_id)),
+            create_book_details(app, book).width(Length::Fill),
+            create_book_actions(book),
         ]
         .spacing(15)
         .padding(10)
         .align_y(iced::Alignment::Center);
+        let book_row = column![book_row, create_open_book_menu(app, book_id)];
 
         list = list.push(
-            container(book_row)
-                .padding(10)
-                .style(container::bordered_box),
+            mouse_area(
+                container(book_row)
+                    .padding(10)
+                    .style(book_box_style(is_book_highlighted(app, book_id))),
+            )
+            .on_press(Message::BookRowClicked(book_id))
+            .on_enter(Message::BookHoverStarted(book_id))
+            .on_exit(Message::BookHoverEnded(book_id)),
         );
     }
     list
 }
 
+// Same books as the list, laid out as cards in rows of per_row
+fn create_books_grid<'a>(
+    app: &'a BookshelfApp,
+    books_to_display: &[&'a BookWithAuthor],
+) -> Column<'a, Message> {
+    let per_row = app.books_per_row();
+    let mut grid = column![].spacing(15).width(Length::Fill).padding(20);
+
+    for chunk in books_to_display.chunks(per_row) {
+        let mut cards = row![].spacing(15).width(Length::Fill);
+
+        for book in chunk {
+            let book_id = book.book.id;
+            let card = column![
+                row![
+                    checkbox("", app.selected_books.contains(&book_id))
+                        .on_toggle(move |_| Message::ToggleBookSelection(book_id)),
+                    create_book_details(app, book).width(Length::Fill),
+                ]
+                .spacing(10),
+                create_book_actions(book),
+                create_open_book_menu(app, book_id),
+            ]
+            .spacing(10)
+            .padding(10);
+
+            cards = cards.push(
+                mouse_area(
+                    container(card)
+                        .padding(10)
+                        .width(Length::FillPortion(1))
+                        .style(book_box_style(is_book_highlighted(app, book_id))),
+                )
+                .on_press(Message::BookRowClicked(book_id))
+                .on_enter(Message::BookHoverStarted(book_id))
+                .on_exit(Message::BookHoverEnded(book_id)),
+            );
+        }
+
+        // Keep the cards of an incomplete last row the same width as the others
+        for _ in chunk.len()..per_row {
+            cards = cards.push(iced::widget::horizontal_space().width(Length::FillPortion(1)));
+        }
+
+        grid = grid.push(cards);
+    }
+    grid
+}
+
+pub fn book_box_style(is_highlighted: bool) -> fn(&Theme) -> container::Style {
+    if is_highlighted {
+        highlighted_box
+    } else {
+        container::bordered_box
+    }
+}
+
+/// Colored dot that makes the list scannable by genre
+pub fn genre_marker<'a>(app: &BookshelfApp, book: &BookWithAuthor) -> Element<'a, Message> {
+    let color = book_genre_color(book.book.genre.as_deref(), &app.config.genre_colors);
+    let size = app.fonts().width(10.0);
+
+    container(horizontal_space())
+        .width(size)
+        .height(size)
+        .style(move |_| container::Style {
+            background: Some(color.into()),
+            border: iced::Border::default().rounded(size / 2.0),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+pub fn create_book_details<'a>(
+    app: &BookshelfApp,
+    book: &'a BookWithAuthor,
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let author_name = book
+        .author
+        .as_ref()
+        .and_then(|a| a.Name.clone())
+        .unwrap_or_else(|| "No Author".to_string());
+
+    let price_text = book
+        .book
+        .price
+        .map(|p| format_price(p, CURRENCY, app.privacy_mode))
+        .unwrap_or_else(|| "No price".to_string());
+
+    // Owning several copies shows as "Title x3"
+    let title = if book.book.quantity > 1 {
+        format!("{} x{}", book.book.title, book.book.quantity)
+    } else {
+        book.book.title.clone()
+    };
+
+    let mut details = column![
+        row![genre_marker(app, book), text(title).size(fonts.item())]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        text(format!("By: {}", author_name)).size(fonts.small()),
+        text(price_text).size(fonts.small()),
+    ]
+    .spacing(8);
+
+    if let Some(series) = book
+        .book
+        .SeriesFK
+        .and_then(|series_id| app.series.iter().find(|s| s.id == series_id))
+    {
+        details = details.push(text(format!("Series: {}", series.name)).size(fonts.small()));
+    }
+
+    if let Some(giver) = &book.book.gift_from {
+        details = details.push(text(format!("Gift from {}", giver)).size(fonts.tiny()));
+    }
+
+    if let Some(genre) = &book.book.genre {
+        details = details.push(text(format!("Genre: {}", genre)).size(fonts.tiny()));
+    }
+
+    if let Some(pages) = book.book.page_count {
+        details = details.push(text(format!("{} pages", pages)).size(fonts.tiny()));
+    }
+
+    if let Some(format) = book.book.book_format() {
+        let label = match book.book.duration_minutes.filter(|_| book.book.is_audiobook()) {
+            Some(minutes) => format!("{}, {}", format, format_duration(minutes)),
+            None => format.to_string(),
+        };
+        details = details.push(text(label).size(fonts.tiny()));
+    }
+
+    if let Some(code) = &book.book.language {
+        details = details.push(text(language_name(code)).size(fonts.tiny()));
+    }
+
+    if let Some(place) = &book.book.acquired_from {
+        details = details.push(text(format!("From {}", place)).size(fonts.tiny()));
+    }
+
+    if let Some(total) = app.reading_totals.get(&book.book.id) {
+        details = details.push(text(format_session_total(total)).size(fonts.tiny()));
+    }
+
+    if let Some(rating) = book.book.rating {
+        details = details.push(text(format_rating(rating)).size(fonts.small()));
+    }
+
+    // There is no start date of its own, reading is assumed to start when it was bought
+    if let Some(days) = stats::reading_duration_days(book.book.bought, book.book.finished) {
+        details = details.push(text(format_reading_duration(days)).size(fonts.tiny()));
+    }
+
+    if let Some(badge) = loan_view::create_loan_badge(app, book.book.id) {
+        details = details.push(badge);
+    }
+
+    if let Some(badge) = quote_view::create_quote_badge(app, book.book.id) {
+        details = details.push(badge);
+    }
+
+    details
+}
+
+fn create_book_actions<'a>(book: &BookWithAuthor) -> Row<'a, Message> {
+    row![
+        button(if book.book.pinned { "Unpin" } else { "Pin" })
+            .on_press(Message::ToggleBookPinned(book.book.id))
+            .style(button::secondary)
+            .padding(8),
+        button("Edit")
+            .on_press(Message::EditBookMode(book.clone()))
+            .style(button::secondary)
+            .padding(8),
+        button("⋯")
+            .on_press(Message::ToggleBookMenu(book.book.id))
+            .style(button::secondary)
+            .padding(8),
+        button("Delete")
+            .on_press(Message::ConfirmDeleteBook(
+                book.book.id,
+                book.book.title.clone()
+            ))
+            .style(button::danger)
+            .padding(8),
+    ]
+    .spacing(15)
+}
+
+// Reading lists offered by the "⋯" button, only below the book it was opened for
+fn create_open_book_menu(app: &BookshelfApp, book_id: ID) -> Element<'_, Message> {
+    if app.book_menu != Some(book_id) {
+        return column![].into();
+    }
+    container(list_view::create_book_menu(app, book_id))
+        .padding([0, 10])
+        .into()
+}
+
+// Counts for whatever the list currently shows, so they follow the active filters
+fn create_stats_strip<'a>(app: &BookshelfApp, books: &[BookWithAuthor]) -> Element<'a, Message> {
+    if !app.config.show_stats_strip {
+        return column![].into();
+    }
+
+    let fonts = app.fonts();
+    let summary = stats::list_summary(books);
+    row![
+        text(format!("{} books", summary.total)).size(fonts.small()),
+        text(format!("{} finished", summary.finished)).size(fonts.small()),
+        text(format!("{} unread", summary.unread)).size(fonts.small()),
+    ]
+    .spacing(20)
+    .padding([0, 15])
+    .into()
+}
+
+// Bulk actions for the selected books, empty when nothing is selected
+fn create_selection_bar(app: &BookshelfApp) -> Element<'_, Message> {
+    if app.selected_books.is_empty() {
+        return column![].into();
+    }
+
+    let fonts = app.fonts();
+    row![
+        text(format!("{} selected", app.selected_books.len())).size(fonts.small()),
+        text_input("Path to another .db file", &app.move_target)
+            .on_input(Message::MoveTargetChanged)
+            .on_submit(Message::MoveSelectedBooks)
+            .padding(8)
+            .width(Length::Fill),
+        button("Move to database")
+            .on_press_maybe(
+                (!app.move_target.trim().is_empty()).then_some(Message::MoveSelectedBooks)
+            )
+            .style(button::primary)
+            .padding(8),
+        button("PDF catalog")
+            .on_press(Message::ExportCatalog(true))
+            .style(button::secondary)
+            .padding(8),
+        button("Clear selection")
+            .on_press(Message::ClearBookSelection)
+            .style(button::secondary)
+            .padding(8),
+    ]
+    .spacing(10)
+    .padding([0, 15])
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
 fn create_empty_list_label(app: &BookshelfApp) -> Column<Message> {
-    column![text(if app.is_searching {
-        format!("No books found matching '{}'", app.search_term_displayed)
+    let fonts = app.fonts();
+    column![text(if app.books_loading {
+        "Loading…".to_string()
+    } else if app.is_searching {
+        format!("No books found {}", filter_description(app))
     } else {
         "No books found".to_string()
     })
-    .size(16)]
+    .size(fonts.body())]
     .spacing(5)
     .width(Length::Fill)
     .padding(20)
 }
 
+// Describes the active search and filters, e.g. "matching 'dune' received as gifts"
+fn filter_description(app: &BookshelfApp) -> String {
+    let mut parts = Vec::new();
+    if !app.search_term_displayed.is_empty() {
+        parts.push(format!("matching '{}'", app.search_term_displayed));
+    }
+    if app.gifts_only {
+        parts.push("received as gifts".to_string());
+    }
+    if let Some(field) = app.missing_filter {
+        parts.push(format!("missing {}", field));
+    }
+    if let Some(code) = &app.language_filter {
+        parts.push(format!("in {}", language_name(code)));
+    }
+    if let Some(place) = &app.acquired_from_filter {
+        parts.push(format!("acquired from {}", place));
+    }
+    if let Some(year) = app.year_filter {
+        let field = app.year_filter_field.to_string().to_lowercase();
+        parts.push(format!("{} in {}", field, year));
+    }
+    parts.join(" ")
+}
+
 fn create_search_status_label(app: &BookshelfApp) -> String {
     let search_status = if app.is_searching {
         if let Some(filtered) = &app.filtered_books {
             if filtered.is_empty() {
-                format!("No books found matching '{}'", app.search_term_displayed)
+                format!("No books found {}", filter_description(app))
             } else {
                 format!(
-                    "Found {} books matching '{}'",
+                    "Found {} books {}, match {} of {}",
                     filtered.len(),
-                    app.search_term_displayed
+                    filter_description(app),
+                    app.current_match.min(filtered.len() - 1) + 1,
+                    filtered.len()
                 )
             }
         } else {
@@ -354,7 +1913,8 @@ fn create_search_status_label(app: &BookshelfApp) -> String {
     search_status
 }
 
-fn view_book_form(app: &BookshelfApp) -> Element<Message> {
+pub fn view_book_form(app: &BookshelfApp) -> Element<Message> {
+    let fonts = app.fonts();
     let title = match app.mode {
         Mode::Add => "Add New Book",
         Mode::Edit => "Edit Book",
@@ -364,64 +1924,468 @@ fn view_book_form(app: &BookshelfApp) -> Element<Message> {
     let mut author_options = app.authors.clone();
     author_options.sort_by(|a, b| a.Name.cmp(&b.Name));
 
-    let form = column![
-        text(title).size(24),
-        text("Title:").size(16),
+    let mut header = row![text(title).size(fonts.title())]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center);
+    if matches!(app.mode, Mode::Add) && app.books_added_this_session > 0 {
+        header = header.push(
+            container(
+                text(format!("{} added this session", app.books_added_this_session))
+                    .size(fonts.tiny()),
+            )
+            .padding([2, 8])
+            .style(container::rounded_box),
+        );
+    }
+    if matches!(app.mode, Mode::Edit) && !app.journal_entries.is_empty() {
+        header = header.push(
+            container(text(format!("{} journal", app.journal_entries.len())).size(fonts.tiny()))
+                .padding([2, 8])
+                .style(container::rounded_box),
+        );
+    }
+
+    let mut form = column![
+        header,
+        text("Title:").size(fonts.body()),
         text_input("Enter book title", &app.book_title)
+            .id(book_title_input_id())
             .on_input(Message::BookTitleChanged)
             .padding(10),
-        text("Price:").size(16),
+        text("Price:").size(fonts.body()),
         text_input("Enter price (optional)", &app.book_price)
             .on_input(Message::BookPriceChanged)
+            .secure(app.privacy_mode)
             .padding(10),
-        text("Bought Date (YYYY-MM-DD HH:MM:SS):").size(16),
-        text_input("YYYY-MM-DD HH:MM:SS (optional)", &app.book_bought_date)
-            .on_input(Message::BookBoughtDateChanged)
-            .padding(10),
-        text("Finished Date (YYYY-MM-DD HH:MM:SS):").size(16),
-        text_input("YYYY-MM-DD HH:MM:SS (optional)", &app.book_finished_date)
-            .on_input(Message::BookFinishedDateChanged)
-            .padding(10),
-        text("Author:").size(16),
+        row![
+            text("Copies:").size(fonts.body()),
+            button(text("-").size(fonts.body()))
+                .on_press_maybe(
+                    (app.book_quantity > BOOK_QUANTITY_MIN)
+                        .then_some(Message::ChangeBookQuantity(-1))
+                )
+                .style(button::secondary),
+            text(app.book_quantity.to_string()).size(fonts.body()),
+            button(text("+").size(fonts.body()))
+                .on_press(Message::ChangeBookQuantity(1))
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        create_date_input(
+            app,
+            "Bought Date:",
+            &app.book_bought_date,
+            Message::BookBoughtDateChanged
+        ),
+        create_date_input(
+            app,
+            "Finished Date:",
+            &app.book_finished_date,
+            Message::BookFinishedDateChanged
+        ),
+        create_copy_dates_button(app),
+        text("Author:").size(fonts.body()),
         // Use our custom searchable dropdown instead of pick_list
-        searchable_dropdown::view_author_dropdown(
+        searchable_dropdown::view_dropdown(
             &app.author_dropdown,
             Message::ToggleAuthorDropdown,
             |term| Message::AuthorSearchChanged(term),
             |author| Message::BookAuthorSelected(author),
+            fonts,
         ),
+        text("Series:").size(fonts.body()),
         row![
-            button("Save")
-                .on_press(Message::SaveBook)
-                .style(button::primary),
-            button("Cancel")
-                .on_press(Message::ViewBookMode)
-                .style(button::secondary),
+            pick_list(
+                app.series.clone(),
+                app.selected_series.clone(),
+                Message::BookSeriesSelected
+            )
+            .placeholder("No series")
+            .padding(10)
+            .width(Length::Fill),
+            button("Clear")
+                .on_press(Message::ClearBookSeries)
+                .style(button::secondary)
+                .padding(10),
         ]
-        .spacing(10)
+        .spacing(10),
+        text("Genre:").size(fonts.body()),
+        text_input("Enter genre (optional)", &app.book_genre)
+            .on_input(Message::BookGenreChanged)
+            .padding(10),
+        text("Pages:").size(fonts.body()),
+        text_input("Enter page count (optional)", &app.book_page_count)
+            .on_input(Message::BookPageCountChanged)
+            .padding(10),
+        create_format_input(app),
+        create_language_input(app),
+        create_acquired_from_input(app),
+        create_rating_input(app),
+        create_gift_input(app),
+        custom_field_view::create_custom_fields_input(app),
+        cover_view::create_cover_section(app),
+        create_form_buttons(app),
     ]
     .spacing(10)
     .padding(20)
-    .max_width(LIST_MAX_WIDTH);
+    .max_width(fonts.width(LIST_MAX_WIDTH));
 
-    container(form)
-        .width(Length::Fill)
+    // Journal entries, quotes, loans and reading sessions need a stored book to attach to
+    if matches!(app.mode, Mode::Edit) {
+        form = form
+            .push(session_view::create_book_sessions(app))
+            .push(loan_view::create_loan_section(app))
+            .push(journal_view::create_journal_section(app))
+            .push(quote_view::create_quote_section(app));
+    }
+
+    scrollable(container(form).width(Length::Fill).center_x(Length::Fill))
         .height(Length::Fill)
-        .center_x(Length::Fill)
         .into()
 }
 
+// Adding offers to stay in the form for the next book
+fn create_form_buttons(app: &BookshelfApp) -> Row<'_, Message> {
+    if let Some(queue) = &app.review_queue {
+        return review_view::create_review_buttons(queue);
+    }
+    let mut buttons = row![button("Save")
+        .on_press(Message::SaveBook)
+        .style(button::primary)]
+    .spacing(10);
+    if offers_add_another(app) {
+        buttons = buttons.push(
+            button("Save and add another")
+                .on_press(Message::SaveBookAndAddAnother)
+                .style(button::primary),
+        );
+    }
+    buttons.push(
+        button("Cancel")
+            .on_press(Message::ViewBookMode)
+            .style(button::secondary),
+    )
+}
+
+// Date input with quick fills, shorthand is resolved below it as it is typed
+fn create_date_input<'a>(
+    app: &BookshelfApp,
+    label: &'a str,
+    value: &'a str,
+    on_change: fn(String) -> Message,
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let now = Local::now().naive_local();
+    let today = now.date().and_time(NaiveTime::MIN);
+    let quick = |name: &'a str, value: String| {
+        button(text(name).size(fonts.small()))
+            .on_press(on_change(value))
+            .style(button::secondary)
+            .padding(6)
+    };
+
+    let input = value.trim();
+    let resolved = if input.is_empty() {
+        None
+    } else {
+        match parse_relative_date(input, now) {
+            Some(date) if date.format(FORM_DATE_FORMAT).to_string() == input => None,
+            Some(date) => Some(format!("= {}", date.format(FORM_DATE_FORMAT))),
+            None => Some("Not a date, try 2024-05-14, 2024-05, -3d or last tuesday".to_string()),
+        }
+    };
+
+    column![
+        text(label).size(fonts.body()),
+        row![
+            text_input("YYYY-MM-DD HH:MM:SS, -3d, last tuesday (optional)", value)
+                .on_input(on_change)
+                .padding(10)
+                .width(Length::Fill),
+            quick("Today", today.format(FORM_DATE_FORMAT).to_string()),
+            quick(
+                "Yesterday",
+                (today - chrono::Days::new(1)).format(FORM_DATE_FORMAT).to_string()
+            ),
+            quick("Clear", String::new()),
+        ]
+        .spacing(5)
+        .align_y(iced::Alignment::Center),
+    ]
+    .push_maybe(resolved.map(|resolved| text(resolved).size(fonts.small())))
+    .spacing(5)
+}
+
+// Repeats the dates of the last saved book, for a stack bought or finished the same day
+fn create_copy_dates_button(app: &BookshelfApp) -> Row<'_, Message> {
+    let fonts = app.fonts();
+    let Some((bought, finished)) = app.last_saved_dates else {
+        return row![];
+    };
+    if bought.is_none() && finished.is_none() {
+        return row![];
+    }
+    let date = |date: Option<NaiveDateTime>| {
+        date.map_or_else(|| "-".to_string(), |d| d.format("%Y-%m-%d").to_string())
+    };
+    row![button(
+        text(format!(
+            "Copy dates from previous book (bought {}, finished {})",
+            date(bought),
+            date(finished)
+        ))
+        .size(fonts.small())
+    )
+    .on_press(Message::CopyPreviousDates)
+    .style(button::secondary)
+    .padding(6)]
+}
+
+// Listening time input, only shown for audiobooks
+fn create_format_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    column![
+        text("Format:").size(fonts.body()),
+        row![
+            pick_list(BookFormat::ALL, app.book_format, |format| {
+                Message::BookFormatSelected(Some(format))
+            })
+            .placeholder("No format")
+            .padding(10)
+            .width(Length::Fill),
+            button("Clear")
+                .on_press(Message::BookFormatSelected(None))
+                .style(button::secondary)
+                .padding(10),
+        ]
+        .spacing(10),
+    ]
+    .push_maybe((app.book_format == Some(BookFormat::Audiobook)).then(|| {
+        column![
+            text("Listening time (minutes):").size(fonts.body()),
+            text_input("e.g. 605 for 10h 5m", &app.book_duration_minutes)
+                .on_input(Message::BookDurationChanged)
+                .padding(10),
+        ]
+        .spacing(10)
+    }))
+    .spacing(10)
+}
+
+/// One chip per language in the library, nothing when no book has a language yet
+pub fn create_language_filters(app: &BookshelfApp) -> Row<'_, Message> {
+    let fonts = app.fonts();
+    let counts = stats::books_per_language(&app.books);
+    if counts.iter().all(|count| count.code.is_none()) {
+        return row![];
+    }
+
+    counts
+        .into_iter()
+        .filter_map(|count| count.code.map(|code| (code, count.books)))
+        .fold(
+            row![text("Language:").size(fonts.small())]
+                .spacing(5)
+                .align_y(iced::Alignment::Center),
+            |chips, (code, books)| {
+                let active = app.language_filter.as_ref() == Some(&code);
+                let label = format!("{} ({})", language_name(&code), books);
+                chips.push(
+                    button(text(label).size(fonts.small()))
+                        .on_press(Message::ToggleLanguageFilter(code))
+                        .style(if active {
+                            button::primary
+                        } else {
+                            button::secondary
+                        })
+                        .padding(6),
+                )
+            },
+        )
+}
+
+/// Picks the place to list books from, nothing when no book records one
+pub fn create_acquired_from_filter(app: &BookshelfApp) -> Row<'_, Message> {
+    let fonts = app.fonts();
+    let places = acquisition_places(&app.books);
+    if places.is_empty() {
+        return row![];
+    }
+
+    let selected = app.acquired_from_filter.clone().map(AcquisitionPlace);
+    let mut filter = row![
+        text("From:").size(fonts.small()),
+        pick_list(places, selected, |place| {
+            Message::AcquiredFromFilterSelected(Some(place))
+        })
+        .placeholder("Anywhere")
+        .text_size(fonts.small())
+        .padding(6),
+    ]
+    .spacing(5)
+    .align_y(iced::Alignment::Center);
+    if app.acquired_from_filter.is_some() {
+        filter = filter.push(
+            button(text("Clear").size(fonts.small()))
+                .on_press(Message::AcquiredFromFilterSelected(None))
+                .style(button::secondary)
+                .padding(6),
+        );
+    }
+    filter
+}
+
+/// Picks a year and the date it applies to, nothing when no book has a date
+pub fn create_year_filter(app: &BookshelfApp) -> Row<'_, Message> {
+    let fonts = app.fonts();
+    if app.filter_years.is_empty() {
+        return row![];
+    }
+
+    let choices: Vec<YearChoice> = std::iter::once(YearChoice::All)
+        .chain(app.filter_years.iter().copied().map(YearChoice::Year))
+        .collect();
+    let selected = app.year_filter.map_or(YearChoice::All, YearChoice::Year);
+    row![
+        pick_list(choices, Some(selected), Message::YearFilterSelected)
+            .text_size(fonts.small())
+            .padding(6),
+        pick_list(
+            YearField::ALL,
+            Some(app.year_filter_field),
+            Message::YearFilterFieldSelected
+        )
+        .text_size(fonts.small())
+        .padding(6),
+    ]
+    .spacing(5)
+    .align_y(iced::Alignment::Center)
+}
+
+fn create_rating_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let stars = (1..=RATING_MAX).fold(row![].spacing(5), |stars, rating| {
+        let filled = app.book_rating.is_some_and(|current| rating <= current);
+        stars.push(
+            button(text(if filled { "★" } else { "☆" }).size(fonts.item()))
+                .on_press(Message::BookRatingSelected(Some(rating)))
+                .style(button::text)
+                .padding(2),
+        )
+    });
+    column![
+        text("Rating:").size(fonts.body()),
+        row![
+            stars,
+            button("Clear")
+                .on_press(Message::BookRatingSelected(None))
+                .style(button::secondary)
+                .padding(10),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(10)
+}
+
+// Free text, with the places of other books offered below it
+fn create_acquired_from_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    column![
+        text("Acquired from:").size(fonts.body()),
+        text_input("Shop or person, e.g. Amazon (optional)", &app.book_acquired_from)
+            .on_input(Message::BookAcquiredFromChanged)
+            .padding(10),
+    ]
+    .push_maybe((!app.acquired_from_dropdown.options.is_empty()).then(|| {
+        searchable_dropdown::view_dropdown(
+            &app.acquired_from_dropdown,
+            Message::ToggleAcquiredFromDropdown,
+            Message::AcquiredFromSearchChanged,
+            Message::AcquiredFromSelected,
+            fonts,
+        )
+    }))
+    .spacing(10)
+}
+
+fn create_language_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let choices =
+        language_choices(app.books.iter().filter_map(|pair| pair.book.language.as_deref()));
+    let selected = app.book_language.clone().map(Language);
+    column![
+        text("Language:").size(fonts.body()),
+        row![
+            pick_list(choices, selected, |language| {
+                Message::BookLanguageSelected(Some(language))
+            })
+            .placeholder("No language")
+            .padding(10)
+            .width(Length::Fill),
+            button("Clear")
+                .on_press(Message::BookLanguageSelected(None))
+                .style(button::secondary)
+                .padding(10),
+        ]
+        .spacing(10),
+    ]
+    .spacing(10)
+}
+
+fn create_gift_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    column![checkbox("Received as a gift", app.book_is_gift)
+        .on_toggle(Message::BookIsGiftToggled)
+        .text_size(fonts.body())]
+    .push_maybe(app.book_is_gift.then(|| {
+        text_input("Who gave it to you?", &app.book_gift_from)
+            .on_input(Message::BookGiftFromChanged)
+            .padding(10)
+    }))
+    .spacing(10)
+}
+
 // New function to display deletion confirmation
 fn view_delete_confirmation<'a>(
-    _: &'a BookshelfApp,
+    app: &'a BookshelfApp,
     id: ID,
     title: &'a str,
 ) -> Element<'a, Message> {
+    let fonts = app.fonts();
     // fn view_delete_confirmation(app: &BookshelfApp, id: i32, title: &str) -> Element<Message> {
-    let confirmation = column![
-        text(format!("Are you sure you want to delete the book:")).size(20),
-        text(format!("\"{}\"?", title)).size(24),
-        text("This action cannot be undone.").size(16),
+    let related = counted_related_records(app, id);
+    let can_delete = can_delete_book(app, id);
+
+    let mut confirmation = column![
+        text(format!("Are you sure you want to delete the book:")).size(fonts.heading()),
+        text(format!("\"{}\"?", title)).size(fonts.title()),
+        text("This action cannot be undone.").size(fonts.body()),
+    ]
+    .spacing(20)
+    .padding(30)
+    .width(Length::Fill)
+    .align_x(iced::Alignment::Center);
+
+    match &related {
+        None => {
+            confirmation =
+                confirmation.push(text("Checking the book's history...").size(fonts.small()))
+        }
+        Some(related) if !related.is_empty() => {
+            confirmation = confirmation
+                .push(text(format!("This also deletes {}.", related.summary())).size(fonts.body()))
+                .push(
+                    checkbox("I understand this history will be lost", app.delete_acknowledged)
+                        .on_toggle(Message::ToggleDeleteAcknowledged)
+                        .text_size(fonts.body()),
+                );
+        }
+        Some(_) => {}
+    }
+
+    confirmation = confirmation.push(
         row![
             button("Cancel")
                 .on_press(Message::CancelDeleteBook)
@@ -429,18 +2393,14 @@ fn view_delete_confirmation<'a>(
                 .padding(10)
                 .width(Length::Fill),
             button("Confirm Delete")
-                .on_press(Message::DeleteBook(id))
+                .on_press_maybe(can_delete.then_some(Message::DeleteBook(id)))
                 .style(button::danger)
                 .padding(10)
                 .width(Length::Fill),
         ]
         .spacing(20)
-        .padding(20)
-    ]
-    .spacing(20)
-    .padding(30)
-    .width(Length::Fill)
-    .align_x(iced::Alignment::Center);
+        .padding(20),
+    );
 
     container(confirmation)
         .width(Length::Fill)
@@ -450,3 +2410,335 @@ fn view_delete_confirmation<'a>(
         .style(container::bordered_box)
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn books(titles: &[&str]) -> Vec<BookWithAuthor> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| BookWithAuthor {
+                book: BookModel::titled(i as ID + 1, title),
+                author: None,
+            })
+            .collect()
+    }
+
+    fn app_with(titles: &[&str]) -> BookshelfApp {
+        let mut app = BookshelfApp::new();
+        let _ = handle_books_loaded(&mut app, Ok(books(titles)));
+        app
+    }
+
+    #[test]
+    fn saving_returns_to_the_list_with_the_edited_book_highlighted() {
+        let mut app = app_with(&["Dune", "Emma", "Ulysses"]);
+        let pair = app.books[1].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+        app.book_title = "Persuasion".to_string();
+
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(2, "Persuasion")));
+        assert!(matches!(app.mode, Mode::View));
+        let _ = handle_books_loaded(&mut app, Ok(books(&["Dune", "Persuasion", "Ulysses"])));
+        assert_eq!(app.highlighted_book, Some(2));
+        assert!(app.pending_scroll_target.is_none());
+    }
+
+    #[test]
+    fn cancelling_discards_the_edit() {
+        let mut app = app_with(&["Dune", "Emma", "Ulysses"]);
+        let pair = app.books[1].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+        app.book_title = "Persuasion".to_string();
+
+        let _ = handle_view_book_mode(&mut app);
+        assert!(matches!(app.mode, Mode::View));
+        assert!(app.selected_book.is_none());
+        let _ = handle_books_loaded(&mut app, Ok(books(&["Dune", "Emma", "Ulysses"])));
+        assert_eq!(app.books[1].book.title, "Emma");
+        assert_eq!(app.highlighted_book, Some(2));
+
+        let pair = app.books[1].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+        assert_eq!(app.book_title, "Emma");
+    }
+
+    #[test]
+    fn a_saved_book_hidden_by_the_filter_falls_back_to_the_top() {
+        let mut app = app_with(&["Dune", "Dune Messiah", "Emma"]);
+        app.search_term_displayed = "dune".to_string();
+        app.apply_filters();
+        let pair = app.displayed_books()[0].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(1, "Arrakis")));
+        let _ = handle_books_loaded(&mut app, Ok(books(&["Arrakis", "Dune Messiah", "Emma"])));
+        assert_eq!(app.displayed_books().len(), 1);
+        assert_eq!(app.highlighted_book, None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Edited book is hidden by the current filter")
+        );
+    }
+
+    #[test]
+    fn editing_before_the_series_load_keeps_the_books_series() {
+        let mut app = app_with(&["Foundation"]);
+        app.books[0].book.SeriesFK = Some(7);
+        let pair = app.books[0].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+        assert_eq!(app.selected_series.as_ref().map(|s| s.id), Some(7));
+
+        let series = SeriesModel {
+            id: 7,
+            name: "Foundation".to_string(),
+            total_volumes: Some(7),
+        };
+        let _ = crate::ui::series_view::handle_series_loaded(&mut app, Ok(vec![series]));
+        let selected = app.selected_series.as_ref().map(|s| s.name.as_str());
+        assert_eq!(selected, Some("Foundation"));
+    }
+
+    fn author(id: ID, name: &str) -> crate::models::AuthorModel {
+        crate::models::AuthorModel {
+            Id: id,
+            Name: Some(name.to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        }
+    }
+
+    // The add form with every field that can be sticky filled in
+    fn filled_add_form(app: &mut BookshelfApp) {
+        let _ = handle_add_book_mode(app);
+        app.book_title = "Solaris".to_string();
+        app.book_price = "25".to_string();
+        app.book_finished_date = "2024-05-20".to_string();
+        app.selected_author = Some(author(3, "Stanisław Lem"));
+        app.selected_series = Some(SeriesModel {
+            id: 4,
+            name: "Ijon Tichy".to_string(),
+            total_volumes: None,
+        });
+        app.book_bought_date = "2024-05-14".to_string();
+        app.book_format = Some(BookFormat::Hardcover);
+        app.book_language = Some("pl".to_string());
+        app.book_acquired_from = "Empik".to_string();
+    }
+
+    #[test]
+    fn save_and_add_another_keeps_only_the_sticky_fields() {
+        let mut app = app_with(&["Dune"]);
+        app.config.sticky_fields = crate::ui::StickyFields {
+            author: true,
+            series: false,
+            bought_date: true,
+            format: false,
+            language: true,
+            acquired_from: false,
+        };
+        filled_add_form(&mut app);
+
+        let _ = app.update(Message::SaveBookAndAddAnother);
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(2, "Solaris")));
+        assert!(matches!(app.mode, Mode::Add));
+        assert_eq!(app.books_added_this_session, 1);
+        assert_eq!(app.selected_author.as_ref().map(|a| a.Id), Some(3));
+        assert_eq!(app.book_bought_date, "2024-05-14");
+        assert_eq!(app.book_language.as_deref(), Some("pl"));
+        assert!(app.selected_series.is_none());
+        assert_eq!(app.book_format, None);
+        assert_eq!(app.book_acquired_from, "");
+        assert_eq!(app.book_title, "");
+        assert_eq!(app.book_price, "");
+        assert_eq!(app.book_finished_date, "");
+    }
+
+    #[test]
+    fn with_nothing_sticky_the_next_form_starts_empty() {
+        let mut app = app_with(&["Dune"]);
+        app.config.sticky_fields = crate::ui::StickyFields {
+            author: false,
+            series: false,
+            bought_date: false,
+            format: false,
+            language: false,
+            acquired_from: false,
+        };
+        filled_add_form(&mut app);
+
+        let _ = app.update(Message::SaveBookAndAddAnother);
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(2, "Solaris")));
+        let _ = app.update(Message::SaveBookAndAddAnother);
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(3, "Eden")));
+        assert!(matches!(app.mode, Mode::Add));
+        assert_eq!(app.books_added_this_session, 2);
+        assert!(app.selected_author.is_none());
+        assert!(app.selected_series.is_none());
+        assert_eq!(app.book_bought_date, "");
+        assert_eq!(app.book_format, None);
+        assert_eq!(app.book_language, None);
+        assert_eq!(app.book_acquired_from, "");
+    }
+
+    #[test]
+    fn editing_never_offers_save_and_add_another() {
+        let mut app = app_with(&["Dune"]);
+        let _ = handle_add_book_mode(&mut app);
+        assert!(offers_add_another(&app));
+
+        let pair = app.books[0].clone();
+        let _ = handle_edit_book_mode(&mut app, &pair);
+        assert!(!offers_add_another(&app));
+
+        // Sent anyway, the edit saves and returns to the list like a plain save
+        let _ = app.update(Message::SaveBookAndAddAnother);
+        assert!(!app.add_another_after_save);
+        let _ = handle_book_saved(&mut app, Ok(BookModel::titled(1, "Dune")));
+        assert!(matches!(app.mode, Mode::View));
+        assert_eq!(app.books_added_this_session, 0);
+    }
+
+    #[test]
+    fn a_book_without_history_is_deleted_with_one_click() {
+        let mut app = app_with(&["Dune"]);
+        let _ = handle_confirm_delete_book(&mut app, 1, "Dune".to_string());
+        // Nothing can be deleted before the history is known
+        assert!(!can_delete_book(&app, 1));
+
+        let _ = handle_related_records_counted(&mut app, 1, Ok(RelatedRecords::default()));
+        assert!(can_delete_book(&app, 1));
+        assert!(!app.delete_acknowledged);
+    }
+
+    #[test]
+    fn a_book_with_history_needs_the_loss_acknowledged() {
+        let mut app = app_with(&["Dune", "Emma"]);
+        let history = RelatedRecords {
+            journal_entries: 5,
+            loans: 1,
+            ..RelatedRecords::default()
+        };
+        let _ = handle_confirm_delete_book(&mut app, 1, "Dune".to_string());
+        let _ = handle_related_records_counted(&mut app, 1, Ok(history));
+        assert!(!can_delete_book(&app, 1));
+
+        let _ = app.update(Message::ToggleDeleteAcknowledged(true));
+        assert!(can_delete_book(&app, 1));
+
+        // The acknowledgment doesn't carry over to the next confirmation
+        let _ = handle_cancel_delete_book(&mut app);
+        let _ = handle_confirm_delete_book(&mut app, 2, "Emma".to_string());
+        assert!(!app.delete_acknowledged);
+        assert!(!can_delete_book(&app, 1));
+    }
+
+    #[test]
+    fn a_count_for_another_book_is_ignored() {
+        let mut app = app_with(&["Dune", "Emma"]);
+        let _ = handle_confirm_delete_book(&mut app, 2, "Emma".to_string());
+        let _ = handle_related_records_counted(&mut app, 1, Ok(RelatedRecords::default()));
+        assert!(app.delete_related.is_none());
+        assert!(!can_delete_book(&app, 2));
+    }
+
+    #[test]
+    fn the_status_lists_what_was_deleted_with_the_book() {
+        let deleted = |removed: RelatedRecords| DeleteReport {
+            item: DeletedItem::Book(Box::new(crate::trash::DeletedBook {
+                book: BookModel::titled(1, "Dune"),
+                author: None,
+                journal: Vec::new(),
+                quotes: Vec::new(),
+                fields: Vec::new(),
+                loans: Vec::new(),
+                sessions: Vec::new(),
+                lists: Vec::new(),
+            })),
+            removed,
+        };
+        let mut app = app_with(&["Dune"]);
+        let _ = handle_book_deleted(&mut app, Ok(deleted(RelatedRecords::default())));
+        assert!(matches!(app.mode, Mode::View));
+        assert_eq!(app.status_message, None);
+
+        let removed = RelatedRecords {
+            journal_entries: 2,
+            loans: 1,
+            ..RelatedRecords::default()
+        };
+        let _ = handle_book_deleted(&mut app, Ok(deleted(removed)));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Deleted \"Dune\" with 2 journal entries, 1 loan record")
+        );
+    }
+
+    #[test]
+    fn the_year_filter_composes_with_search_and_is_described() {
+        let day = |year| {
+            chrono::NaiveDate::from_ymd_opt(year, 6, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+        };
+        let mut app = app_with(&["Dune", "Dune Messiah", "Emma"]);
+        app.books[0].book.bought = day(2021);
+        app.books[1].book.bought = day(2022);
+        app.books[1].book.finished = day(2021);
+        app.books[2].book.bought = day(2021);
+        let shown = |app: &BookshelfApp| -> Vec<String> {
+            app.displayed_books()
+                .iter()
+                .map(|pair| pair.book.title.clone())
+                .collect()
+        };
+
+        app.search_term_displayed = "dune".to_string();
+        let _ = handle_year_filter_field_selected(&mut app, YearField::Bought);
+        let _ = handle_year_filter_selected(&mut app, YearChoice::Year(2021));
+        assert_eq!(shown(&app), vec!["Dune"]);
+        assert_eq!(filter_description(&app), "matching 'dune' bought in 2021");
+
+        let _ = handle_year_filter_field_selected(&mut app, YearField::Finished);
+        assert_eq!(shown(&app), vec!["Dune Messiah"]);
+        assert_eq!(filter_description(&app), "matching 'dune' finished in 2021");
+
+        let _ = handle_year_filter_selected(&mut app, YearChoice::All);
+        assert_eq!(shown(&app), vec!["Dune", "Dune Messiah"]);
+        assert_eq!(filter_description(&app), "matching 'dune'");
+    }
+
+    #[test]
+    fn a_negative_price_is_not_saved() {
+        let mut app = app_with(&["Dune"]);
+        let _ = handle_add_book_mode(&mut app);
+        app.book_title = "Emma".to_string();
+        app.book_price = "-5".to_string();
+
+        let _ = handle_save_book(&mut app);
+        assert!(!app.saving_book);
+        assert_eq!(app.error.as_deref(), Some("Invalid price '-5', it can't be negative"));
+    }
+
+    #[test]
+    fn showing_a_book_in_the_list_waits_for_the_reload() {
+        let mut app = app_with(&["Dune", "Emma"]);
+        app.current_tab = Tab::Stats;
+        let show = Message::RunInTab(Tab::Books, Box::new(Message::ShowBookInList(3)));
+        let _ = app.update(show);
+        assert!(app.books_loading);
+        assert!(!app.is_searching);
+
+        let reloaded = books(&["Dune", "Emma", "Persuasion"]);
+        let _ = handle_books_loaded(&mut app, Ok(reloaded));
+        assert_eq!(app.search_term_displayed, "Persuasion");
+        assert_eq!(app.highlighted_book, Some(3));
+        let shown: Vec<ID> = app.displayed_books().iter().map(|pair| pair.book.id).collect();
+        assert_eq!(shown, [3]);
+        assert!(app.pending_show_in_list.is_none());
+    }
+}
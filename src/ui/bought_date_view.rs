@@ -0,0 +1,276 @@
+// src/ui/bought_date_view.rs
+use crate::db::{self, BoughtDateBackup, BoughtDateFilter, BoughtDatePlan, BoughtDateScope};
+use crate::ui::components::bulk_preview;
+use crate::ui::{parse_relative_date, BookshelfApp, BoughtDateTarget, Message, FORM_DATE_FORMAT};
+use chrono::{Local, NaiveDateTime};
+use iced::widget::{button, checkbox, column, container, pick_list, row, text, text_input};
+use iced::Element;
+
+/// Inputs of the "Set bought date for…" dialog, open while set
+#[derive(Debug, Clone, Default)]
+pub struct BoughtDateForm {
+    pub target: BoughtDateTarget,
+    pub from: String, // First and last day added, only used for AddedBetween
+    pub to: String,
+    pub date: String, // Bought date to set, anything the book form accepts
+}
+
+impl BoughtDateForm {
+    /// The filter for the typed inputs, None while they don't describe any books
+    pub fn filter(&self, app: &BookshelfApp, now: NaiveDateTime) -> Option<BoughtDateFilter> {
+        let scope = match self.target {
+            BoughtDateTarget::Selection if app.selected_books.is_empty() => return None,
+            BoughtDateTarget::Selection => {
+                BoughtDateScope::Selection(app.selected_books.iter().copied().collect())
+            }
+            BoughtDateTarget::AddedBetween => {
+                let from = parse_relative_date(&self.from, now)?.date();
+                let to = parse_relative_date(&self.to, now)?.date();
+                if from > to {
+                    return None;
+                }
+                BoughtDateScope::AddedBetween(from, to)
+            }
+        };
+        Some(BoughtDateFilter {
+            scope,
+            overwrite: false,
+        })
+    }
+
+    pub fn date(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        parse_relative_date(&self.date, now)
+    }
+}
+
+// Handler functions for the bulk bought date
+pub fn handle_open_dialog(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let target = if app.selected_books.is_empty() {
+        BoughtDateTarget::AddedBetween
+    } else {
+        BoughtDateTarget::Selection
+    };
+    app.bought_date_form = Some(BoughtDateForm {
+        target,
+        ..BoughtDateForm::default()
+    });
+    iced::Task::none()
+}
+
+pub fn handle_preview(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let now = Local::now().naive_local();
+    let Some(form) = &app.bought_date_form else {
+        return iced::Task::none();
+    };
+    let (Some(filter), Some(date)) = (form.filter(app, now), form.date(now)) else {
+        app.report_error("Enter a bought date and the books to set it for".to_string());
+        return iced::Task::none();
+    };
+    plan(filter, date)
+}
+
+// Dry run first, the dialog stays open until the preview is ready
+fn plan(filter: BoughtDateFilter, date: NaiveDateTime) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::plan_bought_date_bulk(filter, date) {
+                Ok(plan) => Ok(plan),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BoughtDatePlanned,
+    )
+}
+
+pub fn handle_planned(
+    app: &mut BookshelfApp,
+    result: Result<BoughtDatePlan, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(plan) => {
+            app.bought_date_form = None;
+            app.pending_bought_date = Some(plan);
+        }
+        Err(e) => app.report_error(format!("Failed to prepare the bought dates: {}", e)),
+    }
+    iced::Task::none()
+}
+
+// Overwriting changes which books are affected, so the preview is planned again
+pub fn handle_toggle_overwrite(app: &mut BookshelfApp, overwrite: bool) -> iced::Task<Message> {
+    let Some(pending) = &app.pending_bought_date else {
+        return iced::Task::none();
+    };
+    let filter = BoughtDateFilter {
+        overwrite,
+        ..pending.filter.clone()
+    };
+    plan(filter, pending.date)
+}
+
+pub fn handle_apply(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(pending) = app.pending_bought_date.take() else {
+        return iced::Task::none();
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::set_bought_date_bulk(&pending.filter, pending.date) {
+                Ok(backup) => Ok(backup),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BoughtDateApplied,
+    )
+}
+
+pub fn handle_applied(
+    app: &mut BookshelfApp,
+    result: Result<BoughtDateBackup, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(backup) => {
+            app.notify_finished(format!("Set the bought date of {} books", backup.len()));
+            app.last_bought_date_change = (!backup.is_empty()).then_some(backup);
+            app.update(Message::LoadBooks)
+        }
+        Err(e) => {
+            app.notify_failed(format!("Failed to set the bought dates: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_undo(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(backup) = app.last_bought_date_change.take() else {
+        return iced::Task::none();
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::restore_bought_dates(&backup) {
+                Ok(count) => Ok(count),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BoughtDateUndone,
+    )
+}
+
+pub fn handle_undone(app: &mut BookshelfApp, result: Result<usize, String>) -> iced::Task<Message> {
+    match result {
+        Ok(count) => {
+            app.status_message = Some(format!("Restored the bought date of {} books", count));
+            app.update(Message::LoadBooks)
+        }
+        Err(e) => {
+            app.report_error(format!("Failed to undo the bought dates: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+// View functions for the bulk bought date
+pub fn create_dialog<'a>(app: &'a BookshelfApp, form: &'a BoughtDateForm) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let now = Local::now().naive_local();
+
+    let mut content = column![
+        text("Set bought date for…").size(fonts.heading()),
+        pick_list(
+            BoughtDateTarget::ALL,
+            Some(form.target),
+            Message::BoughtDateTargetSelected
+        )
+        .padding(8),
+    ]
+    .spacing(10);
+
+    content = match form.target {
+        BoughtDateTarget::Selection => content
+            .push(text(format!("{} selected books", app.selected_books.len())).size(fonts.small())),
+        BoughtDateTarget::AddedBetween => content.push(
+            row![
+                text_input("From, e.g. 2024-05-01", &form.from)
+                    .on_input(Message::BoughtDateFromChanged)
+                    .padding(8)
+                    .width(fonts.width(200.0)),
+                text("and").size(fonts.body()),
+                text_input("To, e.g. today", &form.to)
+                    .on_input(Message::BoughtDateToChanged)
+                    .padding(8)
+                    .width(fonts.width(200.0)),
+            ]
+            .spacing(10)
+            .align_y(iced::alignment::Vertical::Center),
+        ),
+    };
+    content = content.push(
+        text("Books that already have a bought date keep it, unless overwritten in the preview")
+            .size(fonts.small()),
+    );
+
+    let resolved = form.date(now).map_or_else(String::new, |date| {
+        format!("= {}", date.format(FORM_DATE_FORMAT))
+    });
+    let ready = form.filter(app, now).is_some() && form.date(now).is_some();
+
+    content = content
+        .push(
+            row![
+                text_input("Bought on, e.g. 2024-05-14", &form.date)
+                    .on_input(Message::BoughtDateInputChanged)
+                    .on_submit(Message::PreviewBoughtDate)
+                    .padding(8)
+                    .width(fonts.width(200.0)),
+                text(resolved).size(fonts.small()),
+            ]
+            .spacing(10)
+            .align_y(iced::alignment::Vertical::Center),
+        )
+        .push(
+            row![
+                button("Preview")
+                    .on_press_maybe(ready.then_some(Message::PreviewBoughtDate))
+                    .style(button::primary),
+                button("Cancel")
+                    .on_press(Message::CloseBoughtDateDialog)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        );
+
+    container(content)
+        .padding(20)
+        .style(container::bordered_box)
+        .into()
+}
+
+/// Preview of the planned change, with the choice to replace existing bought dates
+pub fn view_preview<'a>(app: &BookshelfApp, plan: &'a BoughtDatePlan) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let overwrite = checkbox("Overwrite existing bought dates", plan.filter.overwrite)
+        .on_toggle(Message::ToggleBoughtDateOverwrite)
+        .text_size(fonts.body());
+
+    bulk_preview::view_bulk_preview(
+        &format!("Set bought date to {}", plan.date.format("%Y-%m-%d")),
+        &plan.preview,
+        overwrite.into(),
+        Some(Message::ApplyBoughtDate),
+        Message::CancelBoughtDate,
+        fonts,
+    )
+}
+
+/// Offered next to the status message until the change is undone or the tab changes
+pub fn create_undo_button(app: &BookshelfApp) -> Element<'_, Message> {
+    let Some(backup) = &app.last_bought_date_change else {
+        return row![].into();
+    };
+    button(text(format!("Undo bought dates ({} books)", backup.len())).size(app.fonts().small()))
+        .on_press(Message::UndoBoughtDate)
+        .style(button::secondary)
+        .padding(6)
+        .into()
+}
@@ -0,0 +1,301 @@
+// src/ui/changes_view.rs
+use crate::language::language_name;
+use crate::models::{BookModel, NewBook, ID};
+use crate::ui::book_view;
+use crate::ui::{
+    format_duration, format_price, format_rating, BookshelfApp, Message, CURRENCY, PRICE_MASK,
+};
+use chrono::NaiveDateTime;
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+use std::fmt;
+
+/// Book form fields the review step compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookField {
+    Title,
+    Author,
+    Series,
+    Price,
+    Bought,
+    Finished,
+    GiftFrom,
+    Quantity,
+    Cover,
+    Genre,
+    Pages,
+    Format,
+    Duration,
+    Language,
+    AcquiredFrom,
+    Rating,
+}
+
+impl fmt::Display for BookField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookField::Title => write!(f, "Title"),
+            BookField::Author => write!(f, "Author"),
+            BookField::Series => write!(f, "Series"),
+            BookField::Price => write!(f, "Price"),
+            BookField::Bought => write!(f, "Bought"),
+            BookField::Finished => write!(f, "Finished"),
+            BookField::GiftFrom => write!(f, "Gift from"),
+            BookField::Quantity => write!(f, "Quantity"),
+            BookField::Cover => write!(f, "Cover"),
+            BookField::Genre => write!(f, "Genre"),
+            BookField::Pages => write!(f, "Pages"),
+            BookField::Format => write!(f, "Format"),
+            BookField::Duration => write!(f, "Listening time"),
+            BookField::Language => write!(f, "Language"),
+            BookField::AcquiredFrom => write!(f, "Acquired from"),
+            BookField::Rating => write!(f, "Rating"),
+        }
+    }
+}
+
+/// One changed field, None stands for an empty value. Author and series hold the id,
+/// the dialog shows their names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: BookField,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// An edit held back until its changes are confirmed
+#[derive(Debug, Clone)]
+pub struct PendingBookChanges {
+    pub book_id: ID,
+    pub book: NewBook,
+    pub changes: Vec<FieldChange>,
+}
+
+// Dates show their time only when it is set, the form keeps the time of an unchanged day
+fn date_text(date: Option<NaiveDateTime>) -> Option<String> {
+    date.map(|date| {
+        if date.time() == chrono::NaiveTime::MIN {
+            date.format("%Y-%m-%d").to_string()
+        } else {
+            date.format("%Y-%m-%d %H:%M").to_string()
+        }
+    })
+}
+
+/// Fields of the book that the form values would change, in form order. The added date
+/// and the pin are not part of the form and never show up.
+pub fn diff_book(old: &BookModel, new: &NewBook) -> Vec<FieldChange> {
+    let id = |id: Option<ID>| id.map(|id| id.to_string());
+    let price = |price: Option<f32>| price.map(|price| format_price(price, CURRENCY, false));
+    let number = |value: Option<i32>| value.map(|value| value.to_string());
+
+    let fields = [
+        (
+            BookField::Title,
+            Some(old.title.clone()),
+            Some(new.title.clone()),
+        ),
+        (BookField::Author, id(old.AuthorFK), id(new.AuthorFK)),
+        (BookField::Series, id(old.SeriesFK), id(new.SeriesFK)),
+        (BookField::Price, price(old.price), price(new.price)),
+        (
+            BookField::Bought,
+            date_text(old.bought),
+            date_text(new.bought),
+        ),
+        (
+            BookField::Finished,
+            date_text(old.finished),
+            date_text(new.finished),
+        ),
+        (
+            BookField::GiftFrom,
+            old.gift_from.clone(),
+            new.gift_from.clone(),
+        ),
+        (
+            BookField::Quantity,
+            number(Some(old.quantity)),
+            number(Some(new.quantity)),
+        ),
+        (
+            BookField::Cover,
+            old.cover_path.clone(),
+            new.cover_path.clone(),
+        ),
+        (BookField::Genre, old.genre.clone(), new.genre.clone()),
+        (
+            BookField::Pages,
+            number(old.page_count),
+            number(new.page_count),
+        ),
+        (BookField::Format, old.format.clone(), new.format.clone()),
+        (
+            BookField::Duration,
+            old.duration_minutes.map(format_duration),
+            new.duration_minutes.map(format_duration),
+        ),
+        (
+            BookField::Language,
+            old.language.as_deref().map(language_name),
+            new.language.as_deref().map(language_name),
+        ),
+        (
+            BookField::AcquiredFrom,
+            old.acquired_from.clone(),
+            new.acquired_from.clone(),
+        ),
+        (
+            BookField::Rating,
+            old.rating.map(format_rating),
+            new.rating.map(format_rating),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange { field, old, new })
+        .collect()
+}
+
+// Handler functions for the review step
+pub fn handle_confirm(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(pending) = app.pending_book_changes.take() else {
+        return iced::Task::none();
+    };
+    app.saving_book = true;
+    book_view::save_book(
+        Some(pending.book_id),
+        pending.book,
+        app.book_custom_fields.clone(),
+    )
+}
+
+pub fn handle_back(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.pending_book_changes = None;
+    iced::Task::none()
+}
+
+// View functions for the review step
+// Names for the author and series ids, the rest is shown as it is
+fn value_text(app: &BookshelfApp, field: BookField, value: &Option<String>) -> String {
+    let Some(value) = value else {
+        return "(empty)".to_string();
+    };
+    let id = value.parse::<ID>().ok();
+    match field {
+        BookField::Author => app
+            .authors
+            .iter()
+            .find(|author| Some(author.Id) == id)
+            .map_or_else(|| value.clone(), |author| author.to_string()),
+        BookField::Series => app
+            .series
+            .iter()
+            .find(|series| Some(series.id) == id)
+            .map_or_else(|| value.clone(), |series| series.name.clone()),
+        BookField::Price if app.privacy_mode => PRICE_MASK.to_string(),
+        _ => value.clone(),
+    }
+}
+
+pub fn create_dialog<'a>(
+    app: &'a BookshelfApp,
+    pending: &'a PendingBookChanges,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let changes = pending
+        .changes
+        .iter()
+        .fold(column![].spacing(8), |changes, change| {
+            // A value that was cleared stands out, it is the edit most often made by accident
+            let cleared = change.old.is_some() && change.new.is_none();
+            let new = text(value_text(app, change.field, &change.new)).size(fonts.body());
+            let new = if cleared {
+                new.style(text::danger)
+            } else {
+                new
+            };
+            changes.push(
+                row![
+                    text(change.field.to_string())
+                        .size(fonts.body())
+                        .width(fonts.width(140.0)),
+                    text(value_text(app, change.field, &change.old)).size(fonts.body()),
+                    text("→").size(fonts.body()),
+                    new,
+                ]
+                .spacing(10),
+            )
+        });
+
+    container(
+        column![
+            text(format!("Review {} changes", pending.changes.len())).size(fonts.heading()),
+            scrollable(changes).height(Length::Shrink),
+            row![
+                button("Confirm")
+                    .on_press(Message::ConfirmBookChanges)
+                    .style(button::primary),
+                button("Back")
+                    .on_press(Message::CancelBookChanges)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        ]
+        .spacing(15)
+        .max_width(fonts.width(640.0)),
+    )
+    .padding(20)
+    .style(container::bordered_box)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn finished_book() -> BookModel {
+        let mut book = BookModel::titled(1, "Dune");
+        book.price = Some(12.5);
+        book.finished = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap().and_hms_opt(0, 0, 0);
+        book
+    }
+
+    #[test]
+    fn an_unchanged_book_has_no_changes() {
+        let book = finished_book();
+        assert!(diff_book(&book, &NewBook::from(&book)).is_empty());
+    }
+
+    #[test]
+    fn a_cleared_date_is_a_change_to_nothing() {
+        let book = finished_book();
+        let new = NewBook {
+            finished: None,
+            ..NewBook::from(&book)
+        };
+        let changes = diff_book(&book, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, BookField::Finished);
+        assert_eq!(changes[0].old.as_deref(), Some("2025-03-01"));
+        assert_eq!(changes[0].new, None);
+    }
+
+    #[test]
+    fn a_changed_price_shows_both_prices() {
+        let book = finished_book();
+        let new = NewBook {
+            price: Some(15.0),
+            ..NewBook::from(&book)
+        };
+        let expected = FieldChange {
+            field: BookField::Price,
+            old: Some(format_price(12.5, CURRENCY, false)),
+            new: Some(format_price(15.0, CURRENCY, false)),
+        };
+        assert_eq!(diff_book(&book, &new), [expected]);
+    }
+}
@@ -1,43 +1,122 @@
 // src/ui/common.rs
-use crate::ui::book_view;
-use crate::ui::{author_view, LIST_PADDING, LIST_SPACING};
-use crate::ui::{BookshelfApp, Message, SortDirection, SortField, Tab};
-use iced::widget::{button, column, container, pick_list, row, text, text_input};
+use crate::loans;
+use crate::ui::{book_view, bought_date_view, changes_view, draft_view, import_view};
+use crate::ui::{hover_view, maintenance_view, missing_view, print_view, session_view, trash_view};
+use crate::ui::instance_view;
+use crate::ui::components::modal::modal;
+use crate::ui::components::toasts;
+use crate::ui::{keymap, palette};
+use crate::ui::{author_view, series_view, settings_view, stats_view, LIST_PADDING, LIST_SPACING};
+use crate::ui::{BookshelfApp, Message, SearchField, SortDirection, SortField, Tab, ThenBy};
+use chrono::Local;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, stack, text, text_input,
+};
 use iced::{Element, Length};
 
 pub fn view(app: &BookshelfApp) -> Element<Message> {
+    // Nothing is opened while another instance holds the database
+    if let Some(pid) = app.instance_conflict {
+        return instance_view::create_conflict_prompt(app, pid);
+    }
+    // A draft left by a crash is offered before anything else
+    if let Some(draft) = &app.restorable_draft {
+        return draft_view::create_draft_prompt(app, draft);
+    }
+    if app.print_view {
+        return print_view::view(app);
+    }
+    if let Some(csv_import) = &app.csv_import {
+        return import_view::view(app, csv_import);
+    }
+
+    let fonts = app.fonts();
+    // Overdue loans are surfaced on the Books tab so they are noticed from anywhere
+    let overdue = loans::count_overdue(&app.loans, Local::now().naive_local());
+    let books_label = if overdue > 0 {
+        format!("Books ({} overdue)", overdue)
+    } else {
+        "Books".to_string()
+    };
+
     // Tabs navigation
     let tab_row = row![
-        button(text("Books").size(20))
+        button(text(books_label).size(fonts.heading()))
             .on_press(Message::TabSelected(Tab::Books))
             .style(if matches!(app.current_tab, Tab::Books) {
                 button::primary
             } else {
                 button::secondary
             }),
-        button(text("Authors").size(20))
+        button(text("Authors").size(fonts.heading()))
             .on_press(Message::TabSelected(Tab::Authors))
             .style(if matches!(app.current_tab, Tab::Authors) {
                 button::primary
             } else {
                 button::secondary
             }),
+        button(text("Series").size(fonts.heading()))
+            .on_press(Message::TabSelected(Tab::Series))
+            .style(if matches!(app.current_tab, Tab::Series) {
+                button::primary
+            } else {
+                button::secondary
+            }),
+        button(text("Stats").size(fonts.heading()))
+            .on_press(Message::TabSelected(Tab::Stats))
+            .style(if matches!(app.current_tab, Tab::Stats) {
+                button::primary
+            } else {
+                button::secondary
+            }),
+        button(text("Settings").size(fonts.heading()))
+            .on_press(Message::TabSelected(Tab::Settings))
+            .style(if matches!(app.current_tab, Tab::Settings) {
+                button::primary
+            } else {
+                button::secondary
+            }),
+        iced::widget::horizontal_space(),
+        instance_view::create_read_only_indicator(app),
+        create_privacy_indicator(app),
+        button(
+            text(format!("Recently deleted ({})", app.recently_deleted.len()))
+                .size(fonts.small())
+        )
+        .on_press(Message::ToggleRecentlyDeleted)
+        .style(button::text),
     ]
     .spacing(LIST_SPACING)
-    .padding(LIST_PADDING);
+    .padding(LIST_PADDING)
+    .align_y(iced::Alignment::Center);
 
     // Error messages
     let error_message = if let Some(error) = &app.error {
-        container(text(error).size(14))
+        container(text(error).size(fonts.small()))
             .padding(10)
             .width(Length::Fill)
     } else {
         container(text("")).width(Length::Fill)
     };
 
+    let status_message = if let Some(status) = &app.status_message {
+        container(
+            row![
+                text(status).size(fonts.small()),
+                bought_date_view::create_undo_button(app)
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding(10)
+            .width(Length::Fill)
+    } else {
+        container(text("")).width(Length::Fill)
+    };
+
     // Only show search and sort options in Books tab
     let top_bar = if matches!(app.current_tab, Tab::Books) {
-        let search_placeholder = "Search by title, author, or price...";
+        let search_placeholder = "Search by title, author, price or giver...";
 
         column![
             // Search bar
@@ -52,32 +131,19 @@ pub fn view(app: &BookshelfApp) -> Element<Message> {
                         .on_press(Message::PerformSearch)
                         .style(button::primary)
                         .padding(8),
-                    if !app.search_query.is_empty() {
-                        button("Clear")
-                            .on_press(Message::ClearSearch)
-                            .style(button::secondary)
-                            .padding(8)
-                    } else {
-                        button("Clear")
-                            .style(button::secondary)
-                            .padding(8)
-                    }
+                    create_clear_search_buttons(app)
                 ]
                 .spacing(LIST_SPACING)
                 .padding(LIST_PADDING)
                 .width(Length::Fill)
             ),
+            container(create_search_field_toggles(app)).padding([0.0, LIST_PADDING]),
             // Sort options
             container(
                 row![
-                    text("Sort by:").size(14),
+                    text("Sort by:").size(fonts.small()),
                     pick_list(
-                        vec![
-                            SortField::Title,
-                            SortField::Author,
-                            SortField::Price,
-                            SortField::DateAdded
-                        ],
+                        SortField::ALL.to_vec(),
                         Some(app.sort_field.clone()),
                         Message::SortFieldSelected
                     )
@@ -89,15 +155,42 @@ pub fn view(app: &BookshelfApp) -> Element<Message> {
                         Message::SortDirectionSelected
                     )
                     .padding(8)
-                    .width(Length::FillPortion(3)) // Remove the Apply button
+                    .width(Length::FillPortion(3)), // Remove the Apply button
+                    pick_list(
+                        then_by_choices(&app.sort_field),
+                        Some(ThenBy(app.config.secondary_sort.clone())),
+                        |choice: ThenBy| Message::SecondarySortSelected(choice.0)
+                    )
+                    .text_size(fonts.small())
+                    .padding(6)
+                    .width(Length::FillPortion(2)),
+                    checkbox("Gifts only", app.gifts_only)
+                        .on_toggle(Message::ToggleGiftsOnly)
+                        .text_size(fonts.small()),
+                    missing_view::create_missing_filters(app),
+                    book_view::create_language_filters(app),
+                    book_view::create_acquired_from_filter(app),
+                    book_view::create_year_filter(app),
+                    button(
+                        text(if app.privacy_mode {
+                            "Show prices"
+                        } else {
+                            "Hide prices"
+                        })
+                        .size(fonts.small())
+                    )
+                    .on_press(Message::TogglePrivacyMode)
+                    .style(button::secondary)
+                    .padding(6),
                 ]
+                .align_y(iced::Alignment::Center)
                 .spacing(LIST_SPACING)
                 .padding(LIST_PADDING)
                 .width(Length::Fill)
             )
         ]
     } else {
-        // Empty container for Authors tab
+        // Empty container for the other tabs
         column![container(row![]).width(Length::Fill).height(Length::Shrink)]
     };
 
@@ -105,7 +198,158 @@ pub fn view(app: &BookshelfApp) -> Element<Message> {
     let content = match app.current_tab {
         Tab::Books => book_view::view(app),
         Tab::Authors => author_view::view(app),
+        Tab::Series => series_view::view(app),
+        Tab::Stats => stats_view::view(app),
+        Tab::Settings => settings_view::view(app),
     };
 
-    column![tab_row, error_message, top_bar, content,].into()
+    let page = column![
+        tab_row,
+        error_message,
+        status_message,
+        maintenance_view::create_overnight_banner(app),
+        top_bar,
+        content,
+    ];
+
+    let view = match &app.palette {
+        _ if app.confirm_exit => modal(
+            page,
+            instance_view::create_exit_prompt(app),
+            Message::CancelExit,
+        ),
+        Some(palette) => modal(page, palette::view(app, palette), Message::CloseCommandPalette),
+        None if app.show_key_help => modal(page, create_key_help(app), Message::ToggleKeyHelp),
+        None if app.show_recently_deleted => modal(
+            page,
+            trash_view::create_recently_deleted(app),
+            Message::ToggleRecentlyDeleted,
+        ),
+        None => match (
+            &app.shelf_export,
+            &app.bought_date_form,
+            &app.pending_book_changes,
+        ) {
+            (Some(shelf), _, _) => modal(
+                page,
+                stats_view::create_shelf_export_dialog(app, shelf),
+                Message::CloseShelfExport,
+            ),
+            (None, Some(form), _) => modal(
+                page,
+                bought_date_view::create_dialog(app, form),
+                Message::CloseBoughtDateDialog,
+            ),
+            (None, None, Some(pending)) => modal(
+                page,
+                changes_view::create_dialog(app, pending),
+                Message::CancelBookChanges,
+            ),
+            (None, None, None) => hover_view::with_hover_card(app, page),
+        },
+    };
+    // The timer stays in its corner on every tab, the toasts take the other one
+    let timer = session_view::create_running_timer(app);
+    if app.toasts.is_empty() && timer.is_none() {
+        return view;
+    }
+    let mut layers = stack![view];
+    if let Some(timer) = timer {
+        layers = layers.push(timer);
+    }
+    if !app.toasts.is_empty() {
+        layers = layers.push(toasts::view(&app.toasts, Message::Toast, fonts));
+    }
+    layers.into()
+}
+
+// Secondary sort choices, the primary field would never break a tie
+fn then_by_choices(primary: &SortField) -> Vec<ThenBy> {
+    std::iter::once(ThenBy(None))
+        .chain(
+            SortField::ALL
+                .into_iter()
+                .filter(|field| field != primary)
+                .map(|field| ThenBy(Some(field))),
+        )
+        .collect()
+}
+
+// Lock shown while prices are masked, clicking it shows them again
+fn create_privacy_indicator(app: &BookshelfApp) -> Element<'_, Message> {
+    if !app.privacy_mode {
+        return row![].into();
+    }
+    button(text("🔒 Prices hidden").size(app.fonts().small()))
+        .on_press(Message::TogglePrivacyMode)
+        .style(button::text)
+        .into()
+}
+
+// Restricts which book fields the search box looks at
+fn create_search_field_toggles(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    SearchField::ALL
+        .into_iter()
+        .fold(
+            row![text("Search in:").size(fonts.small())].spacing(LIST_SPACING),
+            |toggles, field| {
+                toggles.push(
+                    checkbox(field.to_string(), app.config.search_fields.contains(field))
+                        .on_toggle(move |enabled| Message::ToggleSearchField(field, enabled))
+                        .text_size(fonts.small()),
+                )
+            },
+        )
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+// Overlay listing the keyboard shortcuts, opened with '?'
+fn create_key_help(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let mut bindings = column![text("Keyboard shortcuts").size(fonts.heading())].spacing(8);
+
+    for (keys, action) in keymap::BINDINGS {
+        bindings = bindings.push(
+            row![
+                text(keys).size(fonts.body()).width(fonts.width(140.0)),
+                text(action).size(fonts.body()),
+            ]
+            .spacing(10),
+        );
+    }
+
+    container(
+        bindings.push(
+            text("Letter keys act on the book focused with the arrow keys").size(fonts.small()),
+        ),
+    )
+    .padding(20)
+    .style(container::bordered_box)
+    .into()
+}
+
+// Clear resets at once, unless several filters are active and it needs a second click
+fn create_clear_search_buttons(app: &BookshelfApp) -> Element<'_, Message> {
+    if app.confirm_clear_search {
+        return row![
+            button("Clear all filters?")
+                .on_press(Message::ClearSearch)
+                .style(button::danger)
+                .padding(8),
+            button("Cancel")
+                .on_press(Message::CancelClearSearch)
+                .style(button::secondary)
+                .padding(8),
+        ]
+        .spacing(LIST_SPACING)
+        .into();
+    }
+
+    button("Clear")
+        .on_press_maybe((app.active_filter_count() > 0).then_some(Message::ClearSearch))
+        .style(button::secondary)
+        .padding(8)
+        .into()
 }
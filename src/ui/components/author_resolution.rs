@@ -0,0 +1,70 @@
+// src/ui/components/author_resolution.rs
+use crate::db::{AuthorResolution, UnknownAuthor};
+use crate::ui::{FontSizes, Message};
+use iced::widget::{button, column, row, text};
+use iced::Element;
+
+/// One row of choices per author name the database doesn't have: map it to one of the
+/// similar names, create it or skip its books. `on_choose` receives the author's index.
+pub fn view_author_resolution<'a>(
+    heading: &str,
+    authors: &'a [UnknownAuthor],
+    on_choose: fn(usize, AuthorResolution) -> Message,
+    fonts: FontSizes,
+) -> Element<'a, Message> {
+    if authors.is_empty() {
+        return column![].into();
+    }
+
+    let choice = |label: String, index: usize, resolution: AuthorResolution, chosen: bool| {
+        button(text(label).size(fonts.small()))
+            .on_press(on_choose(index, resolution))
+            .style(if chosen {
+                button::primary
+            } else {
+                button::secondary
+            })
+            .padding(6)
+    };
+
+    let mut section =
+        column![text(format!("{} ({})", heading, authors.len())).size(fonts.heading())]
+            .spacing(10);
+
+    for (index, author) in authors.iter().enumerate() {
+        let chosen = author.resolution.as_ref();
+        let mut choices = row![].spacing(5).align_y(iced::Alignment::Center);
+        for (id, name) in &author.suggestions {
+            let resolution = AuthorResolution::MapTo(*id, name.clone());
+            let is_chosen = chosen == Some(&resolution);
+            choices = choices.push(choice(
+                format!("Map to {}", name),
+                index,
+                resolution,
+                is_chosen,
+            ));
+        }
+        choices = choices
+            .push(choice(
+                "Create new".to_string(),
+                index,
+                AuthorResolution::CreateNew,
+                chosen == Some(&AuthorResolution::CreateNew),
+            ))
+            .push(choice(
+                "Skip these books".to_string(),
+                index,
+                AuthorResolution::Skip,
+                chosen == Some(&AuthorResolution::Skip),
+            ));
+
+        section = section.push(
+            column![
+                text(format!("{} ({} books)", author.name, author.books)).size(fonts.body()),
+                choices,
+            ]
+            .spacing(5),
+        );
+    }
+    section.into()
+}
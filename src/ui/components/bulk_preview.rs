@@ -0,0 +1,77 @@
+// src/ui/components/bulk_preview.rs
+use crate::bulk::{self, BulkPreview};
+use crate::ui::{FontSizes, Message, LIST_MAX_WIDTH};
+use iced::widget::{button, column, container, row, scrollable, text, Column};
+use iced::{Element, Length};
+
+/// Confirmation screen listing what a bulk operation will do before it runs.
+/// `choices` sits above the buttons for decisions the operation still needs,
+/// Apply stays disabled while `on_apply` is None.
+pub fn view_bulk_preview<'a>(
+    title: &str,
+    preview: &'a BulkPreview,
+    choices: Element<'a, Message>,
+    on_apply: Option<Message>,
+    on_cancel: Message,
+    fonts: FontSizes,
+) -> Element<'a, Message> {
+    let mut content = column![
+        text(title.to_string()).size(fonts.title()),
+        text(preview.summary()).size(fonts.body()),
+    ]
+    .spacing(15)
+    .width(Length::Fill);
+
+    for warning in &preview.warnings {
+        content = content.push(text(format!("⚠ {}", warning)).size(fonts.small()));
+    }
+
+    content = content
+        .push(create_category("Create", &preview.to_create, fonts))
+        .push(create_category("Update", &preview.to_update, fonts))
+        .push(create_category("Skip", &preview.to_skip, fonts))
+        .push(choices)
+        .push(
+            row![
+                button("Apply")
+                    .on_press_maybe(on_apply.filter(|_| !preview.is_noop()))
+                    .style(button::primary),
+                button("Cancel")
+                    .on_press(on_cancel)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        );
+
+    scrollable(
+        container(
+            content
+                .padding(20)
+                .max_width(fonts.width(LIST_MAX_WIDTH * 1.5)),
+        )
+        .width(Length::Fill)
+        .center_x(Length::Fill),
+    )
+    .height(Length::Fill)
+    .into()
+}
+
+// Heading with the count and the first few examples, empty categories are left out
+fn create_category<'a>(label: &str, items: &'a [String], fonts: FontSizes) -> Column<'a, Message> {
+    if items.is_empty() {
+        return column![];
+    }
+
+    let (shown, hidden) = bulk::examples(items);
+    let mut category =
+        column![text(format!("{} ({})", label, items.len())).size(fonts.heading())].spacing(5);
+
+    for item in shown {
+        category = category.push(text(format!("• {}", item)).size(fonts.small()));
+    }
+    if hidden > 0 {
+        category = category.push(text(format!("…and {} more", hidden)).size(fonts.tiny()));
+    }
+
+    category
+}
@@ -1,6 +1,6 @@
 // src/ui/components/searchable_dropdown.rs
 use crate::models::AuthorModel;
-use crate::ui::Message;
+use crate::ui::{author_matches_with_aliases, AcquisitionPlace, FontSizes, Message};
 use iced::widget::{
     button, column, container, row, scrollable, text, text_input,
 };
@@ -49,37 +49,88 @@ impl<T: Clone + PartialEq> SearchableDropdown<T> {
     pub fn selected(&self) -> Option<&T> {
         self.selected.as_ref()
     }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+}
+
+/// Search field of the open dropdown, focused when it is opened from the keyboard
+pub fn search_input_id() -> text_input::Id {
+    text_input::Id::new("dropdown_search")
+}
+
+/// Item a searchable dropdown can list, with the texts the dropdown shows around it
+pub trait DropdownItem: Clone + PartialEq {
+    const PLACEHOLDER: &'static str;
+    const SEARCH_PLACEHOLDER: &'static str;
+    const NO_MATCHES: &'static str;
+
+    fn label(&self) -> String;
+
+    // Whether the option is listed for the lowercase search term
+    fn matches(&self, search_term: &str) -> bool {
+        self.label().to_lowercase().contains(search_term)
+    }
+
+    // Whether this option is the selected one
+    fn same_item(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl DropdownItem for AuthorModel {
+    const PLACEHOLDER: &'static str = "Select an author";
+    const SEARCH_PLACEHOLDER: &'static str = "Search author...";
+    const NO_MATCHES: &'static str = "No matching authors";
+
+    fn label(&self) -> String {
+        self.Name
+            .clone()
+            .unwrap_or_else(|| "Unnamed Author".to_string())
+    }
+
+    // Typing a pen name finds the author
+    fn matches(&self, search_term: &str) -> bool {
+        author_matches_with_aliases(self, search_term)
+    }
+
+    // Compare by ID, the name may have been edited since the option was loaded
+    fn same_item(&self, other: &Self) -> bool {
+        self.Id == other.Id
+    }
+}
+
+impl DropdownItem for AcquisitionPlace {
+    const PLACEHOLDER: &'static str = "Pick a place used before";
+    const SEARCH_PLACEHOLDER: &'static str = "Search places...";
+    const NO_MATCHES: &'static str = "No matching places";
+
+    fn label(&self) -> String {
+        self.0.clone()
+    }
 }
 
-// Implementation specific for AuthorModel
-pub fn view_author_dropdown(
-    dropdown: &SearchableDropdown<AuthorModel>,
+pub fn view_dropdown<T: DropdownItem + 'static>(
+    dropdown: &SearchableDropdown<T>,
     on_toggle: Message,
     on_search: impl Fn(String) -> Message + 'static,
-    on_select: impl Fn(AuthorModel) -> Message + 'static,
-) -> Element<Message> {
+    on_select: impl Fn(T) -> Message + 'static,
+    fonts: FontSizes,
+) -> Element<'_, Message> {
     // Filter options by search term
-    let filtered_options = if dropdown.search_term.is_empty() {
-        dropdown.options.clone()
-    } else {
-        dropdown
-            .options
-            .iter()
-            .filter(|author| {
-                let search_term = dropdown.search_term.to_lowercase();
-                let author_name = author.Name.clone().unwrap_or_default().to_lowercase();
-
-                author_name.contains(&search_term)
-            })
-            .cloned()
-            .collect::<Vec<_>>()
-    };
+    let search_term = dropdown.search_term.to_lowercase();
+    let filtered_options: Vec<&T> = dropdown
+        .options
+        .iter()
+        .filter(|item| search_term.is_empty() || item.matches(&search_term))
+        .collect();
 
     // Create the dropdown header (either selected value or placeholder)
     let selected_text = dropdown
         .selected()
-        .and_then(|author| author.Name.clone())
-        .unwrap_or_else(|| "Select an author".to_string());
+        .map(T::label)
+        .unwrap_or_else(|| T::PLACEHOLDER.to_string());
 
     let header = button(
         row![
@@ -96,14 +147,15 @@ pub fn view_author_dropdown(
     .style(button::secondary);
 
     if dropdown.is_open {
-        let search_input = text_input("Search author...", &dropdown.search_term)
+        let search_input = text_input(T::SEARCH_PLACEHOLDER, &dropdown.search_term)
+            .id(search_input_id())
             .on_input(on_search)
             .padding(10)
             .width(Length::Fill);
 
         let options_list = if filtered_options.is_empty() {
             scrollable(
-                container(text("No matching authors").size(14))
+                container(text(T::NO_MATCHES).size(fonts.small()))
                     .padding(10)
                     .width(Length::Fill)
                     .height(Length::Fill),
@@ -111,20 +163,14 @@ pub fn view_author_dropdown(
             .height(Length::Fill)
             .width(Length::Fill)
         } else {
-            let options_column = column(filtered_options.iter().map(|author| {
-                let name = author
-                    .Name
-                    .clone()
-                    .unwrap_or_else(|| "Unnamed Author".to_string());
-                // Compare by ID for equality since we can't directly compare AuthorModel types
+            let options_column = column(filtered_options.into_iter().map(|item| {
                 let is_selected = dropdown
                     .selected()
-                    .map(|selected_author| selected_author.Id == author.Id)
-                    .unwrap_or(false);
+                    .is_some_and(|selected| selected.same_item(item));
 
                 container(
-                    button(text(name).size(14))
-                        .on_press(on_select(author.clone()))
+                    button(text(item.label()).size(fonts.small()))
+                        .on_press(on_select(item.clone()))
                         .padding(8)
                         .width(Length::Fill)
                         .style(if is_selected {
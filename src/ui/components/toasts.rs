@@ -0,0 +1,225 @@
+// src/ui/components/toasts.rs
+use crate::ui::FontSizes;
+use iced::widget::{button, column, container, row, text};
+use iced::{Color, Element, Length};
+use std::time::{Duration, Instant};
+
+/// How long a success toast stays up before it disappears on its own
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Most toasts stacked at once, the oldest ones make room for new ones
+pub const TOASTS_MAX: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Failure, // Stays until dismissed
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+/// Messages the toast stack handles itself, wrapped in `Message::Toast`
+#[derive(Debug, Clone)]
+pub enum ToastMessage {
+    Dismiss(u64),
+    Tick, // Drops expired toasts, sent every second while one can expire
+}
+
+/// Short notices stacked in a corner of the window, newest last
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    items: Vec<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    /// Adds a toast, dropping the oldest success (or the oldest failure when there is
+    /// none) once the stack is full
+    pub fn push(&mut self, kind: ToastKind, message: String, now: Instant) -> u64 {
+        if self.items.len() >= TOASTS_MAX {
+            let oldest = self
+                .items
+                .iter()
+                .position(|toast| toast.kind == ToastKind::Success)
+                .unwrap_or(0);
+            self.items.remove(oldest);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(Toast {
+            id,
+            kind,
+            message,
+            shown_at: now,
+        });
+        id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|toast| toast.id != id);
+    }
+
+    /// Drops success toasts shown for longer than `TOAST_DURATION`
+    pub fn expire(&mut self, now: Instant) {
+        self.items.retain(|toast| {
+            toast.kind == ToastKind::Failure
+                || now.saturating_duration_since(toast.shown_at) < TOAST_DURATION
+        });
+    }
+
+    pub fn update(&mut self, message: ToastMessage, now: Instant) {
+        match message {
+            ToastMessage::Dismiss(id) => self.dismiss(id),
+            ToastMessage::Tick => self.expire(now),
+        }
+    }
+
+    /// True while a toast is waiting to expire, the ticks are only needed then
+    pub fn has_expiring(&self) -> bool {
+        self.items
+            .iter()
+            .any(|toast| toast.kind == ToastKind::Success)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.items.iter()
+    }
+}
+
+/// The stack of toasts, meant to be layered over the page in its bottom right corner
+pub fn view<'a, Message: Clone + 'a>(
+    toasts: &'a Toasts,
+    on_message: fn(ToastMessage) -> Message,
+    fonts: FontSizes,
+) -> Element<'a, Message> {
+    let stack = toasts.iter().fold(column![].spacing(8), |stack, toast| {
+        let accent = match toast.kind {
+            ToastKind::Success => Color::from_rgb(0.2, 0.6, 0.3),
+            ToastKind::Failure => Color::from_rgb(0.8, 0.2, 0.2),
+        };
+        stack.push(
+            container(
+                row![
+                    text(&toast.message).size(fonts.small()).width(Length::Fill),
+                    button(text("×").size(fonts.small()))
+                        .on_press(on_message(ToastMessage::Dismiss(toast.id)))
+                        .style(button::text)
+                        .padding(2),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+            )
+            .padding(10)
+            .width(fonts.width(320.0))
+            .style(move |theme| {
+                let style = container::bordered_box(theme);
+                container::Style {
+                    border: style.border.color(accent).width(2),
+                    ..style
+                }
+            }),
+        )
+    });
+
+    container(stack)
+        .padding(20)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_right(Length::Fill)
+        .align_bottom(Length::Fill)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(toasts: &Toasts) -> Vec<(ToastKind, &str)> {
+        toasts
+            .iter()
+            .map(|toast| (toast.kind, toast.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn success_toasts_expire_after_five_seconds() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push(ToastKind::Success, "Saved".to_string(), start);
+        toasts.push(
+            ToastKind::Success,
+            "Exported".to_string(),
+            start + Duration::from_secs(2),
+        );
+        assert!(toasts.has_expiring());
+
+        toasts.update(ToastMessage::Tick, start + Duration::from_millis(4999));
+        assert_eq!(toasts.iter().count(), 2);
+        toasts.update(ToastMessage::Tick, start + TOAST_DURATION);
+        assert_eq!(kinds(&toasts), vec![(ToastKind::Success, "Exported")]);
+        toasts.update(ToastMessage::Tick, start + Duration::from_secs(7));
+        assert!(toasts.is_empty());
+        assert!(!toasts.has_expiring());
+    }
+
+    #[test]
+    fn failure_toasts_stay_until_dismissed() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        let failed = toasts.push(ToastKind::Failure, "Backup failed".to_string(), start);
+        let saved = toasts.push(ToastKind::Success, "Saved".to_string(), start);
+        assert_ne!(failed, saved);
+
+        toasts.update(ToastMessage::Tick, start + Duration::from_secs(3600));
+        assert_eq!(kinds(&toasts), vec![(ToastKind::Failure, "Backup failed")]);
+        // Nothing left to expire, so the ticks can stop
+        assert!(!toasts.has_expiring());
+
+        toasts.update(ToastMessage::Dismiss(saved), start);
+        assert_eq!(toasts.iter().count(), 1);
+        toasts.update(ToastMessage::Dismiss(failed), start);
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn a_full_stack_drops_the_oldest_success_first() {
+        let now = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push(ToastKind::Failure, "First failure".to_string(), now);
+        toasts.push(ToastKind::Success, "First success".to_string(), now);
+        toasts.push(ToastKind::Success, "Second success".to_string(), now);
+        toasts.push(ToastKind::Failure, "Second failure".to_string(), now);
+
+        toasts.push(ToastKind::Success, "Third success".to_string(), now);
+        assert_eq!(
+            kinds(&toasts),
+            vec![
+                (ToastKind::Failure, "First failure"),
+                (ToastKind::Success, "Second success"),
+                (ToastKind::Failure, "Second failure"),
+                (ToastKind::Success, "Third success"),
+            ]
+        );
+
+        // With only failures up, the oldest of them makes room
+        let mut toasts = Toasts::default();
+        for n in 1..=TOASTS_MAX {
+            toasts.push(ToastKind::Failure, format!("Failure {}", n), now);
+        }
+        toasts.push(ToastKind::Failure, "Newest".to_string(), now);
+        assert_eq!(toasts.iter().count(), TOASTS_MAX);
+        assert_eq!(toasts.iter().next().unwrap().message, "Failure 2");
+        assert_eq!(toasts.iter().last().unwrap().message, "Newest");
+    }
+}
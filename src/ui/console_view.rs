@@ -0,0 +1,127 @@
+// src/ui/console_view.rs
+use crate::console::{QueryOutput, CONSOLE_ROW_LIMIT};
+use crate::db;
+use crate::ui::{BookshelfApp, FontSizes, Message};
+use iced::widget::{button, column, container, row, scrollable, text, text_editor, Column, Row};
+use iced::Length;
+
+const CONSOLE_COLUMN_WIDTH: f32 = 160.0;
+
+// Handler functions for the query console
+pub fn handle_console_query_action(
+    app: &mut BookshelfApp,
+    action: text_editor::Action,
+) -> iced::Task<Message> {
+    app.console_query.perform(action);
+    iced::Task::none()
+}
+
+pub fn handle_run_console_query(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let sql = app.console_query.text();
+    app.console_running = true;
+    app.console_error = None;
+
+    iced::Task::perform(
+        async move {
+            match db::run_read_only_query(&sql) {
+                Ok(output) => Ok(output),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ConsoleQueryFinished,
+    )
+}
+
+pub fn handle_console_query_finished(
+    app: &mut BookshelfApp,
+    result: Result<QueryOutput, String>,
+) -> iced::Task<Message> {
+    app.console_running = false;
+    match result {
+        Ok(output) => app.console_output = Some(output),
+        // Shown next to the query rather than in the global error bar
+        Err(e) => app.console_error = Some(e),
+    }
+    iced::Task::none()
+}
+
+// View functions for the query console
+pub fn create_console_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+
+    let mut section = column![
+        text("Query console").size(fonts.heading()),
+        text(
+            "⚠ For power users: runs read-only SELECT queries on the library database, \
+             showing values exactly as they are stored."
+        )
+        .size(fonts.small()),
+        text_editor(&app.console_query)
+            .placeholder("SELECT title, price FROM Books ORDER BY price DESC")
+            .on_action(Message::ConsoleQueryAction)
+            .height(fonts.width(120.0))
+            .size(fonts.body()),
+        button(if app.console_running {
+            "Running..."
+        } else {
+            "Run"
+        })
+        .on_press_maybe(
+            (!app.console_running && !app.console_query.text().trim().is_empty())
+                .then_some(Message::RunConsoleQuery)
+        )
+        .style(button::primary),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    if let Some(error) = &app.console_error {
+        section = section.push(text(error.clone()).size(fonts.small()).style(text::danger));
+    } else if let Some(output) = &app.console_output {
+        section = section.push(create_results_table(output, fonts));
+    }
+
+    section
+}
+
+fn create_results_table(output: &QueryOutput, fonts: FontSizes) -> Column<'_, Message> {
+    if output.rows.is_empty() {
+        return column![text("The query returned no rows").size(fonts.small())];
+    }
+
+    let summary = if output.truncated {
+        format!("Showing the first {} rows", CONSOLE_ROW_LIMIT)
+    } else {
+        format!("{} rows", output.rows.len())
+    };
+
+    let mut table = column![create_table_row(&output.columns, fonts)].spacing(4);
+    for values in &output.rows {
+        table = table.push(create_table_row(values, fonts));
+    }
+
+    column![
+        text(summary).size(fonts.tiny()),
+        container(
+            scrollable(table)
+                .direction(scrollable::Direction::Both {
+                    vertical: scrollable::Scrollbar::default(),
+                    horizontal: scrollable::Scrollbar::default(),
+                })
+                .height(fonts.width(400.0))
+        )
+        .padding(10)
+        .style(container::bordered_box),
+    ]
+    .spacing(5)
+}
+
+fn create_table_row<'a>(values: &'a [String], fonts: FontSizes) -> Row<'a, Message> {
+    values.iter().fold(row![].spacing(10), |table_row, value| {
+        table_row.push(
+            text(value)
+                .size(fonts.small())
+                .width(fonts.width(CONSOLE_COLUMN_WIDTH)),
+        )
+    })
+}
@@ -0,0 +1,120 @@
+// src/ui/cover_view.rs
+use crate::covers::{self, fetch};
+use crate::ui::{BookshelfApp, Message};
+use iced::widget::{button, column, image, row, text, Column};
+
+/// Downloaded cover waiting for the user to accept it
+#[derive(Debug, Clone)]
+pub struct CoverPreview {
+    pub bytes: Vec<u8>,
+    pub handle: image::Handle,
+}
+
+// Handler functions for book covers
+pub fn handle_fetch_cover(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let title = app.book_title.trim().to_string();
+    if title.is_empty() {
+        app.error = Some("Enter a title before fetching a cover".to_string());
+        return iced::Task::none();
+    }
+    let author = app.selected_author.as_ref().and_then(|a| a.Name.clone());
+
+    app.cover_fetching = true;
+    app.cover_preview = None;
+    app.status_message = None;
+
+    iced::Task::perform(
+        async move {
+            match fetch::fetch_cover(&fetch::UreqClient::new(), &title, author.as_deref()) {
+                Ok(bytes) => Ok(bytes),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CoverFetched,
+    )
+}
+
+pub fn handle_cover_fetched(
+    app: &mut BookshelfApp,
+    result: Result<Vec<u8>, String>,
+) -> iced::Task<Message> {
+    app.cover_fetching = false;
+    match result {
+        Ok(bytes) => {
+            app.cover_preview = Some(CoverPreview {
+                handle: image::Handle::from_bytes(bytes.clone()),
+                bytes,
+            })
+        }
+        // A missing cover or a slow network is not worth the error bar
+        Err(e) => app.status_message = Some(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_accept_cover(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(preview) = app.cover_preview.take() else {
+        return iced::Task::none();
+    };
+
+    match covers::save_cover(&preview.bytes) {
+        Ok(path) => app.book_cover_path = Some(path.display().to_string()),
+        Err(e) => app.report_error(format!("Failed to save cover: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_discard_cover(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.cover_preview = None;
+    iced::Task::none()
+}
+
+pub fn handle_remove_cover(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.book_cover_path = None;
+    iced::Task::none()
+}
+
+// View functions for book covers
+pub fn create_cover_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let mut section = column![text("Cover:").size(fonts.body())].spacing(10);
+
+    if let Some(preview) = &app.cover_preview {
+        return section
+            .push(image(preview.handle.clone()).height(fonts.width(220.0)))
+            .push(
+                row![
+                    button("Use this cover")
+                        .on_press(Message::AcceptCover)
+                        .style(button::primary),
+                    button("Discard")
+                        .on_press(Message::DiscardCover)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+            );
+    }
+
+    if let Some(path) = &app.book_cover_path {
+        section = section.push(image(path.as_str()).height(fonts.width(160.0)));
+    }
+
+    let fetch_label = if app.cover_fetching {
+        "Fetching cover..."
+    } else {
+        "Fetch cover from Open Library"
+    };
+    let mut actions = row![button(fetch_label)
+        .on_press_maybe((!app.cover_fetching).then_some(Message::FetchCover))
+        .style(button::secondary)]
+    .spacing(10);
+    if app.book_cover_path.is_some() {
+        actions = actions.push(
+            button("Remove cover")
+                .on_press(Message::RemoveCover)
+                .style(button::danger),
+        );
+    }
+
+    section.push(actions)
+}
@@ -0,0 +1,114 @@
+// src/ui/custom_field_view.rs
+use crate::db;
+use crate::models::{BookFieldModel, ID};
+use crate::ui::{BookshelfApp, Message};
+use iced::widget::{button, column, row, text, text_input, Column};
+use iced::Length;
+use std::collections::HashMap;
+
+/// Key/value pairs of the book as the form edits them
+pub fn form_fields(fields: Option<&Vec<BookFieldModel>>) -> Vec<(String, String)> {
+    fields
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|field| (field.key.clone(), field.value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Handler functions for custom fields
+pub fn handle_load_custom_fields(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_all_book_fields() {
+                Ok(fields) => Ok(fields),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CustomFieldsLoaded,
+    )
+}
+
+pub fn handle_custom_fields_loaded(
+    app: &mut BookshelfApp,
+    result: Result<HashMap<ID, Vec<BookFieldModel>>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(fields) => app.custom_fields = fields,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_add_custom_field(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.book_custom_fields.push((String::new(), String::new()));
+    iced::Task::none()
+}
+
+pub fn handle_remove_custom_field(app: &mut BookshelfApp, index: usize) -> iced::Task<Message> {
+    if index < app.book_custom_fields.len() {
+        app.book_custom_fields.remove(index);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_key_changed(
+    app: &mut BookshelfApp,
+    index: usize,
+    key: String,
+) -> iced::Task<Message> {
+    if let Some(field) = app.book_custom_fields.get_mut(index) {
+        field.0 = key;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_value_changed(
+    app: &mut BookshelfApp,
+    index: usize,
+    value: String,
+) -> iced::Task<Message> {
+    if let Some(field) = app.book_custom_fields.get_mut(index) {
+        field.1 = value;
+    }
+    iced::Task::none()
+}
+
+// View functions for custom fields
+// One row per field, rows without a key are dropped when the book is saved
+pub fn create_custom_fields_input(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let rows = app.book_custom_fields.iter().enumerate().fold(
+        column![].spacing(5),
+        |rows, (index, (key, value))| {
+            rows.push(
+                row![
+                    text_input("Name, e.g. Signed", key)
+                        .on_input(move |key| Message::CustomFieldKeyChanged(index, key))
+                        .padding(10)
+                        .width(Length::FillPortion(1)),
+                    text_input("Value", value)
+                        .on_input(move |value| Message::CustomFieldValueChanged(index, value))
+                        .padding(10)
+                        .width(Length::FillPortion(2)),
+                    button("Remove")
+                        .on_press(Message::RemoveCustomField(index))
+                        .style(button::secondary)
+                        .padding(10),
+                ]
+                .spacing(10),
+            )
+        },
+    );
+
+    column![
+        text("Custom fields:").size(fonts.body()),
+        rows,
+        button("Add field")
+            .on_press(Message::AddCustomField)
+            .style(button::secondary),
+    ]
+    .spacing(10)
+}
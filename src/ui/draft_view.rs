@@ -0,0 +1,297 @@
+// src/ui/draft_view.rs
+use crate::db::{self, DbError};
+use crate::draft::{self, BookDraft};
+use crate::models::{AuthorModel, BookWithAuthor, SeriesModel};
+use crate::ui::{book_view, BookshelfApp, Message, Mode, Tab};
+use iced::widget::{button, column, container, row, text, text_editor};
+use iced::{Element, Length};
+
+/// Records a restored draft points at, looked up again as they may have changed since
+#[derive(Debug, Clone)]
+pub struct DraftRestore {
+    pub draft: BookDraft,
+    pub book: Option<BookWithAuthor>, // None when adding, or when the edited book was deleted
+    pub author: Option<AuthorModel>,
+    pub series: Option<SeriesModel>,
+}
+
+/// Current contents of the book form
+pub fn snapshot(app: &BookshelfApp) -> BookDraft {
+    BookDraft {
+        saved_at: None,
+        book_id: app.selected_book.as_ref().map(|pair| pair.book.id),
+        title: app.book_title.clone(),
+        price: app.book_price.clone(),
+        bought_date: app.book_bought_date.clone(),
+        finished_date: app.book_finished_date.clone(),
+        is_gift: app.book_is_gift,
+        gift_from: app.book_gift_from.clone(),
+        quantity: app.book_quantity,
+        cover_path: app.book_cover_path.clone(),
+        genre: app.book_genre.clone(),
+        page_count: app.book_page_count.clone(),
+        format: app.book_format,
+        duration_minutes: app.book_duration_minutes.clone(),
+        language: app.book_language.clone(),
+        acquired_from: app.book_acquired_from.clone(),
+        rating: app.book_rating,
+        author_id: app.selected_author.as_ref().map(|a| a.Id),
+        series_id: app.selected_series.as_ref().map(|s| s.id),
+        journal_draft: app.journal_draft.text(),
+        custom_fields: app.book_custom_fields.clone(),
+    }
+}
+
+/// Whether the book form is open and should be autosaved
+pub fn is_editing_book(app: &BookshelfApp) -> bool {
+    matches!(app.current_tab, Tab::Books) && matches!(app.mode, Mode::Add | Mode::Edit)
+}
+
+/// Whether the open book form differs from how it was opened
+pub fn form_dirty(app: &BookshelfApp) -> bool {
+    is_editing_book(app)
+        && app
+            .form_opened
+            .as_ref()
+            .is_some_and(|opened| *opened != snapshot(app))
+}
+
+/// Removes the draft file once the form is saved or discarded
+pub fn clear_draft(app: &mut BookshelfApp) {
+    app.draft_baseline = None;
+    if let Err(e) = draft::delete() {
+        app.report_error(format!("Failed to remove the draft: {}", e));
+    }
+}
+
+// Handler functions for form drafts
+pub fn handle_autosave_draft(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !is_editing_book(app) {
+        return iced::Task::none();
+    }
+
+    // Only written when the form changed since it was opened or last written
+    let current = snapshot(app);
+    if app.draft_baseline.as_ref() == Some(&current) {
+        return iced::Task::none();
+    }
+
+    match draft::save(&current) {
+        Ok(()) => app.draft_baseline = Some(current),
+        Err(e) => app.report_error(format!("Failed to autosave the draft: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_restore_draft(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(draft) = app.restorable_draft.take() else {
+        return iced::Task::none();
+    };
+
+    iced::Task::perform(
+        async move {
+            match load_draft_records(draft) {
+                Ok(restore) => Ok(restore),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        |result| Message::DraftRecordsLoaded(Box::new(result)),
+    )
+}
+
+fn load_draft_records(draft: BookDraft) -> Result<DraftRestore, DbError> {
+    let book = match draft.book_id {
+        Some(id) => db::find_book(id)?,
+        None => None,
+    };
+    let author = match draft.author_id {
+        Some(id) => db::find_author(id)?,
+        None => None,
+    };
+    let series = match draft.series_id {
+        Some(id) => db::cached::get_series()?.into_iter().find(|s| s.id == id),
+        None => None,
+    };
+    Ok(DraftRestore {
+        draft,
+        book,
+        author,
+        series,
+    })
+}
+
+pub fn handle_draft_records_loaded(
+    app: &mut BookshelfApp,
+    result: Result<DraftRestore, String>,
+) -> iced::Task<Message> {
+    let restore = match result {
+        Ok(restore) => restore,
+        Err(e) => {
+            app.report_error(format!("Failed to restore the draft: {}", e));
+            return iced::Task::none();
+        }
+    };
+    let draft = restore.draft;
+
+    // Open the form as usual, then put the drafted values over it
+    app.current_tab = Tab::Books;
+    let task = match &restore.book {
+        Some(pair) => book_view::handle_edit_book_mode(app, pair),
+        None => book_view::handle_add_book_mode(app),
+    };
+
+    app.book_title = draft.title.clone();
+    app.book_price = draft.price.clone();
+    app.book_bought_date = draft.bought_date.clone();
+    app.book_finished_date = draft.finished_date.clone();
+    app.book_is_gift = draft.is_gift;
+    app.book_gift_from = draft.gift_from.clone();
+    app.book_quantity = draft.quantity;
+    app.book_cover_path = draft.cover_path.clone();
+    app.book_genre = draft.genre.clone();
+    app.book_page_count = draft.page_count.clone();
+    app.book_format = draft.format;
+    app.book_duration_minutes = draft.duration_minutes.clone();
+    app.book_language = draft.language.clone();
+    app.book_acquired_from = draft.acquired_from.clone();
+    app.book_rating = draft.rating;
+    app.selected_author = restore.author;
+    app.selected_series = restore.series;
+    app.journal_draft = text_editor::Content::with_text(&draft.journal_draft);
+    app.book_custom_fields = draft.custom_fields.clone();
+
+    let mut warnings = Vec::new();
+    if draft.book_id.is_some() && restore.book.is_none() {
+        warnings.push("The edited book was deleted, the draft will be saved as a new book");
+    }
+    if draft.author_id.is_some() && app.selected_author.is_none() {
+        warnings.push("The draft's author no longer exists, pick one again");
+    }
+    if draft.series_id.is_some() && app.selected_series.is_none() {
+        warnings.push("The draft's series no longer exists, pick one again");
+    }
+    app.status_message = Some(if warnings.is_empty() {
+        "Draft restored".to_string()
+    } else {
+        format!("Draft restored. {}", warnings.join(". "))
+    });
+
+    // The file already holds the draft, only later edits need writing
+    app.draft_baseline = Some(snapshot(app));
+    task
+}
+
+pub fn handle_discard_draft(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.restorable_draft = None;
+    clear_draft(app);
+    iced::Task::none()
+}
+
+// View functions for form drafts
+pub fn create_draft_prompt<'a>(
+    app: &'a BookshelfApp,
+    draft: &'a BookDraft,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let question = match draft.saved_at {
+        Some(saved_at) => format!(
+            "Restore unsaved draft from {}?",
+            saved_at.format("%Y-%m-%d %H:%M")
+        ),
+        None => "Restore unsaved draft?".to_string(),
+    };
+    let title = if draft.title.trim().is_empty() {
+        "Untitled book"
+    } else {
+        draft.title.as_str()
+    };
+
+    container(
+        container(
+            column![
+                text(question).size(fonts.heading()),
+                text(title).size(fonts.body()),
+                row![
+                    button("Restore")
+                        .on_press(Message::RestoreDraft)
+                        .style(button::primary),
+                    button("Discard")
+                        .on_press(Message::DiscardDraft)
+                        .style(button::danger),
+                ]
+                .spacing(10),
+            ]
+            .spacing(15),
+        )
+        .padding(20)
+        .style(container::bordered_box),
+    )
+    .center(Length::Fill)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restore(draft: BookDraft, author: Option<AuthorModel>) -> DraftRestore {
+        DraftRestore {
+            draft,
+            book: None,
+            author,
+            series: None,
+        }
+    }
+
+    #[test]
+    fn a_draft_restores_into_the_add_form() {
+        let mut app = BookshelfApp::new();
+        let author = AuthorModel {
+            Id: 7,
+            Name: Some("Stanisław Lem".to_string()),
+            website: None,
+            wikipedia: None,
+            published_works: None,
+            Aliases: None,
+        };
+        let draft = BookDraft {
+            title: "Solaris".to_string(),
+            price: "39,90".to_string(),
+            author_id: Some(7),
+            journal_draft: "Long notes".to_string(),
+            ..BookDraft::default()
+        };
+
+        let _ = handle_draft_records_loaded(&mut app, Ok(restore(draft, Some(author))));
+        assert!(matches!(app.mode, Mode::Add));
+        assert_eq!(app.book_title, "Solaris");
+        assert_eq!(app.book_price, "39,90");
+        assert_eq!(app.selected_author.as_ref().map(|a| a.Id), Some(7));
+        assert_eq!(app.journal_draft.text().trim_end(), "Long notes");
+        assert_eq!(app.status_message.as_deref(), Some("Draft restored"));
+        // Already on disk, so autosave waits for the next edit
+        assert_eq!(app.draft_baseline, Some(snapshot(&app)));
+    }
+
+    #[test]
+    fn a_draft_whose_author_was_deleted_restores_without_it() {
+        let mut app = BookshelfApp::new();
+        let draft = BookDraft {
+            book_id: Some(4),
+            title: "Solaris".to_string(),
+            author_id: Some(7),
+            ..BookDraft::default()
+        };
+
+        let _ = handle_draft_records_loaded(&mut app, Ok(restore(draft, None)));
+        assert_eq!(app.book_title, "Solaris");
+        assert!(app.selected_author.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some(
+                "Draft restored. The edited book was deleted, the draft will be saved as a new \
+                 book. The draft's author no longer exists, pick one again"
+            )
+        );
+    }
+}
@@ -0,0 +1,171 @@
+// src/ui/hover_view.rs
+use crate::language::language_name;
+use crate::models::ID;
+use crate::ui::{
+    format_duration, format_price, format_rating, hover_card_due, hover_card_position,
+    BookshelfApp, Message, Mode, Tab, CURRENCY, HOVER_CARD_HEIGHT, HOVER_CARD_WIDTH,
+};
+use chrono::NaiveDateTime;
+use iced::widget::{column, container, row, stack, text, Column};
+use iced::{event, mouse, window, Element, Event, Length, Padding, Point, Size};
+use std::time::Instant;
+
+// Handler functions for the book hover card
+pub fn handle_hover_started(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    app.hovered_book = Some((id, Instant::now()));
+    app.hover_card_shown = false;
+    iced::Task::none()
+}
+
+// Entering the next row can arrive before leaving the previous one, only its own exit
+// clears the hover
+pub fn handle_hover_ended(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if app.hovered_book.is_some_and(|(hovered, _)| hovered == id) {
+        clear_hover(app);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_hover_tick(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some((_, since)) = app.hovered_book {
+        app.hover_card_shown = hover_card_due(since, Instant::now());
+    }
+    iced::Task::none()
+}
+
+pub fn handle_cursor_moved(app: &mut BookshelfApp, position: Point) -> iced::Task<Message> {
+    app.cursor_position = position;
+    iced::Task::none()
+}
+
+pub fn handle_window_resized(app: &mut BookshelfApp, size: Size) -> iced::Task<Message> {
+    app.window_size = size;
+    iced::Task::none()
+}
+
+pub fn clear_hover(app: &mut BookshelfApp) {
+    app.hovered_book = None;
+    app.hover_card_shown = false;
+}
+
+// The cursor is followed only until the card shows, it stays where it appeared
+pub fn on_cursor_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Mouse(mouse::Event::CursorMoved { position }) => {
+            Some(Message::HoverCursorMoved(position))
+        }
+        _ => None,
+    }
+}
+
+pub fn on_window_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Window(window::Event::Opened { size, .. } | window::Event::Resized(size)) => {
+            Some(Message::WindowResized(size))
+        }
+        _ => None,
+    }
+}
+
+// View functions for the book hover card
+/// The page with the card of the hovered book floating over it. The card holds no
+/// widgets that take clicks, so they still reach the row and its buttons underneath.
+pub fn with_hover_card<'a>(
+    app: &'a BookshelfApp,
+    page: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let listed = matches!(app.current_tab, Tab::Books) && matches!(app.mode, Mode::View);
+    let card = app
+        .hovered_book
+        .filter(|_| app.hover_card_shown && listed)
+        .and_then(|(id, _)| create_card(app, id));
+    let Some(card) = card else {
+        return page.into();
+    };
+
+    let fonts = app.fonts();
+    let size = Size::new(
+        fonts.width(HOVER_CARD_WIDTH),
+        fonts.width(HOVER_CARD_HEIGHT),
+    );
+    let at = hover_card_position(app.cursor_position, size, app.window_size);
+    let card = container(card)
+        .width(size.width)
+        .height(size.height)
+        .padding(12)
+        .clip(true)
+        .style(container::bordered_box);
+
+    stack![
+        page.into(),
+        container(card)
+            .padding(Padding::ZERO.top(at.y).left(at.x))
+            .width(Length::Fill)
+            .height(Length::Fill)
+    ]
+    .into()
+}
+
+fn create_card(app: &BookshelfApp, id: ID) -> Option<Column<'_, Message>> {
+    let pair = app.books.iter().find(|pair| pair.book.id == id)?;
+    let book = &pair.book;
+    let fonts = app.fonts();
+    let date = |value: Option<NaiveDateTime>| value.map(|d| d.format("%Y-%m-%d %H:%M").to_string());
+    let series = book
+        .SeriesFK
+        .and_then(|series_id| app.series.iter().find(|s| s.id == series_id))
+        .map(|series| series.name.clone());
+
+    let fields = [
+        ("Author", pair.author.as_ref().and_then(|a| a.Name.clone())),
+        ("Series", series),
+        (
+            "Price",
+            book.price
+                .map(|p| format_price(p, CURRENCY, app.privacy_mode)),
+        ),
+        ("Bought", date(book.bought)),
+        ("Finished", date(book.finished)),
+        ("Added", date(book.added)),
+        ("Rating", book.rating.map(format_rating)),
+        ("Genre", book.genre.clone()),
+        ("Pages", book.page_count.map(|pages| pages.to_string())),
+        (
+            "Format",
+            book.book_format().map(|format| {
+                match book.duration_minutes.filter(|_| book.is_audiobook()) {
+                    Some(minutes) => format!("{}, {}", format, format_duration(minutes)),
+                    None => format.to_string(),
+                }
+            }),
+        ),
+        ("Language", book.language.as_deref().map(language_name)),
+        ("Acquired from", book.acquired_from.clone()),
+        ("Gift from", book.gift_from.clone()),
+    ];
+    let custom = app
+        .custom_fields
+        .get(&id)
+        .into_iter()
+        .flatten()
+        .map(|field| (field.key.as_str(), Some(field.value.clone())));
+
+    // Only what the book has is listed
+    let card = fields
+        .into_iter()
+        .chain(custom)
+        .filter_map(|(label, value)| value.map(|value| (label, value)))
+        .fold(
+            column![text(book.title.clone()).size(fonts.item())].spacing(6),
+            |card, (label, value)| {
+                card.push(
+                    row![
+                        text(label).size(fonts.tiny()).width(fonts.width(100.0)),
+                        text(value).size(fonts.small()),
+                    ]
+                    .spacing(8),
+                )
+            },
+        );
+    Some(card)
+}
@@ -0,0 +1,796 @@
+// src/ui/import_view.rs
+use crate::bulk::{self, BulkPreview};
+use crate::db::{self, AuthorResolution};
+use crate::import::{
+    self, CsvTable, ImportCounts, ImportDecision, ImportField, ImportPlan, ImportedBook,
+};
+use crate::ui::components::{author_resolution, bulk_preview};
+use crate::ui::settings_view::save_config;
+use crate::ui::{format_price, BookshelfApp, Message, CURRENCY, LIST_PADDING, LIST_SPACING};
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, scrollable, text, text_input,
+};
+use iced::{Element, Length};
+use std::path::PathBuf;
+
+/// Rows shown in the preview under the column mapping
+const PREVIEW_ROWS: usize = 5;
+
+/// CSV import in progress, from picking the file to mapping its columns
+#[derive(Debug, Clone, Default)]
+pub struct CsvImport {
+    pub path: String,
+    pub table: Option<CsvTable>,
+    pub mapping: Vec<ImportField>, // One field per column of the table
+    pub preset_name: String,
+    pub importing: bool,
+    pub plan: Option<ImportPlan>, // Set while the dry run is reviewed
+    pub preview: BulkPreview, // Of the plan, with the decisions taken so far
+}
+
+impl CsvImport {
+    // Plans under review are shown through their preview, which follows every decision
+    fn review(&mut self, plan: ImportPlan) {
+        self.preview = plan.preview();
+        self.plan = Some(plan);
+    }
+}
+
+// Handler functions for CSV import
+pub fn handle_open_csv_import(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.csv_import = Some(CsvImport::default());
+    iced::Task::none()
+}
+
+pub fn handle_close_csv_import(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.csv_import = None;
+    iced::Task::none()
+}
+
+pub fn handle_csv_import_path_changed(
+    app: &mut BookshelfApp,
+    value: String,
+) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        csv_import.path = value;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_read_csv_import(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(csv_import) = &app.csv_import else {
+        return iced::Task::none();
+    };
+    let path = PathBuf::from(csv_import.path.trim());
+
+    iced::Task::perform(
+        async move {
+            match import::read_csv(&path) {
+                Ok(table) => Ok(table),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CsvImportRead,
+    )
+}
+
+pub fn handle_csv_import_read(
+    app: &mut BookshelfApp,
+    result: Result<CsvTable, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(table) => {
+            if let Some(csv_import) = &mut app.csv_import {
+                csv_import.mapping = import::guess_mapping(&table.headers);
+                csv_import.table = Some(table);
+            }
+        }
+        Err(e) => app.report_error(format!("Failed to read the CSV file: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_csv_import_field_selected(
+    app: &mut BookshelfApp,
+    column: usize,
+    field: ImportField,
+) -> iced::Task<Message> {
+    if let Some(field_slot) = app
+        .csv_import
+        .as_mut()
+        .and_then(|csv_import| csv_import.mapping.get_mut(column))
+    {
+        *field_slot = field;
+    }
+    if let Some(csv_import) = &mut app.csv_import {
+        csv_import.plan = None;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_csv_import_preset_name_changed(
+    app: &mut BookshelfApp,
+    value: String,
+) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        csv_import.preset_name = value;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_save_csv_import_preset(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(csv_import) = &app.csv_import else {
+        return iced::Task::none();
+    };
+    let Some(table) = &csv_import.table else {
+        return iced::Task::none();
+    };
+    let name = csv_import.preset_name.trim().to_string();
+    if name.is_empty() {
+        app.error = Some("Name the preset before saving it".to_string());
+        return iced::Task::none();
+    }
+
+    let preset = import::mapping_preset(&table.headers, &csv_import.mapping);
+    app.config.import_presets.insert(name.clone(), preset);
+    app.status_message = Some(format!("Saved import preset \"{}\"", name));
+    save_config(&app.config)
+}
+
+pub fn handle_csv_import_preset_selected(
+    app: &mut BookshelfApp,
+    name: String,
+) -> iced::Task<Message> {
+    let Some(preset) = app.config.import_presets.get(&name) else {
+        return iced::Task::none();
+    };
+    if let Some(csv_import) = &mut app.csv_import {
+        if let Some(table) = &csv_import.table {
+            csv_import.mapping = import::preset_mapping(&table.headers, preset);
+        }
+        csv_import.preset_name = name;
+        csv_import.plan = None;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_delete_csv_import_preset(
+    app: &mut BookshelfApp,
+    name: String,
+) -> iced::Task<Message> {
+    if app.config.import_presets.remove(&name).is_none() {
+        return iced::Task::none();
+    }
+    save_config(&app.config)
+}
+
+// Imports run in two phases, planning what every row does and then committing the plan
+pub fn handle_run_csv_import(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(books) = mapped_books(app) else {
+        return iced::Task::none();
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::plan_import(&books) {
+                Ok(plan) => Ok(plan),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CsvImportPlanned,
+    )
+}
+
+// Books of the mapped table, marking the import as running
+fn mapped_books(app: &mut BookshelfApp) -> Option<Vec<ImportedBook>> {
+    let csv_import = app.csv_import.as_mut()?;
+    let table = csv_import.table.as_ref()?;
+    match import::apply_mapping(table, &csv_import.mapping) {
+        Ok(mapped) => {
+            csv_import.importing = true;
+            csv_import.plan = None;
+            Some(mapped.books)
+        }
+        Err(e) => {
+            app.error = Some(e.to_string());
+            None
+        }
+    }
+}
+
+// True when the dry run is shown before committing: always when bulk actions are
+// confirmed, and otherwise when rows match books of the library, an author is close to
+// one the library has or the import would create more new authors than the settings
+// allow, a malformed file would otherwise fill the author list with junk names
+fn needs_review(app: &BookshelfApp, plan: &ImportPlan) -> bool {
+    app.config.confirm_bulk_actions
+        || plan.has_duplicates()
+        || !plan.is_resolved()
+        || plan.authors_to_create().len() > app.config.import_author_limit
+}
+
+pub fn handle_csv_import_planned(
+    app: &mut BookshelfApp,
+    result: Result<ImportPlan, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(plan) if needs_review(app, &plan) => {
+            if let Some(csv_import) = &mut app.csv_import {
+                csv_import.importing = false;
+                csv_import.review(plan);
+            }
+            iced::Task::none()
+        }
+        Ok(plan) => commit_import(app, plan),
+        Err(e) => {
+            if let Some(csv_import) = &mut app.csv_import {
+                csv_import.importing = false;
+            }
+            app.report_error(format!("Failed to check the import: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_confirm_csv_import(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(plan) = app
+        .csv_import
+        .as_mut()
+        .and_then(|csv_import| csv_import.plan.take())
+    else {
+        return iced::Task::none();
+    };
+    commit_import(app, plan)
+}
+
+fn commit_import(app: &mut BookshelfApp, plan: ImportPlan) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        csv_import.importing = true;
+    }
+
+    iced::Task::perform(
+        async move {
+            match db::import_books(&plan) {
+                Ok(counts) => Ok(counts),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CsvImported,
+    )
+}
+
+pub fn handle_csv_import_decision_selected(
+    app: &mut BookshelfApp,
+    index: usize,
+    decision: ImportDecision,
+) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        if let Some(mut plan) = csv_import.plan.take() {
+            if let Some(planned) = plan.books.get_mut(index) {
+                planned.decision = decision;
+            }
+            csv_import.review(plan);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_csv_import_decide_all(
+    app: &mut BookshelfApp,
+    decision: ImportDecision,
+) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        if let Some(mut plan) = csv_import.plan.take() {
+            plan.decide_all(decision);
+            csv_import.review(plan);
+        }
+    }
+    iced::Task::none()
+}
+
+// The choice holds for every row naming the author
+pub fn handle_resolve_csv_import_author(
+    app: &mut BookshelfApp,
+    index: usize,
+    resolution: AuthorResolution,
+) -> iced::Task<Message> {
+    if let Some(csv_import) = &mut app.csv_import {
+        if let Some(mut plan) = csv_import.plan.take() {
+            if let Some(author) = plan.unknown_authors.get_mut(index) {
+                author.resolution = Some(resolution);
+            }
+            csv_import.review(plan);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_csv_imported(
+    app: &mut BookshelfApp,
+    result: Result<ImportCounts, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(counts) => {
+            app.csv_import = None;
+            app.notify_finished(format!("Imported books: {}", counts));
+            iced::Task::batch([
+                app.update(Message::LoadBooks),
+                app.update(Message::LoadAuthors),
+            ])
+        }
+        Err(e) => {
+            if let Some(csv_import) = &mut app.csv_import {
+                csv_import.importing = false;
+            }
+            app.notify_failed(format!("Failed to import books: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+// View functions for CSV import
+pub fn view<'a>(app: &'a BookshelfApp, csv_import: &'a CsvImport) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    if let Some(plan) = &csv_import.plan {
+        return bulk_preview::view_bulk_preview(
+            "Import books from CSV",
+            &csv_import.preview,
+            create_plan_choices(app, plan),
+            plan.is_resolved().then_some(Message::ConfirmCsvImport),
+            Message::CancelCsvImportConfirmation,
+            fonts,
+        );
+    }
+
+    let mut content = column![
+        row![
+            text("Import books from CSV").size(fonts.title()),
+            horizontal_space(),
+            button("Cancel")
+                .on_press(Message::CloseCsvImport)
+                .style(button::secondary),
+        ]
+        .align_y(iced::Alignment::Center),
+        row![
+            text_input("Path to a .csv file", &csv_import.path)
+                .on_input(Message::CsvImportPathChanged)
+                .on_submit(Message::ReadCsvImport)
+                .padding(8)
+                .width(Length::Fill),
+            button("Read file")
+                .on_press_maybe(
+                    (!csv_import.path.trim().is_empty()).then_some(Message::ReadCsvImport)
+                )
+                .style(button::primary),
+        ]
+        .spacing(LIST_SPACING)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(20)
+    .padding(LIST_PADDING);
+
+    if let Some(table) = &csv_import.table {
+        content = content
+            .push(create_presets(app, csv_import))
+            .push(create_column_mapping(app, table, &csv_import.mapping))
+            .push(create_preview(app, table, &csv_import.mapping))
+            .push(create_import_button(app, table, csv_import));
+    }
+
+    scrollable(container(content).width(Length::Fill))
+        .height(Length::Fill)
+        .into()
+}
+
+fn create_presets<'a>(app: &'a BookshelfApp, csv_import: &'a CsvImport) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let names: Vec<String> = app.config.import_presets.keys().cloned().collect();
+    let selected = names
+        .iter()
+        .find(|name| **name == csv_import.preset_name)
+        .cloned();
+
+    let mut presets = row![
+        text("Preset:").size(fonts.body()),
+        pick_list(names, selected.clone(), Message::CsvImportPresetSelected)
+            .placeholder("Load a saved mapping"),
+        text_input("Preset name", &csv_import.preset_name)
+            .on_input(Message::CsvImportPresetNameChanged)
+            .on_submit(Message::SaveCsvImportPreset)
+            .padding(8)
+            .width(fonts.width(200.0)),
+        button("Save preset")
+            .on_press(Message::SaveCsvImportPreset)
+            .style(button::secondary),
+    ]
+    .spacing(LIST_SPACING)
+    .align_y(iced::Alignment::Center);
+    if let Some(name) = selected {
+        presets = presets.push(
+            button("Delete preset")
+                .on_press(Message::DeleteCsvImportPreset(name))
+                .style(button::danger),
+        );
+    }
+    presets.into()
+}
+
+// One row per source column with the field it goes into and a sample value
+fn create_column_mapping<'a>(
+    app: &BookshelfApp,
+    table: &'a CsvTable,
+    mapping: &[ImportField],
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let mut rows = column![row![
+        text("Column in the file")
+            .size(fonts.small())
+            .width(Length::FillPortion(2)),
+        text("Imported as")
+            .size(fonts.small())
+            .width(Length::FillPortion(2)),
+        text("First value")
+            .size(fonts.small())
+            .width(Length::FillPortion(3)),
+    ]
+    .spacing(LIST_SPACING)]
+    .spacing(8);
+
+    for (index, (header, field)) in table.headers.iter().zip(mapping).enumerate() {
+        let sample = table
+            .rows
+            .first()
+            .and_then(|row| row.get(index))
+            .cloned()
+            .unwrap_or_default();
+        rows = rows.push(
+            row![
+                text(header)
+                    .size(fonts.body())
+                    .width(Length::FillPortion(2)),
+                pick_list(ImportField::ALL, Some(*field), move |field| {
+                    Message::CsvImportFieldSelected(index, field)
+                })
+                .width(Length::FillPortion(2)),
+                text(sample)
+                    .size(fonts.small())
+                    .width(Length::FillPortion(3)),
+            ]
+            .spacing(LIST_SPACING)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    container(rows)
+        .padding(LIST_PADDING)
+        .width(Length::Fill)
+        .style(container::bordered_box)
+        .into()
+}
+
+fn describe_book(book: &ImportedBook) -> String {
+    let date = |value: Option<chrono::NaiveDateTime>| {
+        value.map_or_else(|| "-".to_string(), |d| d.format("%Y-%m-%d").to_string())
+    };
+    format!(
+        "{} · {} · {} · bought {} · finished {} · added {}",
+        book.title,
+        book.author.as_deref().unwrap_or("No author"),
+        book.price.map_or_else(
+            || "No price".to_string(),
+            |p| format_price(p, CURRENCY, false)
+        ),
+        date(book.bought),
+        date(book.finished),
+        date(book.added)
+    )
+}
+
+fn create_preview<'a>(
+    app: &BookshelfApp,
+    table: &CsvTable,
+    mapping: &[ImportField],
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let mut preview = column![text("Preview").size(fonts.heading())].spacing(8);
+
+    if let Err(e) = import::validate_mapping(mapping) {
+        return preview.push(text(e.to_string()).size(fonts.body())).into();
+    }
+
+    for (index, row) in table.rows.iter().take(PREVIEW_ROWS).enumerate() {
+        let line = match import::map_row(row, mapping) {
+            Ok(book) => describe_book(&book),
+            Err(e) => format!("Line {} is skipped: {}", index + 2, e),
+        };
+        preview = preview.push(text(line).size(fonts.small()));
+    }
+    if table.rows.len() > PREVIEW_ROWS {
+        preview = preview.push(
+            text(format!("and {} more rows", table.rows.len() - PREVIEW_ROWS)).size(fonts.small()),
+        );
+    }
+    preview.into()
+}
+
+fn create_import_button<'a>(
+    app: &BookshelfApp,
+    table: &CsvTable,
+    csv_import: &CsvImport,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let Ok(mapped) = import::apply_mapping(table, &csv_import.mapping) else {
+        return column![].into();
+    };
+
+    let mut actions = column![button(text(format!("Import {} books", mapped.books.len())))
+        .on_press_maybe(
+            (!csv_import.importing && !mapped.books.is_empty()).then_some(Message::RunCsvImport)
+        )
+        .style(button::primary)]
+    .spacing(8);
+    if !mapped.skipped.is_empty() {
+        actions = actions.push(
+            text(format!(
+                "{} rows will be skipped: {}",
+                mapped.skipped.len(),
+                mapped.skipped.join(", ")
+            ))
+            .size(fonts.small()),
+        );
+    }
+    actions.into()
+}
+
+// The duplicates with a decision each, the unknown authors with theirs and the authors
+// over the limit, above Apply
+fn create_plan_choices<'a>(app: &BookshelfApp, plan: &'a ImportPlan) -> Element<'a, Message> {
+    let authors = plan.authors_to_create();
+    let mut choices = column![].spacing(15);
+
+    if plan.has_duplicates() {
+        choices = choices.push(create_duplicates(app, plan));
+    }
+    choices = choices.push(author_resolution::view_author_resolution(
+        "Authors not in the library",
+        &plan.unknown_authors,
+        Message::ResolveCsvImportAuthor,
+        app.fonts(),
+    ));
+    if authors.len() > app.config.import_author_limit {
+        choices = choices.push(create_author_warning(app, &authors));
+    }
+    choices.into()
+}
+
+fn create_duplicates<'a>(app: &BookshelfApp, plan: &ImportPlan) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let decide_all = ImportDecision::ALL.iter().fold(
+        row![text("All duplicates:").size(fonts.small())]
+            .spacing(LIST_SPACING)
+            .align_y(iced::Alignment::Center),
+        |decide_all, decision| {
+            decide_all.push(
+                button(text(decision.to_string()).size(fonts.small()))
+                    .on_press(Message::CsvImportDecideAll(*decision))
+                    .style(button::secondary),
+            )
+        },
+    );
+
+    let mut rows = column![
+        text(format!(
+            "{} rows match books already in the library",
+            plan.duplicates().count()
+        ))
+        .size(fonts.heading()),
+        decide_all,
+        row![
+            text("Row in the file")
+                .size(fonts.small())
+                .width(Length::FillPortion(3)),
+            text("Book in the library")
+                .size(fonts.small())
+                .width(Length::FillPortion(3)),
+            text("Decision")
+                .size(fonts.small())
+                .width(Length::FillPortion(2)),
+        ]
+        .spacing(LIST_SPACING),
+    ]
+    .spacing(8);
+
+    for index in plan.duplicates() {
+        let planned = &plan.books[index];
+        let Some((_, label)) = &planned.existing else {
+            continue;
+        };
+        rows = rows.push(
+            row![
+                text(describe_book(&planned.book))
+                    .size(fonts.small())
+                    .width(Length::FillPortion(3)),
+                text(label.clone())
+                    .size(fonts.small())
+                    .width(Length::FillPortion(3)),
+                pick_list(
+                    ImportDecision::ALL,
+                    Some(planned.decision),
+                    move |decision| Message::CsvImportDecisionSelected(index, decision)
+                )
+                .width(Length::FillPortion(2)),
+            ]
+            .spacing(LIST_SPACING)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    container(rows)
+        .padding(LIST_PADDING)
+        .width(Length::Fill)
+        .style(container::bordered_box)
+        .into()
+}
+
+fn create_author_warning<'a>(app: &BookshelfApp, names: &[&str]) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let (shown, hidden) = bulk::examples(names);
+    let mut examples = shown.join(", ");
+    if hidden > 0 {
+        examples.push_str(&format!(" and {} more", hidden));
+    }
+
+    container(
+        column![
+            text(format!(
+                "This will create {} new authors — continue?",
+                names.len()
+            ))
+            .size(fonts.heading()),
+            text(examples).size(fonts.small()),
+            text("Check the author column mapping if these don't look like names")
+                .size(fonts.small()),
+        ]
+        .spacing(8),
+    )
+    .padding(LIST_PADDING)
+    .width(Length::Fill)
+    .style(container::bordered_box)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::UnknownAuthor;
+    use crate::import::PlannedBook;
+
+    fn imported(title: &str, author: &str) -> ImportedBook {
+        ImportedBook {
+            title: title.to_string(),
+            author: Some(author.to_string()),
+            price: None,
+            bought: None,
+            finished: None,
+            added: None,
+            acquired_from: None,
+        }
+    }
+
+    // One new book by each new author, none close to an author of the library
+    fn plan_with_new_authors(count: usize) -> ImportPlan {
+        let names: Vec<String> = (1..=count).map(|n| format!("Author {}", n)).collect();
+        ImportPlan {
+            books: names
+                .iter()
+                .map(|name| PlannedBook {
+                    book: imported(&format!("Book by {}", name), name),
+                    existing: None,
+                    decision: ImportDecision::AddAsNew,
+                })
+                .collect(),
+            unknown_authors: names
+                .iter()
+                .map(|name| UnknownAuthor {
+                    name: name.clone(),
+                    books: 1,
+                    suggestions: Vec::new(),
+                    resolution: Some(AuthorResolution::CreateNew),
+                })
+                .collect(),
+        }
+    }
+
+    fn app_importing(limit: usize) -> BookshelfApp {
+        let mut app = BookshelfApp::new();
+        app.config.confirm_bulk_actions = false;
+        app.config.import_author_limit = limit;
+        app.csv_import = Some(CsvImport {
+            importing: true,
+            ..CsvImport::default()
+        });
+        app
+    }
+
+    #[test]
+    fn the_import_asks_first_only_above_the_author_limit() {
+        let app = app_importing(2);
+        assert!(!needs_review(&app, &plan_with_new_authors(0)));
+        assert!(!needs_review(&app, &plan_with_new_authors(2)));
+        assert!(needs_review(&app, &plan_with_new_authors(3)));
+
+        // Authors whose books are all skipped aren't created, so they don't count
+        let mut plan = plan_with_new_authors(3);
+        plan.books[2].decision = ImportDecision::Skip;
+        assert!(!needs_review(&app, &plan));
+
+        let mut app = app_importing(0);
+        assert!(needs_review(&app, &plan_with_new_authors(1)));
+        app.config.import_author_limit = 50;
+        app.config.confirm_bulk_actions = true;
+        assert!(needs_review(&app, &plan_with_new_authors(0)));
+    }
+
+    #[test]
+    fn an_unresolved_author_or_a_duplicate_is_always_reviewed() {
+        let app = app_importing(50);
+        let mut plan = plan_with_new_authors(1);
+        plan.unknown_authors[0].resolution = None;
+        assert!(needs_review(&app, &plan));
+
+        let mut plan = plan_with_new_authors(1);
+        plan.books[0].existing = Some((7, "Book by Author 1".to_string()));
+        plan.books[0].decision = ImportDecision::Skip;
+        assert!(needs_review(&app, &plan));
+    }
+
+    #[test]
+    fn a_plan_over_the_limit_waits_for_confirmation() {
+        let mut app = app_importing(2);
+        let _ = handle_csv_import_planned(&mut app, Ok(plan_with_new_authors(3)));
+        let csv_import = app.csv_import.as_ref().unwrap();
+        assert!(!csv_import.importing);
+        assert_eq!(csv_import.plan, Some(plan_with_new_authors(3)));
+        assert_eq!(csv_import.preview.to_create.len(), 6); // Three books, three authors
+
+        // At the limit the plan is committed straight away
+        let mut app = app_importing(2);
+        let _ = handle_csv_import_planned(&mut app, Ok(plan_with_new_authors(2)));
+        let csv_import = app.csv_import.as_ref().unwrap();
+        assert!(csv_import.importing);
+        assert_eq!(csv_import.plan, None);
+    }
+
+    #[test]
+    fn changing_the_mapping_drops_the_pending_plan() {
+        let mut app = app_importing(2);
+        app.config.import_presets.insert(
+            "Shop".to_string(),
+            import::mapping_preset(&["Title".to_string()], &[ImportField::Title]),
+        );
+        let pending = |app: &mut BookshelfApp| {
+            let csv_import = app.csv_import.as_mut().unwrap();
+            csv_import.table = Some(CsvTable {
+                headers: vec!["Title".to_string()],
+                rows: vec![vec!["Solaris".to_string()]],
+            });
+            csv_import.mapping = vec![ImportField::Title];
+            csv_import.importing = false;
+            csv_import.review(plan_with_new_authors(3));
+        };
+
+        pending(&mut app);
+        let _ = handle_csv_import_field_selected(&mut app, 0, ImportField::Ignore);
+        let csv_import = app.csv_import.as_ref().unwrap();
+        assert_eq!(csv_import.mapping, vec![ImportField::Ignore]);
+        assert_eq!(csv_import.plan, None);
+
+        pending(&mut app);
+        let _ = handle_csv_import_preset_selected(&mut app, "Shop".to_string());
+        assert_eq!(app.csv_import.as_ref().unwrap().plan, None);
+
+        // The plan can no longer be confirmed once dropped
+        let _ = handle_confirm_csv_import(&mut app);
+        assert!(!app.csv_import.as_ref().unwrap().importing);
+    }
+}
@@ -0,0 +1,165 @@
+// src/ui/instance_view.rs
+use crate::db;
+use crate::error_log;
+use crate::instance_lock::{self, LockError};
+use crate::ui::{draft_view, BookshelfApp, Message};
+use iced::widget::{button, column, container, row, text};
+use iced::{Element, Length};
+
+/// Locks the database for this window. True when another instance holds it and the
+/// user has to choose between opening it read-only and quitting.
+pub fn lock_database(app: &mut BookshelfApp) -> bool {
+    // A database that cannot be found is reported when it is opened
+    let Ok(database) = db::startup_database_path(app.database_url.as_deref()) else {
+        return false;
+    };
+    let Some(path) = instance_lock::lock_path(&database) else {
+        return false;
+    };
+    match instance_lock::acquire(&path) {
+        Ok(lock) => {
+            app.instance_lock = Some(lock);
+            false
+        }
+        Err(LockError::Held(pid)) => {
+            app.instance_conflict = Some(pid);
+            true
+        }
+        // E.g. a read-only folder, the database itself may still open fine
+        Err(e) => {
+            error_log::error(&e.to_string());
+            false
+        }
+    }
+}
+
+// Handler functions for the instance lock
+pub fn handle_open_read_only(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.instance_conflict = None;
+    app.read_only = true;
+    db::set_read_only(true);
+    app.update(Message::Initialize)
+}
+
+pub fn handle_quit(app: &mut BookshelfApp) -> iced::Task<Message> {
+    release(app);
+    iced::exit()
+}
+
+// Handler functions for quitting
+/// What quitting now would lose, empty when nothing is pending
+pub fn unsaved_work(app: &BookshelfApp) -> Vec<&'static str> {
+    let mut work = Vec::new();
+    if draft_view::form_dirty(app) {
+        work.push("changes in the book form");
+    }
+    if app.pending_book_changes.is_some() {
+        work.push("a book edit waiting for review");
+    }
+    if !app.quote_draft.text().trim().is_empty() {
+        work.push("a quote that was not added");
+    }
+    if app.saving_book {
+        work.push("a book that is still being saved");
+    }
+    work
+}
+
+pub fn handle_request_exit(app: &mut BookshelfApp) -> iced::Task<Message> {
+    // The conflict prompt has nothing to lose, closing it is the same as Quit
+    let ask = app.config.confirm_exit_with_unsaved_work
+        && app.instance_conflict.is_none()
+        && !unsaved_work(app).is_empty();
+    if ask {
+        app.confirm_exit = true;
+        return iced::Task::none();
+    }
+    handle_quit(app)
+}
+
+pub fn handle_confirm_exit(app: &mut BookshelfApp) -> iced::Task<Message> {
+    // Discarding includes the autosaved draft, it would be offered again at the next start
+    draft_view::clear_draft(app);
+    handle_quit(app)
+}
+
+pub fn handle_cancel_exit(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.confirm_exit = false;
+    iced::Task::none()
+}
+
+fn release(app: &mut BookshelfApp) {
+    if let Some(lock) = app.instance_lock.take() {
+        lock.release();
+    }
+}
+
+// View functions for the instance lock
+pub fn create_conflict_prompt(app: &BookshelfApp, pid: u32) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    container(
+        container(
+            column![
+                text("This library is already open").size(fonts.heading()),
+                text(format!(
+                    "Another Bookshelf window (process {}) is using this database. \
+                     Changes made in two windows at once can overwrite each other.",
+                    pid
+                ))
+                .size(fonts.body()),
+                text("Read-only shows the books but refuses any change until restarted.")
+                    .size(fonts.small()),
+                row![
+                    button("Open anyway (read-only)")
+                        .on_press(Message::OpenReadOnly)
+                        .style(button::primary),
+                    button("Quit")
+                        .on_press(Message::QuitApp)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+            ]
+            .spacing(15)
+            .max_width(fonts.width(520.0)),
+        )
+        .padding(20)
+        .style(container::bordered_box),
+    )
+    .center(Length::Fill)
+    .into()
+}
+
+pub fn create_exit_prompt(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    container(
+        column![
+            text("Quit and discard unsaved changes?").size(fonts.heading()),
+            text(format!("Quitting now loses {}.", unsaved_work(app).join(", ")))
+                .size(fonts.body()),
+            row![
+                button("Quit")
+                    .on_press(Message::ConfirmExit)
+                    .style(button::danger),
+                button("Keep working")
+                    .on_press(Message::CancelExit)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        ]
+        .spacing(15)
+        .max_width(fonts.width(520.0)),
+    )
+    .padding(20)
+    .style(container::bordered_box)
+    .into()
+}
+
+/// Shown in the tab row while changes are refused
+pub fn create_read_only_indicator(app: &BookshelfApp) -> Element<'_, Message> {
+    if !app.read_only {
+        return row![].into();
+    }
+    text("Read-only, open in another window")
+        .size(app.fonts().small())
+        .into()
+}
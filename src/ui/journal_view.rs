@@ -0,0 +1,152 @@
+// src/ui/journal_view.rs
+use crate::db;
+use crate::models::{JournalEntryModel, NewJournalEntry, ID};
+use crate::ui::{BookshelfApp, FontSizes, Message};
+use chrono::Local;
+use iced::widget::{button, column, container, row, text, text_editor, Column};
+use iced::Length;
+
+// Handler functions for journal-related messages
+pub fn handle_load_journal(_: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::get_journal(book_id) {
+                Ok(entries) => Ok(entries),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::JournalLoaded,
+    )
+}
+
+pub fn handle_journal_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<JournalEntryModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(entries) => app.journal_entries = entries,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_journal_draft_action(
+    app: &mut BookshelfApp,
+    action: text_editor::Action,
+) -> iced::Task<Message> {
+    app.journal_draft.perform(action);
+    iced::Task::none()
+}
+
+pub fn handle_add_journal_entry(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(book_id) = app.selected_book.as_ref().map(|pair| pair.book.id) else {
+        return iced::Task::none();
+    };
+
+    let body = app.journal_draft.text().trim().to_string();
+    if body.is_empty() {
+        return iced::Task::none();
+    }
+
+    let entry = NewJournalEntry {
+        book_fk: book_id,
+        written_at: Local::now().naive_local(),
+        body,
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::create_journal_entry(&entry) {
+                Ok(created) => Ok(created),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::JournalEntryAdded,
+    )
+}
+
+pub fn handle_journal_entry_added(
+    app: &mut BookshelfApp,
+    result: Result<JournalEntryModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(entry) => {
+            // Only clear the draft once the entry is safely stored
+            app.journal_draft = text_editor::Content::new();
+            app.journal_entries.insert(0, entry);
+        }
+        Err(e) => app.report_error(format!("Failed to add journal entry: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_delete_journal_entry(_: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::delete_journal_entry(id) {
+                Ok(_) => Ok(id),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::JournalEntryDeleted,
+    )
+}
+
+pub fn handle_journal_entry_deleted(
+    app: &mut BookshelfApp,
+    result: Result<ID, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(id) => app.journal_entries.retain(|entry| entry.id != id),
+        Err(e) => app.report_error(format!("Failed to delete journal entry: {}", e)),
+    }
+    iced::Task::none()
+}
+
+// View functions for the journal
+pub fn create_journal_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+
+    let mut section = column![
+        text(format!("Journal ({})", app.journal_entries.len())).size(fonts.heading()),
+        text_editor(&app.journal_draft)
+            .placeholder("Halfway through, loving it...")
+            .on_action(Message::JournalDraftAction)
+            .height(fonts.width(100.0))
+            .size(fonts.body()),
+        button("Add entry")
+            .on_press_maybe(
+                (!app.journal_draft.text().trim().is_empty()).then_some(Message::AddJournalEntry)
+            )
+            .style(button::secondary),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    for entry in &app.journal_entries {
+        section = section.push(create_journal_entry(entry, fonts));
+    }
+
+    section
+}
+
+fn create_journal_entry<'a>(entry: &JournalEntryModel, fonts: FontSizes) -> Column<'a, Message> {
+    column![container(
+        row![
+            column![
+                text(entry.written_at.format("%Y-%m-%d %H:%M").to_string()).size(fonts.tiny()),
+                text(entry.body.clone()).size(fonts.body()),
+            ]
+            .spacing(5)
+            .width(Length::Fill),
+            button("Delete")
+                .on_press(Message::DeleteJournalEntry(entry.id))
+                .style(button::danger)
+                .padding(6),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center)
+    )
+    .padding(10)
+    .style(container::bordered_box)]
+}
@@ -0,0 +1,331 @@
+// src/ui/keymap.rs
+use crate::models::BookWithAuthor;
+use crate::ui::{Message, RATING_MAX};
+use iced::keyboard::{self, key, Key, Modifiers};
+use iced::{event, window, Event};
+
+/// State the bindings depend on besides the key itself.
+/// Keys typed into a focused text input are captured by the input and never reach the keymap.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyContext<'a> {
+    pub focused_book: Option<&'a BookWithAuthor>, // Only set while the book list is navigable
+    pub help_open: bool,
+    pub print_view_open: bool,
+    pub details_book: Option<&'a BookWithAuthor>, // Book open in the details view
+    pub search_matches: bool, // Search results are listed and can be stepped through
+}
+
+/// Bindings listed in the help overlay, kept next to the map so they stay in sync
+pub const BINDINGS: [(&str, &str); 15] = [
+    ("Ctrl+K", "Open the command palette"),
+    ("Ctrl+P", "Show the current list ready to print"),
+    ("Ctrl+= / Ctrl+-", "Enlarge or shrink the text"),
+    ("Ctrl+H", "Hide or show prices"),
+    ("Alt+A", "Open the author list in the book form"),
+    ("Up / Down", "Move between books"),
+    ("Enter", "Open the focused book"),
+    ("n / Shift+N", "Go to the next or previous search match"),
+    ("e", "Edit the focused book"),
+    ("d", "Delete the focused book"),
+    ("f", "Toggle finished today"),
+    ("b", "Toggle bought today"),
+    ("1 - 5 / 0", "Rate the book in the details view, or clear it"),
+    ("?", "Show or hide this help"),
+    ("Esc", "Close this help"),
+];
+
+/// Key presses for the keymap. Keys a focused text input captured are left out, so
+/// typing a title never edits, deletes or rates the focused book.
+pub fn on_key_event(event: Event, status: event::Status, _: window::Id) -> Option<Message> {
+    match (event, status) {
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }),
+            event::Status::Ignored,
+        ) => Some(Message::KeyPressed(key, modifiers)),
+        _ => None,
+    }
+}
+
+/// Shortcuts of the book form. Listened to while the form is open, including keys a
+/// focused text input captured, so they work while typing.
+pub fn on_book_form_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+            if modifiers.alt() && !modifiers.command() =>
+        {
+            match key.as_ref() {
+                keyboard::Key::Character("a") => Some(Message::AuthorDropdownShortcut),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Maps a key press to the message it triggers in the given context
+pub fn map_key(key: &Key, modifiers: Modifiers, context: &KeyContext) -> Option<Message> {
+    // '?' needs Shift on most layouts, so only command modifiers rule it out
+    if let keyboard::Key::Character("?") = key.as_ref() {
+        return (!modifiers.command() && !modifiers.alt()).then_some(Message::ToggleKeyHelp);
+    }
+
+    if context.help_open {
+        return match key.as_ref() {
+            keyboard::Key::Named(key::Named::Escape) => Some(Message::ToggleKeyHelp),
+            _ => None,
+        };
+    }
+
+    // Book keys stay off so nothing changes behind the print view
+    if context.print_view_open {
+        return match key.as_ref() {
+            keyboard::Key::Named(key::Named::Escape) => Some(Message::TogglePrintView),
+            keyboard::Key::Character("p") if modifiers.command() => Some(Message::TogglePrintView),
+            _ => None,
+        };
+    }
+
+    match key.as_ref() {
+        keyboard::Key::Character("=") | keyboard::Key::Character("+") if modifiers.command() => {
+            Some(Message::IncreaseFontScale)
+        }
+        keyboard::Key::Character("-") if modifiers.command() => Some(Message::DecreaseFontScale),
+        keyboard::Key::Character("k") if modifiers.command() => Some(Message::OpenCommandPalette),
+        keyboard::Key::Character("p") if modifiers.command() => Some(Message::TogglePrintView),
+        keyboard::Key::Character("h") if modifiers.command() => Some(Message::TogglePrivacyMode),
+        keyboard::Key::Named(key::Named::ArrowUp) if modifiers.is_empty() => {
+            Some(Message::MoveFocusedRow(-1))
+        }
+        keyboard::Key::Named(key::Named::ArrowDown) if modifiers.is_empty() => {
+            Some(Message::MoveFocusedRow(1))
+        }
+        keyboard::Key::Named(key::Named::Enter) if modifiers.is_empty() => {
+            Some(Message::OpenFocusedRow)
+        }
+        keyboard::Key::Character("n") if modifiers.is_empty() && context.search_matches => {
+            Some(Message::StepSearchMatch(1))
+        }
+        keyboard::Key::Character("N")
+            if modifiers.shift() && !modifiers.command() && context.search_matches =>
+        {
+            Some(Message::StepSearchMatch(-1))
+        }
+        keyboard::Key::Character(c) if modifiers.is_empty() => match context.details_book {
+            Some(pair) => map_rating_key(c, pair),
+            None => map_book_key(c, context.focused_book?),
+        },
+        _ => None,
+    }
+}
+
+// Digits rate the book open in the details view, 0 clears the rating
+fn map_rating_key(c: &str, pair: &BookWithAuthor) -> Option<Message> {
+    let rating = c.parse::<i32>().ok().filter(|rating| (0..=RATING_MAX).contains(rating))?;
+    Some(Message::BookRatingChanged(
+        pair.book.id,
+        (rating > 0).then_some(rating),
+    ))
+}
+
+// Single letter actions on the focused book
+fn map_book_key(c: &str, pair: &BookWithAuthor) -> Option<Message> {
+    match c {
+        "e" => Some(Message::EditBookMode(pair.clone())),
+        "d" => Some(Message::ConfirmDeleteBook(
+            pair.book.id,
+            pair.book.title.clone(),
+        )),
+        "f" => Some(Message::ToggleBookFinishedToday(pair.book.id)),
+        "b" => Some(Message::ToggleBookBoughtToday(pair.book.id)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BookModel;
+
+    fn book() -> BookWithAuthor {
+        BookWithAuthor {
+            book: BookModel::titled(7, "Dune"),
+            author: None,
+        }
+    }
+
+    fn character(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    fn press(key: Key, modifiers: Modifiers) -> Event {
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            modified_key: key.clone(),
+            key,
+            physical_key: key::Physical::Unidentified(key::NativeCode::Unidentified),
+            location: keyboard::Location::Standard,
+            modifiers,
+            text: None,
+        })
+    }
+
+    #[test]
+    fn letters_act_on_the_focused_book() {
+        let pair = book();
+        let context = KeyContext {
+            focused_book: Some(&pair),
+            ..KeyContext::default()
+        };
+        let map = |c: &str| map_key(&character(c), Modifiers::empty(), &context);
+
+        assert!(matches!(map("e"), Some(Message::EditBookMode(edited)) if edited.book.id == 7));
+        assert!(matches!(
+            map("d"),
+            Some(Message::ConfirmDeleteBook(7, title)) if title == "Dune"
+        ));
+        assert!(matches!(map("f"), Some(Message::ToggleBookFinishedToday(7))));
+        assert!(matches!(map("b"), Some(Message::ToggleBookBoughtToday(7))));
+        assert!(map("x").is_none());
+        // Digits only rate in the details view
+        assert!(map("3").is_none());
+    }
+
+    #[test]
+    fn letters_do_nothing_without_a_focused_book_or_with_modifiers() {
+        let pair = book();
+        let focused = KeyContext {
+            focused_book: Some(&pair),
+            ..KeyContext::default()
+        };
+        for c in ["e", "d", "f", "b"] {
+            assert!(map_key(&character(c), Modifiers::empty(), &KeyContext::default()).is_none());
+            assert!(map_key(&character(c), Modifiers::ALT, &focused).is_none());
+            assert!(map_key(&character(c), Modifiers::COMMAND, &focused).is_none());
+        }
+    }
+
+    #[test]
+    fn digits_rate_the_book_in_the_details_view() {
+        let pair = book();
+        let context = KeyContext {
+            details_book: Some(&pair),
+            ..KeyContext::default()
+        };
+        let map = |c: &str| map_key(&character(c), Modifiers::empty(), &context);
+
+        for rating in 1..=RATING_MAX {
+            assert!(matches!(
+                map(&rating.to_string()),
+                Some(Message::BookRatingChanged(7, Some(r))) if r == rating
+            ));
+        }
+        assert!(matches!(map("0"), Some(Message::BookRatingChanged(7, None))));
+        assert!(map(&(RATING_MAX + 1).to_string()).is_none());
+        // The details view takes the letters over from the list
+        assert!(map("e").is_none());
+    }
+
+    #[test]
+    fn help_toggles_with_question_mark_and_closes_with_escape_only() {
+        let pair = book();
+        let open = KeyContext {
+            focused_book: Some(&pair),
+            help_open: true,
+            ..KeyContext::default()
+        };
+        let question = character("?");
+        assert!(matches!(
+            map_key(&question, Modifiers::SHIFT, &KeyContext::default()),
+            Some(Message::ToggleKeyHelp)
+        ));
+        assert!(map_key(&question, Modifiers::COMMAND, &KeyContext::default()).is_none());
+        assert!(matches!(
+            map_key(&Key::Named(key::Named::Escape), Modifiers::empty(), &open),
+            Some(Message::ToggleKeyHelp)
+        ));
+        assert!(map_key(&character("d"), Modifiers::empty(), &open).is_none());
+    }
+
+    #[test]
+    fn the_print_view_only_listens_to_closing_keys() {
+        let pair = book();
+        let context = KeyContext {
+            focused_book: Some(&pair),
+            print_view_open: true,
+            ..KeyContext::default()
+        };
+        assert!(matches!(
+            map_key(&Key::Named(key::Named::Escape), Modifiers::empty(), &context),
+            Some(Message::TogglePrintView)
+        ));
+        assert!(matches!(
+            map_key(&character("p"), Modifiers::COMMAND, &context),
+            Some(Message::TogglePrintView)
+        ));
+        assert!(map_key(&character("f"), Modifiers::empty(), &context).is_none());
+    }
+
+    #[test]
+    fn list_and_window_shortcuts() {
+        let context = KeyContext {
+            search_matches: true,
+            ..KeyContext::default()
+        };
+        let map = |key: Key, modifiers| map_key(&key, modifiers, &context);
+
+        assert!(matches!(
+            map(Key::Named(key::Named::ArrowUp), Modifiers::empty()),
+            Some(Message::MoveFocusedRow(-1))
+        ));
+        assert!(matches!(
+            map(Key::Named(key::Named::ArrowDown), Modifiers::empty()),
+            Some(Message::MoveFocusedRow(1))
+        ));
+        assert!(matches!(
+            map(Key::Named(key::Named::Enter), Modifiers::empty()),
+            Some(Message::OpenFocusedRow)
+        ));
+        assert!(matches!(
+            map(character("n"), Modifiers::empty()),
+            Some(Message::StepSearchMatch(1))
+        ));
+        assert!(matches!(
+            map(character("N"), Modifiers::SHIFT),
+            Some(Message::StepSearchMatch(-1))
+        ));
+        assert!(map_key(&character("n"), Modifiers::empty(), &KeyContext::default()).is_none());
+        assert!(matches!(
+            map(character("k"), Modifiers::COMMAND),
+            Some(Message::OpenCommandPalette)
+        ));
+        assert!(matches!(
+            map(character("h"), Modifiers::COMMAND),
+            Some(Message::TogglePrivacyMode)
+        ));
+        assert!(matches!(
+            map(character("="), Modifiers::COMMAND),
+            Some(Message::IncreaseFontScale)
+        ));
+        assert!(matches!(
+            map(character("-"), Modifiers::COMMAND),
+            Some(Message::DecreaseFontScale)
+        ));
+    }
+
+    #[test]
+    fn keys_a_text_input_captured_never_reach_the_keymap() {
+        let window = window::Id::unique();
+        let typed = press(character("d"), Modifiers::empty());
+        assert!(on_key_event(typed.clone(), event::Status::Captured, window).is_none());
+        assert!(matches!(
+            on_key_event(typed, event::Status::Ignored, window),
+            Some(Message::KeyPressed(Key::Character(c), _)) if c == "d"
+        ));
+
+        // The form's own shortcuts work while typing
+        let alt_a = press(character("a"), Modifiers::ALT);
+        assert!(matches!(
+            on_book_form_event(alt_a, event::Status::Captured, window),
+            Some(Message::AuthorDropdownShortcut)
+        ));
+    }
+}
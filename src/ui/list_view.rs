@@ -0,0 +1,428 @@
+// src/ui/list_view.rs
+use crate::db;
+use crate::models::{BookListModel, BookWithAuthor, ID};
+use crate::ui::book_view::book_box_style;
+use crate::ui::{BookshelfApp, Message};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, Column,
+};
+use iced::{Element, Length};
+
+/// The list shown instead of the book list on the Books tab
+#[derive(Debug, Clone)]
+pub struct ActiveList {
+    pub list: BookListModel,
+    pub book_ids: Vec<ID>, // Reading order
+    pub rename: String,    // Name input, starts as the current name
+    pub confirm_delete: bool,
+}
+
+impl ActiveList {
+    pub fn new(list: BookListModel) -> Self {
+        Self {
+            rename: list.name.clone(),
+            list,
+            book_ids: Vec::new(),
+            confirm_delete: false,
+        }
+    }
+
+    /// Books of the list in reading order, ones no longer in the library are skipped
+    pub fn books<'a>(&self, books: &'a [BookWithAuthor]) -> Vec<&'a BookWithAuthor> {
+        self.book_ids
+            .iter()
+            .filter_map(|id| books.iter().find(|pair| pair.book.id == *id))
+            .collect()
+    }
+}
+
+/// The first book of the list not finished yet
+pub fn up_next(books: &[&BookWithAuthor]) -> Option<ID> {
+    books
+        .iter()
+        .find(|pair| pair.book.finished.is_none())
+        .map(|pair| pair.book.id)
+}
+
+// Handler functions for reading lists
+pub fn handle_load_lists() -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_book_lists() {
+                Ok(lists) => Ok(lists),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookListsLoaded,
+    )
+}
+
+pub fn handle_lists_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<BookListModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(lists) => {
+            // The open list picks up a new name
+            if let Some(active) = &mut app.active_list {
+                match lists.iter().find(|list| list.id == active.list.id) {
+                    Some(list) => active.list = list.clone(),
+                    None => app.active_list = None,
+                }
+            }
+            app.book_lists = lists;
+        }
+        Err(e) => app.report_error(format!("Failed to load reading lists: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_create_list(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let name = std::mem::take(&mut app.new_list_name);
+    iced::Task::perform(
+        async move {
+            match db::create_book_list(&name) {
+                Ok(list) => Ok(list),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookListCreated,
+    )
+}
+
+pub fn handle_list_created(
+    app: &mut BookshelfApp,
+    result: Result<BookListModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(list) => {
+            app.status_message = Some(format!("Created the list '{}'", list.name));
+            iced::Task::batch(vec![
+                handle_load_lists(),
+                app.update(Message::OpenBookList(list)),
+            ])
+        }
+        Err(e) => {
+            app.report_error(format!("Failed to create the list: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_open_list(app: &mut BookshelfApp, list: BookListModel) -> iced::Task<Message> {
+    let list_id = list.id;
+    app.active_list = Some(ActiveList::new(list));
+    app.book_menu = None;
+    items_task(list_id, move || db::get_book_list_items(list_id))
+}
+
+pub fn handle_close_list(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.active_list = None;
+    iced::Task::none()
+}
+
+// Every change to the entries answers with the list in its new order
+fn items_task(
+    list_id: ID,
+    change: impl FnOnce() -> Result<Vec<ID>, db::DbError> + Send + 'static,
+) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match change() {
+                Ok(ids) => Ok(ids),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        move |result| Message::BookListItemsLoaded(list_id, result),
+    )
+}
+
+pub fn handle_items_loaded(
+    app: &mut BookshelfApp,
+    list_id: ID,
+    result: Result<Vec<ID>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(ids) => {
+            if let Some(active) = app
+                .active_list
+                .as_mut()
+                .filter(|active| active.list.id == list_id)
+            {
+                active.book_ids = ids;
+            }
+        }
+        Err(e) => app.report_error(format!("Failed to update the reading list: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_toggle_book_menu(app: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    app.book_menu = if app.book_menu == Some(book_id) {
+        None
+    } else {
+        Some(book_id)
+    };
+    iced::Task::none()
+}
+
+pub fn handle_add_to_list(app: &mut BookshelfApp, list_id: ID, book_id: ID) -> iced::Task<Message> {
+    app.book_menu = None;
+    let title = app
+        .books
+        .iter()
+        .find(|pair| pair.book.id == book_id)
+        .map_or("The book", |pair| pair.book.title.as_str());
+    let list = app
+        .book_lists
+        .iter()
+        .find(|list| list.id == list_id)
+        .map_or("the list", |list| list.name.as_str());
+    app.status_message = Some(format!("Added '{}' to {}", title, list));
+    items_task(list_id, move || db::add_book_to_list(list_id, book_id))
+}
+
+pub fn handle_move_in_list(
+    app: &mut BookshelfApp,
+    book_id: ID,
+    earlier: bool,
+) -> iced::Task<Message> {
+    let Some(active) = &app.active_list else {
+        return iced::Task::none();
+    };
+    let list_id = active.list.id;
+    items_task(list_id, move || {
+        db::move_book_in_list(list_id, book_id, earlier)
+    })
+}
+
+pub fn handle_remove_from_list(app: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    let Some(active) = &app.active_list else {
+        return iced::Task::none();
+    };
+    let list_id = active.list.id;
+    items_task(list_id, move || db::remove_book_from_list(list_id, book_id))
+}
+
+pub fn handle_rename_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    if let Some(active) = &mut app.active_list {
+        active.rename = value;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_rename_list(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(active) = &app.active_list else {
+        return iced::Task::none();
+    };
+    let (id, name) = (active.list.id, active.rename.clone());
+    iced::Task::perform(
+        async move {
+            match db::rename_book_list(id, &name).and_then(|_| db::get_book_lists()) {
+                Ok(lists) => Ok(lists),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookListsLoaded,
+    )
+}
+
+// The first press asks for confirmation, the second deletes the list but not its books
+pub fn handle_delete_list(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(active) = &mut app.active_list else {
+        return iced::Task::none();
+    };
+    if !active.confirm_delete {
+        active.confirm_delete = true;
+        return iced::Task::none();
+    }
+
+    let id = active.list.id;
+    app.status_message = Some(format!("Deleted the list '{}'", active.list.name));
+    app.active_list = None;
+    iced::Task::perform(
+        async move {
+            match db::delete_book_list(id).and_then(|_| db::get_book_lists()) {
+                Ok(lists) => Ok(lists),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookListsLoaded,
+    )
+}
+
+// View functions for reading lists
+/// Opens a list or starts a new one, shown above the book list
+pub fn create_list_picker(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let name_ready = !app.new_list_name.trim().is_empty();
+    row![
+        pick_list(
+            app.book_lists.as_slice(),
+            None::<BookListModel>,
+            Message::OpenBookList
+        )
+        .placeholder("Reading lists")
+        .text_size(fonts.small()),
+        text_input("New list name", &app.new_list_name)
+            .on_input(Message::NewBookListNameChanged)
+            .on_submit(Message::CreateBookList)
+            .size(fonts.small())
+            .padding(6)
+            .width(fonts.width(200.0)),
+        button(text("Create list").size(fonts.small()))
+            .on_press_maybe(name_ready.then_some(Message::CreateBookList))
+            .style(button::secondary)
+            .padding(6),
+    ]
+    .spacing(10)
+    .padding([0, 15])
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// The lists a book can be added to, opened from the "⋯" button of a book
+pub fn create_book_menu(app: &BookshelfApp, book_id: ID) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    if app.book_lists.is_empty() {
+        return text("No reading lists yet, create one above the books")
+            .size(fonts.small())
+            .into();
+    }
+    app.book_lists
+        .iter()
+        .fold(
+            row![text("Add to:").size(fonts.small())]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+            |menu, list| {
+                menu.push(
+                    button(text(&list.name).size(fonts.small()))
+                        .on_press(Message::AddBookToList(list.id, book_id))
+                        .style(button::secondary)
+                        .padding(6),
+                )
+            },
+        )
+        .into()
+}
+
+pub fn view<'a>(app: &'a BookshelfApp, active: &'a ActiveList) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let books = active.books(&app.books);
+    let finished = books
+        .iter()
+        .filter(|pair| pair.book.finished.is_some())
+        .count();
+
+    let header = row![
+        text(format!("List: {}", active.list.name)).size(fonts.title()),
+        iced::widget::horizontal_space(),
+        button("Back to Books")
+            .on_press(Message::CloseBookList)
+            .style(button::secondary),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    let rename_ready = !active.rename.trim().is_empty() && active.rename.trim() != active.list.name;
+    let actions = row![
+        text_input("List name", &active.rename)
+            .on_input(Message::BookListRenameChanged)
+            .on_submit(Message::RenameBookList)
+            .padding(8)
+            .width(fonts.width(250.0)),
+        button("Rename")
+            .on_press_maybe(rename_ready.then_some(Message::RenameBookList))
+            .style(button::secondary),
+        iced::widget::horizontal_space(),
+        button(if active.confirm_delete {
+            "Delete list, keep the books"
+        } else {
+            "Delete list"
+        })
+        .on_press(Message::DeleteBookList)
+        .style(button::danger),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    let content = if books.is_empty() {
+        column![
+            text("No books on this list yet, add them from the ⋯ button of a book")
+                .size(fonts.body())
+        ]
+    } else {
+        create_list_rows(app, &books)
+    };
+
+    column![
+        header,
+        actions,
+        text(format!("{} of {} finished", finished, books.len())).size(fonts.small()),
+        scrollable(container(content).width(Length::Fill)).height(Length::Fill),
+    ]
+    .spacing(15)
+    .padding(25)
+    .into()
+}
+
+fn create_list_rows<'a>(app: &BookshelfApp, books: &[&'a BookWithAuthor]) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let next = up_next(books);
+    let last = books.len() - 1;
+
+    books
+        .iter()
+        .enumerate()
+        .fold(column![].spacing(8), |rows, (index, pair)| {
+            let book_id = pair.book.id;
+            let author = pair
+                .author
+                .as_ref()
+                .and_then(|author| author.Name.clone())
+                .unwrap_or_else(|| "No Author".to_string());
+            let status = match (pair.book.finished, next == Some(book_id)) {
+                (Some(finished), _) => format!("Finished {}", finished.format("%Y-%m-%d")),
+                (None, true) => "Up next".to_string(),
+                (None, false) => String::new(),
+            };
+
+            rows.push(
+                container(
+                    row![
+                        text(format!("{}.", index + 1))
+                            .size(fonts.heading())
+                            .width(fonts.width(40.0)),
+                        column![
+                            text(&pair.book.title).size(fonts.item()),
+                            text(author).size(fonts.small()),
+                        ]
+                        .width(Length::Fill),
+                        text(status).size(fonts.small()),
+                        button("▲")
+                            .on_press_maybe(
+                                (index > 0).then_some(Message::MoveBookInList(book_id, true,))
+                            )
+                            .style(button::secondary)
+                            .padding(6),
+                        button("▼")
+                            .on_press_maybe(
+                                (index < last).then_some(Message::MoveBookInList(book_id, false,))
+                            )
+                            .style(button::secondary)
+                            .padding(6),
+                        button("Remove")
+                            .on_press(Message::RemoveBookFromList(book_id))
+                            .style(button::secondary)
+                            .padding(6),
+                    ]
+                    .spacing(10)
+                    .align_y(iced::Alignment::Center),
+                )
+                .padding(10)
+                .width(Length::Fill)
+                .style(book_box_style(next == Some(book_id))),
+            )
+        })
+}
@@ -0,0 +1,286 @@
+// src/ui/loan_view.rs
+use crate::db;
+use crate::loans;
+use crate::models::{LoanModel, NewLoan, ID};
+use crate::ui::{BookshelfApp, FontSizes, Message};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use iced::widget::{button, column, container, row, text, text_input, Column, Text};
+use iced::Length;
+
+// Handler functions for loan-related messages
+pub fn handle_load_loans(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_active_loans() {
+                Ok(loans) => Ok(loans),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::LoansLoaded,
+    )
+}
+
+pub fn handle_loans_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<LoanModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(loans) => app.loans = loans,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_load_book_loans(_: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::get_loans_for_book(book_id) {
+                Ok(loans) => Ok(loans),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookLoansLoaded,
+    )
+}
+
+pub fn handle_book_loans_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<LoanModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(loans) => app.book_loans = loans,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_loan_borrower_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.loan_borrower = value;
+    iced::Task::none()
+}
+
+pub fn handle_loan_due_date_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.loan_due_date = value;
+    iced::Task::none()
+}
+
+pub fn handle_lend_book(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(book_id) = app.selected_book.as_ref().map(|pair| pair.book.id) else {
+        return iced::Task::none();
+    };
+
+    let borrower = app.loan_borrower.trim().to_string();
+    if borrower.is_empty() {
+        app.error = Some("Borrower name cannot be empty".to_string());
+        return iced::Task::none();
+    }
+
+    let due_on = match parse_due_date(&app.loan_due_date) {
+        Ok(due_on) => due_on,
+        Err(e) => {
+            app.error = Some(e);
+            return iced::Task::none();
+        }
+    };
+
+    let loan = NewLoan {
+        book_fk: book_id,
+        borrower,
+        loaned_on: Local::now().naive_local(),
+        due_on,
+        returned_on: None,
+    };
+
+    iced::Task::perform(
+        async move {
+            match db::create_loan(&loan) {
+                Ok(created) => Ok(created),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::LoanCreated,
+    )
+}
+
+pub fn handle_loan_created(
+    app: &mut BookshelfApp,
+    result: Result<LoanModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(loan) => {
+            app.loan_borrower.clear();
+            app.loan_due_date.clear();
+            app.error = None;
+            app.loans.push(loan.clone());
+            app.book_loans.insert(0, loan);
+        }
+        Err(e) => app.report_error(format!("Failed to lend book: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_mark_loan_returned(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    let returned_on = Local::now().naive_local();
+
+    // Clear the badge right away, the next load reconciles with the database
+    app.loans.retain(|loan| loan.id != id);
+    if let Some(loan) = app.book_loans.iter_mut().find(|loan| loan.id == id) {
+        loan.returned_on = Some(returned_on);
+    }
+
+    iced::Task::perform(
+        async move {
+            match db::mark_loan_returned(id, returned_on) {
+                Ok(loan) => Ok(loan),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::LoanReturned,
+    )
+}
+
+pub fn handle_loan_returned(
+    app: &mut BookshelfApp,
+    result: Result<LoanModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(_) => iced::Task::none(),
+        Err(e) => {
+            app.report_error(format!("Failed to mark loan as returned: {}", e));
+            // The optimistic update was wrong, fetch the real state again
+            let mut tasks = vec![app.update(Message::LoadLoans)];
+            if let Some(book_id) = app.selected_book.as_ref().map(|pair| pair.book.id) {
+                tasks.push(app.update(Message::LoadBookLoans(book_id)));
+            }
+            iced::Task::batch(tasks)
+        }
+    }
+}
+
+// Empty input means the loan has no due date
+fn parse_due_date(value: &str) -> Result<Option<NaiveDateTime>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0))
+        .map_err(|_| format!("Invalid due date '{}', expected YYYY-MM-DD", value))
+}
+
+fn days_label(days: i64) -> String {
+    format!("{} {}", days, if days == 1 { "day" } else { "days" })
+}
+
+// View functions for loans
+/// "Loaned to" badge for a book row, red once the loan is past due
+pub fn create_loan_badge<'a>(app: &BookshelfApp, book_id: ID) -> Option<Text<'a>> {
+    let fonts = app.fonts();
+    let loan = loans::active_loan_for(&app.loans, book_id)?;
+
+    let badge = match loans::overdue_days(loan.due_on, Local::now().naive_local()) {
+        Some(days) => text(format!(
+            "Loaned to {}, overdue by {}",
+            loan.borrower,
+            days_label(days)
+        ))
+        .style(text::danger),
+        None => text(format!("Loaned to {}", loan.borrower)),
+    };
+
+    Some(badge.size(fonts.small()))
+}
+
+pub fn create_loan_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let now = Local::now().naive_local();
+    let lent_out = app.book_loans.iter().any(loans::is_active);
+
+    let mut section =
+        column![text(format!("Loan history ({})", app.book_loans.len())).size(fonts.heading())]
+            .spacing(10)
+            .width(Length::Fill);
+
+    // A book can only be with one borrower at a time
+    if !lent_out {
+        section = section.push(
+            row![
+                text_input("Borrower", &app.loan_borrower)
+                    .on_input(Message::LoanBorrowerChanged)
+                    .padding(10)
+                    .width(Length::FillPortion(2)),
+                text_input("Due date (YYYY-MM-DD, optional)", &app.loan_due_date)
+                    .on_input(Message::LoanDueDateChanged)
+                    .on_submit(Message::LendBook)
+                    .padding(10)
+                    .width(Length::FillPortion(2)),
+                button("Lend")
+                    .on_press_maybe(
+                        (!app.loan_borrower.trim().is_empty()).then_some(Message::LendBook)
+                    )
+                    .style(button::secondary)
+                    .padding(10),
+            ]
+            .spacing(10),
+        );
+    }
+
+    for loan in &app.book_loans {
+        section = section.push(create_loan_entry(loan, now, fonts));
+    }
+
+    section
+}
+
+fn create_loan_entry<'a>(
+    loan: &LoanModel,
+    now: NaiveDateTime,
+    fonts: FontSizes,
+) -> Column<'a, Message> {
+    let days = loans::loan_duration_days(loan, now);
+    let period = match loan.returned_on {
+        Some(returned_on) => format!(
+            "{} to {} ({})",
+            loan.loaned_on.format("%Y-%m-%d"),
+            returned_on.format("%Y-%m-%d"),
+            days_label(days)
+        ),
+        None => format!(
+            "Since {} ({})",
+            loan.loaned_on.format("%Y-%m-%d"),
+            days_label(days)
+        ),
+    };
+
+    let mut info = column![
+        text(loan.borrower.clone()).size(fonts.body()),
+        text(period).size(fonts.tiny()),
+    ]
+    .spacing(5)
+    .width(Length::Fill);
+
+    if let Some(due_on) = loan.due_on {
+        let due = text(format!("Due {}", due_on.format("%Y-%m-%d"))).size(fonts.tiny());
+        info = info.push(
+            if loans::is_active(loan) && loans::overdue_days(Some(due_on), now).is_some() {
+                due.style(text::danger)
+            } else {
+                due
+            },
+        );
+    }
+
+    let mut entry = row![info]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center);
+    if loans::is_active(loan) {
+        entry = entry.push(
+            button("Mark returned")
+                .on_press(Message::MarkLoanReturned(loan.id))
+                .style(button::secondary)
+                .padding(6),
+        );
+    }
+
+    column![container(entry).padding(10).style(container::bordered_box)]
+}
@@ -0,0 +1,151 @@
+// src/ui/maintenance_view.rs
+use crate::backup;
+use crate::db;
+use crate::paths;
+use crate::ui::settings_view::save_config;
+use crate::ui::{
+    count_data_issues, maintenance_can_start, maintenance_due, BookshelfApp, DataIssue, Message,
+    LIST_PADDING,
+};
+use chrono::{Local, NaiveDateTime};
+use iced::widget::{button, column, container, horizontal_space, pick_list, row, text};
+use iced::{Element, Length};
+
+/// Outcome of a nightly maintenance run, each task reports on its own
+#[derive(Debug, Clone)]
+pub struct MaintenanceReport {
+    pub backup: Result<String, String>, // Path of the backup written
+    pub issues: Result<usize, String>,  // Data issues found in the books
+}
+
+// Nothing is edited, saved, imported or queried, on top of what an idle refresh needs
+fn is_idle(app: &BookshelfApp) -> bool {
+    app.can_refresh_while_idle()
+        && !app.saving_book
+        && app.pending_book_changes.is_none()
+        && !app.console_running
+}
+
+// A failing task doesn't keep the others from running
+fn run_maintenance(now: NaiveDateTime, backups_kept: usize) -> MaintenanceReport {
+    let backup = backup::write_nightly(&paths::exports_dir(), now, backups_kept)
+        .map(|path| path.display().to_string())
+        .map_err(|e| e.to_string());
+    let issues = db::get_books()
+        .map(|books| count_data_issues(&books, now))
+        .map_err(|e| e.to_string());
+    MaintenanceReport { backup, issues }
+}
+
+// Handler functions for the nightly maintenance
+pub fn handle_maintenance_check(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let now = Local::now().naive_local();
+    let Some(last_run) = app.config.last_maintenance else {
+        // Nothing has run yet, the first maintenance hour from now on is the next one
+        app.config.last_maintenance = Some(now);
+        return save_config(&app.config);
+    };
+    let due = app.config.nightly_maintenance
+        && maintenance_due(now, app.config.maintenance_hour, last_run);
+    if !due || !maintenance_can_start(app.read_only, is_idle(app), app.maintenance_running) {
+        return iced::Task::none();
+    }
+
+    app.maintenance_running = true;
+    app.config.last_maintenance = Some(now);
+    let backups_kept = app.config.maintenance_backups_kept;
+    iced::Task::batch([
+        save_config(&app.config),
+        iced::Task::perform(
+            async move { run_maintenance(now, backups_kept) },
+            Message::MaintenanceFinished,
+        ),
+    ])
+}
+
+pub fn handle_maintenance_finished(
+    app: &mut BookshelfApp,
+    report: MaintenanceReport,
+) -> iced::Task<Message> {
+    app.maintenance_running = false;
+    match report.backup {
+        Ok(path) => app.notify_finished(format!("Nightly backup written to {}", path)),
+        Err(e) => app.notify_failed(format!("Nightly backup failed: {}", e)),
+    }
+    match report.issues {
+        Ok(0) => app.overnight_issues = None,
+        Ok(count) => app.overnight_issues = Some(count),
+        Err(e) => app.notify_failed(format!("Nightly data check failed: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_dismiss_overnight_issues(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.overnight_issues = None;
+    iced::Task::none()
+}
+
+// View functions for the nightly maintenance
+// Stays above the page until dismissed, it is meant to be read in the morning
+pub fn create_overnight_banner(app: &BookshelfApp) -> Element<'_, Message> {
+    let Some(count) = app.overnight_issues else {
+        return column![].into();
+    };
+    let fonts = app.fonts();
+
+    container(
+        row![
+            text(format!(
+                "{} data {} found overnight",
+                count,
+                if count == 1 { "issue" } else { "issues" }
+            ))
+            .size(fonts.small()),
+            pick_list(DataIssue::ALL, None::<DataIssue>, Message::StartReview)
+                .placeholder("Review…")
+                .text_size(fonts.small())
+                .padding(6),
+            horizontal_space(),
+            button(text("Dismiss").size(fonts.small()))
+                .on_press(Message::DismissOvernightIssues)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([10.0, LIST_PADDING])
+    .width(Length::Fill)
+    .style(container::bordered_box)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::toasts::ToastKind;
+
+    #[test]
+    fn a_finished_backup_is_reported_in_a_toast() {
+        let mut app = BookshelfApp::new();
+        app.maintenance_running = true;
+        let report = MaintenanceReport {
+            backup: Ok("exports/nightly-2025-03-01-030000.json".to_string()),
+            issues: Ok(2),
+        };
+
+        let _ = handle_maintenance_finished(&mut app, report);
+        assert!(!app.maintenance_running);
+        assert_eq!(app.overnight_issues, Some(2));
+        let toasts: Vec<_> = app.toasts.iter().collect();
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts[0].kind, ToastKind::Success);
+        assert_eq!(
+            toasts[0].message,
+            "Nightly backup written to exports/nightly-2025-03-01-030000.json"
+        );
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some(toasts[0].message.as_str())
+        );
+    }
+}
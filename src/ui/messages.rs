@@ -1,5 +1,29 @@
 // src/ui/messages.rs (additions for searchable dropdown)
-use crate::models::{AuthorModel, BookModel, BookWithAuthor, ID};
+use crate::backup::LibraryDiff;
+use crate::console::QueryOutput;
+use crate::db::{
+    AuthorResolution, BoughtDateBackup, BoughtDatePlan, DeleteReport, MovePlan, RelatedRecords,
+};
+use crate::export::ShelfGrouping;
+use crate::ui::components::toasts::ToastMessage;
+use crate::import::{CsvTable, ImportCounts, ImportDecision, ImportField, ImportPlan};
+use crate::models::{
+    AuthorModel, BookFieldModel, BookFormat, BookListModel, BookModel, BookWithAuthor,
+    JournalEntryModel, LoanModel, QuoteModel, ReadingSessionModel, SeriesModel, ID,
+};
+use crate::language::Language;
+use crate::report::{CsvDateFormat, CsvQuoting, DecimalSeparator, ReportFormat};
+use crate::stats::SectionKey;
+use crate::trash::{DeletedEntry, DeletedItem, RestoreOutcome};
+use crate::ui::draft_view::DraftRestore;
+use crate::ui::maintenance_view::MaintenanceReport;
+use crate::ui::session_view::TimerBook;
+use iced::keyboard::{Key, Modifiers};
+use iced::widget::{scrollable, text_editor};
+use iced::window::Screenshot;
+use iced::{Point, Size};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 /// Defines all the possible messages that can be sent in the application
@@ -7,16 +31,27 @@ use std::fmt;
 pub enum Message {
     // Navigation
     TabSelected(Tab),
+    RunInTab(Tab, Box<Message>), // Switch tab, then handle the inner message
+    ShowBookInList(ID),          // Searches the Books tab for the book's title
+
+    // Command Palette
+    OpenCommandPalette,
+    CloseCommandPalette,
+    PaletteQueryChanged(String),
+    PaletteMoveSelection(i32),
+    PaletteExecute(Option<usize>), // None runs the highlighted entry
 
     // Sorting
     SortFieldSelected(SortField),
     SortDirectionSelected(SortDirection),
+    SecondarySortSelected(Option<SortField>),
     ApplySorting,
 
     // Search Messages
     SearchQueryChanged(String),
     PerformSearch,
     ClearSearch,
+    CancelClearSearch,
 
     // Book Messages
     LoadBooks,
@@ -24,17 +59,239 @@ pub enum Message {
     AddBookMode,
     EditBookMode(BookWithAuthor),
     ViewBookMode,
+    ViewBookDetails(BookWithAuthor),
+    CloseBookDetails,
     BookTitleChanged(String),
     BookPriceChanged(String),
     BookBoughtDateChanged(String),
     BookFinishedDateChanged(String),
+    CopyPreviousDates, // Fills both dates from the last saved book
+    ChangeBookQuantity(i32),
+    FetchCover,
+    CoverFetched(Result<Vec<u8>, String>),
+    AcceptCover,
+    DiscardCover,
+    RemoveCover,
+    AutosaveDraft,
+    RestoreDraft,
+    DiscardDraft,
+    DraftRecordsLoaded(Box<Result<DraftRestore, String>>), // Boxed, a draft is large
+    BookIsGiftToggled(bool),
+    BookGiftFromChanged(String),
+    BookGenreChanged(String),
+    BookPageCountChanged(String),
+    BookFormatSelected(Option<BookFormat>),
+    BookDurationChanged(String),
+    BookLanguageSelected(Option<Language>),
+    BookAcquiredFromChanged(String),
+    ToggleAcquiredFromDropdown,
+    AcquiredFromSearchChanged(String),
+    AcquiredFromSelected(AcquisitionPlace),
+    BookRatingSelected(Option<i32>),
     BookAuthorSelected(AuthorModel),
+    BookSeriesSelected(SeriesModel),
+    ClearBookSeries,
+    LoadJournal(ID),
+    JournalLoaded(Result<Vec<JournalEntryModel>, String>),
+    JournalDraftAction(text_editor::Action),
+    AddJournalEntry,
+    JournalEntryAdded(Result<JournalEntryModel, String>),
+    DeleteJournalEntry(ID),
+    JournalEntryDeleted(Result<ID, String>),
+    LoadCustomFields,
+    CustomFieldsLoaded(Result<HashMap<ID, Vec<BookFieldModel>>, String>),
+    AddCustomField,
+    RemoveCustomField(usize),
+    CustomFieldKeyChanged(usize, String),
+    CustomFieldValueChanged(usize, String),
+    LoadQuoteCounts,
+    QuoteCountsLoaded(Result<HashMap<ID, usize>, String>),
+    LoadBookQuotes(ID),
+    BookQuotesLoaded(Result<Vec<QuoteModel>, String>),
+    QuoteDraftAction(text_editor::Action),
+    QuotePageChanged(String),
+    SaveQuote, // Adds the draft, or saves the quote being edited
+    QuoteSaved(Result<QuoteModel, String>),
+    EditQuote(ID),
+    CancelQuoteEdit,
+    DeleteQuote(ID),
+    QuoteDeleted(Result<ID, String>),
+    CopyQuote(ID),
+    OpenQuoteBrowser,
+    AllQuotesLoaded(Result<Vec<QuoteModel>, String>),
+    QuoteSearchChanged(String),
+    CloseQuoteBrowser,
+    LoadLoans,
+    LoansLoaded(Result<Vec<LoanModel>, String>),
+    LoadBookLoans(ID),
+    BookLoansLoaded(Result<Vec<LoanModel>, String>),
+    LoanBorrowerChanged(String),
+    LoanDueDateChanged(String),
+    LendBook,
+    LoanCreated(Result<LoanModel, String>),
+    MarkLoanReturned(ID),
+    LoanReturned(Result<LoanModel, String>),
+    LoadReadingSessions,
+    ReadingSessionsLoaded(Result<Vec<ReadingSessionModel>, String>),
+    UnfinishedSessionsDiscarded(Result<usize, String>), // Left running by the last run
+    TimerBookSelected(TimerBook),
+    StartReadingSession(ID),
+    ReadingSessionStarted(Result<ReadingSessionModel, String>),
+    ReadingTimerTick,
+    TimerPagesChanged(String),
+    StopReadingSession,
+    ReadingSessionStopped(Result<ReadingSessionModel, String>),
+    DiscardReadingSession,
+    ReadingSessionDiscarded(Result<ID, String>),
     SaveBook,
+    SaveBookAndAddAnother,
     BookSaved(Result<BookModel, String>),
     ConfirmDeleteBook(ID, String), // Add confirmation step
     DeleteBook(ID),
     CancelDeleteBook,
-    BookDeleted(Result<usize, String>),
+    RelatedRecordsCounted(ID, Result<RelatedRecords, String>),
+    ToggleDeleteAcknowledged(bool),
+    BookDeleted(Result<DeleteReport, String>),
+    ToggleBookPinned(ID),
+    BookPinned(Result<BookModel, String>),
+    BookListScrolled(scrollable::Viewport),
+    ListModeSelected(ListMode),
+    ToggleGroupByAuthor(bool),
+    ToggleGroupBySeries(bool),
+    ToggleAuthorGroup(Option<ID>), // None is the group of books without an author
+    StartColumnResize(usize),
+    ColumnResizeMoved(f32),
+    EndColumnResize,
+    ToggleDetailsPane(bool),
+    ToggleOpenDetailsOnClick(bool),
+    StartSplitDrag,
+    SplitDragMoved(f32), // Share of the width left of the splitter
+    EndSplitDrag,
+    CopyBooksAsMarkdown,
+    ToggleSortInDatabase(bool),
+    ToggleConfirmBulkActions(bool),
+    ToggleStatsStrip(bool),
+    TogglePinBooksToTop(bool),
+    SortEmptyValuesSelected(EmptyValues),
+    ToggleAveragePricedOnly(bool),
+    ToggleReadingDuration(bool),
+    TogglePrivacyMode,
+    ToggleHidePrices(bool),
+    ToggleRememberSearch(bool),
+    ToggleScientificPrices(bool),
+    ToggleBoughtWhenFinished(bool),
+    ToggleReviewChanges(bool),
+    ToggleConfirmExit(bool),
+    ConfirmBookChanges,
+    CancelBookChanges,
+    ToggleGiftsOnly(bool),
+    ToggleMissingFilter(MissingField),
+    StartReview(DataIssue),
+    ReviewSkip,
+    ReviewPrevious,
+    ReviewSelect(usize), // Index in the review queue
+    EndReview,
+    ToggleLanguageFilter(String), // Language code
+    AcquiredFromFilterSelected(Option<AcquisitionPlace>),
+    YearFilterSelected(YearChoice),
+    YearFilterFieldSelected(YearField),
+    FilterYearsLoaded(Result<Vec<i32>, String>),
+    AssignAuthorToBook(ID),
+    AuthorAssigned(Result<(BookModel, Option<ID>), String>), // Updated book and its previous author
+    UndoAuthorAssignment,
+    AuthorAssignmentUndone(Result<BookModel, String>),
+    ToggleSearchField(SearchField, bool),
+    ToggleStickyField(StickyField, bool),
+    ToggleQueryConsole(bool),
+    OpenLogFile,
+    ResetSettings,
+    ConfirmResetSettings,
+    CancelResetSettings,
+    ConsoleQueryAction(text_editor::Action),
+    RunConsoleQuery,
+    ConsoleQueryFinished(Result<QueryOutput, String>),
+    WriteBackup,
+    BackupWritten(Result<String, String>),
+    BackupOlderChanged(String),
+    BackupNewerChanged(String),
+    CompareBackups,
+    BackupsCompared(Result<LibraryDiff, String>),
+    SaveBackupDiff,
+    BackupDiffSaved(Result<String, String>),
+    CloseBackupDiff,
+    RestoreBackup,
+    ConfirmRestoreBackup,
+    CancelRestoreBackup,
+    BackupRestored(Result<String, String>),
+    MoveFocusedRow(i32), // Arrow key navigation over the displayed books
+    OpenFocusedRow,
+    KeyPressed(Key, Modifiers),
+    ToggleKeyHelp,
+    ToggleRecentlyDeleted,
+    TogglePrintView,
+    OpenPrintPage,
+    PrintPageOpened(Result<String, String>),
+    RestoreDeleted(usize), // Index into the recently deleted list
+    DeletedRestored(DeletedEntry, Result<RestoreOutcome, String>),
+    ToggleBookFinishedToday(ID),
+    ToggleBookBoughtToday(ID),
+    BookQuickUpdated(Result<BookModel, String>),
+    BookRatingChanged(ID, Option<i32>), // None clears the rating
+    BookRatingSaved(Result<BookModel, String>),
+    BookRowClicked(ID),
+    StepSearchMatch(i32), // 1 for the next match, -1 for the previous one
+    BookHoverStarted(ID),
+    BookHoverEnded(ID),
+    HoverTick, // Shows the hover card once the cursor rested long enough
+    HoverCursorMoved(Point),
+    WindowResized(Size),
+    ChangeBooksPerRow(i32), // Step the grid column count up or down
+    ToggleBookSelection(ID),
+    ClearBookSelection,
+    MoveTargetChanged(String),
+    MoveSelectedBooks,
+    MovePlanned(Result<MovePlan, String>),
+    ResolveMoveAuthor(usize, AuthorResolution),
+
+    // Bulk Bought Date Messages
+    OpenBoughtDateDialog,
+    CloseBoughtDateDialog,
+    BoughtDateTargetSelected(BoughtDateTarget),
+    BoughtDateFromChanged(String),
+    BoughtDateToChanged(String),
+    BoughtDateInputChanged(String),
+    PreviewBoughtDate,
+    BoughtDatePlanned(Result<BoughtDatePlan, String>),
+    ToggleBoughtDateOverwrite(bool),
+    ApplyBoughtDate,
+    CancelBoughtDate,
+    BoughtDateApplied(Result<BoughtDateBackup, String>),
+    UndoBoughtDate,
+    BoughtDateUndone(Result<usize, String>),
+
+    // CSV Import Messages
+    OpenCsvImport,
+    CloseCsvImport,
+    CsvImportPathChanged(String),
+    ReadCsvImport,
+    CsvImportRead(Result<CsvTable, String>),
+    CsvImportFieldSelected(usize, ImportField), // Column index and its new field
+    CsvImportPresetNameChanged(String),
+    SaveCsvImportPreset,
+    CsvImportPresetSelected(String),
+    DeleteCsvImportPreset(String),
+    RunCsvImport,
+    CsvImportPlanned(Result<ImportPlan, String>),
+    CsvImportDecisionSelected(usize, ImportDecision), // Index of the row in the plan
+    CsvImportDecideAll(ImportDecision),
+    ResolveCsvImportAuthor(usize, AuthorResolution), // Index of the author in the plan
+    ConfirmCsvImport,
+    CancelCsvImportConfirmation,
+    CsvImported(Result<ImportCounts, String>),
+    ApplyPendingMove,
+    CancelPendingMove,
+    BooksMoved(Result<usize, String>),
+    ClearBookHighlight,
 
     // Author Messages
     LoadAuthors,
@@ -44,19 +301,133 @@ pub enum Message {
     ViewAuthorMode,
     ViewAuthorDetails(AuthorModel),  // New message for viewing author details
     AuthorBooksLoaded(Result<Vec<BookWithAuthor>, String>),  // New message for loaded books
+    ToggleAuthorSection(SectionKey),
     AuthorNameChanged(String),
+    AuthorWebsiteChanged(String),
+    AuthorWikipediaChanged(String),
+    AuthorPublishedWorksChanged(String),
+    AuthorAliasesChanged(String),
+    AuthorSortSelected(AuthorSort),
+    OpenUrl(String),
     SaveAuthor,
     AuthorSaved(Result<AuthorModel, String>),
     ConfirmDeleteAuthor(ID, String), // New message for delete confirmation
     DeleteAuthor(ID),
     CancelDeleteAuthor, // New message for cancel deletion
-    AuthorDeleted(Result<usize, String>),
+    AuthorDeleted(Result<DeletedItem, String>),
+    FindOrphanAuthors,
+    ExportAuthors,
+    AuthorsExported(Result<String, String>),
+    JumpToAuthorLetter(char),
+    OrphanAuthorsLoaded(Result<Vec<AuthorModel>, String>),
+    ToggleOrphanAuthor(ID),
+    DeleteOrphanAuthors,
+    OrphanAuthorsDeleted(Result<usize, String>),
+    CloseOrphanAuthors,
+    FindMergeSuggestions,
+    MergeDismissalsLoaded(Result<Vec<(ID, ID)>, String>),
+    MergeKeepSelected((ID, ID), ID), // Suggested pair and the author to keep
+    AcceptMerge((ID, ID)),
+    AuthorsMerged(Result<(ID, ID, usize), String>), // Kept author, removed author, books moved
+    DismissMerge((ID, ID)),
+    MergeDismissed(Result<(ID, ID), String>),
+    ToggleMergeFoldDiacritics(bool),
+    CloseMergeSuggestions,
+
+    // Reading list Messages
+    LoadBookLists,
+    BookListsLoaded(Result<Vec<BookListModel>, String>),
+    NewBookListNameChanged(String),
+    CreateBookList,
+    BookListCreated(Result<BookListModel, String>),
+    OpenBookList(BookListModel),
+    CloseBookList,
+    BookListItemsLoaded(ID, Result<Vec<ID>, String>), // List id, its books in order
+    ToggleBookMenu(ID),
+    AddBookToList(ID, ID), // List id, book id
+    MoveBookInList(ID, bool), // Book id, true moves it one place earlier
+    RemoveBookFromList(ID),
+    BookListRenameChanged(String),
+    RenameBookList,
+    DeleteBookList,
+
+    // Series Messages
+    LoadSeries,
+    SeriesLoaded(Result<Vec<SeriesModel>, String>),
+    AddSeriesMode,
+    EditSeriesMode(SeriesModel),
+    ViewSeriesMode,
+    SeriesNameChanged(String),
+    SeriesTotalChanged(String),
+    SaveSeries,
+    SeriesSaved(Result<SeriesModel, String>),
+    ConfirmDeleteSeries(ID, String),
+    DeleteSeries(ID),
+    CancelDeleteSeries,
+    SeriesDeleted(Result<usize, String>),
+
+    // Stats Messages
+    StatsYearSelected(i32),
+    CopyYearInReview,
+    ExportYearInReviewImage,
+    YearInReviewScreenshot(Screenshot),
+    YearInReviewExported(Result<String, String>),
+    ReportFormatSelected(ReportFormat),
+    ReportPathChanged(String),
+    CsvDateFormatSelected(CsvDateFormat),
+    CsvDecimalSeparatorSelected(DecimalSeparator),
+    CsvQuotingSelected(CsvQuoting),
+    ToggleCsvByteOrderMark(bool),
+    ExportReport,
+    ReportExported(Result<String, String>),
+    StaleUnreadDaysSelected(i64),
+    ExportValueReport,
+    ValueReportExported(Result<String, String>),
+    OpenValueReport,
+    ExportCatalog(bool), // Only the selected books
+    CatalogExported(Result<String, String>),
+    OpenShelfExport,
+    CloseShelfExport,
+    ShelfGroupingSelected(ShelfGrouping),
+    ToggleShelfPrices(bool),
+    ShelfPathChanged(String),
+    ExportShelf,
+    ShelfExported(Result<String, String>),
+
+    // Settings Messages
+    IncreaseFontScale,
+    DecreaseFontScale,
+    ResetFontScale,
+    ToggleDarkMode,
+    TaxRateChanged(String),
+    TogglePricesIncludeTax(bool),
+    ToggleIdleRefresh(bool),
+    IdleRefreshMinutesSelected(u64),
+    ImportAuthorLimitSelected(usize),
+    ToggleDesktopNotifications(bool),
+    ToggleNightlyMaintenance(bool),
+    MaintenanceHourSelected(u32),
+    MaintenanceBackupsKeptSelected(usize),
+    IdleRefresh,
+    ConfigSaved(Result<(), String>),
+
+    // Nightly Maintenance Messages
+    MaintenanceCheck,
+    MaintenanceFinished(MaintenanceReport),
+    DismissOvernightIssues,
 
     // Searchable Dropdown Messages
     ToggleAuthorDropdown,
+    AuthorDropdownShortcut, // Alt+A in the book form
     AuthorSearchChanged(String),
 
+    Toast(ToastMessage),
     Initialize,
+    OpenReadOnly, // Another instance holds the database
+    QuitApp,
+    RequestExit, // Window close, asks first when there is unsaved work
+    ConfirmExit,
+    CancelExit,
     Error(String),
 }
 
@@ -64,7 +435,7 @@ pub enum Message {
 #[derive(Debug, Clone)]
 pub enum Mode {
     View,
-    ViewDetails,  // Mode for viewing author details
+    ViewDetails,  // Mode for viewing author or book details
     Add,
     Edit,
     ConfirmDelete(ID, String), // ID and name of item to delete
@@ -75,6 +446,9 @@ pub enum Mode {
 pub enum Tab {
     Books,
     Authors,
+    Series,
+    Stats,
+    Settings,
 }
 
 impl fmt::Display for Tab {
@@ -82,12 +456,361 @@ impl fmt::Display for Tab {
         match self {
             Tab::Books => write!(f, "Books"),
             Tab::Authors => write!(f, "Authors"),
+            Tab::Series => write!(f, "Series"),
+            Tab::Stats => write!(f, "Stats"),
+            Tab::Settings => write!(f, "Settings"),
         }
     }
 }
 
-/// Defines the available sort fields
+/// Defines how the book list is laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListMode {
+    #[default]
+    List,
+    Grid,
+    Table,
+    Shelf,
+}
+
+impl ListMode {
+    pub const ALL: [ListMode; 4] =
+        [ListMode::List, ListMode::Grid, ListMode::Table, ListMode::Shelf];
+}
+
+impl fmt::Display for ListMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListMode::List => write!(f, "List"),
+            ListMode::Grid => write!(f, "Grid"),
+            ListMode::Table => write!(f, "Table"),
+            ListMode::Shelf => write!(f, "Shelf"),
+        }
+    }
+}
+
+/// Defines the order of the author list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuthorSort {
+    #[default]
+    Name,
+    Completion, // Most of the published works owned first
+}
+
+impl AuthorSort {
+    pub const ALL: [AuthorSort; 2] = [AuthorSort::Name, AuthorSort::Completion];
+}
+
+impl fmt::Display for AuthorSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthorSort::Name => write!(f, "Name"),
+            AuthorSort::Completion => write!(f, "Completion %"),
+        }
+    }
+}
+
+/// Book fields the search box can look at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Title,
+    Author,
+    Price,
+    Giver,
+    AcquiredFrom,
+    Genre,
+}
+
+impl SearchField {
+    pub const ALL: [SearchField; 6] = [
+        SearchField::Title,
+        SearchField::Author,
+        SearchField::Price,
+        SearchField::Giver,
+        SearchField::AcquiredFrom,
+        SearchField::Genre,
+    ];
+}
+
+impl fmt::Display for SearchField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchField::Title => write!(f, "Title"),
+            SearchField::Author => write!(f, "Author"),
+            SearchField::Price => write!(f, "Price"),
+            SearchField::Giver => write!(f, "Gift from"),
+            SearchField::AcquiredFrom => write!(f, "Acquired from"),
+            SearchField::Genre => write!(f, "Genre"),
+        }
+    }
+}
+
+/// Where a book was acquired, as typed in the book form
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcquisitionPlace(pub String);
+
+impl fmt::Display for AcquisitionPlace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Book form values carried over to the next book by "Save and add another"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StickyField {
+    Author,
+    Series,
+    BoughtDate,
+    Format,
+    Language,
+    AcquiredFrom,
+}
+
+impl StickyField {
+    pub const ALL: [StickyField; 6] = [
+        StickyField::Author,
+        StickyField::Series,
+        StickyField::BoughtDate,
+        StickyField::Format,
+        StickyField::Language,
+        StickyField::AcquiredFrom,
+    ];
+}
+
+impl fmt::Display for StickyField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StickyField::Author => write!(f, "Author"),
+            StickyField::Series => write!(f, "Series"),
+            StickyField::BoughtDate => write!(f, "Bought date"),
+            StickyField::Format => write!(f, "Format"),
+            StickyField::Language => write!(f, "Language"),
+            StickyField::AcquiredFrom => write!(f, "Acquired from"),
+        }
+    }
+}
+
+/// Which form values "Save and add another" keeps, the ones a stack of new purchases
+/// usually shares by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StickyFields {
+    pub author: bool,
+    pub series: bool,
+    pub bought_date: bool,
+    pub format: bool,
+    pub language: bool,
+    pub acquired_from: bool,
+}
+
+impl Default for StickyFields {
+    fn default() -> Self {
+        Self {
+            author: true,
+            series: false,
+            bought_date: true,
+            format: true,
+            language: false,
+            acquired_from: true,
+        }
+    }
+}
+
+impl StickyFields {
+    pub fn contains(self, field: StickyField) -> bool {
+        match field {
+            StickyField::Author => self.author,
+            StickyField::Series => self.series,
+            StickyField::BoughtDate => self.bought_date,
+            StickyField::Format => self.format,
+            StickyField::Language => self.language,
+            StickyField::AcquiredFrom => self.acquired_from,
+        }
+    }
+
+    pub fn set(&mut self, field: StickyField, enabled: bool) {
+        match field {
+            StickyField::Author => self.author = enabled,
+            StickyField::Series => self.series = enabled,
+            StickyField::BoughtDate => self.bought_date = enabled,
+            StickyField::Format => self.format = enabled,
+            StickyField::Language => self.language = enabled,
+            StickyField::AcquiredFrom => self.acquired_from = enabled,
+        }
+    }
+}
+
+/// Presets listing books that lack a value, to fill them in quickly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingField {
+    Author,
+    Price,
+    BoughtDate,
+}
+
+impl MissingField {
+    pub const ALL: [MissingField; 3] = [
+        MissingField::Author,
+        MissingField::Price,
+        MissingField::BoughtDate,
+    ];
+}
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissingField::Author => write!(f, "author"),
+            MissingField::Price => write!(f, "price"),
+            MissingField::BoughtDate => write!(f, "bought date"),
+        }
+    }
+}
+
+/// Book date the year filter looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YearField {
+    #[default]
+    Added,
+    Bought,
+    Finished,
+}
+
+impl YearField {
+    pub const ALL: [YearField; 3] = [YearField::Added, YearField::Bought, YearField::Finished];
+}
+
+impl fmt::Display for YearField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YearField::Added => write!(f, "Added"),
+            YearField::Bought => write!(f, "Bought"),
+            YearField::Finished => write!(f, "Finished"),
+        }
+    }
+}
+
+/// Entry of the year filter list, All keeps books from every year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YearChoice {
+    All,
+    Year(i32),
+}
+
+impl fmt::Display for YearChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YearChoice::All => write!(f, "All years"),
+            YearChoice::Year(year) => write!(f, "{}", year),
+        }
+    }
+}
+
+/// Problems a review session walks through, one book at a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataIssue {
+    MissingAuthor,
+    MissingPrice,
+    UntidyTitle,
+    SuspiciousDates,
+}
+
+impl DataIssue {
+    pub const ALL: [DataIssue; 4] = [
+        DataIssue::MissingAuthor,
+        DataIssue::MissingPrice,
+        DataIssue::UntidyTitle,
+        DataIssue::SuspiciousDates,
+    ];
+}
+
+impl fmt::Display for DataIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataIssue::MissingAuthor => write!(f, "missing author"),
+            DataIssue::MissingPrice => write!(f, "missing price"),
+            DataIssue::UntidyTitle => write!(f, "untidy title"),
+            DataIssue::SuspiciousDates => write!(f, "suspicious dates"),
+        }
+    }
+}
+
+/// Books the bulk bought date dialog works on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoughtDateTarget {
+    Selection,
+    #[default]
+    AddedBetween, // Books added in a date range that have no bought date yet
+}
+
+impl BoughtDateTarget {
+    pub const ALL: [BoughtDateTarget; 2] =
+        [BoughtDateTarget::Selection, BoughtDateTarget::AddedBetween];
+}
+
+impl fmt::Display for BoughtDateTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoughtDateTarget::Selection => write!(f, "Selected books"),
+            BoughtDateTarget::AddedBetween => write!(f, "Books added between"),
+        }
+    }
+}
+
+/// Which fields a search matches against, all of them by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFields {
+    pub title: bool,
+    pub author: bool,
+    pub price: bool,
+    pub giver: bool,
+    pub acquired_from: bool,
+    pub genre: bool,
+}
+
+impl Default for SearchFields {
+    fn default() -> Self {
+        Self {
+            title: true,
+            author: true,
+            price: true,
+            giver: true,
+            acquired_from: true,
+            genre: true,
+        }
+    }
+}
+
+impl SearchFields {
+    pub fn contains(self, field: SearchField) -> bool {
+        match field {
+            SearchField::Title => self.title,
+            SearchField::Author => self.author,
+            SearchField::Price => self.price,
+            SearchField::Giver => self.giver,
+            SearchField::AcquiredFrom => self.acquired_from,
+            SearchField::Genre => self.genre,
+        }
+    }
+
+    pub fn set(&mut self, field: SearchField, enabled: bool) {
+        match field {
+            SearchField::Title => self.title = enabled,
+            SearchField::Author => self.author = enabled,
+            SearchField::Price => self.price = enabled,
+            SearchField::Giver => self.giver = enabled,
+            SearchField::AcquiredFrom => self.acquired_from = enabled,
+            SearchField::Genre => self.genre = enabled,
+        }
+    }
+
+    pub fn enabled_count(self) -> usize {
+        SearchField::ALL.iter().filter(|field| self.contains(**field)).count()
+    }
+}
+
+/// Defines the available sort fields
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortField {
     Title,
     Author,
@@ -106,6 +829,49 @@ impl fmt::Display for SortField {
     }
 }
 
+impl SortField {
+    pub const ALL: [SortField; 4] = [
+        SortField::Title,
+        SortField::Author,
+        SortField::Price,
+        SortField::DateAdded,
+    ];
+}
+
+/// Entry of the "then by…" pick list, None leaves ties to the title and id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThenBy(pub Option<SortField>);
+
+impl fmt::Display for ThenBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(field) => write!(f, "then by {}", field),
+            None => write!(f, "then by…"),
+        }
+    }
+}
+
+/// Where books without the sorted value go, the same for both directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EmptyValues {
+    First,
+    #[default]
+    Last,
+}
+
+impl EmptyValues {
+    pub const ALL: [EmptyValues; 2] = [EmptyValues::First, EmptyValues::Last];
+}
+
+impl fmt::Display for EmptyValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmptyValues::First => write!(f, "First"),
+            EmptyValues::Last => write!(f, "Last"),
+        }
+    }
+}
+
 /// Defines the sort directions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SortDirection {
@@ -0,0 +1,213 @@
+// src/ui/missing_view.rs
+use crate::db;
+use crate::models::{BookModel, NewBook, ID};
+use crate::ui::components::searchable_dropdown::{self, SearchableDropdown};
+use crate::ui::{book_is_missing, BookshelfApp, DataIssue, Message, MissingField, Mode, Tab};
+use iced::widget::{button, column, pick_list, row, text, Row};
+use iced::{Element, Length};
+
+/// Last author handed out from the assign bar, kept so it can be taken back
+#[derive(Debug, Clone)]
+pub struct AuthorAssignment {
+    pub book_id: ID,
+    pub title: String,
+    pub previous_author: Option<ID>,
+}
+
+/// Clicking a book assigns the picked author while the list shows books without one
+pub fn is_assigning_authors(app: &BookshelfApp) -> bool {
+    app.missing_filter == Some(MissingField::Author)
+        && matches!(app.current_tab, Tab::Books)
+        && matches!(app.mode, Mode::View)
+}
+
+// Handler functions for the missing value presets
+pub fn handle_toggle_missing_filter(
+    app: &mut BookshelfApp,
+    field: MissingField,
+) -> iced::Task<Message> {
+    app.missing_filter = (app.missing_filter != Some(field)).then_some(field);
+    app.focused_row = None;
+    app.last_author_assignment = None;
+    app.apply_filters();
+
+    let mut tasks = vec![app.update(Message::ApplySorting)];
+    if app.missing_filter == Some(MissingField::Author) {
+        // Start without an author, a pick left over from the book form must not be handed out
+        app.selected_author = None;
+        app.author_dropdown = SearchableDropdown::new(app.authors.clone(), None);
+        if app.authors.is_empty() && !app.authors_loading {
+            tasks.push(app.update(Message::LoadAuthors));
+        }
+    }
+    iced::Task::batch(tasks)
+}
+
+pub fn handle_assign_author_to_book(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    let Some(author) = app.selected_author.as_ref() else {
+        app.status_message =
+            Some("Pick an author above, then click the books to give it to".into());
+        return iced::Task::none();
+    };
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == id) else {
+        return iced::Task::none();
+    };
+
+    let previous_author = pair.book.AuthorFK;
+    let mut book = NewBook::from(&pair.book);
+    book.AuthorFK = Some(author.Id);
+
+    iced::Task::perform(
+        async move {
+            match db::update_book(id, &book) {
+                Ok(updated) => Ok((updated, previous_author)),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::AuthorAssigned,
+    )
+}
+
+pub fn handle_author_assigned(
+    app: &mut BookshelfApp,
+    result: Result<(BookModel, Option<ID>), String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok((updated, previous_author)) => {
+            app.status_message = Some(format!("Assigned an author to '{}'", updated.title));
+            app.last_author_assignment = Some(AuthorAssignment {
+                book_id: updated.id,
+                title: updated.title.clone(),
+                previous_author,
+            });
+            replace_book(app, updated)
+        }
+        Err(e) => {
+            app.report_error(e);
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_undo_author_assignment(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(assignment) = app.last_author_assignment.take() else {
+        return iced::Task::none();
+    };
+    let Some(pair) = app
+        .books
+        .iter()
+        .find(|pair| pair.book.id == assignment.book_id)
+    else {
+        return iced::Task::none();
+    };
+
+    let mut book = NewBook::from(&pair.book);
+    book.AuthorFK = assignment.previous_author;
+
+    iced::Task::perform(
+        async move {
+            match db::update_book(assignment.book_id, &book) {
+                Ok(updated) => Ok(updated),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::AuthorAssignmentUndone,
+    )
+}
+
+pub fn handle_author_assignment_undone(
+    app: &mut BookshelfApp,
+    result: Result<BookModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(updated) => {
+            app.status_message = Some(format!("Took the author back from '{}'", updated.title));
+            replace_book(app, updated)
+        }
+        Err(e) => {
+            app.report_error(e);
+            iced::Task::none()
+        }
+    }
+}
+
+// Swaps in the updated book without reloading, the preset then drops or brings it back
+fn replace_book(app: &mut BookshelfApp, updated: BookModel) -> iced::Task<Message> {
+    let author = updated
+        .AuthorFK
+        .and_then(|id| app.authors.iter().find(|author| author.Id == id))
+        .cloned();
+    if let Some(pair) = app.books.iter_mut().find(|pair| pair.book.id == updated.id) {
+        pair.book = updated;
+        pair.author = author;
+    }
+
+    app.apply_filters();
+    app.update(Message::ApplySorting)
+}
+
+// View functions for the missing value presets
+pub fn create_missing_filters(app: &BookshelfApp) -> Row<'_, Message> {
+    let fonts = app.fonts();
+
+    let presets = MissingField::ALL.into_iter().fold(
+        row![text("Missing:").size(fonts.small())]
+            .spacing(5)
+            .align_y(iced::Alignment::Center),
+        |presets, field| {
+            let count = app
+                .books
+                .iter()
+                .filter(|pair| book_is_missing(pair, field))
+                .count();
+            presets.push(
+                button(text(format!("{} ({})", field, count)).size(fonts.small()))
+                    .on_press(Message::ToggleMissingFilter(field))
+                    .style(if app.missing_filter == Some(field) {
+                        button::primary
+                    } else {
+                        button::secondary
+                    })
+                    .padding(6),
+            )
+        },
+    );
+    presets.push(
+        pick_list(DataIssue::ALL, None::<DataIssue>, Message::StartReview)
+            .placeholder("Review…")
+            .text_size(fonts.small())
+            .padding(6),
+    )
+}
+
+pub fn create_assign_bar(app: &BookshelfApp) -> Element<'_, Message> {
+    if !is_assigning_authors(app) {
+        return column![].into();
+    }
+
+    let fonts = app.fonts();
+    let mut bar = row![
+        text("Click a book to give it this author:").size(fonts.small()),
+        column![searchable_dropdown::view_dropdown(
+            &app.author_dropdown,
+            Message::ToggleAuthorDropdown,
+            Message::AuthorSearchChanged,
+            Message::BookAuthorSelected,
+            fonts,
+        )]
+        .width(Length::Fill),
+    ]
+    .spacing(10)
+    .padding([0, 15])
+    .align_y(iced::Alignment::Center);
+
+    if let Some(assignment) = &app.last_author_assignment {
+        bar = bar.push(
+            button(text(format!("Undo '{}'", assignment.title)).size(fonts.small()))
+                .on_press(Message::UndoAuthorAssignment)
+                .style(button::secondary)
+                .padding(8),
+        );
+    }
+    bar.into()
+}
@@ -1,16 +1,51 @@
 mod author_view;
+mod backup_view;
 mod book_view;
+mod bought_date_view;
+mod changes_view;
 mod common;
+mod console_view;
+mod cover_view;
+mod custom_field_view;
+mod draft_view;
+mod hover_view;
+mod import_view;
+mod instance_view;
+mod journal_view;
+mod keymap;
+mod list_view;
+mod loan_view;
+mod maintenance_view;
 mod messages;
+mod missing_view;
+mod palette;
+mod print_view;
+mod quote_view;
+mod review_view;
+mod series_view;
+mod session_view;
+mod settings_view;
+mod shelf_view;
+mod split_view;
 mod state;
+mod stats_view;
+mod table_view;
+mod trash_view;
 mod utils;
 mod variables;
 
 pub mod components {
+    pub mod author_resolution;
+    pub mod bulk_preview;
+    pub mod cursor_tracker;
+    pub mod modal;
     pub mod searchable_dropdown;
+    pub mod toasts;
 }
 
+pub use changes_view::{diff_book, BookField};
 pub use messages::*;
+pub use palette::CommandPalette;
 pub use state::*;
 pub use utils::*;
 pub use variables::*;
@@ -0,0 +1,215 @@
+// src/ui/palette.rs
+use crate::fuzzy::fuzzy_filter;
+use crate::ui::{BookshelfApp, Message, Tab};
+use iced::event::{self, Event};
+use iced::keyboard::{self, key};
+use iced::widget::{button, column, container, text, text_input};
+use iced::{window, Element, Length};
+use std::rc::Rc;
+
+const MAX_VISIBLE_ENTRIES: usize = 10;
+
+/// A single action offered by the command palette
+pub struct PaletteEntry {
+    pub label: String,
+    pub message_factory: Rc<dyn Fn() -> Message>,
+}
+
+impl PaletteEntry {
+    fn new(label: impl Into<String>, message_factory: impl Fn() -> Message + 'static) -> Self {
+        Self {
+            label: label.into(),
+            message_factory: Rc::new(message_factory),
+        }
+    }
+}
+
+impl AsRef<str> for PaletteEntry {
+    fn as_ref(&self) -> &str {
+        &self.label
+    }
+}
+
+// State of the open command palette
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected: usize,
+}
+
+pub fn palette_input_id() -> text_input::Id {
+    text_input::Id::new("command_palette")
+}
+
+/// Every command available from the current state, static actions first
+pub fn build_registry(app: &BookshelfApp) -> Vec<PaletteEntry> {
+    let mut entries = vec![
+        PaletteEntry::new("Add book", || {
+            Message::RunInTab(Tab::Books, Box::new(Message::AddBookMode))
+        }),
+        PaletteEntry::new("Add author", || {
+            Message::RunInTab(Tab::Authors, Box::new(Message::AddAuthorMode))
+        }),
+        PaletteEntry::new("Add series", || {
+            Message::RunInTab(Tab::Series, Box::new(Message::AddSeriesMode))
+        }),
+        PaletteEntry::new("Go to Books", || Message::TabSelected(Tab::Books)),
+        PaletteEntry::new("Go to Authors", || Message::TabSelected(Tab::Authors)),
+        PaletteEntry::new("Go to Series", || Message::TabSelected(Tab::Series)),
+        PaletteEntry::new("Go to Stats", || Message::TabSelected(Tab::Stats)),
+        PaletteEntry::new("Go to Settings", || Message::TabSelected(Tab::Settings)),
+        PaletteEntry::new("Toggle dark mode", || Message::ToggleDarkMode),
+        PaletteEntry::new("Toggle hidden prices", || Message::TogglePrivacyMode),
+        PaletteEntry::new("Print view", || Message::TogglePrintView),
+    ];
+
+    for pair in &app.books {
+        let pair = pair.clone();
+        entries.push(PaletteEntry::new(
+            format!("Open book: {}", pair.book.title),
+            move || Message::RunInTab(Tab::Books, Box::new(Message::EditBookMode(pair.clone()))),
+        ));
+    }
+
+    for author in &app.authors {
+        let author = author.clone();
+        // Pen names are part of the label so the fuzzy filter finds them
+        let label = match author.Aliases.as_deref().filter(|aliases| !aliases.is_empty()) {
+            Some(aliases) => format!("Open author: {} (also {})", author, aliases),
+            None => format!("Open author: {}", author),
+        };
+        entries.push(PaletteEntry::new(
+            label,
+            move || {
+                Message::RunInTab(
+                    Tab::Authors,
+                    Box::new(Message::ViewAuthorDetails(author.clone())),
+                )
+            },
+        ));
+    }
+
+    entries
+}
+
+/// Registry entries matching the query, best matches first
+pub fn matching_entries(app: &BookshelfApp, query: &str) -> Vec<PaletteEntry> {
+    let registry = build_registry(app);
+    let order = fuzzy_filter(query, &registry);
+    let mut registry: Vec<Option<PaletteEntry>> = registry.into_iter().map(Some).collect();
+
+    order
+        .into_iter()
+        .take(MAX_VISIBLE_ENTRIES)
+        .filter_map(|index| registry[index].take())
+        .collect()
+}
+
+// Keys handled while the palette is open, including ones the search input captures
+pub fn on_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => match key {
+            keyboard::Key::Named(key::Named::Escape) => Some(Message::CloseCommandPalette),
+            keyboard::Key::Named(key::Named::ArrowUp) => Some(Message::PaletteMoveSelection(-1)),
+            keyboard::Key::Named(key::Named::ArrowDown) => Some(Message::PaletteMoveSelection(1)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Handler functions for palette-related messages
+pub fn handle_open_palette(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.palette = Some(CommandPalette::default());
+
+    // Authors are loaded lazily, make sure they can be found from any tab
+    let mut tasks = vec![text_input::focus(palette_input_id())];
+    if app.authors.is_empty() && !app.authors_loading {
+        tasks.push(app.update(Message::LoadAuthors));
+    }
+    iced::Task::batch(tasks)
+}
+
+pub fn handle_close_palette(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.palette = None;
+    iced::Task::none()
+}
+
+pub fn handle_palette_query_changed(app: &mut BookshelfApp, query: String) -> iced::Task<Message> {
+    if let Some(palette) = &mut app.palette {
+        palette.query = query;
+        palette.selected = 0;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_palette_move_selection(app: &mut BookshelfApp, delta: i32) -> iced::Task<Message> {
+    let Some(query) = app.palette.as_ref().map(|p| p.query.clone()) else {
+        return iced::Task::none();
+    };
+    let count = matching_entries(app, &query).len();
+
+    if let Some(palette) = &mut app.palette {
+        if count == 0 {
+            palette.selected = 0;
+        } else {
+            let selected = palette.selected as i32 + delta;
+            palette.selected = selected.clamp(0, count as i32 - 1) as usize;
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_palette_execute(app: &mut BookshelfApp, index: Option<usize>) -> iced::Task<Message> {
+    let Some(palette) = app.palette.take() else {
+        return iced::Task::none();
+    };
+
+    let entries = matching_entries(app, &palette.query);
+    match entries.get(index.unwrap_or(palette.selected)) {
+        Some(entry) => {
+            let message = (entry.message_factory)();
+            app.update(message)
+        }
+        None => iced::Task::none(),
+    }
+}
+
+// View function for the palette overlay
+pub fn view<'a>(app: &'a BookshelfApp, palette: &'a CommandPalette) -> Element<'a, Message> {
+    let fonts = app.fonts();
+
+    let input = text_input("Type a command, book or author...", &palette.query)
+        .id(palette_input_id())
+        .on_input(Message::PaletteQueryChanged)
+        .on_submit(Message::PaletteExecute(None))
+        .padding(10)
+        .size(fonts.body());
+
+    let entries = matching_entries(app, &palette.query);
+    let mut list = column![].spacing(2).width(Length::Fill);
+
+    if entries.is_empty() {
+        list = list.push(text("No matching commands").size(fonts.small()));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        list = list.push(
+            button(text(entry.label.clone()).size(fonts.small()))
+                .on_press(Message::PaletteExecute(Some(index)))
+                .padding(8)
+                .width(Length::Fill)
+                .style(if index == palette.selected {
+                    button::primary
+                } else {
+                    button::secondary
+                }),
+        );
+    }
+
+    container(column![input, list].spacing(10))
+        .padding(20)
+        .width(fonts.width(500.0))
+        .style(container::bordered_box)
+        .into()
+}
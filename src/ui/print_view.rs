@@ -0,0 +1,160 @@
+// src/ui/print_view.rs
+use crate::models::BookWithAuthor;
+use crate::paths;
+use crate::report;
+use crate::ui::{
+    describe_list_filters, format_price, BookshelfApp, ListFilters, Message, Mode, Tab, CURRENCY,
+};
+use chrono::Local;
+use iced::widget::{button, column, horizontal_space, row, scrollable, text};
+use iced::{Element, Length};
+
+/// Title, filter description and books of the list the print view shows, None when the
+/// current page has no list to print
+pub fn printable_list(app: &BookshelfApp) -> Option<(String, String, &[BookWithAuthor])> {
+    match (&app.current_tab, &app.mode) {
+        (Tab::Books, Mode::View) => Some((
+            "Books".to_string(),
+            describe_list_filters(
+                &ListFilters {
+                    query: &app.search_term_displayed,
+                    gifts_only: app.gifts_only,
+                    missing: app.missing_filter,
+                    language: app.language_filter.as_deref(),
+                    acquired_from: app.acquired_from_filter.as_deref(),
+                    year: app.year_filter.map(|year| (year, app.year_filter_field)),
+                },
+                &app.sort_field,
+                &app.sort_direction,
+            ),
+            app.displayed_books().as_slice(),
+        )),
+        (Tab::Authors, Mode::ViewDetails) => {
+            let name = app
+                .current_author
+                .as_ref()?
+                .Name
+                .clone()
+                .unwrap_or_else(|| "Unnamed Author".to_string());
+            Some((
+                format!("Books by {}", name),
+                format!("All books by {}", name),
+                app.author_books.as_slice(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+// Handler functions for the print view
+pub fn handle_toggle_print_view(app: &mut BookshelfApp) -> iced::Task<Message> {
+    // Only a flag over the current state, leaving shows the list exactly as it was
+    if app.print_view {
+        app.print_view = false;
+    } else if printable_list(app).is_some() {
+        app.print_view = true;
+    } else {
+        app.status_message = Some("Open the book list or an author to print it".to_string());
+    }
+    iced::Task::none()
+}
+
+pub fn handle_open_print_page(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some((title, description, books)) = printable_list(app) else {
+        return iced::Task::none();
+    };
+    let html = report::books_print_html(
+        &title,
+        &description,
+        books,
+        Local::now().naive_local(),
+        CURRENCY,
+    );
+    let path = paths::exports_dir().join("print.html");
+
+    iced::Task::perform(
+        async move {
+            report::write_html(&path, &html).map_err(|e| e.to_string())?;
+            open::that_detached(&path).map_err(|e| e.to_string())?;
+            Ok(path.display().to_string())
+        },
+        Message::PrintPageOpened,
+    )
+}
+
+pub fn handle_print_page_opened(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => app.status_message = Some(format!("Opened {} for printing", path)),
+        Err(e) => app.report_error(format!("Failed to open the printable page: {}", e)),
+    }
+    iced::Task::none()
+}
+
+// View functions for the print view
+pub fn view(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let Some((title, description, books)) = printable_list(app) else {
+        return column![].into();
+    };
+    let date = |value: Option<chrono::NaiveDateTime>| {
+        value.map_or_else(String::new, |d| d.format("%Y-%m-%d").to_string())
+    };
+
+    let cell = |value: String, portion: u16| {
+        text(value)
+            .size(fonts.small())
+            .width(Length::FillPortion(portion))
+    };
+
+    // Plain text rows without boxes or buttons, as dense as the list gets
+    let mut rows = column![].spacing(2);
+    for pair in books {
+        let author = pair
+            .author
+            .as_ref()
+            .and_then(|author| author.Name.clone())
+            .unwrap_or_default();
+        let price = pair.book.price.map_or_else(String::new, |price| {
+            format_price(price, CURRENCY, app.privacy_mode)
+        });
+        rows = rows.push(
+            row![
+                cell(pair.book.title.clone(), 4),
+                cell(author, 3),
+                cell(price, 1),
+                cell(date(pair.book.bought), 1),
+                cell(date(pair.book.finished), 1),
+            ]
+            .spacing(10),
+        );
+    }
+
+    column![
+        row![
+            text(title).size(fonts.heading()),
+            horizontal_space(),
+            button("Open in browser to print")
+                .on_press(Message::OpenPrintPage)
+                .style(button::text),
+            button("Back")
+                .on_press(Message::TogglePrintView)
+                .style(button::text),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        text(format!(
+            "{} · {} books · {}",
+            description,
+            books.len(),
+            Local::now().format("%Y-%m-%d")
+        ))
+        .size(fonts.small()),
+        scrollable(rows).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(20)
+    .into()
+}
@@ -0,0 +1,431 @@
+// src/ui/quote_view.rs
+use crate::db;
+use crate::models::{NewQuote, QuoteModel, ID};
+use crate::quotes;
+use crate::ui::{BookshelfApp, FontSizes, Message};
+use chrono::Local;
+use iced::widget::{
+    button, column, container, horizontal_space, row, scrollable, text, text_editor, text_input,
+    Column, Row,
+};
+use iced::{Element, Length};
+use std::collections::HashMap;
+
+/// The all-quotes page of the statistics tab
+#[derive(Debug, Clone, Default)]
+pub struct QuoteBrowser {
+    pub quotes: Vec<QuoteModel>, // Newest first
+    pub search: String,
+}
+
+// Title and author name of the quoted book, as far as the loaded books know it
+fn attribution(app: &BookshelfApp, book_id: ID) -> (String, Option<String>) {
+    match app.books.iter().find(|pair| pair.book.id == book_id) {
+        Some(pair) => (
+            pair.book.title.clone(),
+            pair.author.as_ref().and_then(|author| author.Name.clone()),
+        ),
+        None => ("Unknown book".to_string(), None),
+    }
+}
+
+// Handler functions for quotes
+pub fn handle_load_quote_counts(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_quote_counts() {
+                Ok(counts) => Ok(counts),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::QuoteCountsLoaded,
+    )
+}
+
+pub fn handle_quote_counts_loaded(
+    app: &mut BookshelfApp,
+    result: Result<HashMap<ID, usize>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(counts) => app.quote_counts = counts,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_load_book_quotes(_: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::get_quotes(book_id) {
+                Ok(quotes) => Ok(quotes),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::BookQuotesLoaded,
+    )
+}
+
+pub fn handle_book_quotes_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<QuoteModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(quotes) => app.book_quotes = quotes,
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_quote_draft_action(
+    app: &mut BookshelfApp,
+    action: text_editor::Action,
+) -> iced::Task<Message> {
+    app.quote_draft.perform(action);
+    iced::Task::none()
+}
+
+pub fn handle_quote_page_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.quote_page = value;
+    iced::Task::none()
+}
+
+pub fn handle_save_quote(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(book_id) = app.selected_book.as_ref().map(|pair| pair.book.id) else {
+        return iced::Task::none();
+    };
+
+    let text = app.quote_draft.text().trim().to_string();
+    if text.is_empty() {
+        return iced::Task::none();
+    }
+    let page = match quotes::parse_page(&app.quote_page) {
+        Ok(page) => page,
+        Err(e) => {
+            app.report_error(e);
+            return iced::Task::none();
+        }
+    };
+
+    // An edited quote keeps the day it was noted
+    let editing = app.editing_quote;
+    let noted_at = editing
+        .and_then(|id| app.book_quotes.iter().find(|quote| quote.id == id))
+        .map_or_else(|| Local::now().naive_local(), |quote| quote.noted_at);
+    let quote = NewQuote {
+        book_fk: book_id,
+        page,
+        text,
+        noted_at,
+    };
+
+    iced::Task::perform(
+        async move {
+            let saved = match editing {
+                Some(id) => db::update_quote(id, &quote),
+                None => db::create_quote(&quote),
+            };
+            match saved {
+                Ok(saved) => Ok(saved),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::QuoteSaved,
+    )
+}
+
+pub fn handle_quote_saved(
+    app: &mut BookshelfApp,
+    result: Result<QuoteModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(quote) => {
+            // Only clear the form once the quote is safely stored
+            reset_form(app);
+            match app.book_quotes.iter_mut().find(|old| old.id == quote.id) {
+                Some(old) => *old = quote,
+                None => {
+                    *app.quote_counts.entry(quote.book_fk).or_default() += 1;
+                    app.book_quotes.insert(0, quote);
+                }
+            }
+        }
+        Err(e) => app.report_error(format!("Failed to save quote: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_edit_quote(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    if let Some(quote) = app.book_quotes.iter().find(|quote| quote.id == id) {
+        app.quote_draft = text_editor::Content::with_text(&quote.text);
+        app.quote_page = quote.page.map_or_else(String::new, |page| page.to_string());
+        app.editing_quote = Some(id);
+    }
+    iced::Task::none()
+}
+
+pub fn handle_cancel_quote_edit(app: &mut BookshelfApp) -> iced::Task<Message> {
+    reset_form(app);
+    iced::Task::none()
+}
+
+fn reset_form(app: &mut BookshelfApp) {
+    app.quote_draft = text_editor::Content::new();
+    app.quote_page.clear();
+    app.editing_quote = None;
+}
+
+pub fn handle_delete_quote(_: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::delete_quote(id) {
+                Ok(_) => Ok(id),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::QuoteDeleted,
+    )
+}
+
+pub fn handle_quote_deleted(
+    app: &mut BookshelfApp,
+    result: Result<ID, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(id) => {
+            if let Some(quote) = app.book_quotes.iter().find(|quote| quote.id == id) {
+                if let Some(count) = app.quote_counts.get_mut(&quote.book_fk) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+            app.book_quotes.retain(|quote| quote.id != id);
+            if app.editing_quote == Some(id) {
+                reset_form(app);
+            }
+        }
+        Err(e) => app.report_error(format!("Failed to delete quote: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_copy_quote(app: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    let browsed = app.quote_browser.as_ref().map(|browser| &browser.quotes);
+    let Some(quote) = app
+        .book_quotes
+        .iter()
+        .chain(browsed.into_iter().flatten())
+        .find(|quote| quote.id == id)
+        .cloned()
+    else {
+        return iced::Task::none();
+    };
+    let (title, author) = attribution(app, quote.book_fk);
+    app.status_message = Some("Copied quote to clipboard".to_string());
+    iced::clipboard::write(quotes::format_citation(&quote, &title, author.as_deref()))
+}
+
+pub fn handle_open_browser(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.quote_browser = Some(QuoteBrowser::default());
+    iced::Task::perform(
+        async {
+            match db::get_all_quotes() {
+                Ok(quotes) => Ok(quotes),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::AllQuotesLoaded,
+    )
+}
+
+pub fn handle_all_quotes_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<QuoteModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(quotes) => {
+            if let Some(browser) = &mut app.quote_browser {
+                browser.quotes = quotes;
+            }
+        }
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_search_changed(app: &mut BookshelfApp, search: String) -> iced::Task<Message> {
+    if let Some(browser) = &mut app.quote_browser {
+        browser.search = search;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_close_browser(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.quote_browser = None;
+    iced::Task::none()
+}
+
+// View functions for quotes
+/// "3 quotes" next to a book in the list, nothing for a book without quotes
+pub fn create_quote_badge<'a>(app: &BookshelfApp, book_id: ID) -> Option<Element<'a, Message>> {
+    let count = app
+        .quote_counts
+        .get(&book_id)
+        .copied()
+        .filter(|count| *count > 0)?;
+    let label = if count == 1 {
+        "1 quote".to_string()
+    } else {
+        format!("{} quotes", count)
+    };
+    Some(
+        container(text(label).size(app.fonts().tiny()))
+            .padding([2, 8])
+            .style(container::rounded_box)
+            .into(),
+    )
+}
+
+pub fn create_quote_section(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let editing = app.editing_quote.is_some();
+
+    let mut buttons = row![button(if editing { "Save quote" } else { "Add quote" })
+        .on_press_maybe((!app.quote_draft.text().trim().is_empty()).then_some(Message::SaveQuote))
+        .style(button::secondary)]
+    .spacing(10);
+    if editing {
+        buttons = buttons.push(
+            button("Cancel")
+                .on_press(Message::CancelQuoteEdit)
+                .style(button::secondary),
+        );
+    }
+
+    let mut section = column![
+        text(format!("Quotes ({})", app.book_quotes.len())).size(fonts.heading()),
+        text_editor(&app.quote_draft)
+            .placeholder("A passage worth keeping...")
+            .on_action(Message::QuoteDraftAction)
+            .height(fonts.width(100.0))
+            .size(fonts.body()),
+        row![
+            text("Page:").size(fonts.body()),
+            text_input("optional", &app.quote_page)
+                .on_input(Message::QuotePageChanged)
+                .width(fonts.width(100.0))
+                .padding(6),
+            horizontal_space(),
+            buttons,
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    for quote in &app.book_quotes {
+        let actions = row![
+            button("Copy")
+                .on_press(Message::CopyQuote(quote.id))
+                .style(button::secondary)
+                .padding(6),
+            button("Edit")
+                .on_press(Message::EditQuote(quote.id))
+                .style(button::secondary)
+                .padding(6),
+            button("Delete")
+                .on_press(Message::DeleteQuote(quote.id))
+                .style(button::danger)
+                .padding(6),
+        ]
+        .spacing(5);
+        section = section.push(create_quote(quote, None, actions, fonts));
+    }
+
+    section
+}
+
+// One quote with its page and date, the browser adds where it is from
+fn create_quote<'a>(
+    quote: &QuoteModel,
+    source: Option<String>,
+    actions: Row<'a, Message>,
+    fonts: FontSizes,
+) -> Column<'a, Message> {
+    let mut noted = quote.noted_at.format("%Y-%m-%d").to_string();
+    if let Some(page) = quote.page {
+        noted = format!("p. {}, {}", page, noted);
+    }
+    let details = column![
+        text(quote.text.clone()).size(fonts.body()),
+        text(match source {
+            Some(source) => format!("— {}, {}", source, noted),
+            None => noted,
+        })
+        .size(fonts.tiny()),
+    ]
+    .spacing(5)
+    .width(Length::Fill);
+
+    column![container(
+        row![details, actions]
+            .spacing(10)
+            .align_y(iced::alignment::Vertical::Center)
+    )
+    .padding(10)
+    .style(container::bordered_box)]
+}
+
+pub fn create_browser<'a>(
+    app: &'a BookshelfApp,
+    browser: &'a QuoteBrowser,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let shown: Vec<&QuoteModel> = browser
+        .quotes
+        .iter()
+        .filter(|quote| quotes::matches_search(quote, &browser.search))
+        .collect();
+
+    let mut list = column![].spacing(10).width(Length::Fill);
+    if shown.is_empty() {
+        list = list.push(
+            text(if browser.quotes.is_empty() {
+                "No quotes yet, add them from a book's edit page"
+            } else {
+                "No quote matches the search"
+            })
+            .size(fonts.body()),
+        );
+    }
+    for quote in shown {
+        let (title, author) = attribution(app, quote.book_fk);
+        let source = match author {
+            Some(author) => format!("{}, {}", author, title),
+            None => title,
+        };
+        let actions = row![button("Copy")
+            .on_press(Message::CopyQuote(quote.id))
+            .style(button::secondary)
+            .padding(6)];
+        list = list.push(create_quote(quote, Some(source), actions, fonts));
+    }
+
+    column![
+        row![
+            text(format!("Quotes ({})", browser.quotes.len())).size(fonts.title()),
+            horizontal_space(),
+            button("Back to statistics")
+                .on_press(Message::CloseQuoteBrowser)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        text_input("Search quotes", &browser.search)
+            .on_input(Message::QuoteSearchChanged)
+            .padding(10),
+        scrollable(list).height(Length::Fill),
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
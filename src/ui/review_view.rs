@@ -0,0 +1,416 @@
+// src/ui/review_view.rs
+use crate::models::{BookModel, BookWithAuthor, ID};
+use crate::ui::{book_has_issue, book_view, draft_view, BookshelfApp, DataIssue, Message};
+use chrono::{Local, NaiveDateTime};
+use iced::widget::{button, column, container, progress_bar, row, scrollable, text, Column, Row};
+use iced::{Element, Length};
+
+/// Books sharing one data issue, opened one after another in the edit form
+#[derive(Debug, Clone)]
+pub struct ReviewQueue {
+    pub issue: DataIssue,
+    pub ids: Vec<ID>,    // Books still to review, in list order
+    pub position: usize, // Index of the book in the form, ids.len() once past the last one
+    pub total: usize,    // Books queued when the review started
+    pub fixed: usize,    // Books that lost the issue, here or elsewhere
+}
+
+impl ReviewQueue {
+    pub fn new(issue: DataIssue, books: &[BookWithAuthor], now: NaiveDateTime) -> Self {
+        let ids: Vec<ID> = books
+            .iter()
+            .filter(|pair| book_has_issue(pair, issue, now))
+            .map(|pair| pair.book.id)
+            .collect();
+        Self {
+            issue,
+            total: ids.len(),
+            ids,
+            position: 0,
+            fixed: 0,
+        }
+    }
+
+    pub fn current(&self) -> Option<ID> {
+        self.ids.get(self.position).copied()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.position >= self.ids.len()
+    }
+
+    /// Moves on and leaves the current book queued
+    pub fn skip(&mut self) {
+        self.position = (self.position + 1).min(self.ids.len());
+    }
+
+    pub fn previous(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.position = index.min(self.ids.len());
+    }
+
+    /// Drops a fixed book from the queue, the book after it takes its place
+    pub fn mark_fixed(&mut self, id: ID) {
+        let Some(index) = self.ids.iter().position(|queued| *queued == id) else {
+            return;
+        };
+        self.ids.remove(index);
+        self.fixed += 1;
+        if index < self.position {
+            self.position -= 1;
+        }
+    }
+
+    /// Drops books deleted or fixed since they were queued, true when the current book
+    /// was one of them
+    pub fn refresh(&mut self, books: &[BookWithAuthor], now: NaiveDateTime) -> bool {
+        let current = self.current();
+        let stale: Vec<ID> = self
+            .ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                !books
+                    .iter()
+                    .any(|pair| pair.book.id == *id && book_has_issue(pair, self.issue, now))
+            })
+            .collect();
+        for id in &stale {
+            self.mark_fixed(*id);
+        }
+        current.is_some_and(|id| stale.contains(&id))
+    }
+
+    /// Books still showing the issue, the skipped ones included
+    pub fn remaining(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+// Handler functions for the review queue
+pub fn handle_start_review(app: &mut BookshelfApp, issue: DataIssue) -> iced::Task<Message> {
+    let queue = ReviewQueue::new(issue, &app.books, Local::now().naive_local());
+    if queue.is_done() {
+        app.status_message = Some(format!("No books with {}", issue));
+        return iced::Task::none();
+    }
+    app.review_queue = Some(queue);
+    open_current(app)
+}
+
+pub fn handle_review_skip(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some(queue) = &mut app.review_queue {
+        queue.skip();
+    }
+    open_current(app)
+}
+
+pub fn handle_review_previous(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some(queue) = &mut app.review_queue {
+        queue.previous();
+    }
+    open_current(app)
+}
+
+pub fn handle_review_select(app: &mut BookshelfApp, index: usize) -> iced::Task<Message> {
+    if let Some(queue) = &mut app.review_queue {
+        queue.select(index);
+    }
+    open_current(app)
+}
+
+/// Back to the list, with a summary of the session in the status bar
+pub fn finish_review(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(queue) = app.review_queue.take() else {
+        return iced::Task::none();
+    };
+    let task = book_view::handle_view_book_mode(app);
+    app.status_message = Some(format!(
+        "Review of {} done: {} of {} fixed, {} left as they were",
+        queue.issue,
+        queue.fixed,
+        queue.total,
+        queue.remaining()
+    ));
+    task
+}
+
+// Saving moves on to the next book, a book that still has the issue stays queued
+pub fn handle_review_saved(app: &mut BookshelfApp, saved: BookModel) -> iced::Task<Message> {
+    draft_view::clear_draft(app);
+    let author = saved
+        .AuthorFK
+        .and_then(|id| app.authors.iter().find(|author| author.Id == id))
+        .cloned();
+    let Some(pair) = app.books.iter_mut().find(|pair| pair.book.id == saved.id) else {
+        return open_current(app);
+    };
+    pair.book = saved;
+    pair.author = author;
+    let still_has_issue = app
+        .review_queue
+        .as_ref()
+        .is_some_and(|queue| book_has_issue(pair, queue.issue, Local::now().naive_local()));
+    let title = pair.book.title.clone();
+    let id = pair.book.id;
+
+    if let Some(queue) = &mut app.review_queue {
+        if still_has_issue {
+            queue.skip();
+        } else {
+            queue.mark_fixed(id);
+        }
+    }
+    let task = open_current(app);
+    if still_has_issue && app.review_queue.is_some() {
+        app.status_message = Some(format!("Saved '{}', it still needs a look", title));
+    }
+    iced::Task::batch(vec![task, app.update(Message::LoadBooks)])
+}
+
+/// Called once books are reloaded, books fixed elsewhere leave the queue
+pub fn refresh_review(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(queue) = &mut app.review_queue else {
+        return iced::Task::none();
+    };
+    if queue.refresh(&app.books, Local::now().naive_local()) {
+        return open_current(app);
+    }
+    iced::Task::none()
+}
+
+// Loads the current book into the form, or ends the review once every book was seen
+fn open_current(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let current = app.review_queue.as_ref().and_then(ReviewQueue::current);
+    let pair = current.and_then(|id| app.books.iter().find(|pair| pair.book.id == id).cloned());
+    match pair {
+        Some(pair) => book_view::handle_edit_book_mode(app, &pair),
+        None => finish_review(app),
+    }
+}
+
+// View functions for the review queue
+pub fn view<'a>(app: &'a BookshelfApp, queue: &'a ReviewQueue) -> Element<'a, Message> {
+    row![
+        container(create_queue_pane(app, queue)).width(Length::FillPortion(1)),
+        container(book_view::view_book_form(app)).width(Length::FillPortion(2)),
+    ]
+    .height(Length::Fill)
+    .into()
+}
+
+fn create_queue_pane<'a>(app: &'a BookshelfApp, queue: &'a ReviewQueue) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let books = queue
+        .ids
+        .iter()
+        .enumerate()
+        .fold(column![].spacing(5), |books, (index, id)| {
+            let title = app
+                .books
+                .iter()
+                .find(|pair| pair.book.id == *id)
+                .map_or("Unknown book", |pair| pair.book.title.as_str());
+            books.push(
+                button(text(title).size(fonts.small()))
+                    .on_press(Message::ReviewSelect(index))
+                    .style(if index == queue.position {
+                        button::primary
+                    } else {
+                        button::text
+                    })
+                    .width(Length::Fill)
+                    .padding(6),
+            )
+        });
+
+    column![
+        text(format!("Books with {}", queue.issue)).size(fonts.heading()),
+        text(format!("{} of {} fixed", queue.fixed, queue.total)).size(fonts.body()),
+        progress_bar(0.0..=queue.total.max(1) as f32, queue.fixed as f32).height(8),
+        scrollable(books).height(Length::Fill),
+        button("End review")
+            .on_press(Message::EndReview)
+            .style(button::secondary),
+    ]
+    .spacing(10)
+    .padding(20)
+}
+
+/// Save, previous and skip, shown instead of the usual form buttons during a review
+pub fn create_review_buttons(queue: &ReviewQueue) -> Row<'_, Message> {
+    row![
+        button("Save and next")
+            .on_press(Message::SaveBook)
+            .style(button::primary),
+        button("Previous")
+            .on_press_maybe((queue.position > 0).then_some(Message::ReviewPrevious))
+            .style(button::secondary),
+        button("Skip")
+            .on_press(Message::ReviewSkip)
+            .style(button::secondary),
+    ]
+    .spacing(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Mode;
+
+    // Books without a price except the ones given one
+    fn books(titles: &[&str], priced: &[ID]) -> Vec<BookWithAuthor> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let mut book = BookModel::titled(i as ID + 1, title);
+                book.price = priced.contains(&book.id).then_some(10.0);
+                BookWithAuthor { book, author: None }
+            })
+            .collect()
+    }
+
+    fn now() -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+
+    fn app_reviewing(titles: &[&str], priced: &[ID]) -> BookshelfApp {
+        let mut app = BookshelfApp::new();
+        let _ = book_view::handle_books_loaded(&mut app, Ok(books(titles, priced)));
+        let _ = handle_start_review(&mut app, DataIssue::MissingPrice);
+        app
+    }
+
+    fn editing(app: &BookshelfApp) -> Option<String> {
+        matches!(app.mode, Mode::Edit)
+            .then(|| app.book_title.clone())
+            .filter(|_| app.review_queue.is_some())
+    }
+
+    #[test]
+    fn the_queue_holds_the_books_with_the_issue_in_list_order() {
+        let queue = ReviewQueue::new(
+            DataIssue::MissingPrice,
+            &books(&["Dune", "Emma", "Mort", "Ubik"], &[2]),
+            now(),
+        );
+        assert_eq!(queue.ids, vec![1, 3, 4]);
+        assert_eq!((queue.total, queue.fixed, queue.current()), (3, 0, Some(1)));
+    }
+
+    #[test]
+    fn skipping_and_going_back_stay_within_the_queue() {
+        let mut queue = ReviewQueue::new(
+            DataIssue::MissingPrice,
+            &books(&["Dune", "Emma", "Mort"], &[]),
+            now(),
+        );
+        queue.previous();
+        assert_eq!(queue.current(), Some(1));
+        queue.skip();
+        queue.skip();
+        assert_eq!(queue.current(), Some(3));
+        queue.skip();
+        assert!(queue.is_done());
+        queue.skip();
+        assert_eq!(queue.position, 3);
+        queue.previous();
+        assert_eq!(queue.current(), Some(3));
+        queue.select(0);
+        assert_eq!(queue.current(), Some(1));
+        queue.select(10);
+        assert!(queue.is_done());
+        // Skipped books stay queued
+        assert_eq!(queue.remaining(), 3);
+    }
+
+    #[test]
+    fn a_fixed_book_leaves_the_queue_and_the_next_takes_its_place() {
+        let mut queue = ReviewQueue::new(
+            DataIssue::MissingPrice,
+            &books(&["Dune", "Emma", "Mort", "Ubik"], &[]),
+            now(),
+        );
+        queue.select(1);
+        queue.mark_fixed(2);
+        assert_eq!(queue.current(), Some(3));
+        // One before the current book keeps the same book current
+        queue.mark_fixed(1);
+        assert_eq!(queue.current(), Some(3));
+        queue.mark_fixed(99);
+        assert_eq!((queue.fixed, queue.remaining()), (2, 2));
+    }
+
+    #[test]
+    fn saving_a_fix_opens_the_next_book_and_counts_it() {
+        let mut app = app_reviewing(&["Dune", "Emma", "Mort"], &[2]);
+        assert_eq!(editing(&app).as_deref(), Some("Dune"));
+
+        let mut fixed = BookModel::titled(1, "Dune");
+        fixed.price = Some(25.0);
+        let _ = handle_review_saved(&mut app, fixed);
+        assert_eq!(editing(&app).as_deref(), Some("Mort"));
+        let queue = app.review_queue.as_ref().unwrap();
+        assert_eq!(
+            (queue.fixed, queue.total, queue.ids.clone()),
+            (1, 2, vec![3])
+        );
+    }
+
+    #[test]
+    fn saving_without_fixing_moves_on_and_keeps_the_book_queued() {
+        let mut app = app_reviewing(&["Dune", "Mort"], &[]);
+        let _ = handle_review_saved(&mut app, BookModel::titled(1, "Dune, revised"));
+        assert_eq!(editing(&app).as_deref(), Some("Mort"));
+        assert_eq!(app.review_queue.as_ref().unwrap().ids, vec![1, 2]);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Saved 'Dune, revised', it still needs a look")
+        );
+    }
+
+    #[test]
+    fn going_past_the_last_book_ends_the_review_with_a_summary() {
+        let mut app = app_reviewing(&["Dune", "Mort"], &[]);
+        let _ = handle_review_skip(&mut app);
+        let _ = handle_review_previous(&mut app);
+        assert_eq!(editing(&app).as_deref(), Some("Dune"));
+        let _ = handle_review_skip(&mut app);
+        let _ = handle_review_skip(&mut app);
+        assert!(app.review_queue.is_none());
+        assert!(matches!(app.mode, Mode::View));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Review of missing price done: 0 of 2 fixed, 2 left as they were")
+        );
+    }
+
+    #[test]
+    fn books_fixed_or_deleted_elsewhere_are_dropped_on_reload() {
+        let mut app = app_reviewing(&["Dune", "Emma", "Mort", "Ubik"], &[]);
+        let _ = handle_review_select(&mut app, 1);
+        assert_eq!(editing(&app).as_deref(), Some("Emma"));
+
+        // Emma got a price in another window and Ubik was deleted
+        let reloaded = books(&["Dune", "Emma", "Mort"], &[2]);
+        let _ = app.update(Message::BooksLoaded(Ok(reloaded)));
+        let queue = app.review_queue.as_ref().unwrap();
+        assert_eq!(queue.ids, vec![1, 3]);
+        assert_eq!(queue.fixed, 2);
+        assert_eq!(editing(&app).as_deref(), Some("Mort"));
+    }
+
+    #[test]
+    fn a_review_with_nothing_to_fix_does_not_start() {
+        let app = app_reviewing(&["Dune"], &[1]);
+        assert!(app.review_queue.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No books with missing price")
+        );
+    }
+}
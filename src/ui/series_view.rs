@@ -0,0 +1,349 @@
+// src/ui/series_view.rs
+use crate::db;
+use crate::models::{NewSeries, SeriesModel, ID};
+use crate::stats::{self, SeriesProgress};
+use crate::ui::{BookshelfApp, FontSizes, Message, Mode, LIST_MAX_WIDTH};
+use iced::widget::{
+    button, column, container, progress_bar, row, scrollable, text, text_input, Column,
+};
+use iced::{Element, Length};
+
+// Handler functions for series-related messages
+pub fn handle_load_series(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::cached::get_series() {
+                Ok(series) => Ok(series),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::SeriesLoaded,
+    )
+}
+
+pub fn handle_series_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<SeriesModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(series) => {
+            // The book form may hold a series known only by its id
+            if let Some(selected) = &app.selected_series {
+                app.selected_series = series.iter().find(|s| s.id == selected.id).cloned();
+            }
+            app.series = series;
+        }
+        Err(e) => {
+            app.report_error(e);
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_add_series_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.mode = Mode::Add;
+    app.current_series = None;
+    app.series_name = String::new();
+    app.series_total = String::new();
+    iced::Task::none()
+}
+
+pub fn handle_edit_series_mode(app: &mut BookshelfApp, series: SeriesModel) -> iced::Task<Message> {
+    app.mode = Mode::Edit;
+    app.series_name = series.name.clone();
+    app.series_total = series
+        .total_volumes
+        .map_or_else(String::new, |total| total.to_string());
+    app.current_series = Some(series);
+    iced::Task::none()
+}
+
+pub fn handle_view_series_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.mode = Mode::View;
+    app.current_series = None;
+
+    iced::Task::batch(vec![
+        app.update(Message::LoadSeries),
+        app.update(Message::LoadBooks),
+    ])
+}
+
+pub fn handle_series_name_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.series_name = value;
+    iced::Task::none()
+}
+
+pub fn handle_series_total_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.series_total = value;
+    iced::Task::none()
+}
+
+pub fn handle_save_series(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if app.series_name.trim().is_empty() {
+        app.error = Some("Series name cannot be empty".to_string());
+        return iced::Task::none();
+    }
+
+    let total_volumes = if app.series_total.trim().is_empty() {
+        None
+    } else {
+        match app.series_total.trim().parse::<i32>() {
+            Ok(total) if total >= 0 => Some(total),
+            _ => {
+                app.error = Some("Total volumes must be a whole number".to_string());
+                return iced::Task::none();
+            }
+        }
+    };
+
+    let new_series = NewSeries {
+        name: app.series_name.trim().to_string(),
+        total_volumes,
+    };
+
+    // Extract series_id outside the closure if we're in edit mode
+    let series_id = app.current_series.as_ref().map(|series| series.id);
+
+    iced::Task::perform(
+        async move {
+            if let Some(id) = series_id {
+                match db::update_series(id, &new_series) {
+                    Ok(updated) => Ok(updated),
+                    Err(e) => Err(e.to_string()),
+                }
+            } else {
+                match db::create_series(&new_series) {
+                    Ok(created) => Ok(created),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+        },
+        Message::SeriesSaved,
+    )
+}
+
+pub fn handle_series_saved(
+    app: &mut BookshelfApp,
+    result: Result<SeriesModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(_) => {
+            app.mode = Mode::View;
+            app.update(Message::LoadSeries)
+        }
+        Err(e) => {
+            app.report_error(e);
+            iced::Task::none()
+        }
+    }
+}
+
+pub fn handle_confirm_delete_series(
+    app: &mut BookshelfApp,
+    id: ID,
+    name: String,
+) -> iced::Task<Message> {
+    app.mode = Mode::ConfirmDelete(id, name);
+    iced::Task::none()
+}
+
+pub fn handle_cancel_delete_series(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.mode = Mode::View;
+    iced::Task::none()
+}
+
+pub fn handle_delete_series(_: &mut BookshelfApp, id: ID) -> iced::Task<Message> {
+    iced::Task::perform(
+        async move {
+            match db::delete_series(id) {
+                Ok(count) => Ok(count),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::SeriesDeleted,
+    )
+}
+
+pub fn handle_series_deleted(
+    app: &mut BookshelfApp,
+    result: Result<usize, String>,
+) -> iced::Task<Message> {
+    app.mode = Mode::View;
+
+    if let Err(e) = result {
+        app.report_error(e);
+    }
+
+    iced::Task::batch(vec![
+        app.update(Message::LoadSeries),
+        app.update(Message::LoadBooks),
+    ])
+}
+
+// View functions for series
+pub fn view(app: &BookshelfApp) -> Element<'_, Message> {
+    match &app.mode {
+        Mode::Add | Mode::Edit => view_series_form(app),
+        Mode::ConfirmDelete(id, name) => view_delete_confirmation(*id, name, app.fonts()),
+        Mode::View | Mode::ViewDetails => view_series_list(app),
+    }
+}
+
+fn view_series_list(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let add_button = button("Add New Series")
+        .on_press(Message::AddSeriesMode)
+        .style(button::primary);
+
+    let series_list = if app.series.is_empty() {
+        column![text("No series found").size(fonts.body())]
+            .spacing(5)
+            .width(Length::Fill)
+    } else {
+        let progress = stats::series_progress(&app.books, &app.series);
+        let mut list = column![].spacing(10).width(Length::Fill);
+
+        for (series, progress) in app.series.iter().zip(progress.iter()) {
+            list = list.push(
+                container(create_series_row(series, progress, fonts))
+                    .padding(10)
+                    .style(container::bordered_box),
+            );
+        }
+
+        list
+    };
+
+    column![
+        row![
+            text("Series").size(fonts.title()),
+            iced::widget::horizontal_space(),
+            add_button
+        ]
+        .padding(10)
+        .width(Length::Fill),
+        scrollable(container(series_list).padding(10).width(Length::Fill)).height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
+/// Summary line with a thin progress bar, shared by the series list and stats
+pub fn create_series_progress<'a>(
+    progress: &SeriesProgress,
+    fonts: FontSizes,
+) -> Column<'a, Message> {
+    let mut content = column![text(progress.summary()).size(fonts.body())].spacing(5);
+
+    if let Some(completion) = progress.completion() {
+        content = content.push(progress_bar(0.0..=1.0, completion).height(6));
+    }
+
+    if progress.exceeds_total() {
+        content = content.push(
+            text(format!(
+                "You own {} volumes, more than the declared total",
+                progress.owned
+            ))
+            .size(fonts.tiny()),
+        );
+    }
+
+    content
+}
+
+fn create_series_row<'a>(
+    series: &SeriesModel,
+    progress: &SeriesProgress,
+    fonts: FontSizes,
+) -> Element<'a, Message> {
+    row![
+        create_series_progress(progress, fonts).width(Length::Fill),
+        button("Edit")
+            .on_press(Message::EditSeriesMode(series.clone()))
+            .style(button::secondary),
+        button("Delete")
+            .on_press(Message::ConfirmDeleteSeries(series.id, series.name.clone()))
+            .style(button::danger),
+    ]
+    .spacing(10)
+    .align_y(iced::alignment::Vertical::Center)
+    .into()
+}
+
+fn view_series_form(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let title = match app.mode {
+        Mode::Add => "Add New Series",
+        Mode::Edit => "Edit Series",
+        _ => unreachable!(),
+    };
+
+    let form = column![
+        text(title).size(fonts.title()),
+        text("Name:").size(fonts.body()),
+        text_input("Enter series name", &app.series_name)
+            .on_input(Message::SeriesNameChanged)
+            .padding(10),
+        text("Total volumes:").size(fonts.body()),
+        text_input(
+            "Number of volumes in the series (optional)",
+            &app.series_total
+        )
+        .on_input(Message::SeriesTotalChanged)
+        .padding(10),
+        row![
+            button("Save")
+                .on_press(Message::SaveSeries)
+                .style(button::primary),
+            button("Cancel")
+                .on_press(Message::ViewSeriesMode)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+    ]
+    .spacing(10)
+    .padding(20)
+    .max_width(fonts.width(LIST_MAX_WIDTH));
+
+    container(form)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .into()
+}
+
+fn view_delete_confirmation<'a>(id: ID, name: &str, fonts: FontSizes) -> Element<'a, Message> {
+    let confirmation = column![
+        text("Are you sure you want to delete the series:").size(fonts.heading()),
+        text(format!("\"{}\"?", name)).size(fonts.title()),
+        text("Books in this series are kept, only the series is removed.").size(fonts.body()),
+        row![
+            button("Cancel")
+                .on_press(Message::CancelDeleteSeries)
+                .style(button::secondary)
+                .padding(10)
+                .width(Length::Fill),
+            button("Confirm Delete")
+                .on_press(Message::DeleteSeries(id))
+                .style(button::danger)
+                .padding(10)
+                .width(Length::Fill),
+        ]
+        .spacing(20)
+        .padding(20)
+    ]
+    .spacing(20)
+    .padding(30)
+    .width(Length::Fill)
+    .align_x(iced::Alignment::Center);
+
+    container(confirmation)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .style(container::bordered_box)
+        .into()
+}
@@ -0,0 +1,367 @@
+// src/ui/session_view.rs
+use crate::db;
+use crate::models::{ReadingSessionModel, ID};
+use crate::quotes;
+use crate::sessions;
+use crate::ui::{format_duration, format_elapsed, format_session_total, BookshelfApp, Message};
+use chrono::{Local, NaiveDateTime};
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Column};
+use iced::{Element, Length};
+use std::fmt;
+
+/// The session being timed. It lives only in memory, a restart discards it.
+#[derive(Debug, Clone)]
+pub struct ReadingTimer {
+    pub session_id: Option<ID>, // None until the session is stored
+    pub title: String,
+    pub started_at: NaiveDateTime,
+    pub now: NaiveDateTime, // Time of the last tick
+    pub pages: String,
+}
+
+/// A book the timer can be started for, as the picker lists it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerBook {
+    pub id: ID,
+    pub title: String,
+}
+
+impl fmt::Display for TimerBook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+// Books still being read, by title
+fn timer_books(app: &BookshelfApp) -> Vec<TimerBook> {
+    let mut books: Vec<TimerBook> = app
+        .books
+        .iter()
+        .filter(|pair| pair.book.finished.is_none())
+        .map(|pair| TimerBook {
+            id: pair.book.id,
+            title: pair.book.title.clone(),
+        })
+        .collect();
+    books.sort_by_key(|book| book.title.to_lowercase());
+    books
+}
+
+fn can_start(app: &BookshelfApp) -> bool {
+    !app.read_only && app.reading_timer.is_none()
+}
+
+// Handler functions for reading sessions
+pub fn handle_load_reading_sessions(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::get_reading_sessions() {
+                Ok(sessions) => Ok(sessions),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ReadingSessionsLoaded,
+    )
+}
+
+pub fn handle_reading_sessions_loaded(
+    app: &mut BookshelfApp,
+    result: Result<Vec<ReadingSessionModel>, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(sessions) => {
+            app.reading_totals = sessions::totals_by_book(&sessions);
+            app.reading_sessions = sessions;
+        }
+        Err(e) => app.report_error(e),
+    }
+    iced::Task::none()
+}
+
+/// Runs once the database is open, a session left running by the last run can't be
+/// told apart from one that was never stopped
+pub fn discard_unfinished_sessions() -> iced::Task<Message> {
+    iced::Task::perform(
+        async {
+            match db::discard_unfinished_sessions() {
+                Ok(count) => Ok(count),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::UnfinishedSessionsDiscarded,
+    )
+}
+
+pub fn handle_unfinished_sessions_discarded(
+    app: &mut BookshelfApp,
+    result: Result<usize, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(0) => {}
+        Ok(count) => {
+            app.status_message = Some(format!(
+                "Discarded {} unfinished reading {} from the last run",
+                count,
+                if count == 1 { "session" } else { "sessions" }
+            ))
+        }
+        Err(e) => app.report_error(format!("Failed to clean up reading sessions: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_timer_book_selected(app: &mut BookshelfApp, book: TimerBook) -> iced::Task<Message> {
+    app.timer_book = Some(book);
+    iced::Task::none()
+}
+
+pub fn handle_start_session(app: &mut BookshelfApp, book_id: ID) -> iced::Task<Message> {
+    if !can_start(app) {
+        return iced::Task::none();
+    }
+    let Some(pair) = app.books.iter().find(|pair| pair.book.id == book_id) else {
+        return iced::Task::none();
+    };
+
+    let now = Local::now().naive_local();
+    // Shown at once, stopping waits until the session is stored
+    app.reading_timer = Some(ReadingTimer {
+        session_id: None,
+        title: pair.book.title.clone(),
+        started_at: now,
+        now,
+        pages: String::new(),
+    });
+    iced::Task::perform(
+        async move {
+            match db::start_reading_session(book_id, now) {
+                Ok(session) => Ok(session),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ReadingSessionStarted,
+    )
+}
+
+pub fn handle_session_started(
+    app: &mut BookshelfApp,
+    result: Result<ReadingSessionModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(session) => {
+            if let Some(timer) = &mut app.reading_timer {
+                timer.session_id = Some(session.id);
+            }
+        }
+        Err(e) => {
+            app.reading_timer = None;
+            app.notify_failed(format!("Failed to start the reading timer: {}", e));
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_timer_tick(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some(timer) = &mut app.reading_timer {
+        timer.now = Local::now().naive_local();
+    }
+    iced::Task::none()
+}
+
+pub fn handle_timer_pages_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    if let Some(timer) = &mut app.reading_timer {
+        timer.pages = value;
+    }
+    iced::Task::none()
+}
+
+pub fn handle_stop_session(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(timer) = &app.reading_timer else {
+        return iced::Task::none();
+    };
+    let Some(session_id) = timer.session_id else {
+        return iced::Task::none();
+    };
+    let pages = match quotes::parse_page(&timer.pages) {
+        Ok(pages) => pages,
+        Err(_) => {
+            let message = format!("'{}' is not a number of pages", timer.pages.trim());
+            app.report_error(message);
+            return iced::Task::none();
+        }
+    };
+
+    let ended_at = Local::now().naive_local();
+    iced::Task::perform(
+        async move {
+            match db::finish_reading_session(session_id, ended_at, pages) {
+                Ok(session) => Ok(session),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ReadingSessionStopped,
+    )
+}
+
+// The timer keeps running when storing the end fails, so stopping can be tried again
+pub fn handle_session_stopped(
+    app: &mut BookshelfApp,
+    result: Result<ReadingSessionModel, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(session) => {
+            let title = app
+                .reading_timer
+                .take()
+                .map_or_else(String::new, |timer| timer.title);
+            let minutes = sessions::session_minutes(&session);
+            app.notify_finished(format!(
+                "Recorded {} of {}",
+                format_duration(minutes as i32),
+                title
+            ));
+            app.reading_sessions.push(session);
+            app.reading_totals = sessions::totals_by_book(&app.reading_sessions);
+        }
+        Err(e) => app.notify_failed(format!("Failed to record the reading session: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_discard_session(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(timer) = &app.reading_timer else {
+        return iced::Task::none();
+    };
+    let Some(session_id) = timer.session_id else {
+        return iced::Task::none();
+    };
+    iced::Task::perform(
+        async move {
+            match db::delete_reading_session(session_id) {
+                Ok(_) => Ok(session_id),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ReadingSessionDiscarded,
+    )
+}
+
+pub fn handle_session_discarded(
+    app: &mut BookshelfApp,
+    result: Result<ID, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(_) => app.reading_timer = None,
+        Err(e) => app.notify_failed(format!("Failed to discard the reading session: {}", e)),
+    }
+    iced::Task::none()
+}
+
+// View functions for reading sessions
+/// The running timer in the lower left corner, above whatever page is open
+pub fn create_running_timer(app: &BookshelfApp) -> Option<Element<'_, Message>> {
+    let timer = app.reading_timer.as_ref()?;
+    let fonts = app.fonts();
+    let stored = timer.session_id.is_some();
+
+    let card = container(
+        column![
+            text(format!("Reading {}", timer.title)).size(fonts.small()),
+            text(format_elapsed((timer.now - timer.started_at).num_seconds()))
+                .size(fonts.heading()),
+            row![
+                text_input("Pages", &timer.pages)
+                    .on_input(Message::TimerPagesChanged)
+                    .on_submit(Message::StopReadingSession)
+                    .size(fonts.small())
+                    .width(fonts.width(70.0)),
+                button(text("Stop").size(fonts.small()))
+                    .on_press_maybe(stored.then_some(Message::StopReadingSession))
+                    .style(button::primary),
+                button(text("Discard").size(fonts.small()))
+                    .on_press_maybe(stored.then_some(Message::DiscardReadingSession))
+                    .style(button::secondary),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(6),
+    )
+    .padding(10)
+    .style(container::bordered_box);
+
+    Some(
+        container(card)
+            .padding(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_left(Length::Fill)
+            .align_bottom(Length::Fill)
+            .into(),
+    )
+}
+
+/// Time spent on the book being edited, with a button starting the timer for it
+pub fn create_book_sessions(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let Some(pair) = &app.selected_book else {
+        return column![];
+    };
+    let book_id = pair.book.id;
+    let total = match app.reading_totals.get(&book_id) {
+        Some(total) => format_session_total(total),
+        None => "No reading sessions yet".to_string(),
+    };
+
+    let mut section = column![
+        text("Reading sessions").size(fonts.heading()),
+        text(total).size(fonts.body()),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+    if pair.book.finished.is_none() {
+        section = section.push(
+            button("Start reading timer")
+                .on_press_maybe(can_start(app).then_some(Message::StartReadingSession(book_id)))
+                .style(button::secondary),
+        );
+    }
+    section
+}
+
+/// Reading time of the current week, and a picker to start the timer from the stats tab
+pub fn create_reading_time(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let minutes = sessions::minutes_this_week(&app.reading_sessions, Local::now().naive_local());
+    let selected = app.timer_book.as_ref().map(|book| book.id);
+
+    let content = column![
+        text("Reading time").size(fonts.heading()),
+        text(format!("This week: {}", format_duration(minutes as i32))).size(fonts.body()),
+        row![
+            pick_list(
+                timer_books(app),
+                app.timer_book.clone(),
+                Message::TimerBookSelected
+            )
+            .placeholder("Book you are reading")
+            .padding(6),
+            button("Start")
+                .on_press_maybe(
+                    selected
+                        .filter(|_| can_start(app))
+                        .map(Message::StartReadingSession)
+                )
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
@@ -0,0 +1,642 @@
+// src/ui/settings_view.rs
+use crate::config::{self, AppConfig};
+use crate::db;
+use crate::paths;
+use crate::ui::{backup_view, console_view};
+use crate::ui::{
+    parse_tax_rate, table_column_count, table_column_widths, BookshelfApp, EmptyValues, ListMode,
+    Message, SortField,
+    StickyField,
+    BOOKS_PER_ROW_MAX, BOOKS_PER_ROW_MIN, FONT_SCALE_MAX, FONT_SCALE_MIN, FONT_SCALE_STEP,
+    IDLE_REFRESH_MINUTES, IMPORT_AUTHOR_LIMITS, MAINTENANCE_BACKUPS_KEPT, TABLE_COLUMN_WIDTHS,
+    TAX_RATE_MAX,
+};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, text, text_input, toggler,
+};
+use iced::{Element, Length};
+
+// Persists the current settings in the background
+pub fn save_config(config: &AppConfig) -> iced::Task<Message> {
+    let config = config.clone();
+    iced::Task::perform(
+        async move {
+            match config::save(&config) {
+                Ok(()) => Ok(()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ConfigSaved,
+    )
+}
+
+// Handler functions for settings-related messages
+pub fn handle_change_font_scale(app: &mut BookshelfApp, steps: i32) -> iced::Task<Message> {
+    let scale = app.config.font_scale + steps as f32 * FONT_SCALE_STEP;
+    // Round to the step so repeated presses don't accumulate float error
+    let scale = (scale / FONT_SCALE_STEP).round() * FONT_SCALE_STEP;
+    app.config.font_scale = scale.clamp(FONT_SCALE_MIN, FONT_SCALE_MAX);
+    save_config(&app.config)
+}
+
+pub fn handle_reset_font_scale(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.config.font_scale = AppConfig::default().font_scale;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_dark_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let is_dark = app.theme() == iced::Theme::Dark;
+    app.config.dark_mode = Some(!is_dark);
+    save_config(&app.config)
+}
+
+pub fn handle_list_mode_selected(app: &mut BookshelfApp, mode: ListMode) -> iced::Task<Message> {
+    app.config.list_mode = mode;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_group_by_author(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.group_by_author = enabled;
+    app.config.group_by_series &= !enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_group_by_series(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.group_by_series = enabled;
+    app.config.group_by_author &= !enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_change_books_per_row(app: &mut BookshelfApp, steps: i32) -> iced::Task<Message> {
+    let per_row = app.config.books_per_row as i32 + steps;
+    app.config.books_per_row =
+        (per_row.max(0) as usize).clamp(BOOKS_PER_ROW_MIN, BOOKS_PER_ROW_MAX);
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_sort_in_database(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.sort_in_database = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_confirm_bulk_actions(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.confirm_bulk_actions = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_stats_strip(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.show_stats_strip = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_open_details_on_click(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.open_details_on_click = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_sticky_field(
+    app: &mut BookshelfApp,
+    field: StickyField,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.sticky_fields.set(field, enabled);
+    save_config(&app.config)
+}
+
+pub fn handle_sort_empty_values_selected(
+    app: &mut BookshelfApp,
+    empty: EmptyValues,
+) -> iced::Task<Message> {
+    app.config.sort_empty_values = empty;
+    iced::Task::batch([app.update(Message::ApplySorting), save_config(&app.config)])
+}
+
+pub fn handle_secondary_sort_selected(
+    app: &mut BookshelfApp,
+    field: Option<SortField>,
+) -> iced::Task<Message> {
+    app.config.secondary_sort = field;
+    iced::Task::batch([app.update(Message::ApplySorting), save_config(&app.config)])
+}
+
+pub fn handle_toggle_pin_books_to_top(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.pin_books_to_top = enabled;
+    iced::Task::batch([app.update(Message::ApplySorting), save_config(&app.config)])
+}
+
+// The other columns keep their widths relative to each other
+pub fn handle_toggle_reading_duration(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    let columns = table_column_count(app.config.show_reading_duration);
+    let mut widths = table_column_widths(&app.config.column_widths, columns);
+    if enabled {
+        let added = TABLE_COLUMN_WIDTHS[TABLE_COLUMN_WIDTHS.len() - 1];
+        widths = widths.iter().map(|width| width * (1.0 - added)).collect();
+        widths.push(added);
+    } else {
+        widths.truncate(table_column_count(false));
+    }
+
+    app.config.show_reading_duration = enabled;
+    app.config.column_widths = table_column_widths(&widths, table_column_count(enabled));
+    save_config(&app.config)
+}
+
+// Only masks prices until the app is closed, the setting decides how it starts
+pub fn handle_toggle_privacy_mode(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.privacy_mode = !app.privacy_mode;
+    iced::Task::none()
+}
+
+pub fn handle_toggle_hide_prices(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.hide_prices = enabled;
+    app.privacy_mode = enabled;
+    save_config(&app.config)
+}
+
+// Invalid rates stay in the input without touching the saved one
+pub fn handle_tax_rate_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    let rate = parse_tax_rate(&value);
+    app.tax_rate_input = value;
+    match rate {
+        Some(rate) if rate != app.config.tax_rate => {
+            app.config.tax_rate = rate;
+            save_config(&app.config)
+        }
+        _ => iced::Task::none(),
+    }
+}
+
+pub fn handle_toggle_prices_include_tax(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.prices_include_tax = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_idle_refresh(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.idle_refresh = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_idle_refresh_minutes_selected(
+    app: &mut BookshelfApp,
+    minutes: u64,
+) -> iced::Task<Message> {
+    app.config.idle_refresh_minutes = minutes;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_desktop_notifications(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.desktop_notifications = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_nightly_maintenance(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.nightly_maintenance = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_maintenance_hour_selected(app: &mut BookshelfApp, hour: u32) -> iced::Task<Message> {
+    app.config.maintenance_hour = hour;
+    save_config(&app.config)
+}
+
+pub fn handle_maintenance_backups_kept_selected(
+    app: &mut BookshelfApp,
+    kept: usize,
+) -> iced::Task<Message> {
+    app.config.maintenance_backups_kept = kept;
+    save_config(&app.config)
+}
+
+pub fn handle_import_author_limit_selected(
+    app: &mut BookshelfApp,
+    limit: usize,
+) -> iced::Task<Message> {
+    app.config.import_author_limit = limit;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_remember_search(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.remember_search = enabled;
+    app.config.last_search = if enabled {
+        app.search_term_displayed.clone()
+    } else {
+        String::new()
+    };
+    save_config(&app.config)
+}
+
+/// Stores the book search shown in the list when it should survive a restart
+pub fn remember_search(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !app.config.remember_search || app.config.last_search == app.search_term_displayed {
+        return iced::Task::none();
+    }
+    app.config.last_search = app.search_term_displayed.clone();
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_scientific_prices(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.allow_scientific_prices = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_bought_when_finished(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.bought_when_finished = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_review_changes(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.review_changes_before_save = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_confirm_exit(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.confirm_exit_with_unsaved_work = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_query_console(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.show_query_console = enabled;
+    save_config(&app.config)
+}
+
+pub fn handle_reset_settings(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.confirm_reset_settings = true;
+    iced::Task::none()
+}
+
+pub fn handle_cancel_reset_settings(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.confirm_reset_settings = false;
+    iced::Task::none()
+}
+
+/// Puts every setting back to its default. Values copied out of the settings when the
+/// app started are reset with them, and the list is filtered and sorted again.
+pub fn handle_confirm_reset_settings(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.confirm_reset_settings = false;
+    app.config = AppConfig::default();
+    app.privacy_mode = app.config.hide_prices;
+    app.tax_rate_input = app.config.tax_rate.to_string();
+    app.apply_filters();
+    app.status_message = Some("All settings were reset to their defaults".to_string());
+    iced::Task::batch([app.update(Message::ApplySorting), save_config(&app.config)])
+}
+
+pub fn handle_open_log_file(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let path = paths::log_file();
+    if !path.exists() {
+        app.status_message = Some("No errors have been logged yet".to_string());
+        return iced::Task::none();
+    }
+
+    if let Err(e) = open::that_detached(&path) {
+        app.report_error(format!("Failed to open {}: {}", path.display(), e));
+    }
+    iced::Task::none()
+}
+
+pub fn handle_config_saved(
+    app: &mut BookshelfApp,
+    result: Result<(), String>,
+) -> iced::Task<Message> {
+    if let Err(e) = result {
+        app.report_error(format!("Failed to save settings: {}", e));
+    }
+    iced::Task::none()
+}
+
+// View functions for settings
+fn preset_choices<T: Copy + Ord>(presets: &[T], current: T) -> Vec<T> {
+    let mut choices = presets.to_vec();
+    if !choices.contains(&current) {
+        // Keep a value set by hand in the settings file selectable
+        choices.push(current);
+        choices.sort();
+    }
+    choices
+}
+
+fn pool_diagnostics() -> String {
+    match db::pool_status() {
+        Some(status) => format!(
+            "Database connections: {} open, {} idle, at most {}. Timed out waiting: {}",
+            status.connections, status.idle_connections, status.max_size, status.timeouts
+        ),
+        None => "Database connections: not connected".to_string(),
+    }
+}
+
+pub fn view(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+
+    let font_section = column![
+        text("Text size").size(fonts.heading()),
+        row![
+            button(text("-").size(fonts.body()))
+                .on_press(Message::DecreaseFontScale)
+                .style(button::secondary),
+            text(format!("{:.0}%", app.config.font_scale * 100.0)).size(fonts.body()),
+            button(text("+").size(fonts.body()))
+                .on_press(Message::IncreaseFontScale)
+                .style(button::secondary),
+            button(text("Reset").size(fonts.body()))
+                .on_press(Message::ResetFontScale)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        text("Shortcuts: Ctrl+= to enlarge, Ctrl+- to shrink").size(fonts.small()),
+        text("Appearance").size(fonts.heading()),
+        toggler(app.theme() == iced::Theme::Dark)
+            .label("Dark mode")
+            .on_toggle(|_| Message::ToggleDarkMode),
+        toggler(app.config.hide_prices)
+            .label("Hide prices when the app starts")
+            .on_toggle(Message::ToggleHidePrices),
+        text("Prices can be shown or hidden at any time with the button next to the sort options")
+            .size(fonts.small()),
+        text("Tax").size(fonts.heading()),
+        row![
+            text("Tax rate (%):").size(fonts.body()),
+            text_input("0", &app.tax_rate_input)
+                .on_input(Message::TaxRateChanged)
+                .padding(8)
+                .width(fonts.width(100.0)),
+            text(if parse_tax_rate(&app.tax_rate_input).is_some() {
+                String::new()
+            } else {
+                format!("Enter a rate from 0 to {}", TAX_RATE_MAX)
+            })
+            .size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        toggler(app.config.prices_include_tax)
+            .label("Stored prices already include tax")
+            .on_toggle(Message::TogglePricesIncludeTax),
+        text("With a tax rate set, totals in the stats show both the net and the gross amount")
+            .size(fonts.small()),
+        toggler(app.config.average_priced_only)
+            .label("Leave books without a price out of the average price")
+            .on_toggle(Message::ToggleAveragePricedOnly),
+        text("Book layout").size(fonts.heading()),
+        toggler(app.config.show_stats_strip)
+            .label("Show book counts above the list")
+            .on_toggle(Message::ToggleStatsStrip),
+        toggler(app.config.pin_books_to_top)
+            .label("Keep pinned books at the top of the list")
+            .on_toggle(Message::TogglePinBooksToTop),
+        row![
+            text("Books without a price or added date sort:").size(fonts.body()),
+            pick_list(
+                EmptyValues::ALL,
+                Some(app.config.sort_empty_values),
+                Message::SortEmptyValuesSelected
+            )
+            .padding(6),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        row![
+            pick_list(
+                ListMode::ALL,
+                Some(app.config.list_mode),
+                Message::ListModeSelected
+            ),
+            text("Books per row:").size(fonts.body()),
+            button(text("-").size(fonts.body()))
+                .on_press(Message::ChangeBooksPerRow(-1))
+                .style(button::secondary),
+            text(app.config.books_per_row.to_string()).size(fonts.body()),
+            button(text("+").size(fonts.body()))
+                .on_press(Message::ChangeBooksPerRow(1))
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        toggler(app.config.open_details_on_click)
+            .label("Open a book's details with a single click on its row")
+            .on_toggle(Message::ToggleOpenDetailsOnClick),
+        toggler(app.config.show_details_pane)
+            .label("Show the details of the clicked book next to the list")
+            .on_toggle(Message::ToggleDetailsPane),
+        text("Drag the divider to resize the panes, double-click it to reset")
+            .size(fonts.small()),
+        toggler(app.config.show_reading_duration)
+            .label("Show how long each book took to read in the table layout")
+            .on_toggle(Message::ToggleReadingDuration),
+        toggler(app.config.idle_refresh)
+            .label("Reload the lists while the app is idle")
+            .on_toggle(Message::ToggleIdleRefresh),
+        row![
+            text("Every").size(fonts.body()),
+            pick_list(
+                preset_choices(&IDLE_REFRESH_MINUTES, app.config.idle_refresh_minutes),
+                Some(app.config.idle_refresh_minutes),
+                Message::IdleRefreshMinutesSelected
+            )
+            .padding(6),
+            text("minutes, skipped while a form is open or a search is typed")
+                .size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        toggler(app.config.nightly_maintenance)
+            .label("Back up and check the library overnight while the app is left open")
+            .on_toggle(Message::ToggleNightlyMaintenance),
+        row![
+            text("After").size(fonts.body()),
+            pick_list(
+                (0..24).collect::<Vec<u32>>(),
+                Some(app.config.maintenance_hour),
+                Message::MaintenanceHourSelected
+            )
+            .padding(6),
+            text(":00, keeping the last").size(fonts.body()),
+            pick_list(
+                preset_choices(&MAINTENANCE_BACKUPS_KEPT, app.config.maintenance_backups_kept),
+                Some(app.config.maintenance_backups_kept),
+                Message::MaintenanceBackupsKeptSelected
+            )
+            .padding(6),
+            text("nightly backups. Waits while the app is busy").size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        toggler(app.config.remember_search)
+            .label("Restore the last book search when the app starts")
+            .on_toggle(Message::ToggleRememberSearch),
+        toggler(app.config.sort_in_database)
+            .label("Sort books in the database (faster for large libraries)")
+            .on_toggle(Message::ToggleSortInDatabase),
+        toggler(app.config.confirm_bulk_actions)
+            .label("Preview bulk operations before applying them")
+            .on_toggle(Message::ToggleConfirmBulkActions),
+        toggler(app.config.desktop_notifications)
+            .label("Show a desktop notification when imports, exports and bulk changes finish")
+            .on_toggle(Message::ToggleDesktopNotifications),
+        row![
+            text("Ask before a CSV import creates more than").size(fonts.body()),
+            pick_list(
+                preset_choices(&IMPORT_AUTHOR_LIMITS, app.config.import_author_limit),
+                Some(app.config.import_author_limit),
+                Message::ImportAuthorLimitSelected
+            )
+            .padding(6),
+            text("new authors").size(fonts.body()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        toggler(app.config.allow_scientific_prices)
+            .label("Accept prices in scientific notation like 1.5e3")
+            .on_toggle(Message::ToggleScientificPrices),
+        toggler(app.config.bought_when_finished)
+            .label("Mark books as bought when they are finished")
+            .on_toggle(Message::ToggleBoughtWhenFinished),
+        text(
+            "Finishing a book with no bought date, from the form or with the quick action, \
+             sets the bought date to the finished date."
+        )
+        .size(fonts.small()),
+        toggler(app.config.review_changes_before_save)
+            .label("Review changes before saving an edited book")
+            .on_toggle(Message::ToggleReviewChanges),
+        toggler(app.config.confirm_exit_with_unsaved_work)
+            .label("Ask before quitting with unsaved changes")
+            .on_toggle(Message::ToggleConfirmExit),
+        create_sticky_field_toggles(app),
+        text("Press Ctrl+K anywhere to open the command palette").size(fonts.small()),
+        text("In the book list, Up/Down move between books and Enter opens one")
+            .size(fonts.small()),
+        text("Press ? to see all keyboard shortcuts").size(fonts.small()),
+        text("Advanced").size(fonts.heading()),
+        row![
+            button("Open log file")
+                .on_press(Message::OpenLogFile)
+                .style(button::secondary),
+            text(paths::log_file().display().to_string()).size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        text(match db::database_path() {
+            Some(path) => format!("Database file: {}", path.display()),
+            None => "Database file: not opened".to_string(),
+        })
+        .size(fonts.small()),
+        text(pool_diagnostics()).size(fonts.small()),
+        text(format!(
+            "To change the window icon, save a PNG as {} and restart",
+            paths::icon_file().display()
+        ))
+        .size(fonts.small()),
+        toggler(app.config.show_query_console)
+            .label("Show the SQL query console")
+            .on_toggle(Message::ToggleQueryConsole),
+        create_reset_settings(app),
+    ]
+    .spacing(10);
+
+    let mut settings = column![font_section, backup_view::create_backup_section(app)].spacing(20);
+    if app.config.show_query_console {
+        settings = settings.push(console_view::create_console_section(app));
+    }
+
+    column![
+        text("Settings").size(fonts.title()),
+        scrollable(
+            container(settings)
+                .padding(20)
+                .width(Length::Fill)
+                .style(container::bordered_box)
+        )
+        .height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
+// Asks once more before resetting, saved import presets go with the rest
+fn create_reset_settings(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    if !app.confirm_reset_settings {
+        return button("Reset all settings to defaults")
+            .on_press(Message::ResetSettings)
+            .style(button::secondary)
+            .into();
+    }
+
+    row![
+        text("Every setting, saved import presets included, goes back to its default")
+            .size(fonts.body()),
+        button("Reset settings")
+            .on_press(Message::ConfirmResetSettings)
+            .style(button::danger),
+        button("Cancel")
+            .on_press(Message::CancelResetSettings)
+            .style(button::secondary),
+    ]
+    .spacing(10)
+    .align_y(iced::alignment::Vertical::Center)
+    .into()
+}
+
+// Values "Save and add another" keeps for the next book
+fn create_sticky_field_toggles(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    StickyField::ALL
+        .into_iter()
+        .fold(
+            row![text("Save and add another keeps:").size(fonts.small())].spacing(10),
+            |toggles, field| {
+                toggles.push(
+                    checkbox(field.to_string(), app.config.sticky_fields.contains(field))
+                        .on_toggle(move |enabled| Message::ToggleStickyField(field, enabled))
+                        .text_size(fonts.small()),
+                )
+            },
+        )
+        .align_y(iced::Alignment::Center)
+        .into()
+}
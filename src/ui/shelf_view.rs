@@ -0,0 +1,115 @@
+// src/ui/shelf_view.rs
+use crate::models::BookWithAuthor;
+use crate::ui::book_view::is_book_highlighted;
+use crate::ui::{book_genre_color, BookshelfApp, Message};
+use iced::widget::{column, container, mouse_area, text, tooltip, Column, Row};
+use iced::{Color, Length, Size, Theme};
+
+const SPINE_HEIGHT: f32 = 200.0;
+const SPINE_DEFAULT_WIDTH: f32 = 28.0; // Books without a page count
+const SPINE_MIN_WIDTH: f32 = 16.0;
+const SPINE_MAX_WIDTH: f32 = 72.0;
+const PAGES_PER_PIXEL: f32 = 12.0;
+const SPINE_LETTER_HEIGHT: f32 = 1.3; // Line height of a stacked letter, in font sizes
+
+/// Spine size before font scaling, thicker books get wider spines
+pub fn spine_size(page_count: Option<i32>) -> Size {
+    let width = match page_count {
+        Some(pages) if pages > 0 => {
+            (pages as f32 / PAGES_PER_PIXEL).clamp(SPINE_MIN_WIDTH, SPINE_MAX_WIDTH)
+        }
+        _ => SPINE_DEFAULT_WIDTH,
+    };
+    Size::new(width, SPINE_HEIGHT)
+}
+
+// Black or white, whichever reads better on the spine color
+fn spine_text_color(background: Color) -> Color {
+    let luminance = 0.299 * background.r + 0.587 * background.g + 0.114 * background.b;
+    if luminance > 0.5 {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+// Letters stacked top to bottom, text can't be rotated so this stands in for it
+fn vertical_title(title: &str, max_letters: usize) -> String {
+    let letters: Vec<char> = title.chars().filter(|c| !c.is_control()).collect();
+    let mut shown: Vec<String> = letters
+        .iter()
+        .take(max_letters)
+        .map(|c| {
+            if c.is_whitespace() {
+                " ".to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect();
+    if letters.len() > max_letters && max_letters > 0 {
+        shown[max_letters - 1] = "…".to_string();
+    }
+    shown.join("\n")
+}
+
+// View functions for the shelf layout
+pub fn create_books_shelf<'a>(
+    app: &'a BookshelfApp,
+    books_to_display: &[&'a BookWithAuthor],
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+
+    let spines = books_to_display.iter().map(|pair| {
+        let size = spine_size(pair.book.page_count);
+        let (width, height) = (fonts.width(size.width), fonts.width(size.height));
+        let background = book_genre_color(pair.book.genre.as_deref(), &app.config.genre_colors);
+        let letter_size = fonts.tiny();
+        let max_letters = (height / (letter_size * SPINE_LETTER_HEIGHT)) as usize;
+        let highlighted = is_book_highlighted(app, pair.book.id);
+
+        let spine = container(
+            text(vertical_title(&pair.book.title, max_letters))
+                .size(letter_size)
+                .color(spine_text_color(background)),
+        )
+        .width(width)
+        .height(height)
+        .center_x(width)
+        .padding([6, 0])
+        .style(move |theme: &Theme| container::Style {
+            background: Some(background.into()),
+            border: iced::Border {
+                color: if highlighted {
+                    theme.palette().primary
+                } else {
+                    Color::BLACK.scale_alpha(0.3)
+                },
+                width: if highlighted { 3.0 } else { 1.0 },
+                radius: 2.0.into(),
+            },
+            ..container::Style::default()
+        });
+
+        let label = match pair.author.as_ref().and_then(|a| a.Name.as_deref()) {
+            Some(name) => format!("{} by {}", pair.book.title, name),
+            None => pair.book.title.clone(),
+        };
+
+        tooltip(
+            mouse_area(spine).on_press(Message::EditBookMode((*pair).clone())),
+            container(text(label).size(fonts.small()))
+                .padding(6)
+                .style(container::bordered_box),
+            tooltip::Position::Top,
+        )
+        .into()
+    });
+
+    column![Row::with_children(spines)
+        .spacing(4)
+        .align_y(iced::Alignment::End)
+        .wrap()]
+    .width(Length::Fill)
+    .padding(20)
+}
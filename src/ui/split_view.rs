@@ -0,0 +1,119 @@
+// src/ui/split_view.rs
+use crate::ui::book_view::create_book_details;
+use crate::ui::components::cursor_tracker::cursor_tracker;
+use crate::ui::settings_view::save_config;
+use crate::ui::table_view::portion;
+use crate::ui::{
+    split_ratio_at, BookshelfApp, Message, DOUBLE_CLICK_MS, SPLIT_RATIO_DEFAULT, SPLIT_RATIO_MAX,
+    SPLIT_RATIO_MIN,
+};
+use iced::widget::{button, column, container, mouse_area, row, scrollable, text, vertical_rule};
+use iced::{event, mouse, window, Element, Event, Length};
+use std::time::{Duration, Instant};
+
+const SPLITTER_WIDTH: f32 = 10.0;
+
+// Handler functions for the splitter between the book list and the details pane
+pub fn handle_start_split_drag(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let now = Instant::now();
+    let double_click = app.last_splitter_press.is_some_and(|at| {
+        now.saturating_duration_since(at) <= Duration::from_millis(DOUBLE_CLICK_MS)
+    });
+    if double_click {
+        app.last_splitter_press = None;
+        app.config.split_ratio = SPLIT_RATIO_DEFAULT;
+        return save_config(&app.config);
+    }
+
+    app.last_splitter_press = Some(now);
+    app.dragging_splitter = true;
+    iced::Task::none()
+}
+
+pub fn handle_split_drag_moved(app: &mut BookshelfApp, ratio: f32) -> iced::Task<Message> {
+    if app.dragging_splitter {
+        app.config.split_ratio = ratio;
+    }
+    iced::Task::none()
+}
+
+// The ratio is only written to the settings once the drag is over
+pub fn handle_end_split_drag(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if !std::mem::take(&mut app.dragging_splitter) {
+        return iced::Task::none();
+    }
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_details_pane(app: &mut BookshelfApp, enabled: bool) -> iced::Task<Message> {
+    app.config.show_details_pane = enabled;
+    save_config(&app.config)
+}
+
+pub fn on_split_drag_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            Some(Message::EndSplitDrag)
+        }
+        _ => None,
+    }
+}
+
+// View functions for the details pane
+pub fn create_split_panes<'a>(
+    app: &'a BookshelfApp,
+    list: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    // Clamped again as the settings file can be edited by hand
+    let ratio = app
+        .config
+        .split_ratio
+        .clamp(SPLIT_RATIO_MIN, SPLIT_RATIO_MAX);
+    let splitter = mouse_area(
+        container(vertical_rule(2))
+            .center_x(SPLITTER_WIDTH)
+            .height(Length::Fill),
+    )
+    .on_press(Message::StartSplitDrag)
+    .interaction(mouse::Interaction::ResizingHorizontally);
+
+    let panes = row![
+        container(list).width(portion(ratio)),
+        splitter,
+        container(create_details_pane(app)).width(portion(1.0 - ratio)),
+    ]
+    .height(Length::Fill);
+
+    if !app.dragging_splitter {
+        return panes.into();
+    }
+    cursor_tracker(panes, |x, width| {
+        Message::SplitDragMoved(split_ratio_at(x, width, SPLITTER_WIDTH))
+    })
+}
+
+fn create_details_pane(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let focused = app
+        .focused_row
+        .and_then(|index| app.displayed_books().get(index));
+
+    let Some(pair) = focused else {
+        return container(text("Click a book to see its details here").size(fonts.body()))
+            .center(Length::Fill)
+            .into();
+    };
+
+    scrollable(
+        column![
+            create_book_details(app, pair),
+            button("Edit")
+                .on_press(Message::EditBookMode(pair.clone()))
+                .style(button::primary),
+        ]
+        .spacing(15)
+        .padding(15),
+    )
+    .height(Length::Fill)
+    .into()
+}
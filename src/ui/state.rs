@@ -1,13 +1,81 @@
-use crate::db;
-use crate::models::{AuthorModel, BookWithAuthor};
+use crate::config::{self, AppConfig};
+use crate::draft::{self, BookDraft};
+use crate::error_log;
+use crate::instance_lock::InstanceLock;
+use crate::console::QueryOutput;
+use crate::db::{self, BoughtDateBackup, BoughtDatePlan, MovePlan, RelatedRecords};
+use crate::links;
+use crate::notifications;
+use crate::models::{
+    AuthorModel, BookFieldModel, BookFormat, BookListModel, BookWithAuthor, JournalEntryModel,
+    LoanModel, QuoteModel, ReadingSessionModel, SeriesModel, ID,
+};
+use crate::report::ReportFormat;
+use crate::sessions::SessionTotal;
+use crate::stats::SectionKey;
+use crate::trash::RecentlyDeleted;
 use crate::ui::components::searchable_dropdown::SearchableDropdown;
-use crate::ui::{author_view, book_view, sort_books, Message, Mode, SortDirection, SortField, Tab};
+use crate::ui::components::toasts::{ToastKind, ToastMessage, Toasts};
+use crate::ui::author_view::MergeSuggestion;
+use crate::ui::backup_view::{self, BackupCompare};
+use crate::ui::bought_date_view::{self, BoughtDateForm};
+use crate::ui::changes_view::{self, PendingBookChanges};
+use crate::ui::cover_view::{self, CoverPreview};
+use crate::ui::import_view::{self, CsvImport};
+use crate::ui::instance_view;
+use crate::ui::list_view::{self, ActiveList};
+use crate::ui::missing_view::{self, AuthorAssignment};
+use crate::ui::quote_view::{self, QuoteBrowser};
+use crate::ui::review_view::{self, ReviewQueue};
+use crate::ui::session_view::{self, ReadingTimer, TimerBook};
+use crate::ui::stats_view::ShelfExport;
+use crate::ui::{custom_field_view, draft_view, hover_view, pinned_first, print_view, split_view};
+use crate::ui::{maintenance_view, table_view, trash_view};
+use crate::ui::{book_in_year, AcquisitionPlace, AuthorSort, YearField};
+use crate::ui::{apply_tax, format_price};
+use crate::ui::{
+    author_view, book_is_missing, book_view, console_view, filter_books, journal_view, keymap, loan_view, palette, series_view, settings_view, sort_books,
+    stats_view, CommandPalette, FontSizes, ListMode, Message, MissingField, Mode, SortDirection, SortField, Tab, ROW_HIGHLIGHT_MS, DRAFT_AUTOSAVE_SECS,
+    HOVER_CARD_DELAY_MS, MAINTENANCE_CHECK_MINUTES, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+use chrono::{Datelike, Local, NaiveDateTime};
+use iced::widget::scrollable::AbsoluteOffset;
+use iced::widget::text_editor;
+use iced::{Point, Size};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Position of the book list to return to once it reloads after leaving edit mode
+#[derive(Debug, Clone)]
+pub struct ScrollTarget {
+    pub book_id: ID,
+    pub index: usize,
+    pub list_len: usize,
+    pub offset: AbsoluteOffset,
+}
 
 pub struct BookshelfApp {
     // State
     pub current_tab: Tab,
     pub mode: Mode,
 
+    // Persisted settings
+    pub config: AppConfig,
+    pub database_url: Option<String>, // Given with --db, opened instead of DATABASE_URL
+    pub instance_lock: Option<InstanceLock>, // Held while this window has the database open
+    pub instance_conflict: Option<u32>, // Process holding the database, asked about at startup
+    pub read_only: bool, // Opened while another instance holds the database
+    pub confirm_exit: bool, // Closing was held back by unsaved work
+    pub tax_rate_input: String, // Saved to the config whenever it holds a valid rate
+    pub privacy_mode: bool, // Prices masked for screen sharing, starts from the settings
+
+    // Command palette overlay, None when closed
+    pub palette: Option<CommandPalette>,
+    pub show_key_help: bool, // Keyboard shortcut overlay opened with '?'
+    pub show_recently_deleted: bool,
+    pub print_view: bool, // Chrome-free view of the current list, drawn over it
+    pub recently_deleted: RecentlyDeleted, // This session's deletions, lost on exit
+
     // Sorting state
     pub sort_field: SortField,
     pub sort_direction: SortDirection,
@@ -17,56 +85,485 @@ pub struct BookshelfApp {
     pub search_term_displayed: String, // Static term that was searched for
     pub is_searching: bool,
     pub filtered_books: Option<Vec<BookWithAuthor>>,
+    pub gifts_only: bool, // Only list books received as gifts
+    pub missing_filter: Option<MissingField>, // Only list books lacking this value
+    pub language_filter: Option<String>, // Only list books in this language
+    pub acquired_from_filter: Option<String>, // Only list books acquired from this place
+    pub year_filter: Option<i32>, // Only list books whose year_filter_field date is in this year
+    pub year_filter_field: YearField,
+    pub filter_years: Vec<i32>, // Years any book has a date in, newest first
+    pub last_author_assignment: Option<AuthorAssignment>, // Undoable from the assign bar
 
     // Book state
     pub books: Vec<BookWithAuthor>,
+    pub books_loading: bool, // A LoadBooks query is in flight
     pub selected_book: Option<BookWithAuthor>,
     pub book_title: String,
     pub book_price: String,
     pub book_bought_date: String,
     pub book_finished_date: String,
+    pub book_is_gift: bool,
+    pub book_gift_from: String,
+    pub book_quantity: i32,
+    pub book_cover_path: Option<String>,
+    pub book_genre: String,
+    pub book_page_count: String,
+    pub book_format: Option<BookFormat>,
+    pub book_duration_minutes: String, // Only shown for audiobooks
+    pub book_language: Option<String>,
+    pub book_acquired_from: String,
+    pub book_rating: Option<i32>,
+    pub acquired_from_dropdown: SearchableDropdown<AcquisitionPlace>, // Places used before
+    pub cover_preview: Option<CoverPreview>, // Fetched cover waiting to be accepted
+    pub cover_fetching: bool,
     pub selected_author: Option<AuthorModel>,
+    pub selected_series: Option<SeriesModel>,
+    pub add_another_after_save: bool, // Consumed by BookSaved, reopens an empty form
+    // Bought and finished dates of the last saved book, offered to the next one
+    pub last_saved_dates: Option<(Option<NaiveDateTime>, Option<NaiveDateTime>)>,
+    pub books_added_this_session: usize, // Shown in the add form while entering a stack of books
+    pub draft_baseline: Option<BookDraft>, // Form as opened or as last autosaved
+    pub form_opened: Option<BookDraft>, // Form as opened, autosaves leave it alone
+    pub saving_book: bool, // A book save is in flight
+    pub restorable_draft: Option<BookDraft>, // Left by a previous run, offered before the views
+    pub pending_book_changes: Option<PendingBookChanges>, // Edit waiting for its review
+
+    // Book list scroll state
+    pub book_list_offset: AbsoluteOffset,
+    pub pending_scroll_target: Option<ScrollTarget>, // Consumed by the next BooksLoaded
+    pub pending_show_in_list: Option<ID>, // Book searched for once the next BooksLoaded arrives
+    pub highlighted_book: Option<ID>,
+    pub journal_entries: Vec<JournalEntryModel>, // Journal of the book being edited, newest first
+    pub journal_draft: text_editor::Content,
+    pub book_custom_fields: Vec<(String, String)>, // Key/value rows of the book form
+    pub custom_fields: HashMap<ID, Vec<BookFieldModel>>, // Custom fields of every book
+    pub book_quotes: Vec<QuoteModel>, // Quotes of the book being edited, newest first
+    pub quote_draft: text_editor::Content,
+    pub quote_page: String,
+    pub editing_quote: Option<ID>, // Quote loaded into the draft for editing
+    pub quote_counts: HashMap<ID, usize>, // Shown as a badge on the book rows
+    pub quote_browser: Option<QuoteBrowser>, // All quotes, opened from the statistics
+    pub book_loans: Vec<LoanModel>, // Loan history of the book being edited, newest first
+    pub loan_borrower: String,
+    pub loan_due_date: String,
+    pub confirm_clear_search: bool, // Clear was pressed once while several filters were active
+    pub confirm_reset_settings: bool, // Reset was pressed, the settings ask before resetting
+    pub resizing_column: Option<usize>, // Table divider being dragged, right of this column
+    pub dragging_splitter: bool, // Between the book list and the details pane
+    pub last_splitter_press: Option<Instant>, // Used to detect double clicks on the splitter
+    pub focused_row: Option<usize>, // Keyboard focus, an index into the displayed books
+    pub current_match: usize, // Search result stepped to with n / N, an index like focused_row
+    pub last_row_click: Option<(ID, Instant)>, // Used to detect double clicks on book rows
+    pub hovered_book: Option<(ID, Instant)>, // Book row under the cursor and since when
+    pub hover_card_shown: bool,
+    pub cursor_position: Point, // In the window, followed while a row is hovered
+    pub window_size: Size,
+
+    // Book selection for bulk actions
+    pub selected_books: HashSet<ID>,
+    pub move_target: String, // Path of the database selected books are moved to
+    pub pending_move: Option<MovePlan>, // Planned move waiting for confirmation
+    pub bought_date_form: Option<BoughtDateForm>, // "Set bought date for…" dialog
+    pub pending_bought_date: Option<BoughtDatePlan>, // Previewed bulk bought date
+    pub last_bought_date_change: Option<BoughtDateBackup>, // Undoable from the status area
+    pub csv_import: Option<CsvImport>, // Shown instead of the tabs while open
+    pub review_queue: Option<ReviewQueue>, // Books opened one by one in the form to fix an issue
+
+    // Books currently lent out, across the whole library
+    pub loans: Vec<LoanModel>,
+
+    // Reading session state
+    pub reading_sessions: Vec<ReadingSessionModel>, // Finished sessions of every book
+    pub reading_totals: HashMap<ID, SessionTotal>,
+    pub reading_timer: Option<ReadingTimer>, // Shown in a corner on every tab while running
+    pub timer_book: Option<TimerBook>, // Picked on the stats tab
 
     // Author dropdown state
     pub author_dropdown: SearchableDropdown<AuthorModel>,
 
     // Author state
     pub authors: Vec<AuthorModel>,
+    pub authors_loading: bool,
     pub current_author: Option<AuthorModel>,
+    pub current_book: Option<BookWithAuthor>, // Book shown read-only in the details view
+    pub delete_related: Option<(ID, RelatedRecords)>, // History of the book being deleted
+    pub delete_acknowledged: bool, // The loss of that history was accepted
     pub author_name: String,
+    pub author_website: String,
+    pub author_wikipedia: String,
+    pub author_published_works: String,
+    pub author_aliases: String, // Comma separated pen names
+    pub author_sort: AuthorSort,
     pub author_books: Vec<BookWithAuthor>, // Books by the current author
+    pub orphan_authors: Option<Vec<AuthorModel>>, // Authors without books, shown while cleaning up
+    pub orphan_selection: HashSet<ID>,
+    pub merge_suggestions: Option<Vec<MergeSuggestion>>, // Likely duplicate authors, while open
+    pub collapsed_sections: HashSet<SectionKey>, // Collapsed read status groups on author details
+    pub collapsed_authors: HashSet<Option<ID>>, // Collapsed author groups of the book list
+
+    // Reading list state
+    pub book_lists: Vec<BookListModel>,
+    pub active_list: Option<ActiveList>, // Shown instead of the book list while open
+    pub new_list_name: String,
+    pub book_menu: Option<ID>, // Book whose "⋯" menu is open
+
+    // Series state
+    pub series: Vec<SeriesModel>,
+    pub current_series: Option<SeriesModel>,
+    pub series_name: String,
+    pub series_total: String,
+
+    // Stats state
+    pub stats_year: i32,
+    pub report_format: ReportFormat,
+    pub report_path: String, // Empty means the default file in the exports directory
+    pub value_report_path: Option<String>, // Last written value report, offered for opening
+    pub shelf_export: Option<ShelfExport>, // Options dialog of the shareable shelf page
+
+    // Query console state
+    pub console_query: text_editor::Content,
+    pub console_output: Option<QueryOutput>,
+    pub console_error: Option<String>, // Shown inline under the query
+    pub console_running: bool,
+
+    pub backup_compare: BackupCompare, // Backups picked for comparison in the settings
+    pub maintenance_running: bool,
+    pub overnight_issues: Option<usize>, // Found by the nightly maintenance, shown until dismissed
 
     // Error handling
     pub error: Option<String>,
+    pub status_message: Option<String>,
+    pub toasts: Toasts, // Outcomes of long-running tasks, stacked in a corner
 }
 
 impl BookshelfApp {
+    /// Starts with the database given on the command line instead of DATABASE_URL
+    pub fn with_database_url(database_url: Option<String>) -> Self {
+        Self {
+            database_url,
+            ..Self::new()
+        }
+    }
+
     pub fn new() -> Self {
+        let config = config::load();
         Self {
+            database_url: None,
+            instance_lock: None,
+            instance_conflict: None,
+            read_only: false,
+            confirm_exit: false,
             current_tab: Tab::Books,
             mode: Mode::View,
+            privacy_mode: config.hide_prices,
+            tax_rate_input: config.tax_rate.to_string(),
+            config,
+            palette: None,
+            show_key_help: false,
+            show_recently_deleted: false,
+            print_view: false,
+            recently_deleted: RecentlyDeleted::default(),
             sort_field: SortField::Title,
             sort_direction: SortDirection::Ascending,
             search_query: String::new(),
             search_term_displayed: String::new(),
             is_searching: false,
             filtered_books: None,
+            gifts_only: false,
+            missing_filter: None,
+            language_filter: None,
+            acquired_from_filter: None,
+            year_filter: None,
+            year_filter_field: YearField::default(),
+            filter_years: Vec::new(),
+            last_author_assignment: None,
             books: Vec::new(),
+            books_loading: false,
             selected_book: None,
             book_title: String::new(),
             book_price: String::new(),
             book_bought_date: String::new(),
             book_finished_date: String::new(),
+            book_is_gift: false,
+            book_gift_from: String::new(),
+            book_quantity: 1,
+            book_cover_path: None,
+            book_genre: String::new(),
+            book_page_count: String::new(),
+            book_format: None,
+            book_duration_minutes: String::new(),
+            book_language: None,
+            book_acquired_from: String::new(),
+            book_rating: None,
+            acquired_from_dropdown: SearchableDropdown::new(Vec::new(), None),
+            cover_preview: None,
+            cover_fetching: false,
             selected_author: None,
+            selected_series: None,
+            add_another_after_save: false,
+            last_saved_dates: None,
+            books_added_this_session: 0,
+            draft_baseline: None,
+            form_opened: None,
+            saving_book: false,
+            restorable_draft: draft::load(),
+            pending_book_changes: None,
+            book_list_offset: AbsoluteOffset::default(),
+            pending_scroll_target: None,
+            pending_show_in_list: None,
+            highlighted_book: None,
+            journal_entries: Vec::new(),
+            journal_draft: text_editor::Content::new(),
+            book_custom_fields: Vec::new(),
+            custom_fields: HashMap::new(),
+            book_quotes: Vec::new(),
+            quote_draft: text_editor::Content::new(),
+            quote_page: String::new(),
+            editing_quote: None,
+            quote_counts: HashMap::new(),
+            quote_browser: None,
+            book_loans: Vec::new(),
+            loan_borrower: String::new(),
+            loan_due_date: String::new(),
+            confirm_clear_search: false,
+            confirm_reset_settings: false,
+            resizing_column: None,
+            dragging_splitter: false,
+            last_splitter_press: None,
+            focused_row: None,
+            current_match: 0,
+            last_row_click: None,
+            hovered_book: None,
+            hover_card_shown: false,
+            cursor_position: Point::ORIGIN,
+            window_size: Size::new(WINDOW_WIDTH, WINDOW_HEIGHT),
+            selected_books: HashSet::new(),
+            move_target: String::new(),
+            pending_move: None,
+            bought_date_form: None,
+            pending_bought_date: None,
+            last_bought_date_change: None,
+            csv_import: None,
+            review_queue: None,
+            loans: Vec::new(),
+            reading_sessions: Vec::new(),
+            reading_totals: HashMap::new(),
+            reading_timer: None,
+            timer_book: None,
             authors: Vec::new(),
+            authors_loading: false,
             current_author: None,
+            current_book: None,
+            delete_related: None,
+            delete_acknowledged: false,
             author_name: String::new(),
+            author_website: String::new(),
+            author_wikipedia: String::new(),
+            author_published_works: String::new(),
+            author_aliases: String::new(),
+            author_sort: AuthorSort::default(),
             author_books: Vec::new(),
+            orphan_authors: None,
+            orphan_selection: HashSet::new(),
+            merge_suggestions: None,
+            collapsed_sections: HashSet::new(),
+            collapsed_authors: HashSet::new(),
+            series: Vec::new(),
+            current_series: None,
+            series_name: String::new(),
+            series_total: String::new(),
+            book_lists: Vec::new(),
+            active_list: None,
+            new_list_name: String::new(),
+            book_menu: None,
+            stats_year: Local::now().year(),
+            report_format: ReportFormat::Json,
+            report_path: String::new(),
+            value_report_path: None,
+            shelf_export: None,
+            console_query: text_editor::Content::new(),
+            console_output: None,
+            console_error: None,
+            console_running: false,
+            backup_compare: BackupCompare::default(),
+            maintenance_running: false,
+            overnight_issues: None,
             error: None,
+            status_message: None,
+            toasts: Toasts::default(),
             author_dropdown: SearchableDropdown::new(Vec::new(), None),
         }
     }
 
+    pub fn fonts(&self) -> FontSizes {
+        FontSizes {
+            scale: self.config.font_scale,
+        }
+    }
+
+    /// Whether the lists can be reloaded behind the user's back: only in the plain list
+    /// view, with no search typed and no form, overlay or pending move open
+    pub fn can_refresh_while_idle(&self) -> bool {
+        matches!(self.mode, Mode::View)
+            && self.search_query.trim().is_empty()
+            && self.search_term_displayed.is_empty()
+            && self.palette.is_none()
+            && !self.show_key_help
+            && !self.print_view
+            && self.pending_move.is_none()
+            && self.bought_date_form.is_none()
+            && self.pending_bought_date.is_none()
+            && self.csv_import.is_none()
+            && self.restorable_draft.is_none()
+            && self.resizing_column.is_none()
+            && !self.dragging_splitter
+            && !self.books_loading
+            && !self.authors_loading
+    }
+
+    /// Total as shown in summaries, split into net and gross once a tax rate is set
+    pub fn format_total(&self, total: f32, currency: &str) -> String {
+        if self.config.tax_rate <= 0.0 {
+            return format_price(total, currency, self.privacy_mode);
+        }
+        let (net, gross) = apply_tax(total, self.config.tax_rate, self.config.prices_include_tax);
+        format!(
+            "{} net, {} gross",
+            format_price(net, currency, self.privacy_mode),
+            format_price(gross, currency, self.privacy_mode)
+        )
+    }
+
+    /// Fetches the data a tab displays
+    pub fn load_tab_data(&mut self, tab: Tab) -> iced::Task<Message> {
+        match tab {
+            Tab::Books => iced::Task::batch(vec![
+                self.update(Message::LoadBooks),
+                self.update(Message::LoadSeries),
+                self.update(Message::LoadLoans),
+                self.update(Message::LoadBookLists),
+                self.update(Message::LoadQuoteCounts),
+                self.update(Message::LoadCustomFields),
+            ]),
+            Tab::Series => iced::Task::batch(vec![
+                self.update(Message::LoadBooks),
+                self.update(Message::LoadSeries),
+            ]),
+            Tab::Authors => {
+                // Author rows count books, fetch them too unless they are already here
+                let mut tasks = vec![self.update(Message::LoadAuthors)];
+                if self.books.is_empty() && !self.books_loading {
+                    tasks.push(self.update(Message::LoadBooks));
+                }
+                iced::Task::batch(tasks)
+            }
+            Tab::Stats => iced::Task::batch(vec![
+                self.update(Message::LoadSeries),
+                self.update(Message::LoadBooks),
+                self.update(Message::LoadAuthors),
+                self.update(Message::LoadReadingSessions),
+            ]),
+            Tab::Settings => iced::Task::none(),
+        }
+    }
+
+    /// Shows an error in the UI and keeps a copy in the log file
+    pub fn report_error(&mut self, message: String) {
+        error_log::error(&message);
+        self.error = Some(message);
+    }
+
+    /// Reports a finished long-running task in the status area and as a toast, plus a
+    /// desktop notification when enabled, for users who switched to another window
+    pub fn notify_finished(&mut self, message: String) {
+        if self.config.desktop_notifications {
+            notifications::send("Bookshelf", &message);
+        }
+        self.toasts
+            .push(ToastKind::Success, message.clone(), Instant::now());
+        self.status_message = Some(message);
+    }
+
+    /// Like `report_error`, with a toast that stays until it is dismissed
+    pub fn notify_failed(&mut self, message: String) {
+        if self.config.desktop_notifications {
+            notifications::send("Bookshelf: something went wrong", &message);
+        }
+        self.toasts
+            .push(ToastKind::Failure, message.clone(), Instant::now());
+        self.report_error(message);
+    }
+
+    /// Number of books shown side by side in the current layout
+    pub fn books_per_row(&self) -> usize {
+        match self.config.list_mode {
+            ListMode::List | ListMode::Table | ListMode::Shelf => 1,
+            ListMode::Grid => self.config.books_per_row.max(1),
+        }
+    }
+
+    /// Number of independent filters currently narrowing the book list
+    pub fn active_filter_count(&self) -> usize {
+        // One entry per filter dimension
+        let filters = [
+            !self.search_query.trim().is_empty() || !self.search_term_displayed.is_empty(),
+            self.gifts_only,
+            self.missing_filter.is_some(),
+            self.language_filter.is_some(),
+            self.acquired_from_filter.is_some(),
+            self.year_filter.is_some(),
+        ];
+        filters.into_iter().filter(|active| *active).count()
+    }
+
+    /// Rebuilds the filtered list from the searched term and the other filters
+    pub fn apply_filters(&mut self) {
+        self.current_match = 0;
+        self.is_searching = !self.search_term_displayed.is_empty()
+            || self.gifts_only
+            || self.missing_filter.is_some()
+            || self.language_filter.is_some()
+            || self.acquired_from_filter.is_some()
+            || self.year_filter.is_some();
+        self.filtered_books = self.is_searching.then(|| {
+            let mut books = if self.search_term_displayed.is_empty() {
+                self.books.clone()
+            } else {
+                filter_books(&self.books, &self.search_term_displayed, self.config.search_fields)
+            };
+            if self.gifts_only {
+                books.retain(|pair| pair.book.is_gift());
+            }
+            if let Some(field) = self.missing_filter {
+                books.retain(|pair| book_is_missing(pair, field));
+            }
+            if let Some(code) = &self.language_filter {
+                books.retain(|pair| pair.book.language.as_ref() == Some(code));
+            }
+            if let Some(place) = &self.acquired_from_filter {
+                books.retain(|pair| {
+                    pair.book
+                        .acquired_from
+                        .as_deref()
+                        .is_some_and(|acquired| acquired.trim().eq_ignore_ascii_case(place))
+                });
+            }
+            if let Some(year) = self.year_filter {
+                books.retain(|pair| book_in_year(&pair.book, year, self.year_filter_field));
+            }
+            books
+        });
+    }
+
+    /// Books currently shown in the list, taking the active search into account
+    pub fn displayed_books(&self) -> &Vec<BookWithAuthor> {
+        if self.is_searching {
+            self.filtered_books.as_ref().unwrap_or(&self.books)
+        } else {
+            &self.books
+        }
+    }
+
     pub fn handle_toggle_author_dropdown(&mut self) -> iced::Task<Message> {
         self.author_dropdown.toggle();
         iced::Task::none()
@@ -79,17 +576,52 @@ impl BookshelfApp {
 
     pub fn update(&mut self, message: Message) -> iced::Task<Message> {
         match message {
+            Message::Toast(message) => {
+                self.toasts.update(message, Instant::now());
+                iced::Task::none()
+            }
             Message::Initialize => {
-                if let Err(e) = db::initialize_pool() {
-                    self.error = Some(format!("Failed to initialize database: {}", e));
+                // Read-only was chosen after another instance was found holding the lock
+                if !self.read_only && instance_view::lock_database(self) {
                     return iced::Task::none();
                 }
-                iced::Task::batch(vec![
-                    self.update(Message::LoadBooks),
-                    self.update(Message::LoadAuthors),
-                ])
+                let opened = match &self.database_url {
+                    Some(url) => db::initialize_pool_with_url(url, &self.config.database_pool),
+                    None => db::initialize_pool(&self.config.database_pool),
+                };
+                if let Err(e) = opened {
+                    self.report_error(format!("Failed to initialize database: {}", e));
+                    return iced::Task::none();
+                }
+                // Stays up until dismissed, the user may be looking for the old file
+                if let Some(notice) = db::take_legacy_database_notice() {
+                    error_log::error(&notice);
+                    self.toasts
+                        .push(ToastKind::Failure, notice.clone(), Instant::now());
+                    self.status_message = Some(notice);
+                }
+                // The restored search is applied once BooksLoaded filters the list
+                if self.config.remember_search && matches!(self.current_tab, Tab::Books) {
+                    self.search_query = self.config.last_search.clone();
+                    self.search_term_displayed = self.config.last_search.clone();
+                }
+                // Only the visible tab is loaded, the others load when first selected
+                let mut tasks = vec![
+                    self.load_tab_data(self.current_tab.clone()),
+                    self.update(Message::LoadReadingSessions),
+                ];
+                if !self.read_only {
+                    tasks.push(session_view::discard_unfinished_sessions());
+                }
+                iced::Task::batch(tasks)
             }
 
+            Message::OpenReadOnly => instance_view::handle_open_read_only(self),
+            Message::QuitApp => instance_view::handle_quit(self),
+            Message::RequestExit => instance_view::handle_request_exit(self),
+            Message::ConfirmExit => instance_view::handle_confirm_exit(self),
+            Message::CancelExit => instance_view::handle_cancel_exit(self),
+
             Message::TabSelected(tab) => {
                 self.current_tab = tab.clone();
                 self.mode = Mode::View;
@@ -97,13 +629,51 @@ impl BookshelfApp {
                 self.search_term_displayed = String::new();
                 self.is_searching = false;
                 self.filtered_books = None;
+                self.gifts_only = false;
+                self.missing_filter = None;
+                self.language_filter = None;
+                self.acquired_from_filter = None;
+                self.year_filter = None;
+                self.last_author_assignment = None;
+                self.confirm_clear_search = false;
+                self.pending_scroll_target = None;
+                self.pending_show_in_list = None;
+                self.highlighted_book = None;
+                self.focused_row = None;
+                hover_view::clear_hover(self);
+                self.status_message = None;
+                self.orphan_authors = None;
+                self.merge_suggestions = None;
+                self.pending_move = None;
+                self.bought_date_form = None;
+                self.pending_bought_date = None;
+                self.last_bought_date_change = None;
+                self.review_queue = None;
+                self.active_list = None;
+                self.book_menu = None;
+                self.pending_book_changes = None;
+                self.quote_browser = None;
 
-                match tab {
-                    Tab::Books => self.update(Message::LoadBooks),
-                    Tab::Authors => self.update(Message::LoadAuthors),
-                }
+                self.load_tab_data(tab)
             }
 
+            Message::RunInTab(tab, message) => iced::Task::batch(vec![
+                self.update(Message::TabSelected(tab)),
+                self.update(*message),
+            ]),
+            Message::ShowBookInList(id) => book_view::handle_show_book_in_list(self, id),
+
+            // Command palette messages handled in the palette module
+            Message::OpenCommandPalette => palette::handle_open_palette(self),
+            Message::CloseCommandPalette => palette::handle_close_palette(self),
+            Message::PaletteQueryChanged(query) => {
+                palette::handle_palette_query_changed(self, query)
+            }
+            Message::PaletteMoveSelection(delta) => {
+                palette::handle_palette_move_selection(self, delta)
+            }
+            Message::PaletteExecute(index) => palette::handle_palette_execute(self, index),
+
             // Sorting messages
             Message::SortFieldSelected(field) => {
                 self.sort_field = field;
@@ -115,16 +685,30 @@ impl BookshelfApp {
                 self.update(Message::ApplySorting)
             }
 
+            Message::SecondarySortSelected(field) => {
+                settings_view::handle_secondary_sort_selected(self, field)
+            }
+
             Message::ApplySorting => {
+                self.focused_row = None;
+
                 // Sort the books based on the selected field and direction
                 let books_to_sort = if self.is_searching {
                     self.filtered_books.as_mut()
+                } else if self.config.sort_in_database {
+                    // Search results stay in memory, the full list is re-fetched in order
+                    return self.update(Message::LoadBooks);
                 } else {
                     Some(&mut self.books)
                 };
 
                 if let Some(books) = books_to_sort {
-                    sort_books(books, &self.sort_field, &self.sort_direction);
+                    let empty = self.config.sort_empty_values;
+                    let secondary = self.config.secondary_sort.as_ref();
+                    sort_books(books, &self.sort_field, &self.sort_direction, secondary, empty);
+                    if self.config.pin_books_to_top {
+                        pinned_first(books);
+                    }
                 }
 
                 iced::Task::none()
@@ -135,80 +719,108 @@ impl BookshelfApp {
                 self.search_query = query;
                 iced::Task::none()
             }
+            Message::CancelClearSearch => {
+                self.confirm_clear_search = false;
+                iced::Task::none()
+            }
             Message::ToggleAuthorDropdown => self.handle_toggle_author_dropdown(),
+            Message::AuthorDropdownShortcut => book_view::handle_author_dropdown_shortcut(self),
             Message::AuthorSearchChanged(term) => self.handle_author_search_changed(term),
             Message::BookAuthorSelected(author) => {
                 self.selected_author = Some(author.clone());
                 self.author_dropdown.select(author);
                 iced::Task::none()
             }
+            Message::BookSeriesSelected(series) => {
+                self.selected_series = Some(series);
+                iced::Task::none()
+            }
+            Message::ClearBookSeries => {
+                self.selected_series = None;
+                iced::Task::none()
+            }
             Message::PerformSearch => {
-                if self.search_query.is_empty() {
-                    self.is_searching = false;
-                    self.filtered_books = None;
-                    return iced::Task::none();
-                }
-
-                self.is_searching = true;
+                self.focused_row = None;
 
                 // Perform local search in the Books tab
                 if let Tab::Books = self.current_tab {
-                    let query = self.search_query.to_lowercase();
-                    let filtered: Vec<BookWithAuthor> = self
-                        .books
-                        .iter()
-                        .filter(|book| {
-                            // Search by title
-                            let title_match = book.book.title.to_lowercase().contains(&query);
-
-                            // Search by author name
-                            let author_match = book
-                                .author
-                                .as_ref()
-                                .and_then(|a| a.Name.clone())
-                                .map(|name| name.to_lowercase().contains(&query))
-                                .unwrap_or(false);
-
-                            // Search by price - flexible matching without rounding
-                            let price_match = book.book.price.map_or(false, |price| {
-                                // Try to parse the query as a number (float or integer)
-                                if let Ok(query_num) = query.parse::<f32>() {
-                                    // Convert the price to string to check if it contains the query
-                                    let price_str = price.to_string();
-
-                                    // Check if the price starts with the query number
-                                    // (e.g., searching for "41" should match "41.99")
-                                    price_str.starts_with(&query_num.to_string()) ||
-
-                                        // Or a direct equality check for exact prices
-                                        (price == query_num)
-                                } else {
-                                    // If query isn't a valid number, check if price string contains the query
-                                    price.to_string().contains(&query)
-                                }
-                            });
-
-                            title_match || author_match || price_match
-                        })
-                        .cloned()
-                        .collect();
-
-                    self.filtered_books = Some(filtered);
                     self.search_term_displayed = self.search_query.clone();
+                    self.apply_filters();
 
                     // Apply current sorting to search results
-                    return self.update(Message::ApplySorting);
+                    let sorting = self.update(Message::ApplySorting);
+                    return iced::Task::batch([sorting, settings_view::remember_search(self)]);
                 }
 
                 iced::Task::none()
             }
+            Message::StartReview(issue) => review_view::handle_start_review(self, issue),
+            Message::ReviewSkip => review_view::handle_review_skip(self),
+            Message::ReviewPrevious => review_view::handle_review_previous(self),
+            Message::ReviewSelect(index) => review_view::handle_review_select(self, index),
+            Message::EndReview => review_view::finish_review(self),
+            Message::ToggleMissingFilter(field) => {
+                missing_view::handle_toggle_missing_filter(self, field)
+            }
+            Message::ToggleLanguageFilter(code) => {
+                book_view::handle_toggle_language_filter(self, code)
+            }
+            Message::AcquiredFromFilterSelected(place) => {
+                book_view::handle_acquired_from_filter_selected(self, place)
+            }
+            Message::YearFilterSelected(choice) => {
+                book_view::handle_year_filter_selected(self, choice)
+            }
+            Message::YearFilterFieldSelected(field) => {
+                book_view::handle_year_filter_field_selected(self, field)
+            }
+            Message::FilterYearsLoaded(result) => {
+                match result {
+                    Ok(years) => self.filter_years = years,
+                    Err(e) => self.report_error(format!("Failed to load the book years: {}", e)),
+                }
+                iced::Task::none()
+            }
+            Message::AssignAuthorToBook(id) => missing_view::handle_assign_author_to_book(self, id),
+            Message::AuthorAssigned(result) => missing_view::handle_author_assigned(self, result),
+            Message::UndoAuthorAssignment => missing_view::handle_undo_author_assignment(self),
+            Message::AuthorAssignmentUndone(result) => {
+                missing_view::handle_author_assignment_undone(self, result)
+            }
+            Message::ToggleSearchField(field, enabled) => {
+                book_view::handle_toggle_search_field(self, field, enabled)
+            }
+            Message::ToggleStickyField(field, enabled) => {
+                settings_view::handle_toggle_sticky_field(self, field, enabled)
+            }
+            Message::ToggleGiftsOnly(enabled) => {
+                self.focused_row = None;
+                self.gifts_only = enabled;
+                self.apply_filters();
+                self.update(Message::ApplySorting)
+            }
 
             Message::ClearSearch => {
+                // Ask before wiping a view composed from several filters
+                if self.active_filter_count() > 1 && !self.confirm_clear_search {
+                    self.confirm_clear_search = true;
+                    return iced::Task::none();
+                }
+
+                self.confirm_clear_search = false;
+                self.status_message = None;
+                self.focused_row = None;
                 self.search_query = String::new();
                 self.search_term_displayed = String::new();
                 self.is_searching = false;
                 self.filtered_books = None;
-                iced::Task::none()
+                self.gifts_only = false;
+                self.missing_filter = None;
+                self.language_filter = None;
+                self.acquired_from_filter = None;
+                self.year_filter = None;
+                self.last_author_assignment = None;
+                settings_view::remember_search(self)
             }
 
             // Book messages handled in the book module
@@ -219,27 +831,462 @@ impl BookshelfApp {
                 if !self.books.is_empty() {
                     let _ = self.update(Message::ApplySorting);
                 }
-                command
+                iced::Task::batch(vec![
+                    command,
+                    review_view::refresh_review(self),
+                    book_view::load_filter_years(),
+                ])
             }
             Message::AddBookMode => book_view::handle_add_book_mode(self),
             Message::EditBookMode(book) => book_view::handle_edit_book_mode(self, &book),
             Message::ViewBookMode => book_view::handle_view_book_mode(self),
+            Message::ViewBookDetails(pair) => book_view::handle_view_book_details(self, pair),
+            Message::CloseBookDetails => book_view::handle_close_book_details(self),
             Message::BookTitleChanged(value) => book_view::handle_book_title_changed(self, value),
             Message::BookPriceChanged(value) => book_view::handle_book_price_changed(self, value),
             Message::BookBoughtDateChanged(value) => {
                 book_view::handle_book_bought_date_changed(self, value)
             }
+            Message::AutosaveDraft => draft_view::handle_autosave_draft(self),
+            Message::RestoreDraft => draft_view::handle_restore_draft(self),
+            Message::DiscardDraft => draft_view::handle_discard_draft(self),
+            Message::DraftRecordsLoaded(result) => {
+                draft_view::handle_draft_records_loaded(self, *result)
+            }
+            Message::FetchCover => cover_view::handle_fetch_cover(self),
+            Message::CoverFetched(result) => cover_view::handle_cover_fetched(self, result),
+            Message::AcceptCover => cover_view::handle_accept_cover(self),
+            Message::DiscardCover => cover_view::handle_discard_cover(self),
+            Message::RemoveCover => cover_view::handle_remove_cover(self),
+            Message::ChangeBookQuantity(delta) => {
+                book_view::handle_change_book_quantity(self, delta)
+            }
+            Message::BookIsGiftToggled(is_gift) => {
+                book_view::handle_book_is_gift_toggled(self, is_gift)
+            }
+            Message::BookGenreChanged(value) => {
+                self.book_genre = value;
+                iced::Task::none()
+            }
+            Message::BookPageCountChanged(value) => {
+                self.book_page_count = value;
+                iced::Task::none()
+            }
+            Message::BookFormatSelected(format) => {
+                self.book_format = format;
+                iced::Task::none()
+            }
+            Message::BookDurationChanged(value) => {
+                self.book_duration_minutes = value;
+                iced::Task::none()
+            }
+            Message::BookLanguageSelected(language) => {
+                self.book_language = language.map(|language| language.0);
+                iced::Task::none()
+            }
+            Message::BookRatingSelected(rating) => {
+                self.book_rating = rating;
+                iced::Task::none()
+            }
+            Message::BookAcquiredFromChanged(value) => {
+                self.book_acquired_from = value;
+                iced::Task::none()
+            }
+            Message::ToggleAcquiredFromDropdown => {
+                self.acquired_from_dropdown.toggle();
+                iced::Task::none()
+            }
+            Message::AcquiredFromSearchChanged(term) => {
+                self.acquired_from_dropdown.search(term);
+                iced::Task::none()
+            }
+            Message::AcquiredFromSelected(place) => {
+                // Only fills the input, the dropdown keeps offering the places
+                self.book_acquired_from = place.0;
+                self.acquired_from_dropdown.close();
+                iced::Task::none()
+            }
+            Message::BookGiftFromChanged(value) => {
+                book_view::handle_book_gift_from_changed(self, value)
+            }
             Message::BookFinishedDateChanged(value) => {
                 book_view::handle_book_finished_date_changed(self, value)
             }
-            Message::SaveBook => book_view::handle_save_book(self),
+            Message::CopyPreviousDates => book_view::handle_copy_previous_dates(self),
+            Message::SaveBook => {
+                self.add_another_after_save = false;
+                book_view::handle_save_book(self)
+            }
+            Message::SaveBookAndAddAnother => {
+                self.add_another_after_save = book_view::offers_add_another(self);
+                book_view::handle_save_book(self)
+            }
             Message::BookSaved(result) => book_view::handle_book_saved(self, result),
             Message::ConfirmDeleteBook(id, title) => {
                 book_view::handle_confirm_delete_book(self, id, title)
             }
             Message::CancelDeleteBook => book_view::handle_cancel_delete_book(self),
             Message::DeleteBook(id) => book_view::handle_delete_book(self, id),
+            Message::RelatedRecordsCounted(id, result) => {
+                book_view::handle_related_records_counted(self, id, result)
+            }
+            Message::ToggleDeleteAcknowledged(acknowledged) => {
+                self.delete_acknowledged = acknowledged;
+                iced::Task::none()
+            }
             Message::BookDeleted(result) => book_view::handle_book_deleted(self, result),
+            Message::ToggleBookPinned(id) => book_view::handle_toggle_book_pinned(self, id),
+            Message::BookPinned(result) => book_view::handle_book_pinned(self, result),
+            Message::BookListScrolled(viewport) => {
+                self.book_list_offset = viewport.absolute_offset();
+                iced::Task::none()
+            }
+            Message::ClearBookHighlight => {
+                self.highlighted_book = None;
+                iced::Task::none()
+            }
+            // Journal messages handled in the journal module
+            Message::LoadJournal(book_id) => journal_view::handle_load_journal(self, book_id),
+            Message::JournalLoaded(result) => journal_view::handle_journal_loaded(self, result),
+            Message::JournalDraftAction(action) => {
+                journal_view::handle_journal_draft_action(self, action)
+            }
+            Message::AddJournalEntry => journal_view::handle_add_journal_entry(self),
+            Message::JournalEntryAdded(result) => {
+                journal_view::handle_journal_entry_added(self, result)
+            }
+            Message::DeleteJournalEntry(id) => journal_view::handle_delete_journal_entry(self, id),
+            Message::JournalEntryDeleted(result) => {
+                journal_view::handle_journal_entry_deleted(self, result)
+            }
+            // Custom field messages handled in the custom field module
+            Message::LoadCustomFields => custom_field_view::handle_load_custom_fields(self),
+            Message::CustomFieldsLoaded(result) => {
+                custom_field_view::handle_custom_fields_loaded(self, result)
+            }
+            Message::AddCustomField => custom_field_view::handle_add_custom_field(self),
+            Message::RemoveCustomField(index) => {
+                custom_field_view::handle_remove_custom_field(self, index)
+            }
+            Message::CustomFieldKeyChanged(index, key) => {
+                custom_field_view::handle_key_changed(self, index, key)
+            }
+            Message::CustomFieldValueChanged(index, value) => {
+                custom_field_view::handle_value_changed(self, index, value)
+            }
+            // Quote messages handled in the quote module
+            Message::LoadQuoteCounts => quote_view::handle_load_quote_counts(self),
+            Message::QuoteCountsLoaded(result) => {
+                quote_view::handle_quote_counts_loaded(self, result)
+            }
+            Message::LoadBookQuotes(book_id) => quote_view::handle_load_book_quotes(self, book_id),
+            Message::BookQuotesLoaded(result) => {
+                quote_view::handle_book_quotes_loaded(self, result)
+            }
+            Message::QuoteDraftAction(action) => {
+                quote_view::handle_quote_draft_action(self, action)
+            }
+            Message::QuotePageChanged(value) => quote_view::handle_quote_page_changed(self, value),
+            Message::SaveQuote => quote_view::handle_save_quote(self),
+            Message::QuoteSaved(result) => quote_view::handle_quote_saved(self, result),
+            Message::EditQuote(id) => quote_view::handle_edit_quote(self, id),
+            Message::CancelQuoteEdit => quote_view::handle_cancel_quote_edit(self),
+            Message::DeleteQuote(id) => quote_view::handle_delete_quote(self, id),
+            Message::QuoteDeleted(result) => quote_view::handle_quote_deleted(self, result),
+            Message::CopyQuote(id) => quote_view::handle_copy_quote(self, id),
+            Message::OpenQuoteBrowser => quote_view::handle_open_browser(self),
+            Message::AllQuotesLoaded(result) => quote_view::handle_all_quotes_loaded(self, result),
+            Message::QuoteSearchChanged(search) => quote_view::handle_search_changed(self, search),
+            Message::CloseQuoteBrowser => quote_view::handle_close_browser(self),
+            // Loan messages handled in the loan module
+            Message::LoadLoans => loan_view::handle_load_loans(self),
+            Message::LoansLoaded(result) => loan_view::handle_loans_loaded(self, result),
+            Message::LoadBookLoans(book_id) => loan_view::handle_load_book_loans(self, book_id),
+            Message::BookLoansLoaded(result) => loan_view::handle_book_loans_loaded(self, result),
+            Message::LoanBorrowerChanged(value) => {
+                loan_view::handle_loan_borrower_changed(self, value)
+            }
+            Message::LoanDueDateChanged(value) => {
+                loan_view::handle_loan_due_date_changed(self, value)
+            }
+            Message::LendBook => loan_view::handle_lend_book(self),
+            Message::LoanCreated(result) => loan_view::handle_loan_created(self, result),
+            Message::MarkLoanReturned(id) => loan_view::handle_mark_loan_returned(self, id),
+            Message::LoanReturned(result) => loan_view::handle_loan_returned(self, result),
+
+            // Reading session messages handled in the session module
+            Message::LoadReadingSessions => session_view::handle_load_reading_sessions(self),
+            Message::ReadingSessionsLoaded(result) => {
+                session_view::handle_reading_sessions_loaded(self, result)
+            }
+            Message::UnfinishedSessionsDiscarded(result) => {
+                session_view::handle_unfinished_sessions_discarded(self, result)
+            }
+            Message::TimerBookSelected(book) => {
+                session_view::handle_timer_book_selected(self, book)
+            }
+            Message::StartReadingSession(book_id) => {
+                session_view::handle_start_session(self, book_id)
+            }
+            Message::ReadingSessionStarted(result) => {
+                session_view::handle_session_started(self, result)
+            }
+            Message::ReadingTimerTick => session_view::handle_timer_tick(self),
+            Message::TimerPagesChanged(value) => {
+                session_view::handle_timer_pages_changed(self, value)
+            }
+            Message::StopReadingSession => session_view::handle_stop_session(self),
+            Message::ReadingSessionStopped(result) => {
+                session_view::handle_session_stopped(self, result)
+            }
+            Message::DiscardReadingSession => session_view::handle_discard_session(self),
+            Message::ReadingSessionDiscarded(result) => {
+                session_view::handle_session_discarded(self, result)
+            }
+
+            Message::KeyPressed(key, modifiers) => {
+                let context = keymap::KeyContext {
+                    focused_book: book_view::focused_book(self)
+                        .filter(|_| !self.show_recently_deleted),
+                    help_open: self.show_key_help,
+                    print_view_open: self.print_view,
+                    details_book: book_view::details_book(self),
+                    search_matches: book_view::has_search_matches(self)
+                        && !self.show_recently_deleted,
+                };
+                match keymap::map_key(&key, modifiers, &context) {
+                    Some(message) => self.update(message),
+                    None => iced::Task::none(),
+                }
+            }
+            Message::ToggleKeyHelp => {
+                self.show_key_help = !self.show_key_help;
+                iced::Task::none()
+            }
+            Message::TogglePrintView => print_view::handle_toggle_print_view(self),
+            Message::OpenPrintPage => print_view::handle_open_print_page(self),
+            Message::PrintPageOpened(result) => print_view::handle_print_page_opened(self, result),
+            Message::ToggleRecentlyDeleted => trash_view::handle_toggle_recently_deleted(self),
+            Message::RestoreDeleted(index) => trash_view::handle_restore_deleted(self, index),
+            Message::DeletedRestored(entry, result) => {
+                trash_view::handle_deleted_restored(self, entry, result)
+            }
+            Message::ToggleBookFinishedToday(id) => {
+                book_view::handle_toggle_finished_today(self, id)
+            }
+            Message::ToggleBookBoughtToday(id) => book_view::handle_toggle_bought_today(self, id),
+            Message::BookQuickUpdated(result) => book_view::handle_book_quick_updated(self, result),
+            Message::BookRatingChanged(id, rating) => {
+                book_view::handle_book_rating_changed(self, id, rating)
+            }
+            Message::BookRatingSaved(result) => book_view::handle_book_rating_saved(self, result),
+            Message::MoveFocusedRow(delta) => book_view::handle_move_focused_row(self, delta),
+            Message::OpenFocusedRow => book_view::handle_open_focused_row(self),
+            Message::BookRowClicked(id) => book_view::handle_book_row_clicked(self, id),
+            Message::StepSearchMatch(delta) => book_view::handle_step_search_match(self, delta),
+            Message::BookHoverStarted(id) => hover_view::handle_hover_started(self, id),
+            Message::BookHoverEnded(id) => hover_view::handle_hover_ended(self, id),
+            Message::HoverTick => hover_view::handle_hover_tick(self),
+            Message::HoverCursorMoved(position) => hover_view::handle_cursor_moved(self, position),
+            Message::WindowResized(size) => hover_view::handle_window_resized(self, size),
+            Message::ToggleSortInDatabase(enabled) => {
+                settings_view::handle_toggle_sort_in_database(self, enabled)
+            }
+            Message::CopyBooksAsMarkdown => book_view::handle_copy_books_as_markdown(self),
+            Message::ToggleStatsStrip(enabled) => {
+                settings_view::handle_toggle_stats_strip(self, enabled)
+            }
+            Message::TogglePinBooksToTop(enabled) => {
+                settings_view::handle_toggle_pin_books_to_top(self, enabled)
+            }
+            Message::SortEmptyValuesSelected(empty) => {
+                settings_view::handle_sort_empty_values_selected(self, empty)
+            }
+            Message::ToggleAveragePricedOnly(enabled) => {
+                self.config.average_priced_only = enabled;
+                settings_view::save_config(&self.config)
+            }
+            Message::ToggleReadingDuration(enabled) => {
+                settings_view::handle_toggle_reading_duration(self, enabled)
+            }
+            Message::ToggleRememberSearch(enabled) => {
+                settings_view::handle_toggle_remember_search(self, enabled)
+            }
+            Message::TogglePrivacyMode => settings_view::handle_toggle_privacy_mode(self),
+            Message::ToggleHidePrices(enabled) => {
+                settings_view::handle_toggle_hide_prices(self, enabled)
+            }
+            Message::OpenLogFile => settings_view::handle_open_log_file(self),
+            Message::ResetSettings => settings_view::handle_reset_settings(self),
+            Message::ConfirmResetSettings => settings_view::handle_confirm_reset_settings(self),
+            Message::CancelResetSettings => settings_view::handle_cancel_reset_settings(self),
+            Message::ToggleQueryConsole(enabled) => {
+                settings_view::handle_toggle_query_console(self, enabled)
+            }
+            Message::ConsoleQueryAction(action) => {
+                console_view::handle_console_query_action(self, action)
+            }
+            Message::RunConsoleQuery => console_view::handle_run_console_query(self),
+            Message::ConsoleQueryFinished(result) => {
+                console_view::handle_console_query_finished(self, result)
+            }
+            Message::WriteBackup => backup_view::handle_write_backup(self),
+            Message::BackupWritten(result) => backup_view::handle_backup_written(self, result),
+            Message::BackupOlderChanged(path) => backup_view::handle_older_changed(self, path),
+            Message::BackupNewerChanged(path) => backup_view::handle_newer_changed(self, path),
+            Message::CompareBackups => backup_view::handle_compare(self),
+            Message::BackupsCompared(result) => backup_view::handle_compared(self, result),
+            Message::SaveBackupDiff => backup_view::handle_save_diff(self),
+            Message::BackupDiffSaved(result) => backup_view::handle_diff_saved(self, result),
+            Message::CloseBackupDiff => backup_view::handle_close_diff(self),
+            Message::RestoreBackup => backup_view::handle_restore(self),
+            Message::ConfirmRestoreBackup => backup_view::handle_confirm_restore(self),
+            Message::CancelRestoreBackup => backup_view::handle_cancel_restore(self),
+            Message::BackupRestored(result) => backup_view::handle_restored(self, result),
+            Message::ToggleScientificPrices(enabled) => {
+                settings_view::handle_toggle_scientific_prices(self, enabled)
+            }
+            Message::ToggleBoughtWhenFinished(enabled) => {
+                settings_view::handle_toggle_bought_when_finished(self, enabled)
+            }
+            Message::ToggleConfirmExit(enabled) => {
+                settings_view::handle_toggle_confirm_exit(self, enabled)
+            }
+            Message::ToggleReviewChanges(enabled) => {
+                settings_view::handle_toggle_review_changes(self, enabled)
+            }
+            Message::ConfirmBookChanges => changes_view::handle_confirm(self),
+            Message::CancelBookChanges => changes_view::handle_back(self),
+            Message::ToggleConfirmBulkActions(enabled) => {
+                settings_view::handle_toggle_confirm_bulk_actions(self, enabled)
+            }
+            Message::ListModeSelected(mode) => settings_view::handle_list_mode_selected(self, mode),
+            Message::ToggleGroupByAuthor(enabled) => {
+                settings_view::handle_toggle_group_by_author(self, enabled)
+            }
+            Message::ToggleGroupBySeries(enabled) => {
+                settings_view::handle_toggle_group_by_series(self, enabled)
+            }
+            Message::ToggleAuthorGroup(author_id) => {
+                book_view::handle_toggle_author_group(self, author_id)
+            }
+            Message::StartColumnResize(divider) => {
+                table_view::handle_start_column_resize(self, divider)
+            }
+            Message::ColumnResizeMoved(position) => {
+                table_view::handle_column_resize_moved(self, position)
+            }
+            Message::EndColumnResize => table_view::handle_end_column_resize(self),
+            Message::ToggleDetailsPane(enabled) => {
+                split_view::handle_toggle_details_pane(self, enabled)
+            }
+            Message::ToggleOpenDetailsOnClick(enabled) => {
+                settings_view::handle_toggle_open_details_on_click(self, enabled)
+            }
+            Message::StartSplitDrag => split_view::handle_start_split_drag(self),
+            Message::SplitDragMoved(ratio) => split_view::handle_split_drag_moved(self, ratio),
+            Message::EndSplitDrag => split_view::handle_end_split_drag(self),
+            Message::ChangeBooksPerRow(steps) => {
+                settings_view::handle_change_books_per_row(self, steps)
+            }
+            Message::ToggleBookSelection(id) => book_view::handle_toggle_book_selection(self, id),
+            Message::ClearBookSelection => book_view::handle_clear_book_selection(self),
+            Message::MoveTargetChanged(value) => book_view::handle_move_target_changed(self, value),
+            Message::MoveSelectedBooks => book_view::handle_move_selected_books(self),
+            Message::MovePlanned(result) => book_view::handle_move_planned(self, result),
+            Message::ResolveMoveAuthor(index, resolution) => {
+                book_view::handle_resolve_move_author(self, index, resolution)
+            }
+            Message::ApplyPendingMove => book_view::handle_apply_pending_move(self),
+
+            // Bulk bought date messages handled in the bought date module
+            Message::OpenBoughtDateDialog => bought_date_view::handle_open_dialog(self),
+            Message::CloseBoughtDateDialog => {
+                self.bought_date_form = None;
+                iced::Task::none()
+            }
+            Message::BoughtDateTargetSelected(target) => {
+                if let Some(form) = &mut self.bought_date_form {
+                    form.target = target;
+                }
+                iced::Task::none()
+            }
+            Message::BoughtDateFromChanged(value) => {
+                if let Some(form) = &mut self.bought_date_form {
+                    form.from = value;
+                }
+                iced::Task::none()
+            }
+            Message::BoughtDateToChanged(value) => {
+                if let Some(form) = &mut self.bought_date_form {
+                    form.to = value;
+                }
+                iced::Task::none()
+            }
+            Message::BoughtDateInputChanged(value) => {
+                if let Some(form) = &mut self.bought_date_form {
+                    form.date = value;
+                }
+                iced::Task::none()
+            }
+            Message::PreviewBoughtDate => bought_date_view::handle_preview(self),
+            Message::BoughtDatePlanned(result) => bought_date_view::handle_planned(self, result),
+            Message::ToggleBoughtDateOverwrite(overwrite) => {
+                bought_date_view::handle_toggle_overwrite(self, overwrite)
+            }
+            Message::ApplyBoughtDate => bought_date_view::handle_apply(self),
+            Message::CancelBoughtDate => {
+                self.pending_bought_date = None;
+                iced::Task::none()
+            }
+            Message::BoughtDateApplied(result) => bought_date_view::handle_applied(self, result),
+            Message::UndoBoughtDate => bought_date_view::handle_undo(self),
+            Message::BoughtDateUndone(result) => bought_date_view::handle_undone(self, result),
+
+            // CSV import messages handled in the import module
+            Message::OpenCsvImport => import_view::handle_open_csv_import(self),
+            Message::CloseCsvImport => import_view::handle_close_csv_import(self),
+            Message::CsvImportPathChanged(value) => {
+                import_view::handle_csv_import_path_changed(self, value)
+            }
+            Message::ReadCsvImport => import_view::handle_read_csv_import(self),
+            Message::CsvImportRead(result) => import_view::handle_csv_import_read(self, result),
+            Message::CsvImportFieldSelected(column, field) => {
+                import_view::handle_csv_import_field_selected(self, column, field)
+            }
+            Message::CsvImportPresetNameChanged(value) => {
+                import_view::handle_csv_import_preset_name_changed(self, value)
+            }
+            Message::SaveCsvImportPreset => import_view::handle_save_csv_import_preset(self),
+            Message::CsvImportPresetSelected(name) => {
+                import_view::handle_csv_import_preset_selected(self, name)
+            }
+            Message::DeleteCsvImportPreset(name) => {
+                import_view::handle_delete_csv_import_preset(self, name)
+            }
+            Message::RunCsvImport => import_view::handle_run_csv_import(self),
+            Message::CsvImportPlanned(result) => {
+                import_view::handle_csv_import_planned(self, result)
+            }
+            Message::CsvImportDecisionSelected(index, decision) => {
+                import_view::handle_csv_import_decision_selected(self, index, decision)
+            }
+            Message::CsvImportDecideAll(decision) => {
+                import_view::handle_csv_import_decide_all(self, decision)
+            }
+            Message::ResolveCsvImportAuthor(index, resolution) => {
+                import_view::handle_resolve_csv_import_author(self, index, resolution)
+            }
+            Message::ConfirmCsvImport => import_view::handle_confirm_csv_import(self),
+            Message::CancelCsvImportConfirmation => {
+                if let Some(csv_import) = &mut self.csv_import {
+                    csv_import.plan = None;
+                }
+                iced::Task::none()
+            }
+            Message::CsvImported(result) => import_view::handle_csv_imported(self, result),
+            Message::CancelPendingMove => book_view::handle_cancel_pending_move(self),
+            Message::BooksMoved(result) => book_view::handle_books_moved(self, result),
 
             // Author messages handled in the author module
             Message::LoadAuthors => author_view::handle_load_authors(self),
@@ -253,9 +1300,31 @@ impl BookshelfApp {
             Message::AuthorBooksLoaded(result) => {
                 author_view::handle_author_books_loaded(self, result)
             }
+            Message::ToggleAuthorSection(key) => author_view::handle_toggle_author_section(self, key),
             Message::AuthorNameChanged(value) => {
                 author_view::handle_author_name_changed(self, value)
             }
+            Message::AuthorWebsiteChanged(value) => {
+                author_view::handle_author_website_changed(self, value)
+            }
+            Message::AuthorWikipediaChanged(value) => {
+                author_view::handle_author_wikipedia_changed(self, value)
+            }
+            Message::AuthorPublishedWorksChanged(value) => {
+                author_view::handle_author_published_works_changed(self, value)
+            }
+            Message::AuthorAliasesChanged(value) => {
+                author_view::handle_author_aliases_changed(self, value)
+            }
+            Message::AuthorSortSelected(sort) => {
+                author_view::handle_author_sort_selected(self, sort)
+            }
+            Message::OpenUrl(url) => {
+                if let Err(e) = links::open_in_browser(&url) {
+                    self.report_error(format!("Failed to open link: {}", e));
+                }
+                iced::Task::none()
+            }
             Message::SaveAuthor => author_view::handle_save_author(self),
             Message::AuthorSaved(result) => author_view::handle_author_saved(self, result),
             Message::ConfirmDeleteAuthor(id, name) => {
@@ -264,9 +1333,197 @@ impl BookshelfApp {
             Message::CancelDeleteAuthor => author_view::handle_cancel_delete_author(self),
             Message::DeleteAuthor(id) => author_view::handle_delete_author(self, id),
             Message::AuthorDeleted(result) => author_view::handle_author_deleted(self, result),
+            Message::ExportAuthors => author_view::handle_export_authors(self),
+            Message::AuthorsExported(result) => author_view::handle_authors_exported(self, result),
+            Message::FindOrphanAuthors => author_view::handle_find_orphan_authors(self),
+            Message::JumpToAuthorLetter(letter) => author_view::handle_jump_to_letter(self, letter),
+            Message::OrphanAuthorsLoaded(result) => {
+                author_view::handle_orphan_authors_loaded(self, result)
+            }
+            Message::ToggleOrphanAuthor(id) => author_view::handle_toggle_orphan_author(self, id),
+            Message::DeleteOrphanAuthors => author_view::handle_delete_orphan_authors(self),
+            Message::OrphanAuthorsDeleted(result) => {
+                author_view::handle_orphan_authors_deleted(self, result)
+            }
+            Message::CloseOrphanAuthors => author_view::handle_close_orphan_authors(self),
+            Message::FindMergeSuggestions => author_view::handle_find_merge_suggestions(self),
+            Message::MergeDismissalsLoaded(result) => {
+                author_view::handle_merge_dismissals_loaded(self, result)
+            }
+            Message::MergeKeepSelected(pair, keep) => {
+                author_view::handle_merge_keep_selected(self, pair, keep)
+            }
+            Message::AcceptMerge(pair) => author_view::handle_accept_merge(self, pair),
+            Message::AuthorsMerged(result) => author_view::handle_authors_merged(self, result),
+            Message::DismissMerge(pair) => author_view::handle_dismiss_merge(self, pair),
+            Message::MergeDismissed(result) => author_view::handle_merge_dismissed(self, result),
+            Message::ToggleMergeFoldDiacritics(fold) => {
+                author_view::handle_toggle_merge_fold_diacritics(self, fold)
+            }
+            Message::CloseMergeSuggestions => {
+                self.merge_suggestions = None;
+                iced::Task::none()
+            }
+
+            // Reading list messages handled in the list module
+            Message::LoadBookLists => list_view::handle_load_lists(),
+            Message::BookListsLoaded(result) => list_view::handle_lists_loaded(self, result),
+            Message::NewBookListNameChanged(value) => {
+                self.new_list_name = value;
+                iced::Task::none()
+            }
+            Message::CreateBookList => list_view::handle_create_list(self),
+            Message::BookListCreated(result) => list_view::handle_list_created(self, result),
+            Message::OpenBookList(list) => list_view::handle_open_list(self, list),
+            Message::CloseBookList => list_view::handle_close_list(self),
+            Message::BookListItemsLoaded(list_id, result) => {
+                list_view::handle_items_loaded(self, list_id, result)
+            }
+            Message::ToggleBookMenu(book_id) => list_view::handle_toggle_book_menu(self, book_id),
+            Message::AddBookToList(list_id, book_id) => {
+                list_view::handle_add_to_list(self, list_id, book_id)
+            }
+            Message::MoveBookInList(book_id, earlier) => {
+                list_view::handle_move_in_list(self, book_id, earlier)
+            }
+            Message::RemoveBookFromList(book_id) => {
+                list_view::handle_remove_from_list(self, book_id)
+            }
+            Message::BookListRenameChanged(value) => list_view::handle_rename_changed(self, value),
+            Message::RenameBookList => list_view::handle_rename_list(self),
+            Message::DeleteBookList => list_view::handle_delete_list(self),
+
+            // Series messages handled in the series module
+            Message::LoadSeries => series_view::handle_load_series(self),
+            Message::SeriesLoaded(result) => series_view::handle_series_loaded(self, result),
+            Message::AddSeriesMode => series_view::handle_add_series_mode(self),
+            Message::EditSeriesMode(series) => series_view::handle_edit_series_mode(self, series),
+            Message::ViewSeriesMode => series_view::handle_view_series_mode(self),
+            Message::SeriesNameChanged(value) => {
+                series_view::handle_series_name_changed(self, value)
+            }
+            Message::SeriesTotalChanged(value) => {
+                series_view::handle_series_total_changed(self, value)
+            }
+            Message::SaveSeries => series_view::handle_save_series(self),
+            Message::SeriesSaved(result) => series_view::handle_series_saved(self, result),
+            Message::ConfirmDeleteSeries(id, name) => {
+                series_view::handle_confirm_delete_series(self, id, name)
+            }
+            Message::CancelDeleteSeries => series_view::handle_cancel_delete_series(self),
+            Message::DeleteSeries(id) => series_view::handle_delete_series(self, id),
+            Message::SeriesDeleted(result) => series_view::handle_series_deleted(self, result),
+
+            // Stats messages handled in the stats module
+            Message::StatsYearSelected(year) => stats_view::handle_stats_year_selected(self, year),
+            Message::CopyYearInReview => stats_view::handle_copy_year_in_review(self),
+            Message::ExportYearInReviewImage => {
+                stats_view::handle_export_year_in_review_image(self)
+            }
+            Message::YearInReviewScreenshot(screenshot) => {
+                stats_view::handle_year_in_review_screenshot(self, screenshot)
+            }
+            Message::YearInReviewExported(result) => {
+                stats_view::handle_year_in_review_exported(self, result)
+            }
+            Message::ReportFormatSelected(format) => {
+                stats_view::handle_report_format_selected(self, format)
+            }
+            Message::ReportPathChanged(value) => stats_view::handle_report_path_changed(self, value),
+            Message::CsvDateFormatSelected(format) => {
+                stats_view::handle_csv_date_format_selected(self, format)
+            }
+            Message::CsvDecimalSeparatorSelected(separator) => {
+                stats_view::handle_csv_decimal_separator_selected(self, separator)
+            }
+            Message::CsvQuotingSelected(quoting) => {
+                stats_view::handle_csv_quoting_selected(self, quoting)
+            }
+            Message::ToggleCsvByteOrderMark(enabled) => {
+                stats_view::handle_toggle_csv_byte_order_mark(self, enabled)
+            }
+            Message::ExportReport => stats_view::handle_export_report(self),
+            Message::ReportExported(result) => stats_view::handle_report_exported(self, result),
+            Message::StaleUnreadDaysSelected(days) => {
+                stats_view::handle_stale_unread_days_selected(self, days)
+            }
+            Message::ExportValueReport => stats_view::handle_export_value_report(self),
+            Message::ExportCatalog(selected_only) => {
+                stats_view::handle_export_catalog(self, selected_only)
+            }
+            Message::CatalogExported(result) => stats_view::handle_catalog_exported(self, result),
+            Message::OpenShelfExport => stats_view::handle_open_shelf_export(self),
+            Message::CloseShelfExport => {
+                self.shelf_export = None;
+                iced::Task::none()
+            }
+            Message::ShelfGroupingSelected(grouping) => {
+                if let Some(shelf) = &mut self.shelf_export {
+                    shelf.options.grouping = grouping;
+                }
+                iced::Task::none()
+            }
+            Message::ToggleShelfPrices(include) => {
+                if let Some(shelf) = &mut self.shelf_export {
+                    shelf.options.include_prices = include;
+                }
+                iced::Task::none()
+            }
+            Message::ShelfPathChanged(value) => {
+                if let Some(shelf) = &mut self.shelf_export {
+                    shelf.path = value;
+                }
+                iced::Task::none()
+            }
+            Message::ExportShelf => stats_view::handle_export_shelf(self),
+            Message::ShelfExported(result) => stats_view::handle_shelf_exported(self, result),
+            Message::ValueReportExported(result) => {
+                stats_view::handle_value_report_exported(self, result)
+            }
+            Message::OpenValueReport => stats_view::handle_open_value_report(self),
+
+            // Settings messages handled in the settings module
+            Message::IncreaseFontScale => settings_view::handle_change_font_scale(self, 1),
+            Message::DecreaseFontScale => settings_view::handle_change_font_scale(self, -1),
+            Message::ResetFontScale => settings_view::handle_reset_font_scale(self),
+            Message::ToggleDarkMode => settings_view::handle_toggle_dark_mode(self),
+            Message::TaxRateChanged(value) => settings_view::handle_tax_rate_changed(self, value),
+            Message::TogglePricesIncludeTax(enabled) => {
+                settings_view::handle_toggle_prices_include_tax(self, enabled)
+            }
+            Message::ToggleIdleRefresh(enabled) => {
+                settings_view::handle_toggle_idle_refresh(self, enabled)
+            }
+            Message::ToggleDesktopNotifications(enabled) => {
+                settings_view::handle_toggle_desktop_notifications(self, enabled)
+            }
+            Message::ImportAuthorLimitSelected(limit) => {
+                settings_view::handle_import_author_limit_selected(self, limit)
+            }
+            Message::IdleRefreshMinutesSelected(minutes) => {
+                settings_view::handle_idle_refresh_minutes_selected(self, minutes)
+            }
+            Message::ToggleNightlyMaintenance(enabled) => {
+                settings_view::handle_toggle_nightly_maintenance(self, enabled)
+            }
+            Message::MaintenanceHourSelected(hour) => {
+                settings_view::handle_maintenance_hour_selected(self, hour)
+            }
+            Message::MaintenanceBackupsKeptSelected(kept) => {
+                settings_view::handle_maintenance_backups_kept_selected(self, kept)
+            }
+            Message::IdleRefresh => book_view::handle_idle_refresh(self),
+            Message::MaintenanceCheck => maintenance_view::handle_maintenance_check(self),
+            Message::MaintenanceFinished(report) => {
+                maintenance_view::handle_maintenance_finished(self, report)
+            }
+            Message::DismissOvernightIssues => {
+                maintenance_view::handle_dismiss_overnight_issues(self)
+            }
+            Message::ConfigSaved(result) => settings_view::handle_config_saved(self, result),
 
             Message::Error(error) => {
-                self.error = Some(error);
+                self.report_error(error);
                 iced::Task::none()
             }
         }
@@ -275,4 +1532,111 @@ impl BookshelfApp {
     pub fn view(&self) -> iced::Element<'_, Message> {
         crate::ui::common::view(self)
     }
+
+    pub fn subscription(&self) -> iced::Subscription<Message> {
+        // Bindings are resolved in update, where the app state is available
+        let keyboard = iced::event::listen_with(keymap::on_key_event);
+        let close_requests = iced::window::close_requests().map(|_| Message::RequestExit);
+
+        let highlight = if self.highlighted_book.is_some() {
+            iced::time::every(Duration::from_millis(ROW_HIGHLIGHT_MS))
+                .map(|_| Message::ClearBookHighlight)
+        } else {
+            iced::Subscription::none()
+        };
+
+        let palette = if self.palette.is_some() {
+            iced::event::listen_with(palette::on_event)
+        } else {
+            iced::Subscription::none()
+        };
+
+        let (autosave, book_form_keys) = if draft_view::is_editing_book(self) {
+            (
+                iced::time::every(Duration::from_secs(DRAFT_AUTOSAVE_SECS))
+                    .map(|_| Message::AutosaveDraft),
+                iced::event::listen_with(keymap::on_book_form_event),
+            )
+        } else {
+            (iced::Subscription::none(), iced::Subscription::none())
+        };
+
+        // The drag ends wherever the button is released, even outside the header
+        let column_resize = if self.resizing_column.is_some() {
+            iced::event::listen_with(table_view::on_resize_event)
+        } else {
+            iced::Subscription::none()
+        };
+
+        let split_drag = if self.dragging_splitter {
+            iced::event::listen_with(split_view::on_split_drag_event)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // Ticks even while busy, the handler skips the refresh then
+        let idle_refresh = if self.config.idle_refresh {
+            let minutes = self.config.idle_refresh_minutes.max(1);
+            iced::time::every(Duration::from_secs(minutes * 60)).map(|_| Message::IdleRefresh)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // Checked now and then, the handler decides whether the hour has come
+        let maintenance = if self.config.nightly_maintenance && !self.read_only {
+            iced::time::every(Duration::from_secs(MAINTENANCE_CHECK_MINUTES * 60))
+                .map(|_| Message::MaintenanceCheck)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // The dwell is timed and the cursor followed only while a row is hovered
+        let window_size = iced::event::listen_with(hover_view::on_window_event);
+        let (hover_tick, hover_cursor) = match self.hovered_book {
+            Some(_) if !self.hover_card_shown => (
+                iced::time::every(Duration::from_millis(HOVER_CARD_DELAY_MS / 5))
+                    .map(|_| Message::HoverTick),
+                iced::event::listen_with(hover_view::on_cursor_event),
+            ),
+            _ => (iced::Subscription::none(), iced::Subscription::none()),
+        };
+
+        let reading_timer = if self.reading_timer.is_some() {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::ReadingTimerTick)
+        } else {
+            iced::Subscription::none()
+        };
+
+        let toasts = if self.toasts.has_expiring() {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::Toast(ToastMessage::Tick))
+        } else {
+            iced::Subscription::none()
+        };
+
+        iced::Subscription::batch(vec![
+            keyboard,
+            close_requests,
+            highlight,
+            palette,
+            autosave,
+            book_form_keys,
+            column_resize,
+            split_drag,
+            idle_refresh,
+            maintenance,
+            window_size,
+            hover_tick,
+            hover_cursor,
+            reading_timer,
+            toasts,
+        ])
+    }
+
+    pub fn theme(&self) -> iced::Theme {
+        match self.config.dark_mode {
+            Some(true) => iced::Theme::Dark,
+            Some(false) => iced::Theme::Light,
+            None => iced::Theme::default(),
+        }
+    }
 }
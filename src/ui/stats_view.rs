@@ -0,0 +1,845 @@
+// src/ui/stats_view.rs
+use crate::export::{self, ShelfGrouping, ShelfOptions};
+use crate::language::language_name;
+use crate::models::BookWithAuthor;
+use crate::paths;
+use crate::report::{self, CsvDateFormat, CsvQuoting, DecimalSeparator, ReportFormat};
+use crate::stats::{self, YearInReview};
+use crate::ui::quote_view;
+use crate::ui::session_view;
+use crate::ui::series_view::create_series_progress;
+use crate::ui::settings_view::save_config;
+use crate::ui::{
+    format_duration, format_price, format_rating, BookshelfApp, Message, Tab, CSV_PREVIEW_ROWS,
+    CURRENCY, LIST_PADDING, LIST_SPACING, STALE_UNREAD_SHOWN, STALE_UNREAD_THRESHOLDS,
+    TOP_AUTHORS_SHOWN,
+};
+use chrono::{Datelike, Local};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, progress_bar, row, scrollable, text,
+    text_input, Column,
+};
+use iced::window::Screenshot;
+use iced::{Element, Length};
+use std::path::PathBuf;
+
+// Handler functions for stats-related messages
+pub fn handle_stats_year_selected(app: &mut BookshelfApp, year: i32) -> iced::Task<Message> {
+    app.stats_year = year;
+    iced::Task::none()
+}
+
+pub fn handle_copy_year_in_review(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let review = stats::year_in_review(&app.books, app.stats_year, CURRENCY);
+    app.status_message = Some(format!("Copied {} summary to clipboard", review.year));
+    iced::clipboard::write(review.to_text())
+}
+
+pub fn handle_export_year_in_review_image(_: &mut BookshelfApp) -> iced::Task<Message> {
+    iced::window::get_oldest()
+        .and_then(iced::window::screenshot)
+        .map(Message::YearInReviewScreenshot)
+}
+
+pub fn handle_year_in_review_screenshot(
+    app: &mut BookshelfApp,
+    screenshot: Screenshot,
+) -> iced::Task<Message> {
+    let path = paths::exports_dir().join(format!("year-in-review-{}.png", app.stats_year));
+
+    iced::Task::perform(
+        async move {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            image::save_buffer(
+                &path,
+                &screenshot.bytes,
+                screenshot.size.width,
+                screenshot.size.height,
+                image::ColorType::Rgba8,
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(path.display().to_string())
+        },
+        Message::YearInReviewExported,
+    )
+}
+
+pub fn handle_year_in_review_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => app.status_message = Some(format!("Saved image to {}", path)),
+        Err(e) => app.report_error(format!("Failed to export image: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_report_format_selected(
+    app: &mut BookshelfApp,
+    format: ReportFormat,
+) -> iced::Task<Message> {
+    app.report_format = format;
+    iced::Task::none()
+}
+
+pub fn handle_report_path_changed(app: &mut BookshelfApp, value: String) -> iced::Task<Message> {
+    app.report_path = value;
+    iced::Task::none()
+}
+
+// The export profile is shared by every CSV export, changes are saved right away
+pub fn handle_csv_date_format_selected(
+    app: &mut BookshelfApp,
+    format: CsvDateFormat,
+) -> iced::Task<Message> {
+    app.config.export_profile.date_format = format;
+    save_config(&app.config)
+}
+
+pub fn handle_csv_decimal_separator_selected(
+    app: &mut BookshelfApp,
+    separator: DecimalSeparator,
+) -> iced::Task<Message> {
+    app.config.export_profile.decimal_separator = separator;
+    save_config(&app.config)
+}
+
+pub fn handle_csv_quoting_selected(
+    app: &mut BookshelfApp,
+    quoting: CsvQuoting,
+) -> iced::Task<Message> {
+    app.config.export_profile.quoting = quoting;
+    save_config(&app.config)
+}
+
+pub fn handle_toggle_csv_byte_order_mark(
+    app: &mut BookshelfApp,
+    enabled: bool,
+) -> iced::Task<Message> {
+    app.config.export_profile.byte_order_mark = enabled;
+    save_config(&app.config)
+}
+
+fn default_report_path(format: ReportFormat) -> PathBuf {
+    paths::exports_dir().join(format!("bookshelf-report.{}", format.extension()))
+}
+
+pub fn handle_export_report(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let report = report::build(&app.books, &app.authors);
+    let format = app.report_format;
+    let profile = app.config.export_profile;
+    let path = if app.report_path.trim().is_empty() {
+        default_report_path(format)
+    } else {
+        PathBuf::from(app.report_path.trim())
+    };
+
+    iced::Task::perform(
+        async move {
+            match report.write(format, &profile, &path) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ReportExported,
+    )
+}
+
+pub fn handle_report_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => app.notify_finished(format!("Saved report to {}", path)),
+        Err(e) => app.notify_failed(format!("Failed to export report: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_stale_unread_days_selected(
+    app: &mut BookshelfApp,
+    days: i64,
+) -> iced::Task<Message> {
+    app.config.stale_unread_days = days;
+    save_config(&app.config)
+}
+
+pub fn handle_export_value_report(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let report = report::build_value_report(&app.books);
+    let path = paths::exports_dir().join("library-value.html");
+
+    iced::Task::perform(
+        async move {
+            match report.write_html(CURRENCY, &path) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ValueReportExported,
+    )
+}
+
+pub fn handle_value_report_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => {
+            app.notify_finished(format!("Saved value report to {}", path));
+            app.value_report_path = Some(path);
+        }
+        Err(e) => app.notify_failed(format!("Failed to export value report: {}", e)),
+    }
+    iced::Task::none()
+}
+
+pub fn handle_open_value_report(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if let Some(path) = &app.value_report_path {
+        if let Err(e) = open::that_detached(path) {
+            app.report_error(format!("Failed to open {}: {}", path, e));
+        }
+    }
+    iced::Task::none()
+}
+
+pub fn handle_export_catalog(app: &mut BookshelfApp, selected_only: bool) -> iced::Task<Message> {
+    let books: Vec<_> = app
+        .books
+        .iter()
+        .filter(|pair| !selected_only || app.selected_books.contains(&pair.book.id))
+        .cloned()
+        .collect();
+    if books.is_empty() {
+        app.status_message = Some("No books to put in the catalog".to_string());
+        return iced::Task::none();
+    }
+    let path = paths::exports_dir().join("catalog.pdf");
+
+    iced::Task::perform(
+        async move {
+            match export::export_catalog_pdf(&books, &path, CURRENCY) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::CatalogExported,
+    )
+}
+
+pub fn handle_catalog_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => {
+            app.notify_finished(format!("Saved catalog to {}", path));
+            if let Err(e) = open::that_detached(&path) {
+                app.report_error(format!("Failed to open {}: {}", path, e));
+            }
+        }
+        Err(e) => app.notify_failed(format!("Failed to export catalog: {}", e)),
+    }
+    iced::Task::none()
+}
+
+/// Options of the shelf page export, open as a dialog while set
+#[derive(Debug, Clone, Default)]
+pub struct ShelfExport {
+    pub options: ShelfOptions,
+    pub path: String, // Empty means the default file in the exports directory
+}
+
+fn default_shelf_path() -> PathBuf {
+    paths::exports_dir().join("bookshelf.html")
+}
+
+pub fn handle_open_shelf_export(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.shelf_export = Some(ShelfExport::default());
+    iced::Task::none()
+}
+
+pub fn handle_export_shelf(app: &mut BookshelfApp) -> iced::Task<Message> {
+    let Some(shelf) = &app.shelf_export else {
+        return iced::Task::none();
+    };
+    if app.books.is_empty() {
+        app.status_message = Some("No books to put on the shelf page".to_string());
+        return iced::Task::none();
+    }
+    let books = app.books.clone();
+    let options = shelf.options;
+    let path = if shelf.path.trim().is_empty() {
+        default_shelf_path()
+    } else {
+        PathBuf::from(shelf.path.trim())
+    };
+
+    iced::Task::perform(
+        async move {
+            match export::export_shelf_html(&books, options, &path, CURRENCY) {
+                Ok(()) => Ok(path.display().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        Message::ShelfExported,
+    )
+}
+
+pub fn handle_shelf_exported(
+    app: &mut BookshelfApp,
+    result: Result<String, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(path) => {
+            app.shelf_export = None;
+            app.notify_finished(format!("Saved shelf page to {}", path));
+            if let Err(e) = open::that_detached(&path) {
+                app.report_error(format!("Failed to open {}: {}", path, e));
+            }
+        }
+        Err(e) => app.notify_failed(format!("Failed to export shelf page: {}", e)),
+    }
+    iced::Task::none()
+}
+
+// View functions for stats
+pub fn view(app: &BookshelfApp) -> Element<'_, Message> {
+    if let Some(browser) = &app.quote_browser {
+        return quote_view::create_browser(app, browser);
+    }
+    let fonts = app.fonts();
+    let mut years = stats::years_with_data(&app.books);
+    let current_year = Local::now().year();
+    if !years.contains(&current_year) {
+        years.insert(0, current_year);
+    }
+
+    let header = row![
+        text("Statistics").size(fonts.title()),
+        iced::widget::horizontal_space(),
+        button("Quotes")
+            .on_press(Message::OpenQuoteBrowser)
+            .style(button::secondary),
+        pick_list(years, Some(app.stats_year), Message::StatsYearSelected).padding(8),
+    ]
+    .spacing(LIST_SPACING)
+    .padding(10)
+    .width(Length::Fill);
+
+    let review = stats::year_in_review(&app.books, app.stats_year, CURRENCY);
+
+    column![
+        header,
+        scrollable(
+            container(
+                column![
+                    create_year_in_review(app, &review),
+                    session_view::create_reading_time(app),
+                    create_series_completion(app),
+                    create_gifts_by_giver(app),
+                    create_books_per_language(app),
+                    create_top_authors(app),
+                    create_oldest_unread(app),
+                    create_price_extremes(app),
+                    create_library_value(app),
+                    create_report_export(app)
+                ]
+                .spacing(20)
+            )
+            .padding(LIST_PADDING)
+            .width(Length::Fill)
+        )
+        .height(Length::Fill)
+    ]
+    .spacing(20)
+    .padding(20)
+    .into()
+}
+
+fn create_year_in_review<'a>(app: &BookshelfApp, review: &YearInReview) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let mut content = column![text(format!("Your {} in books", review.year)).size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    if review.finished_titles.is_empty() {
+        content = content.push(text("No books finished this year yet.").size(fonts.body()));
+    } else {
+        content = content
+            .push(text(format!("Books finished: {}", review.finished_count())).size(fonts.body()));
+        for title in &review.finished_titles {
+            content = content.push(text(format!("• {}", title)).size(fonts.small()));
+        }
+    }
+
+    if review.pages_read > 0 {
+        content =
+            content.push(text(format!("Pages read: {}", review.pages_read)).size(fonts.body()));
+    }
+    if review.listening_minutes > 0 {
+        content = content.push(
+            text(format!(
+                "Listening time: {}",
+                format_duration(review.listening_minutes)
+            ))
+            .size(fonts.body()),
+        );
+    }
+
+    if let Some((name, count)) = &review.top_author {
+        content = content
+            .push(text(format!("Top author: {} ({} finished)", name, count)).size(fonts.body()));
+    }
+    if let Some(pair) = &review.favorite {
+        let stars = format_rating(pair.book.rating.unwrap_or_default());
+        let favorite = format!("Favorite: {} {}", pair.book.title, stars);
+        content = content.push(text(favorite).size(fonts.body()));
+    }
+
+    content = content
+        .push(text(format!("Books bought: {}", review.bought_count)).size(fonts.body()))
+        .push(
+            text(format!(
+                "Total spent: {}",
+                app.format_total(review.total_spent, &review.currency)
+            ))
+            .size(fonts.body()),
+        )
+        .push(
+            row![
+                button("Copy as text")
+                    .on_press(Message::CopyYearInReview)
+                    .style(button::secondary),
+                button("Export as image")
+                    .on_press(Message::ExportYearInReviewImage)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        );
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+fn create_series_completion(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let mut content = column![text("Series completion").size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    let progress = stats::series_by_completion(&app.books, &app.series);
+    if progress.is_empty() {
+        content = content.push(text("No series yet.").size(fonts.body()));
+    } else {
+        for series in &progress {
+            content = content.push(create_series_progress(series, fonts));
+        }
+    }
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+fn create_gifts_by_giver(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let mut content = column![text("Gifts by giver").size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    let givers = stats::gifts_by_giver(&app.books);
+    if givers.is_empty() {
+        content = content.push(text("No gifts recorded yet.").size(fonts.body()));
+    } else {
+        for giver in &givers {
+            content = content.push(
+                text(format!(
+                    "• {}: {} {}",
+                    giver.giver,
+                    giver.books,
+                    if giver.books == 1 { "book" } else { "books" }
+                ))
+                .size(fonts.small()),
+            );
+        }
+    }
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+fn create_books_per_language(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let mut content = column![text("Books per language").size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    let languages = stats::books_per_language(&app.books);
+    if languages.iter().all(|totals| totals.code.is_none()) {
+        content = content.push(text("No book has a language set yet.").size(fonts.body()));
+    } else {
+        for totals in &languages {
+            let name = totals
+                .code
+                .as_deref()
+                .map_or_else(|| "No language".to_string(), language_name);
+            content = content.push(
+                text(format!(
+                    "• {}: {} books, {} finished",
+                    name, totals.books, totals.finished
+                ))
+                .size(fonts.small()),
+            );
+        }
+    }
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+fn create_top_authors(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let mut content = column![text("Most finished authors").size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    let ranked = stats::top_authors_by_finished(&app.books, TOP_AUTHORS_SHOWN);
+    let most = ranked.first().map_or(1, |(_, finished)| *finished);
+    if ranked.is_empty() {
+        content = content.push(text("No finished books yet.").size(fonts.body()));
+    }
+    for (place, (name, finished)) in ranked.into_iter().enumerate() {
+        // Resolved from the loaded books, the "Unknown" group has no page to open
+        let open_author = app
+            .books
+            .iter()
+            .filter_map(|pair| pair.author.as_ref())
+            .find(|author| author.Name.as_deref() == Some(name.as_str()))
+            .map(|author| {
+                let details = Message::ViewAuthorDetails(author.clone());
+                Message::RunInTab(Tab::Authors, Box::new(details))
+            });
+        let label = text(format!("{}. {} ({} finished)", place + 1, name, finished))
+            .size(fonts.small());
+        content = content.push(
+            column![
+                button(label)
+                    .on_press_maybe(open_author)
+                    .style(button::text)
+                    .padding(0),
+                progress_bar(0.0..=most as f32, finished as f32).height(6),
+            ]
+            .spacing(5),
+        );
+    }
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+fn create_oldest_unread(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let threshold = app.config.stale_unread_days;
+    let mut thresholds = STALE_UNREAD_THRESHOLDS.to_vec();
+    if !thresholds.contains(&threshold) {
+        // Keep a value set by hand in the settings file selectable
+        thresholds.push(threshold);
+        thresholds.sort();
+    }
+
+    let mut content = column![
+        row![
+            text("Your oldest unread").size(fonts.heading()),
+            iced::widget::horizontal_space(),
+            text("Bought at least").size(fonts.small()),
+            pick_list(thresholds, Some(threshold), Message::StaleUnreadDaysSelected).padding(6),
+            text("days ago").size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center)
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    let stale = stats::stale_unread(&app.books, Local::now().naive_local(), threshold);
+    if stale.is_empty() {
+        content = content.push(text("No books waiting that long.").size(fonts.body()));
+    }
+    for (pair, days) in stale.iter().take(STALE_UNREAD_SHOWN) {
+        content = content.push(
+            text(format!("• {} ({} days)", pair.book.title, days)).size(fonts.small()),
+        );
+    }
+    if stale.len() > STALE_UNREAD_SHOWN {
+        content = content.push(
+            text(format!("and {} more", stale.len() - STALE_UNREAD_SHOWN)).size(fonts.small()),
+        );
+    }
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+// Priced books at both ends, a title opens the book list searched for it
+fn create_price_extremes(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let content = column![text("Price range").size(fonts.heading())]
+        .spacing(10)
+        .width(Length::Fill);
+
+    match stats::price_extremes(&app.books) {
+        (Some(most), Some(least)) => content.push(
+            row![
+                create_price_card(app, "Most expensive", most),
+                create_price_card(app, "Cheapest", least),
+            ]
+            .spacing(LIST_SPACING),
+        ),
+        _ => content.push(text("No priced books yet.").size(fonts.body())),
+    }
+}
+
+fn create_price_card<'a>(
+    app: &BookshelfApp,
+    label: &'a str,
+    pair: &'a BookWithAuthor,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let show = Message::RunInTab(Tab::Books, Box::new(Message::ShowBookInList(pair.book.id)));
+    let mut card = column![
+        text(label).size(fonts.tiny()),
+        button(text(pair.book.title.clone()).size(fonts.item()))
+            .on_press(show)
+            .style(button::text)
+            .padding(0),
+    ]
+    .spacing(5);
+    if let Some(name) = pair.author.as_ref().and_then(|author| author.Name.clone()) {
+        card = card.push(text(name).size(fonts.small()));
+    }
+    if let Some(price) = pair.book.price {
+        card = card.push(text(format_price(price, CURRENCY, app.privacy_mode)).size(fonts.body()));
+    }
+
+    container(card)
+        .padding(15)
+        .width(Length::FillPortion(1))
+        .style(container::bordered_box)
+        .into()
+}
+
+fn create_library_value(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let report = report::build_value_report(&app.books);
+
+    let mut actions = row![
+        button("Export value report…")
+            .on_press(Message::ExportValueReport)
+            .style(button::secondary),
+        button("Export PDF catalog…")
+            .on_press(Message::ExportCatalog(false))
+            .style(button::secondary),
+        button("Share as a web page…")
+            .on_press(Message::OpenShelfExport)
+            .style(button::secondary),
+    ]
+    .spacing(10);
+    if app.value_report_path.is_some() {
+        actions = actions.push(
+            button("Open report")
+                .on_press(Message::OpenValueReport)
+                .style(button::secondary),
+        );
+    }
+
+    let content = column![
+        text("Library value").size(fonts.heading()),
+        text(format!(
+            "Total purchase value: {}",
+            app.format_total(report.total_value, CURRENCY)
+        ))
+        .size(fonts.body()),
+        text(format!(
+            "{} of {} books have a price, the rest are listed separately in the report",
+            report.priced_books, report.total_books
+        ))
+        .size(fonts.small()),
+        text(match stats::average_price(&app.books, app.config.average_priced_only) {
+            Some(average) => format!(
+                "Average price: {}{}",
+                format_price(average, CURRENCY, app.privacy_mode),
+                if app.config.average_priced_only {
+                    ", books without a price left out"
+                } else {
+                    ", books without a price counted as free"
+                }
+            ),
+            None => "Average price: no book has a price".to_string(),
+        })
+        .size(fonts.body()),
+        actions,
+    ]
+    .spacing(10)
+    .width(Length::Fill);
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+pub fn create_shelf_export_dialog<'a>(
+    app: &'a BookshelfApp,
+    shelf: &'a ShelfExport,
+) -> Element<'a, Message> {
+    let fonts = app.fonts();
+    let placeholder = default_shelf_path().display().to_string();
+
+    container(
+        column![
+            text("Share as a web page").size(fonts.heading()),
+            text("A single HTML file with a card for every book, covers included")
+                .size(fonts.small()),
+            row![
+                text("Group:").size(fonts.body()),
+                pick_list(
+                    ShelfGrouping::ALL,
+                    Some(shelf.options.grouping),
+                    Message::ShelfGroupingSelected
+                )
+                .padding(8),
+            ]
+            .spacing(10)
+            .align_y(iced::alignment::Vertical::Center),
+            checkbox("Include prices", shelf.options.include_prices)
+                .on_toggle(Message::ToggleShelfPrices)
+                .text_size(fonts.body()),
+            text_input(&placeholder, &shelf.path)
+                .on_input(Message::ShelfPathChanged)
+                .on_submit(Message::ExportShelf)
+                .padding(8)
+                .width(fonts.width(420.0)),
+            row![
+                button("Save page")
+                    .on_press(Message::ExportShelf)
+                    .style(button::primary),
+                button("Cancel")
+                    .on_press(Message::CloseShelfExport)
+                    .style(button::secondary),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10),
+    )
+    .padding(20)
+    .style(container::bordered_box)
+    .into()
+}
+
+fn create_report_export(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let placeholder = default_report_path(app.report_format).display().to_string();
+
+    let content = column![
+        text("Export report").size(fonts.heading()),
+        text("Spending per month, books finished per year and totals per author")
+            .size(fonts.small()),
+        row![
+            pick_list(
+                [ReportFormat::Json, ReportFormat::Csv],
+                Some(app.report_format),
+                Message::ReportFormatSelected
+            )
+            .padding(8),
+            text_input(&placeholder, &app.report_path)
+                .on_input(Message::ReportPathChanged)
+                .on_submit(Message::ExportReport)
+                .padding(8)
+                .width(Length::Fill),
+            button("Export report…")
+                .on_press(Message::ExportReport)
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+    ]
+    .push_maybe((app.report_format == ReportFormat::Csv).then(|| create_csv_options(app)))
+    .spacing(10)
+    .width(Length::Fill);
+
+    column![container(content)
+        .padding(20)
+        .style(container::bordered_box)]
+    .width(Length::Fill)
+}
+
+// Export profile of CSV files, with the start of the report as it would be written
+fn create_csv_options(app: &BookshelfApp) -> Column<'_, Message> {
+    let fonts = app.fonts();
+    let profile = &app.config.export_profile;
+    let preview = report::preview_csv(
+        report::build(&app.books, &app.authors).csv_rows(),
+        CSV_PREVIEW_ROWS,
+        profile,
+    );
+
+    column![
+        row![
+            pick_list(
+                CsvDateFormat::ALL,
+                Some(profile.date_format),
+                Message::CsvDateFormatSelected
+            )
+            .text_size(fonts.small())
+            .padding(6),
+            pick_list(
+                DecimalSeparator::ALL,
+                Some(profile.decimal_separator),
+                Message::CsvDecimalSeparatorSelected
+            )
+            .text_size(fonts.small())
+            .padding(6),
+            pick_list(
+                CsvQuoting::ALL,
+                Some(profile.quoting),
+                Message::CsvQuotingSelected
+            )
+            .text_size(fonts.small())
+            .padding(6),
+            checkbox("Byte order mark for Excel", profile.byte_order_mark)
+                .on_toggle(Message::ToggleCsvByteOrderMark)
+                .text_size(fonts.small()),
+        ]
+        .spacing(10)
+        .align_y(iced::alignment::Vertical::Center),
+        text(if profile.decimal_separator == DecimalSeparator::Comma {
+            "Preview, fields are separated by semicolons with comma decimals:"
+        } else {
+            "Preview:"
+        })
+        .size(fonts.small()),
+        container(
+            text(preview)
+                .size(fonts.small())
+                .font(iced::Font::MONOSPACE)
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(container::rounded_box),
+    ]
+    .spacing(10)
+}
@@ -0,0 +1,160 @@
+// src/ui/table_view.rs
+use crate::models::BookWithAuthor;
+use crate::ui::book_view::{book_box_style, genre_marker, is_book_highlighted};
+use crate::ui::components::cursor_tracker::cursor_tracker;
+use crate::ui::settings_view::save_config;
+use crate::ui::{
+    format_price, format_reading_duration, resize_table_column, table_column_count,
+    table_column_widths, BookshelfApp, Message, CURRENCY, TABLE_COLUMNS,
+};
+use crate::stats;
+use iced::widget::{
+    checkbox, column, container, horizontal_space, mouse_area, row, text, vertical_rule, Column,
+};
+use iced::{event, mouse, window, Element, Event, Length};
+
+const SELECT_COLUMN_WIDTH: f32 = 30.0;
+const DIVIDER_WIDTH: f32 = 8.0;
+const HEADER_PADDING: f32 = 8.0; // Matches the padding of the book rows
+
+// Handler functions for resizing table columns
+pub fn handle_start_column_resize(app: &mut BookshelfApp, divider: usize) -> iced::Task<Message> {
+    app.resizing_column = Some(divider);
+    iced::Task::none()
+}
+
+pub fn handle_column_resize_moved(app: &mut BookshelfApp, position: f32) -> iced::Task<Message> {
+    if let Some(column) = app.resizing_column {
+        let columns = table_column_count(app.config.show_reading_duration);
+        app.config.column_widths =
+            resize_table_column(&app.config.column_widths, columns, column, position);
+    }
+    iced::Task::none()
+}
+
+// Widths are only written to the settings once the drag is over
+pub fn handle_end_column_resize(app: &mut BookshelfApp) -> iced::Task<Message> {
+    if app.resizing_column.take().is_none() {
+        return iced::Task::none();
+    }
+    save_config(&app.config)
+}
+
+pub fn on_resize_event(event: Event, _: event::Status, _: window::Id) -> Option<Message> {
+    match event {
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            Some(Message::EndColumnResize)
+        }
+        _ => None,
+    }
+}
+
+// View functions for the table layout
+pub fn portion(share: f32) -> Length {
+    Length::FillPortion((share * 1000.0).round().max(1.0) as u16)
+}
+
+fn table_cells(pair: &BookWithAuthor, privacy_mode: bool) -> [String; 6] {
+    let date = |value: Option<chrono::NaiveDateTime>| {
+        value.map_or_else(String::new, |d| d.format("%Y-%m-%d").to_string())
+    };
+    let title = if pair.book.quantity > 1 {
+        format!("{} x{}", pair.book.title, pair.book.quantity)
+    } else {
+        pair.book.title.clone()
+    };
+
+    [
+        title,
+        pair.author
+            .as_ref()
+            .and_then(|a| a.Name.clone())
+            .unwrap_or_default(),
+        pair.book
+            .price
+            .map_or_else(String::new, |p| format_price(p, CURRENCY, privacy_mode)),
+        date(pair.book.bought),
+        date(pair.book.finished),
+        stats::reading_duration_days(pair.book.bought, pair.book.finished)
+            .map_or_else(String::new, format_reading_duration),
+    ]
+}
+
+pub fn create_books_table<'a>(
+    app: &'a BookshelfApp,
+    books_to_display: &[&'a BookWithAuthor],
+) -> Column<'a, Message> {
+    let fonts = app.fonts();
+    let columns = table_column_count(app.config.show_reading_duration);
+    let widths = table_column_widths(&app.config.column_widths, columns);
+    let mut table = column![create_table_header(app, widths.clone())]
+        .spacing(4)
+        .width(Length::Fill)
+        .padding(20);
+
+    for pair in books_to_display {
+        let book_id = pair.book.id;
+        let mut book_row = row![container(
+            checkbox("", app.selected_books.contains(&book_id))
+                .on_toggle(move |_| Message::ToggleBookSelection(book_id))
+        )
+        .width(SELECT_COLUMN_WIDTH)]
+        .align_y(iced::Alignment::Center);
+        book_row = book_row.push(genre_marker(app, pair));
+
+        let cells = table_cells(pair, app.privacy_mode);
+        for (index, (value, share)) in cells.into_iter().zip(&widths).enumerate() {
+            if index > 0 {
+                book_row = book_row.push(horizontal_space().width(DIVIDER_WIDTH));
+            }
+            book_row = book_row.push(text(value).size(fonts.small()).width(portion(*share)));
+        }
+
+        table = table.push(
+            mouse_area(
+                container(book_row)
+                    .padding(HEADER_PADDING)
+                    .style(book_box_style(is_book_highlighted(app, book_id))),
+            )
+            .on_press(Message::BookRowClicked(book_id))
+            .on_enter(Message::BookHoverStarted(book_id))
+            .on_exit(Message::BookHoverEnded(book_id)),
+        );
+    }
+    table
+}
+
+// Column names with draggable dividers between them
+fn create_table_header(app: &BookshelfApp, widths: Vec<f32>) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let columns = widths.len();
+    let mut header = row![horizontal_space().width(SELECT_COLUMN_WIDTH)]
+        .height(fonts.width(32.0))
+        .align_y(iced::Alignment::Center);
+
+    // Zipping with the widths leaves out the hidden columns
+    for (index, (name, share)) in TABLE_COLUMNS.iter().zip(&widths).enumerate() {
+        if index > 0 {
+            header = header.push(
+                mouse_area(container(vertical_rule(2)).center_x(DIVIDER_WIDTH))
+                    .on_press(Message::StartColumnResize(index - 1))
+                    .interaction(mouse::Interaction::ResizingHorizontally),
+            );
+        }
+        header = header.push(text(*name).size(fonts.small()).width(portion(*share)));
+    }
+
+    let header = container(header).padding([0.0, HEADER_PADDING]);
+    let Some(column) = app.resizing_column else {
+        return header.into();
+    };
+
+    // Cursor positions become shares of the space the columns fill, the unit the widths
+    // are stored in
+    cursor_tracker(header, move |x, width| {
+        let dividers = (columns - 1) as f32 * DIVIDER_WIDTH;
+        let fill_width = (width - 2.0 * HEADER_PADDING - SELECT_COLUMN_WIDTH - dividers).max(1.0);
+        let offset = HEADER_PADDING + SELECT_COLUMN_WIDTH + (column as f32 + 0.5) * DIVIDER_WIDTH;
+        Message::ColumnResizeMoved((x - offset) / fill_width)
+    })
+}
@@ -0,0 +1,107 @@
+// src/ui/trash_view.rs
+use crate::db;
+use crate::trash::{DeletedEntry, DeletedItem, RestoreOutcome};
+use crate::ui::{BookshelfApp, Message};
+use chrono::Local;
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+/// Remembers a deletion so it can be restored from the panel
+pub fn record_deletion(app: &mut BookshelfApp, item: DeletedItem) {
+    app.recently_deleted.push(item, Local::now().naive_local());
+}
+
+// Handler functions for the recently deleted panel
+pub fn handle_toggle_recently_deleted(app: &mut BookshelfApp) -> iced::Task<Message> {
+    app.show_recently_deleted = !app.show_recently_deleted;
+    iced::Task::none()
+}
+
+pub fn handle_restore_deleted(app: &mut BookshelfApp, index: usize) -> iced::Task<Message> {
+    // Taken out right away so a second click can't insert it twice
+    let Some(entry) = app.recently_deleted.take(index) else {
+        return iced::Task::none();
+    };
+    let item = entry.item.clone();
+
+    iced::Task::perform(
+        async move {
+            match db::restore_deleted(&item) {
+                Ok(outcome) => Ok(outcome),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+        move |result| Message::DeletedRestored(entry.clone(), result),
+    )
+}
+
+pub fn handle_deleted_restored(
+    app: &mut BookshelfApp,
+    entry: DeletedEntry,
+    result: Result<RestoreOutcome, String>,
+) -> iced::Task<Message> {
+    match result {
+        Ok(outcome) => {
+            if let Some(author_id) = outcome.recreated_author {
+                app.recently_deleted.forget_author(author_id);
+            }
+            app.status_message = Some(outcome.summary());
+            iced::Task::batch([
+                app.update(Message::LoadBooks),
+                app.update(Message::LoadAuthors),
+            ])
+        }
+        Err(e) => {
+            app.recently_deleted.put_back(entry);
+            app.report_error(format!("Failed to restore: {}", e));
+            iced::Task::none()
+        }
+    }
+}
+
+// View functions for the recently deleted panel
+pub fn create_recently_deleted(app: &BookshelfApp) -> Element<'_, Message> {
+    let fonts = app.fonts();
+    let mut entries = column![].spacing(8);
+
+    if app.recently_deleted.is_empty() {
+        entries = entries.push(text("Nothing deleted yet.").size(fonts.body()));
+    }
+    for (index, entry) in app.recently_deleted.entries().enumerate() {
+        entries = entries.push(
+            row![
+                column![
+                    text(entry.item.to_string()).size(fonts.body()),
+                    text(format!(
+                        "Deleted at {}",
+                        entry.deleted_at.format("%H:%M:%S")
+                    ))
+                    .size(fonts.small()),
+                ]
+                .width(Length::Fill),
+                button("Restore")
+                    .on_press(Message::RestoreDeleted(index))
+                    .style(button::secondary),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    container(
+        column![
+            text("Recently deleted").size(fonts.heading()),
+            text("Only this session's deletions are kept, they are gone once the app closes")
+                .size(fonts.small()),
+            scrollable(entries).height(Length::Shrink),
+            button("Close")
+                .on_press(Message::ToggleRecentlyDeleted)
+                .style(button::secondary),
+        ]
+        .spacing(15)
+        .width(fonts.width(480.0)),
+    )
+    .padding(20)
+    .style(container::bordered_box)
+    .into()
+}
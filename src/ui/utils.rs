@@ -1,50 +1,908 @@
 // src/ui/utils.rs
-use crate::models::BookWithAuthor;
-use crate::ui::{SortDirection, SortField};
+use crate::language::language_name;
+use crate::models::{AuthorModel, BookModel, BookWithAuthor, ID};
+use crate::sessions::SessionTotal;
+use crate::ui::{
+    AcquisitionPlace, DataIssue, EmptyValues, MissingField, SearchFields, SortDirection, SortField,
+    YearField, DOUBLE_CLICK_MS, EARLIEST_BOOK_YEAR, FORM_DATE_FORMAT, GENRE_NEUTRAL_COLOR,
+    HOVER_CARD_DELAY_MS, HOVER_CARD_OFFSET, PRICE_MASK, RATING_MAX, SPLIT_RATIO_DEFAULT,
+    SPLIT_RATIO_MAX, SPLIT_RATIO_MIN, TABLE_COLUMNS, TABLE_COLUMN_MIN, TABLE_COLUMN_WIDTHS,
+    TAX_RATE_MAX,
+};
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use iced::{Color, Point, Size};
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 
-/// Helper function to sort books based on given field and direction
-pub fn sort_books(books: &mut Vec<BookWithAuthor>, field: &SortField, direction: &SortDirection) {
+/// Formats a price with two decimals followed by the currency symbol.
+/// Hidden prices are masked for privacy mode, exports always pass false.
+pub fn format_price(price: f32, currency: &str, hidden: bool) -> String {
+    if hidden {
+        return PRICE_MASK.to_string();
+    }
+    format!("{:.2}{}", price, currency)
+}
+
+/// Net and gross amounts of a total for a tax rate in percent. The total is the gross
+/// amount when prices already include the tax, the net amount otherwise
+pub fn apply_tax(total: f32, rate: f32, already_included: bool) -> (f32, f32) {
+    let factor = 1.0 + rate.max(0.0) / 100.0;
+    if already_included {
+        (total / factor, total)
+    } else {
+        (total, total * factor)
+    }
+}
+
+/// Tax rate typed into the settings, None unless it is a number from 0 to TAX_RATE_MAX
+pub fn parse_tax_rate(input: &str) -> Option<f32> {
+    let input = input.trim().trim_end_matches('%').trim().replace(',', ".");
+    if input.is_empty() {
+        return Some(0.0);
+    }
+    input
+        .parse::<f32>()
+        .ok()
+        .filter(|rate| (0.0..=TAX_RATE_MAX).contains(rate))
+}
+
+/// Reading duration as shown next to a finished book
+pub fn format_reading_duration(days: i64) -> String {
+    match days {
+        0 => "Read in under a day".to_string(),
+        1 => "Read in 1 day".to_string(),
+        days => format!("Read in {} days", days),
+    }
+}
+
+/// Listening time of an audiobook, like "10h 5m"
+pub fn format_duration(minutes: i32) -> String {
+    format!("{}h {}m", minutes / 60, minutes % 60)
+}
+
+/// Time on the running reading timer, like "1:05:09"
+pub fn format_elapsed(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Reading sessions of a book, like "6h 40m across 9 sessions"
+pub fn format_session_total(total: &SessionTotal) -> String {
+    format!(
+        "{} across {} {}",
+        format_duration(total.minutes as i32),
+        total.sessions,
+        if total.sessions == 1 { "session" } else { "sessions" }
+    )
+}
+
+/// Rating as filled and empty stars, like "★★★★☆"
+pub fn format_rating(rating: i32) -> String {
+    let filled = rating.clamp(0, RATING_MAX) as usize;
+    format!(
+        "{}{}",
+        "★".repeat(filled),
+        "☆".repeat(RATING_MAX as usize - filled)
+    )
+}
+
+/// Resolves a date typed into the book form. Besides full dates it accepts
+/// "2024-05-14", "2024-05" (the 1st), "today", "yesterday", offsets like "-3d", "-2w",
+/// "-1m", "-1y" or "3 days ago", weekdays like "last tuesday" and "last week/month/year".
+/// Everything but a full date resolves to midnight.
+pub fn parse_relative_date(input: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+    if let Ok(date) = NaiveDateTime::parse_from_str(&input, FORM_DATE_FORMAT) {
+        return Some(date);
+    }
+
+    let today = now.date();
+    let date = if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        date
+    } else if let Ok(date) = NaiveDate::parse_from_str(&format!("{}-01", input), "%Y-%m-%d") {
+        date
+    } else {
+        match input.as_str() {
+            "today" | "now" => today,
+            "yesterday" => today.pred_opt()?,
+            "last week" => today - Days::new(7),
+            "last month" => today.checked_sub_months(Months::new(1))?.with_day(1)?,
+            "last year" => NaiveDate::from_ymd_opt(today.year() - 1, 1, 1)?,
+            _ => relative_offset(&input, today).or_else(|| last_weekday(&input, today))?,
+        }
+    };
+    Some(date.and_time(NaiveTime::MIN))
+}
+
+// "-3d" style and "3 days ago" style offsets into the past
+fn relative_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (amount, unit) = if let Some(rest) = input.strip_prefix('-') {
+        let split = rest.find(|c: char| !c.is_ascii_digit())?;
+        (&rest[..split], rest[split..].trim())
+    } else {
+        let rest = input.strip_suffix(" ago")?;
+        rest.split_once(' ')?
+    };
+    let amount: u32 = amount.parse().ok()?;
+
+    match unit {
+        "d" | "day" | "days" => today.checked_sub_days(Days::new(amount.into())),
+        "w" | "week" | "weeks" => today.checked_sub_days(Days::new(u64::from(amount) * 7)),
+        "m" | "month" | "months" => today.checked_sub_months(Months::new(amount)),
+        "y" | "year" | "years" => today.checked_sub_months(Months::new(amount.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+// "tuesday" is the latest Tuesday up to today, "last tuesday" the one before today
+fn last_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (name, before_today) = match input.strip_prefix("last ") {
+        Some(name) => (name, true),
+        None => (input, false),
+    };
+    let weekday: Weekday = name.parse().ok()?;
+    let skip = u64::from(before_today);
+    (skip..skip + 7)
+        .map(|days| today - Days::new(days))
+        .find(|date| date.weekday() == weekday)
+}
+
+/// Parses a price typed into the book form.
+/// Rejects infinity, NaN, negative values, values over `max` and, unless allowed,
+/// scientific notation.
+pub fn parse_price(input: &str, max: f32, allow_scientific: bool) -> Result<f32, String> {
+    let input = input.trim();
+    if !allow_scientific && input.contains(['e', 'E']) {
+        return Err(format!(
+            "Invalid price '{}', scientific notation is not allowed",
+            input
+        ));
+    }
+
+    let price = input
+        .parse::<f32>()
+        .map_err(|_| format!("Invalid price '{}'", input))?;
+    if !price.is_finite() {
+        return Err(format!("Invalid price '{}', it must be a number", input));
+    }
+    if price < 0.0 {
+        return Err(format!("Invalid price '{}', it can't be negative", input));
+    }
+    if price > max {
+        return Err(format!(
+            "Price {} is over the maximum of {}",
+            input,
+            format_price(max, "", false)
+        ));
+    }
+    Ok(price)
+}
+
+/// Letter an author is indexed under, names not starting with a letter go under '#'
+pub fn author_index_letter(author: &AuthorModel) -> char {
+    author
+        .Name
+        .as_deref()
+        .and_then(|name| name.trim_start().chars().next())
+        .filter(|c| c.is_alphabetic())
+        .and_then(|c| c.to_uppercase().next())
+        .unwrap_or('#')
+}
+
+/// Orders authors the way the letter index expects: '#' first, then by letter and name
+pub fn sort_authors(authors: &mut [AuthorModel]) {
+    authors.sort_by_cached_key(|author| {
+        let letter = author_index_letter(author);
+        let name = author.Name.as_deref().unwrap_or("").trim().to_lowercase();
+        (letter != '#', letter, name)
+    });
+}
+
+/// Letters shown in the author index: '#', A to Z and any other letter that names start with
+pub fn author_index_letters(authors: &[AuthorModel]) -> Vec<char> {
+    let mut letters: Vec<char> = std::iter::once('#').chain('A'..='Z').collect();
+    for author in authors {
+        let letter = author_index_letter(author);
+        if !letters.contains(&letter) {
+            letters.push(letter);
+        }
+    }
+    letters
+}
+
+/// Position of the first author listed under the letter, None when there is none
+pub fn first_author_with_letter(authors: &[AuthorModel], letter: char) -> Option<usize> {
+    authors
+        .iter()
+        .position(|author| author_index_letter(author) == letter)
+}
+
+// Keeps a value inside a single Markdown table cell
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Displayed books as a GitHub-flavored Markdown table
+pub fn books_to_markdown(books: &[BookWithAuthor], currency: &str) -> String {
+    let mut lines = vec![
+        "| Title | Author | Price | Qty | Status | Gift from |".to_string(),
+        "| --- | --- | ---: | ---: | --- | --- |".to_string(),
+    ];
+
+    for pair in books {
+        let author = pair
+            .author
+            .as_ref()
+            .and_then(|author| author.Name.clone())
+            .unwrap_or_default();
+        let price = pair
+            .book
+            .price
+            .map(|price| format_price(price, currency, false))
+            .unwrap_or_default();
+        let status = if pair.book.finished.is_some() {
+            "Finished"
+        } else if pair.book.bought.is_some() {
+            "Owned"
+        } else {
+            "Wishlist"
+        };
+
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            markdown_cell(&pair.book.title),
+            markdown_cell(&author),
+            price,
+            pair.book.quantity,
+            status,
+            markdown_cell(pair.book.gift_from.as_deref().unwrap_or_default())
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// True when a click on `id` at `now` follows a click on the same row closely enough
+pub fn is_double_click(last_click: Option<(ID, Instant)>, id: ID, now: Instant) -> bool {
+    match last_click {
+        Some((last_id, at)) => {
+            last_id == id
+                && now.saturating_duration_since(at) <= Duration::from_millis(DOUBLE_CLICK_MS)
+        }
+        None => false,
+    }
+}
+
+/// True once the cursor has rested on a book row long enough to show its hover card
+pub fn hover_card_due(hovered_since: Instant, now: Instant) -> bool {
+    now.saturating_duration_since(hovered_since) >= Duration::from_millis(HOVER_CARD_DELAY_MS)
+}
+
+/// Top left corner of a `card` sized hover card next to the cursor. The card goes below
+/// and right of the cursor, flips to the other side where it would not fit and always
+/// stays inside the window.
+pub fn hover_card_position(cursor: Point, card: Size, window: Size) -> Point {
+    let place = |cursor: f32, card: f32, window: f32| {
+        let after = cursor + HOVER_CARD_OFFSET;
+        let start = if after + card <= window {
+            after
+        } else {
+            cursor - HOVER_CARD_OFFSET - card
+        };
+        start.clamp(0.0, (window - card).max(0.0))
+    };
+    Point::new(
+        place(cursor.x, card.width, window.width),
+        place(cursor.y, card.height, window.height),
+    )
+}
+
+/// Orders two optional values in the direction, books without a value go where `empty`
+/// says whatever the direction
+pub fn compare_optional<T: PartialOrd>(
+    a: Option<T>,
+    b: Option<T>,
+    direction: &SortDirection,
+    empty: EmptyValues,
+) -> Ordering {
+    let order = match (a, b) {
+        (Some(a), Some(b)) => {
+            let order = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            return match direction {
+                SortDirection::Ascending => order,
+                SortDirection::Descending => order.reverse(),
+            };
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    match empty {
+        EmptyValues::First => order.reverse(),
+        EmptyValues::Last => order,
+    }
+}
+
+/// Sorts by the field in the direction, then by the optional secondary field ascending.
+/// Remaining ties go by title and then id, so equal values never swap between reloads.
+pub fn sort_books(
+    books: &mut Vec<BookWithAuthor>,
+    field: &SortField,
+    direction: &SortDirection,
+    secondary: Option<&SortField>,
+    empty: EmptyValues,
+) {
     books.sort_by(|a, b| {
-        let order = match field {
-            SortField::Title => a
-                .book
-                .title
-                .to_lowercase()
-                .cmp(&b.book.title.to_lowercase()),
-            SortField::Author => {
-                let a_author = a
-                    .author
-                    .as_ref()
-                    .and_then(|author| author.Name.clone())
-                    .unwrap_or_else(|| String::from(""));
-                let b_author = b
-                    .author
-                    .as_ref()
-                    .and_then(|author| author.Name.clone())
-                    .unwrap_or_else(|| String::from(""));
-                a_author.to_lowercase().cmp(&b_author.to_lowercase())
-            }
-            SortField::Price => {
-                let a_price = a.book.price.unwrap_or(0.0);
-                let b_price = b.book.price.unwrap_or(0.0);
-                a_price.partial_cmp(&b_price).unwrap_or(Ordering::Equal)
-            }
-            SortField::DateAdded => {
-                let a_date = a.book.added;
-                let b_date = b.book.added;
-                match (a_date, b_date) {
-                    (Some(a_d), Some(b_d)) => a_d.cmp(&b_d),
-                    (Some(_), None) => Ordering::Less,
-                    (None, Some(_)) => Ordering::Greater,
-                    (None, None) => Ordering::Equal,
+        compare_books(a, b, field, direction, empty)
+            .then_with(|| match secondary {
+                Some(secondary) => {
+                    compare_books(a, b, secondary, &SortDirection::Ascending, empty)
                 }
-            }
-        };
+                None => Ordering::Equal,
+            })
+            .then_with(|| compare_books(a, b, &SortField::Title, &SortDirection::Ascending, empty))
+            .then_with(|| a.book.id.cmp(&b.book.id))
+    });
+}
+
+fn compare_books(
+    a: &BookWithAuthor,
+    b: &BookWithAuthor,
+    field: &SortField,
+    direction: &SortDirection,
+    empty: EmptyValues,
+) -> Ordering {
+    let order = match field {
+        SortField::Title => a
+            .book
+            .title
+            .to_lowercase()
+            .cmp(&b.book.title.to_lowercase()),
+        SortField::Author => {
+            let a_author = a
+                .author
+                .as_ref()
+                .and_then(|author| author.Name.clone())
+                .unwrap_or_else(|| String::from(""));
+            let b_author = b
+                .author
+                .as_ref()
+                .and_then(|author| author.Name.clone())
+                .unwrap_or_else(|| String::from(""));
+            a_author.to_lowercase().cmp(&b_author.to_lowercase())
+        }
+        // Already directed, the empty values keep their place
+        SortField::Price => {
+            return compare_optional(a.book.price, b.book.price, direction, empty);
+        }
+        SortField::DateAdded => {
+            return compare_optional(a.book.added, b.book.added, direction, empty);
+        }
+    };
+
+    match direction {
+        SortDirection::Ascending => order,
+        SortDirection::Descending => order.reverse(),
+    }
+}
+
+/// Moves pinned books ahead of the others, both groups keep their sorted order
+pub fn pinned_first(books: &mut [BookWithAuthor]) {
+    books.sort_by_key(|pair| !pair.book.pinned);
+}
+
+/// Number of pinned books leading a list ordered by `pinned_first`
+pub fn pinned_count(books: &[BookWithAuthor]) -> usize {
+    books.iter().take_while(|pair| pair.book.pinned).count()
+}
+
+/// Returns true when one of the enabled fields (title, author name, price, giver, place
+/// of acquisition or genre) matches the lowercase query
+pub fn book_matches(book: &BookWithAuthor, query: &str, fields: SearchFields) -> bool {
+    // Search by title
+    let title_match = fields.title && book.book.title.to_lowercase().contains(query);
+
+    // Search by author name or one of their pen names
+    let author_match = fields.author
+        && book
+            .author
+            .as_ref()
+            .is_some_and(|author| author_matches_with_aliases(author, query));
+
+    // Search by price - flexible matching without rounding
+    let price_match = fields.price && book.book.price.map_or(false, |price| {
+        // Try to parse the query as a number (float or integer)
+        if let Ok(query_num) = query.parse::<f32>() {
+            // Convert the price to string to check if it contains the query
+            let price_str = price.to_string();
+
+            // Check if the price starts with the query number
+            // (e.g., searching for "41" should match "41.99")
+            price_str.starts_with(&query_num.to_string()) ||
 
-        match direction {
-            SortDirection::Ascending => order,
-            SortDirection::Descending => order.reverse(),
+                // Or a direct equality check for exact prices
+                (price == query_num)
+        } else {
+            // If query isn't a valid number, check if price string contains the query
+            price.to_string().contains(query)
         }
     });
-}
\ No newline at end of file
+
+    // Search by who gave the book
+    let giver_match = fields.giver
+        && book
+            .book
+            .gift_from
+            .as_ref()
+            .is_some_and(|giver| giver.to_lowercase().contains(query));
+
+    // Search by where the book was acquired
+    let acquired_match = fields.acquired_from
+        && book
+            .book
+            .acquired_from
+            .as_ref()
+            .is_some_and(|place| place.to_lowercase().contains(query));
+
+    // Search by genre, the one label a book is tagged with
+    let genre_match = fields.genre
+        && book
+            .book
+            .genre
+            .as_ref()
+            .is_some_and(|genre| genre.to_lowercase().contains(query));
+
+    title_match || author_match || price_match || giver_match || acquired_match || genre_match
+}
+
+/// True when the author's name or one of their aliases contains the query, ignoring case
+pub fn author_matches_with_aliases(author: &AuthorModel, query: &str) -> bool {
+    let query = query.to_lowercase();
+    author
+        .Name
+        .as_deref()
+        .into_iter()
+        .chain(author.aliases())
+        .any(|name| name.to_lowercase().contains(&query))
+}
+
+/// Distinct places books were acquired from, compared ignoring case, in alphabetical order
+pub fn acquisition_places(books: &[BookWithAuthor]) -> Vec<AcquisitionPlace> {
+    let mut places: Vec<AcquisitionPlace> = Vec::new();
+    for place in books.iter().filter_map(|pair| pair.book.acquired_from.as_deref()) {
+        let place = place.trim();
+        if !place.is_empty() && !places.iter().any(|p| p.0.eq_ignore_ascii_case(place)) {
+            places.push(AcquisitionPlace(place.to_string()));
+        }
+    }
+    places.sort_by_key(|place| place.0.to_lowercase());
+    places
+}
+
+/// Returns true when the book has no value for the field
+pub fn book_is_missing(book: &BookWithAuthor, field: MissingField) -> bool {
+    match field {
+        MissingField::Author => book.book.AuthorFK.is_none(),
+        MissingField::Price => book.book.price.is_none(),
+        MissingField::BoughtDate => book.book.bought.is_none(),
+    }
+}
+
+/// Returns true when the chosen date of the book falls in the year, books without it never do
+pub fn book_in_year(book: &BookModel, year: i32, field: YearField) -> bool {
+    let date = match field {
+        YearField::Added => book.added,
+        YearField::Bought => book.bought,
+        YearField::Finished => book.finished,
+    };
+    date.is_some_and(|date| date.year() == year)
+}
+
+/// Returns true when the book shows the issue, dates after `now` count as suspicious
+pub fn book_has_issue(book: &BookWithAuthor, issue: DataIssue, now: NaiveDateTime) -> bool {
+    match issue {
+        DataIssue::MissingAuthor => book_is_missing(book, MissingField::Author),
+        DataIssue::MissingPrice => book_is_missing(book, MissingField::Price),
+        DataIssue::UntidyTitle => title_is_untidy(&book.book.title),
+        DataIssue::SuspiciousDates => [book.book.added, book.book.bought, book.book.finished]
+            .into_iter()
+            .flatten()
+            .any(|date| date > now || date.year() < EARLIEST_BOOK_YEAR),
+    }
+}
+
+/// Number of issues the books show, a book counts once for every issue it has
+pub fn count_data_issues(books: &[BookWithAuthor], now: NaiveDateTime) -> usize {
+    books
+        .iter()
+        .map(|book| {
+            DataIssue::ALL
+                .into_iter()
+                .filter(|issue| book_has_issue(book, *issue, now))
+                .count()
+        })
+        .sum()
+}
+
+/// Whether the nightly maintenance is due: the clock has passed the maintenance hour
+/// (0-23, local time) since the last run. An hour out of range never comes.
+pub fn maintenance_due(now: NaiveDateTime, hour: u32, last_run: NaiveDateTime) -> bool {
+    let Some(today) = now.date().and_hms_opt(hour, 0, 0) else {
+        return false;
+    };
+    let latest = if now >= today {
+        today
+    } else {
+        today - Days::new(1)
+    };
+    last_run < latest
+}
+
+/// Whether the nightly maintenance may start now. It never writes to a database opened
+/// read-only, waits until the app is idle and doesn't start twice.
+pub fn maintenance_can_start(read_only: bool, idle: bool, running: bool) -> bool {
+    !read_only && idle && !running
+}
+
+// Empty, stray or doubled spaces, or written without a single capital letter
+fn title_is_untidy(title: &str) -> bool {
+    let tidy = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    tidy.is_empty()
+        || tidy != title
+        || (title.chars().any(char::is_lowercase) && !title.chars().any(char::is_uppercase))
+}
+
+/// Helper function to filter books by a search query
+pub fn filter_books(
+    books: &[BookWithAuthor],
+    query: &str,
+    fields: SearchFields,
+) -> Vec<BookWithAuthor> {
+    let query = query.to_lowercase();
+    books
+        .iter()
+        .filter(|book| book_matches(book, &query, fields))
+        .cloned()
+        .collect()
+}
+
+/// Number of table columns shown, the trailing "Read in" column is optional
+pub fn table_column_count(show_reading_duration: bool) -> usize {
+    if show_reading_duration {
+        TABLE_COLUMNS.len()
+    } else {
+        TABLE_COLUMNS.len() - 1
+    }
+}
+
+/// Shares of the first `columns` table columns from the settings, or the defaults when
+/// the file holds a different number of columns or unusable values. The result always
+/// sums to 1.
+pub fn table_column_widths(widths: &[f32], columns: usize) -> Vec<f32> {
+    let valid = widths.len() == columns
+        && widths.iter().all(|width| width.is_finite() && *width > 0.0);
+    let widths = if valid {
+        widths.to_vec()
+    } else {
+        TABLE_COLUMN_WIDTHS[..columns.min(TABLE_COLUMN_WIDTHS.len())].to_vec()
+    };
+    let total: f32 = widths.iter().sum();
+    widths.iter().map(|width| width / total).collect()
+}
+
+/// Moves the divider right of `column` to `position`, a share of the table width.
+/// Only the two columns next to the divider change and neither gets narrower than
+/// TABLE_COLUMN_MIN.
+pub fn resize_table_column(
+    widths: &[f32],
+    columns: usize,
+    column: usize,
+    position: f32,
+) -> Vec<f32> {
+    let mut widths = table_column_widths(widths, columns);
+    if column + 1 >= widths.len() || !position.is_finite() {
+        return widths;
+    }
+
+    let left: f32 = widths[..column].iter().sum();
+    let pair = widths[column] + widths[column + 1];
+    let min = TABLE_COLUMN_MIN.min(pair / 2.0);
+    let first = (position - left).clamp(min, pair - min);
+    widths[column] = first;
+    widths[column + 1] = pair - first;
+    widths
+}
+
+/// Share of the width left of a splitter dragged to `x`, measured from the left edge of
+/// a `width` wide area that includes the splitter itself
+pub fn split_ratio_at(x: f32, width: f32, splitter_width: f32) -> f32 {
+    let panes = width - splitter_width;
+    if panes <= 0.0 || !x.is_finite() {
+        return SPLIT_RATIO_DEFAULT;
+    }
+    ((x - splitter_width / 2.0) / panes).clamp(SPLIT_RATIO_MIN, SPLIT_RATIO_MAX)
+}
+
+/// Filters narrowing the book list, as described above printed lists
+pub struct ListFilters<'a> {
+    pub query: &'a str,
+    pub gifts_only: bool,
+    pub missing: Option<MissingField>,
+    pub language: Option<&'a str>,
+    pub acquired_from: Option<&'a str>,
+    pub year: Option<(i32, YearField)>,
+}
+
+/// One line describing what the book list shows, printed above it
+pub fn describe_list_filters(
+    list: &ListFilters,
+    sort_field: &SortField,
+    sort_direction: &SortDirection,
+) -> String {
+    let mut filters = Vec::new();
+    if !list.query.is_empty() {
+        filters.push(format!("matching \"{}\"", list.query));
+    }
+    if list.gifts_only {
+        filters.push("gifts only".to_string());
+    }
+    if let Some(field) = list.missing {
+        filters.push(format!("missing {}", field));
+    }
+    if let Some(code) = list.language {
+        filters.push(format!("in {}", language_name(code)));
+    }
+    if let Some(place) = list.acquired_from {
+        filters.push(format!("acquired from {}", place));
+    }
+    if let Some((year, field)) = list.year {
+        filters.push(format!("{} in {}", field.to_string().to_lowercase(), year));
+    }
+
+    let books = if filters.is_empty() {
+        "All books".to_string()
+    } else {
+        format!("Books {}", filters.join(", "))
+    };
+    let direction = match sort_direction {
+        SortDirection::Ascending => "ascending",
+        SortDirection::Descending => "descending",
+    };
+    format!(
+        "{}, sorted by {} {}",
+        books,
+        sort_field.to_string().to_lowercase(),
+        direction
+    )
+}
+
+/// Stable color for a genre, the hue comes from a hash of the lowercased name so the
+/// same genre always looks the same
+pub fn genre_color(genre: &str) -> Color {
+    // FNV-1a, stable across runs and platforms unlike the std hasher
+    let hash = genre
+        .trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0x811c_9dc5_u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        });
+    let hue = (hash % 360) as f32;
+
+    // HSL to RGB with fixed saturation and lightness, readable on light and dark themes
+    let (saturation, lightness) = (0.55, 0.55);
+    let chroma = (1.0 - (2.0 * lightness - 1.0_f32).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Color::from_rgb(r + m, g + m, b + m)
+}
+
+/// Color of a book's genre marker, a color set in the settings wins over the hashed one.
+/// Books without a genre, and unparsable settings, get the neutral color.
+pub fn book_genre_color(genre: Option<&str>, overrides: &BTreeMap<String, String>) -> Color {
+    let Some(genre) = genre.map(str::trim).filter(|genre| !genre.is_empty()) else {
+        return GENRE_NEUTRAL_COLOR;
+    };
+    match overrides
+        .iter()
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(genre))
+    {
+        Some((_, color)) => Color::parse(color.trim()).unwrap_or(GENRE_NEUTRAL_COLOR),
+        None => genre_color(genre),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(id: ID, title: &str) -> BookWithAuthor {
+        BookWithAuthor {
+            book: BookModel::titled(id, title),
+            author: None,
+        }
+    }
+
+    #[test]
+    fn markdown_cells_escape_pipes_and_line_breaks() {
+        let mut pair = book(1, "Either | Or\nPart one");
+        pair.book.gift_from = Some("Ann|Bob".to_string());
+
+        let markdown = books_to_markdown(&[pair], "zł");
+        let row = markdown.lines().nth(2).unwrap();
+        assert_eq!(row, r"| Either \| Or Part one |  |  | 1 | Wishlist | Ann\|Bob |");
+        assert_eq!(markdown.lines().count(), 3);
+    }
+
+    #[test]
+    fn prices_out_of_range_or_not_numbers_are_rejected() {
+        assert!(parse_price("1e10", 100_000.0, false).is_err());
+        assert!(parse_price("1e10", 100_000.0, true).is_err());
+        assert!(parse_price("inf", 100_000.0, true).is_err());
+        assert!(parse_price("NaN", 100_000.0, true).is_err());
+        assert!(parse_price("100000.01", 100_000.0, false).is_err());
+        assert_eq!(parse_price("100000", 100_000.0, false), Ok(100_000.0));
+    }
+
+    #[test]
+    fn relative_dates_resolve_against_now() {
+        // A Wednesday afternoon
+        let now = NaiveDate::from_ymd_opt(2024, 3, 13)
+            .unwrap()
+            .and_hms_opt(15, 30, 0)
+            .unwrap();
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap().and_time(NaiveTime::MIN);
+        let cases = [
+            (
+                "2024-05-14 10:20:30",
+                NaiveDate::from_ymd_opt(2024, 5, 14).unwrap().and_hms_opt(10, 20, 30),
+            ),
+            ("2024-05-14", Some(day(2024, 5, 14))),
+            (" 2024-05 ", Some(day(2024, 5, 1))),
+            ("today", Some(day(2024, 3, 13))),
+            ("NOW", Some(day(2024, 3, 13))),
+            ("yesterday", Some(day(2024, 3, 12))),
+            ("-3d", Some(day(2024, 3, 10))),
+            ("-2w", Some(day(2024, 2, 28))),
+            ("-1m", Some(day(2024, 2, 13))),
+            ("-1y", Some(day(2023, 3, 13))),
+            ("3 days ago", Some(day(2024, 3, 10))),
+            ("1 week ago", Some(day(2024, 3, 6))),
+            ("2 months ago", Some(day(2024, 1, 13))),
+            ("last week", Some(day(2024, 3, 6))),
+            ("last month", Some(day(2024, 2, 1))),
+            ("last year", Some(day(2023, 1, 1))),
+            ("tuesday", Some(day(2024, 3, 12))),
+            ("wednesday", Some(day(2024, 3, 13))),
+            ("Last Tuesday", Some(day(2024, 3, 12))),
+            ("last wednesday", Some(day(2024, 3, 6))),
+            ("", None),
+            ("   ", None),
+            ("tomorrow", None),
+            ("+3d", None),
+            ("-d", None),
+            ("-3x", None),
+            ("-1.5d", None),
+            ("3 days", None),
+            ("2024-13", None),
+            ("2024-02-30", None),
+            ("last", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_relative_date(input, now), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn a_month_back_from_a_long_month_ends_on_the_last_day() {
+        let now = NaiveDate::from_ymd_opt(2024, 3, 31)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_time(NaiveTime::MIN);
+        assert_eq!(parse_relative_date("-1m", now), Some(expected));
+        assert_eq!(parse_relative_date("1 month ago", now), Some(expected));
+    }
+
+    #[test]
+    fn only_the_chosen_date_counts_for_the_year() {
+        let in_2021 = NaiveDate::from_ymd_opt(2021, 12, 31)
+            .unwrap()
+            .and_hms_opt(23, 59, 59);
+        let mut bought_only = BookModel::titled(1, "Dune");
+        bought_only.bought = in_2021;
+        assert!(book_in_year(&bought_only, 2021, YearField::Bought));
+        assert!(!book_in_year(&bought_only, 2022, YearField::Bought));
+        assert!(!book_in_year(&bought_only, 2021, YearField::Added));
+        assert!(!book_in_year(&bought_only, 2021, YearField::Finished));
+
+        let mut finished_only = BookModel::titled(2, "Emma");
+        finished_only.finished = in_2021;
+        assert!(book_in_year(&finished_only, 2021, YearField::Finished));
+        assert!(!book_in_year(&finished_only, 2021, YearField::Bought));
+
+        let undated = BookModel::titled(3, "Ulysses");
+        for field in YearField::ALL {
+            assert!(!book_in_year(&undated, 2021, field));
+        }
+    }
+
+    #[test]
+    fn negative_prices_are_rejected() {
+        let error = parse_price("-5", 100_000.0, false).unwrap_err();
+        assert_eq!(error, "Invalid price '-5', it can't be negative");
+        assert_eq!(parse_price(" 12.50 ", 100_000.0, false), Ok(12.5));
+    }
+
+    #[test]
+    fn a_query_matching_only_the_genre_finds_the_book() {
+        let mut pair = book(1, "Dune");
+        pair.book.genre = Some("Science fiction".to_string());
+        let books = [pair, book(2, "Emma")];
+
+        let found = filter_books(&books, "fiction", SearchFields::default());
+        assert_eq!(found.iter().map(|pair| pair.book.id).collect::<Vec<_>>(), [1]);
+
+        let fields = SearchFields {
+            genre: false,
+            ..SearchFields::default()
+        };
+        assert!(filter_books(&books, "fiction", fields).is_empty());
+    }
+
+    #[test]
+    fn tax_splits_a_total_into_net_and_gross() {
+        assert_eq!(apply_tax(100.0, 0.0, false), (100.0, 100.0));
+        assert_eq!(apply_tax(100.0, 0.0, true), (100.0, 100.0));
+        assert_eq!(apply_tax(100.0, 100.0, false), (100.0, 200.0));
+        assert_eq!(apply_tax(200.0, 100.0, true), (100.0, 200.0));
+        assert_eq!(apply_tax(123.0, 23.0, true), (100.0, 123.0));
+        assert_eq!(apply_tax(100.0, 23.0, false), (100.0, 123.0));
+    }
+
+    #[test]
+    fn empty_values_go_where_asked_in_both_directions() {
+        use SortDirection::{Ascending, Descending};
+        let sorted = |direction: SortDirection, empty: EmptyValues| {
+            let mut prices = [Some(2.0), None, Some(1.0), Some(3.0)];
+            prices.sort_by(|a, b| compare_optional(*a, *b, &direction, empty));
+            prices
+        };
+
+        assert_eq!(
+            sorted(Ascending, EmptyValues::First),
+            [None, Some(1.0), Some(2.0), Some(3.0)]
+        );
+        assert_eq!(
+            sorted(Ascending, EmptyValues::Last),
+            [Some(1.0), Some(2.0), Some(3.0), None]
+        );
+        assert_eq!(
+            sorted(Descending, EmptyValues::First),
+            [None, Some(3.0), Some(2.0), Some(1.0)]
+        );
+        assert_eq!(
+            sorted(Descending, EmptyValues::Last),
+            [Some(3.0), Some(2.0), Some(1.0), None]
+        );
+    }
+}
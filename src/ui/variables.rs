@@ -1,3 +1,95 @@
+use iced::Color;
+
 pub const LIST_PADDING: f32 = 20.0;
 pub const LIST_SPACING: f32 = 10.0;
 pub const LIST_MAX_WIDTH: f32 = 500.0;
+pub const CURRENCY: &str = "zł";
+pub const STALE_UNREAD_THRESHOLDS: [i64; 4] = [90, 180, 365, 730]; // Days, offered in stats
+pub const STALE_UNREAD_SHOWN: usize = 10;
+pub const TOP_AUTHORS_SHOWN: usize = 10;
+pub const UNKNOWN_AUTHOR: &str = "Unknown"; // Groups finished books without an author
+pub const NO_AUTHOR_GROUP: &str = "No author"; // Last group of the book list grouped by author
+pub const NO_SERIES_GROUP: &str = "Not in a series"; // Last group of the list grouped by series
+pub const GENRE_NEUTRAL_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6); // Books without a genre
+pub const PRICE_MASK: &str = "•••"; // Shown instead of prices in privacy mode
+pub const TAX_RATE_MAX: f32 = 100.0; // Percent
+pub const ROW_HIGHLIGHT_MS: u64 = 2000;
+pub const DOUBLE_CLICK_MS: u64 = 400;
+pub const HOVER_CARD_DELAY_MS: u64 = 500; // Rest on a book row this long to see its card
+pub const HOVER_CARD_OFFSET: f32 = 16.0; // Gap between the cursor and the card
+pub const HOVER_CARD_WIDTH: f32 = 320.0;
+pub const HOVER_CARD_HEIGHT: f32 = 280.0;
+pub const WINDOW_WIDTH: f32 = 1024.0; // Size the window opens with
+pub const WINDOW_HEIGHT: f32 = 768.0;
+pub const BOOKS_PER_ROW_MIN: usize = 1;
+pub const BOOKS_PER_ROW_MAX: usize = 6;
+pub const BOOK_QUANTITY_MIN: i32 = 1;
+pub const RATING_MAX: i32 = 5;
+pub const EARLIEST_BOOK_YEAR: i32 = 1900; // Older dates are flagged as likely typos
+pub const DRAFT_AUTOSAVE_SECS: u64 = 5;
+pub const IDLE_REFRESH_MINUTES: [u64; 5] = [1, 5, 15, 30, 60]; // Offered in the settings
+pub const CSV_PREVIEW_ROWS: usize = 3; // Records shown under the header before a CSV export
+pub const IMPORT_AUTHOR_LIMITS: [usize; 5] = [10, 25, 50, 100, 500]; // Offered in the settings
+pub const MAINTENANCE_CHECK_MINUTES: u64 = 10; // How often the maintenance hour is checked
+pub const MAINTENANCE_BACKUPS_KEPT: [usize; 4] = [3, 7, 14, 30]; // Offered in the settings
+pub const FORM_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S"; // Dates typed into the book form
+
+// Columns of the table layout and their default share of its width
+// The last column, "Read in", is only shown when enabled in the settings
+pub const TABLE_COLUMNS: [&str; 6] = ["Title", "Author", "Price", "Bought", "Finished", "Read in"];
+pub const TABLE_COLUMN_WIDTHS: [f32; 6] = [0.35, 0.25, 0.12, 0.14, 0.14, 0.12];
+pub const TABLE_COLUMN_MIN: f32 = 0.05; // Narrowest a column can be dragged, as a share
+
+// Share of the width the book list takes next to the details pane
+pub const SPLIT_RATIO_DEFAULT: f32 = 0.5;
+pub const SPLIT_RATIO_MIN: f32 = 0.25;
+pub const SPLIT_RATIO_MAX: f32 = 0.75;
+
+// Base text sizes, multiplied by the font scale setting
+pub const TEXT_TITLE: f32 = 24.0;
+pub const TEXT_HEADING: f32 = 20.0;
+pub const TEXT_ITEM: f32 = 18.0;
+pub const TEXT_BODY: f32 = 16.0;
+pub const TEXT_SMALL: f32 = 14.0;
+pub const TEXT_TINY: f32 = 12.0;
+
+pub const FONT_SCALE_MIN: f32 = 0.8;
+pub const FONT_SCALE_MAX: f32 = 2.0;
+pub const FONT_SCALE_STEP: f32 = 0.1;
+
+/// Text sizes with the user's font scale applied
+#[derive(Debug, Clone, Copy)]
+pub struct FontSizes {
+    pub scale: f32,
+}
+
+impl FontSizes {
+    pub fn title(self) -> f32 {
+        TEXT_TITLE * self.scale
+    }
+
+    pub fn heading(self) -> f32 {
+        TEXT_HEADING * self.scale
+    }
+
+    pub fn item(self) -> f32 {
+        TEXT_ITEM * self.scale
+    }
+
+    pub fn body(self) -> f32 {
+        TEXT_BODY * self.scale
+    }
+
+    pub fn small(self) -> f32 {
+        TEXT_SMALL * self.scale
+    }
+
+    pub fn tiny(self) -> f32 {
+        TEXT_TINY * self.scale
+    }
+
+    /// Widths grow with the text so larger fonts don't clip
+    pub fn width(self, base: f32) -> f32 {
+        base * self.scale.max(1.0)
+    }
+}